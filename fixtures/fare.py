@@ -0,0 +1,3 @@
+def total_fare(base, taxes):
+    """Return the fare including taxes."""
+    return base + taxes
@@ -0,0 +1,322 @@
+//! Answering questions about a directory's documents with a language model the client provides
+//!
+//! The documents are split into passages and the passages ranked against the question
+//! by keyword relevance (BM25). The best passages go to the model, reached through a
+//! callback the server routes to the client's `sampling/createMessage`, numbered so the
+//! answer can cite them; every cited number maps back to a file and page.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::chunk::{ChunkUnit, Chunker};
+use crate::language::stop_words;
+use crate::listing::{list_files_in_directory, ListOptions};
+use crate::search::document_pages;
+use crate::summarize::SamplingRequest;
+
+/// Passages sent to the model by default
+pub const DEFAULT_MAX_PASSAGES: usize = 8;
+
+/// Length of a passage by default, in estimated tokens
+pub const DEFAULT_PASSAGE_TOKENS: usize = 300;
+
+/// Tokens the model may answer with by default
+pub const DEFAULT_MAX_ANSWER_TOKENS: usize = 800;
+
+/// BM25 term frequency saturation
+const BM25_K1: f64 = 1.2;
+
+/// BM25 document length normalization
+const BM25_B: f64 = 0.75;
+
+const SYSTEM_PROMPT: &str = "You answer questions using only the numbered passages you are given. Cite the passages \
+                             you rely on by their number in square brackets, e.g. [2]. If the passages do not \
+                             contain the answer, say so instead of guessing.";
+
+/// A passage the answer was based on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Source {
+    /// Number the passage was given in the request, cited as `[number]`
+    pub number: usize,
+    /// Path relative to the searched directory, with `/` separators
+    pub file: String,
+    /// 1-based page number, for formats with pages (PDF)
+    pub page: Option<usize>,
+    pub text: String,
+    /// BM25 relevance to the question
+    pub score: f64,
+    /// True when the answer cites the passage
+    pub cited: bool,
+}
+
+/// The answer to a question about a directory's documents
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Answer {
+    pub answer: String,
+    pub sources: Vec<Source>,
+    /// Number of documents read
+    pub documents: usize,
+    /// Documents that could not be read
+    pub unreadable: Vec<String>,
+    /// True when more files matched than the listing limit
+    pub truncated: bool,
+}
+
+/// Settings for `ask_documents`
+pub struct AskOptions {
+    list: ListOptions,
+    max_passages: usize,
+    passage_tokens: usize,
+    max_answer_tokens: usize,
+}
+
+impl Default for AskOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AskOptions {
+    /// Searches the whole directory tree, sending `DEFAULT_MAX_PASSAGES` passages to the model
+    pub fn new() -> Self {
+        Self {
+            list: ListOptions::new().with_recursive(true),
+            max_passages: DEFAULT_MAX_PASSAGES,
+            passage_tokens: DEFAULT_PASSAGE_TOKENS,
+            max_answer_tokens: DEFAULT_MAX_ANSWER_TOKENS,
+        }
+    }
+
+    /// Sets which files of the directory are searched
+    pub fn with_list_options(mut self, list: ListOptions) -> Self {
+        self.list = list;
+        self
+    }
+
+    /// Sets the number of passages sent to the model; at least one
+    pub fn with_max_passages(mut self, max_passages: usize) -> Self {
+        self.max_passages = max_passages.max(1);
+        self
+    }
+
+    /// Sets the length of a passage, in estimated tokens; at least 10
+    pub fn with_passage_tokens(mut self, passage_tokens: usize) -> Self {
+        self.passage_tokens = passage_tokens.max(10);
+        self
+    }
+
+    /// Sets the tokens the model may answer with
+    pub fn with_max_answer_tokens(mut self, max_answer_tokens: usize) -> Self {
+        self.max_answer_tokens = max_answer_tokens;
+        self
+    }
+}
+
+/// Lowercased words of a text, without function words and single characters
+fn terms<'a>(text: &'a str, stop_words: &'a HashSet<&str>) -> impl Iterator<Item = String> + 'a {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() > 1 && !stop_words.contains(word.as_str()))
+}
+
+/// Ranks passages against the terms of a question, best first; passages without any
+/// of the terms are left out
+fn rank_passages(passages: Vec<Source>, question_terms: &HashSet<String>) -> Vec<Source> {
+    let stop_words: HashSet<&str> = stop_words().collect();
+    let counts: Vec<(HashMap<String, usize>, usize)> = passages
+        .iter()
+        .map(|passage| {
+            let mut counts = HashMap::new();
+            let mut length = 0;
+            for term in terms(&passage.text, &stop_words) {
+                length += 1;
+                if question_terms.contains(&term) {
+                    *counts.entry(term).or_insert(0) += 1;
+                }
+            }
+            (counts, length)
+        })
+        .collect();
+
+    let total = passages.len() as f64;
+    let average_length = counts.iter().map(|(_, length)| *length as f64).sum::<f64>() / total.max(1.0);
+    let mut document_frequency: HashMap<&str, f64> = HashMap::new();
+    for (passage_counts, _) in &counts {
+        for term in passage_counts.keys() {
+            *document_frequency.entry(term).or_insert(0.0) += 1.0;
+        }
+    }
+
+    let mut ranked: Vec<Source> = passages
+        .into_iter()
+        .zip(&counts)
+        .filter_map(|(mut passage, (passage_counts, length))| {
+            let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * *length as f64 / average_length.max(1.0));
+            passage.score = passage_counts
+                .iter()
+                .map(|(term, &count)| {
+                    let frequency = document_frequency[term.as_str()];
+                    let idf = ((total - frequency + 0.5) / (frequency + 0.5)).ln_1p();
+                    idf * count as f64 * (BM25_K1 + 1.0) / (count as f64 + norm)
+                })
+                .sum();
+            (passage.score > 0.0).then_some(passage)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked
+}
+
+/// Numbers cited in an answer as `[n]`, `[n, m]` or `[n][m]`
+fn cited_numbers(answer: &str) -> HashSet<usize> {
+    answer
+        .split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']'))
+        .flat_map(|(inside, _)| inside.split(',').filter_map(|number| number.trim().parse().ok()))
+        .collect()
+}
+
+/// Builds the request asking the model to answer from numbered passages
+fn answer_request(question: &str, sources: &[Source], max_tokens: usize) -> SamplingRequest {
+    let passages = sources
+        .iter()
+        .map(|source| match source.page {
+            Some(page) => format!("[{}] {}, page {}\n{}", source.number, source.file, page, source.text),
+            None => format!("[{}] {}\n{}", source.number, source.file, source.text),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    SamplingRequest {
+        system_prompt: SYSTEM_PROMPT.to_string(),
+        prompt: format!("Question: {}\n\nPassages:\n\n{}", question, passages),
+        max_tokens,
+    }
+}
+
+/// Answers a question about the documents of a directory, calling `sample` for the
+/// request to the model
+///
+/// Documents that cannot be read are skipped and listed. A question none of the
+/// passages relate to is an error, without a request to the model.
+pub fn ask_documents<F>(directory: &Path, question: &str, options: &AskOptions, mut sample: F) -> Result<Answer>
+where
+    F: FnMut(&SamplingRequest) -> Result<String>,
+{
+    let stop_words: HashSet<&str> = stop_words().collect();
+    let question_terms: HashSet<String> = terms(question, &stop_words).collect();
+    if question_terms.is_empty() {
+        return Err(anyhow::anyhow!("The question has no words to search for"));
+    }
+
+    let listing = list_files_in_directory(directory, &options.list)?;
+    let chunker = Chunker::new(options.passage_tokens, options.passage_tokens / 5)?.with_unit(ChunkUnit::Tokens);
+    let mut passages = Vec::new();
+    let mut documents = 0;
+    let mut unreadable = Vec::new();
+    for file in listing.files {
+        let Ok(pages) = document_pages(&file.path) else {
+            unreadable.push(file.relative_path);
+            continue;
+        };
+        documents += 1;
+        for page in pages {
+            passages.extend(chunker.chunk_text(&page.text, &file.relative_path).into_iter().map(|chunk| Source {
+                number: 0,
+                file: file.relative_path.clone(),
+                page: Some(page.page).filter(|&page| page > 0),
+                text: chunk.text,
+                score: 0.0,
+                cited: false,
+            }));
+        }
+    }
+
+    let mut sources = rank_passages(passages, &question_terms);
+    if sources.is_empty() {
+        return Err(anyhow::anyhow!("No passage in {} relates to the question", directory.display()));
+    }
+    sources.truncate(options.max_passages);
+    for (index, source) in sources.iter_mut().enumerate() {
+        source.number = index + 1;
+    }
+
+    let request = answer_request(question.trim(), &sources, options.max_answer_tokens);
+    let answer = sample(&request).context("Answer request failed")?.trim().to_string();
+    let cited = cited_numbers(&answer);
+    for source in &mut sources {
+        source.cited = cited.contains(&source.number);
+    }
+    Ok(Answer { answer, sources, documents, unreadable, truncated: listing.truncated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("lease.txt"), "The tenant pays the rent on the first day of each month.").unwrap();
+        let travel = "# Travel\n\nHotel costs are refunded up to 150 EUR per night.";
+        fs::write(dir.path().join("travel.md"), travel).unwrap();
+        fs::create_dir(dir.path().join("notes")).unwrap();
+        fs::write(dir.path().join("notes/rent.txt"), "Rent increases need two months notice. Rent rent.").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_cited_numbers() {
+        let cited = cited_numbers("Rent is due monthly [1][3]. Hotels are refunded [2, 4]; see [note] and [].");
+        assert_eq!(cited, HashSet::from([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_ask_documents() {
+        let dir = sample_directory();
+        let mut requests = Vec::new();
+        let answer = ask_documents(dir.path(), "When is the rent due?", &AskOptions::new(), |request| {
+            requests.push(request.clone());
+            Ok(" On the first day of each month [2]. ".to_string())
+        })
+        .unwrap();
+
+        assert_eq!(answer.answer, "On the first day of each month [2].");
+        assert_eq!(answer.documents, 3);
+        let files: Vec<&str> = answer.sources.iter().map(|source| source.file.as_str()).collect();
+        assert_eq!(files, ["notes/rent.txt", "lease.txt"], "Passages without the terms are left out");
+        assert!(answer.sources[0].score > answer.sources[1].score);
+        assert_eq!(answer.sources[1].number, 2);
+        assert!(answer.sources[1].cited && !answer.sources[0].cited);
+        assert_eq!(answer.sources[1].page, None);
+
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].prompt.starts_with("Question: When is the rent due?"));
+        assert!(requests[0].prompt.contains("[2] lease.txt\nThe tenant pays the rent"));
+        assert_eq!(requests[0].max_tokens, DEFAULT_MAX_ANSWER_TOKENS);
+    }
+
+    #[test]
+    fn test_ask_documents_limits_passages() {
+        let dir = sample_directory();
+        let options = AskOptions::new().with_max_passages(1);
+        let answer = ask_documents(dir.path(), "rent", &options, |_| Ok("Monthly.".to_string())).unwrap();
+        assert_eq!(answer.sources.len(), 1);
+        assert_eq!(answer.sources[0].file, "notes/rent.txt");
+    }
+
+    #[test]
+    fn test_ask_documents_errors() {
+        let dir = sample_directory();
+        let options = AskOptions::new();
+        let never = |_: &SamplingRequest| -> Result<String> { panic!("The model should not be asked") };
+        assert!(ask_documents(dir.path(), "the of and", &options, never).is_err());
+        assert!(ask_documents(dir.path(), "quantum chromodynamics", &options, never).is_err());
+        assert!(ask_documents(&dir.path().join("missing"), "rent", &options, never).is_err());
+
+        let refuse = |_: &SamplingRequest| -> Result<String> { Err(anyhow::anyhow!("client refused")) };
+        let error = ask_documents(dir.path(), "rent", &options, refuse).unwrap_err();
+        assert_eq!(format!("{:#}", error), "Answer request failed: client refused");
+    }
+}
@@ -0,0 +1,224 @@
+//! Named places in documents, saved next to the configuration
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::config::{config_file_path, load_json, save_json};
+use crate::extractor::{extract_text_slice, get_document_pages, OutputFormat, TextSlice};
+use crate::timestamp::{format_unix_time, unix_seconds};
+
+/// Name of the bookmarks file, in the configuration directory
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.json";
+
+/// A named place in a document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    /// Canonical path of the document
+    pub path: PathBuf,
+    /// 1-based page, for formats with pages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// Offset in the extracted text (of the page, when there is one), in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// When the bookmark was saved, as an ISO 8601 UTC timestamp
+    pub created: String,
+}
+
+/// Text at a bookmark
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookmarkView {
+    pub bookmark: Bookmark,
+    /// Text starting at the bookmarked offset, of the bookmarked page if any
+    #[serde(flatten)]
+    pub slice: TextSlice,
+}
+
+/// Saved bookmarks, in the order they were added
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bookmarks {
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    /// Returns the path of the bookmarks file (`<config dir>/docu-mcp/bookmarks.json`)
+    pub fn bookmarks_path() -> Result<PathBuf> {
+        config_file_path(BOOKMARKS_FILE_NAME)
+    }
+
+    /// Loads the bookmarks from the default location
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::bookmarks_path()?)
+    }
+
+    /// Loads a bookmarks file; a missing file gives no bookmarks
+    pub fn load_from(path: &Path) -> Result<Self> {
+        load_json(path, "bookmarks")
+    }
+
+    /// Saves the bookmarks to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::bookmarks_path()?)
+    }
+
+    /// Saves the bookmarks, replacing the file atomically
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        save_json(path, self, "bookmarks")
+    }
+
+    /// Saves a bookmark, replacing any bookmark with the same name
+    pub fn add(
+        &mut self,
+        name: &str,
+        file_path: &Path,
+        page: Option<usize>,
+        offset: Option<usize>,
+        note: Option<&str>,
+    ) -> Result<&Bookmark> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("The bookmark name is empty"));
+        }
+        if page == Some(0) {
+            return Err(anyhow::anyhow!("Page numbers start at 1"));
+        }
+        let path = file_path
+            .canonicalize()
+            .with_context(|| format!("File not found: {}", file_path.display()))?;
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", path.display()));
+        }
+
+        let bookmark = Bookmark {
+            name: name.to_string(),
+            path,
+            page,
+            offset,
+            note: note.map(str::trim).filter(|note| !note.is_empty()).map(str::to_string),
+            created: unix_seconds(SystemTime::now()).map(format_unix_time).unwrap_or_default(),
+        };
+        self.bookmarks.retain(|existing| existing.name != bookmark.name);
+        self.bookmarks.push(bookmark);
+        Ok(&self.bookmarks[self.bookmarks.len() - 1])
+    }
+
+    /// Returns the bookmark with this name
+    pub fn get(&self, name: &str) -> Result<&Bookmark> {
+        self.bookmarks
+            .iter()
+            .find(|bookmark| bookmark.name == name.trim())
+            .ok_or_else(|| anyhow::anyhow!("Bookmark not found: {}", name))
+    }
+
+    /// Removes and returns the bookmark with this name
+    pub fn remove(&mut self, name: &str) -> Result<Bookmark> {
+        let index = self
+            .bookmarks
+            .iter()
+            .position(|bookmark| bookmark.name == name.trim())
+            .ok_or_else(|| anyhow::anyhow!("Bookmark not found: {}", name))?;
+        Ok(self.bookmarks.remove(index))
+    }
+
+    /// Returns the bookmarks of one document, in page and offset order
+    pub fn for_document(&self, file_path: &Path) -> Vec<&Bookmark> {
+        let path = file_path.canonicalize().unwrap_or_else(|_| file_path.to_path_buf());
+        let mut bookmarks: Vec<&Bookmark> = self.bookmarks.iter().filter(|bookmark| bookmark.path == path).collect();
+        bookmarks.sort_by_key(|bookmark| (bookmark.page, bookmark.offset));
+        bookmarks
+    }
+}
+
+/// Extracts the text at a bookmark: up to `max_chars` characters from its offset, within
+/// its page when it has one
+pub fn open_bookmark(bookmark: &Bookmark, max_chars: usize) -> Result<BookmarkView> {
+    if max_chars == 0 {
+        return Err(anyhow::anyhow!("max_chars must be at least 1"));
+    }
+    let offset = bookmark.offset.unwrap_or(0);
+    let slice = match bookmark.page {
+        Some(page) => {
+            let pages = get_document_pages(&bookmark.path, page..=page)?;
+            let text = pages.first().map(|page| page.text.as_str()).unwrap_or_default();
+            TextSlice::from_text(text, offset, max_chars)
+        }
+        None => extract_text_slice(&bookmark.path, offset, max_chars, OutputFormat::Text)?,
+    };
+    Ok(BookmarkView { bookmark: bookmark.clone(), slice })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_add_and_persist_bookmarks() {
+        let dir = tempfile::tempdir().unwrap();
+        let document = dir.path().join("contract.txt");
+        fs::write(&document, "Clause 1. Payment within 30 days.\nClause 2. Termination with notice.").unwrap();
+
+        let mut bookmarks = Bookmarks::default();
+        let bookmark = bookmarks.add(" termination ", &document, None, Some(34), Some("Check notice period")).unwrap();
+        assert_eq!(bookmark.name, "termination");
+        assert_eq!(bookmark.path, document.canonicalize().unwrap());
+        assert!(bookmark.created.ends_with('Z'));
+        bookmarks.add("payment", &document, None, Some(10), None).unwrap();
+
+        let path = dir.path().join("config").join(BOOKMARKS_FILE_NAME);
+        bookmarks.save_to(&path).unwrap();
+        let loaded = Bookmarks::load_from(&path).unwrap();
+        assert_eq!(loaded, bookmarks);
+        assert_eq!(Bookmarks::load_from(&dir.path().join("missing.json")).unwrap(), Bookmarks::default());
+
+        let names: Vec<&str> = loaded.for_document(&document).iter().map(|bookmark| bookmark.name.as_str()).collect();
+        assert_eq!(names, ["payment", "termination"], "Bookmarks should be listed in document order");
+    }
+
+    #[test]
+    fn test_replace_and_remove_bookmarks() {
+        let dir = tempfile::tempdir().unwrap();
+        let document = dir.path().join("notes.txt");
+        fs::write(&document, "Notes").unwrap();
+
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.add("start", &document, None, None, None).unwrap();
+        bookmarks.add("start", &document, None, Some(2), Some("  ")).unwrap();
+        assert_eq!(bookmarks.bookmarks.len(), 1, "Same-name bookmarks should be replaced");
+        assert_eq!(bookmarks.get("start").unwrap().offset, Some(2));
+        assert_eq!(bookmarks.get("start").unwrap().note, None);
+
+        assert_eq!(bookmarks.remove("start").unwrap().name, "start");
+        assert!(bookmarks.remove("start").is_err());
+        assert!(bookmarks.get("start").is_err());
+
+        assert!(bookmarks.add("", &document, None, None, None).is_err());
+        assert!(bookmarks.add("page zero", &document, Some(0), None, None).is_err());
+        assert!(bookmarks.add("missing", &dir.path().join("missing.txt"), None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_open_bookmark() {
+        let dir = tempfile::tempdir().unwrap();
+        let document = dir.path().join("contract.txt");
+        fs::write(&document, "Clause 1. Payment within 30 days.\nClause 2. Termination with notice.").unwrap();
+
+        let mut bookmarks = Bookmarks::default();
+        let bookmark = bookmarks.add("termination", &document, None, Some(34), None).unwrap().clone();
+        let view = open_bookmark(&bookmark, 21).unwrap();
+        assert_eq!(view.slice.text, "Clause 2. Termination");
+        assert_eq!(view.slice.next_offset, Some(55));
+
+        let mut pdf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf.push("fixtures");
+        pdf.push("fareTable.pdf");
+        let bookmark = bookmarks.add("fares", &pdf, Some(2), None, None).unwrap().clone();
+        let view = open_bookmark(&bookmark, 10_000).unwrap();
+        assert_eq!(view.slice.text, get_document_pages(&pdf, 2..=2).unwrap()[0].text);
+    }
+}
@@ -0,0 +1,309 @@
+//! A cache of extracted text, shared by the sessions of one server
+//!
+//! Texts are keyed by the SHA-256 of the file's contents, together with its extension
+//! (which picks the extractor) and the output format, so a touched, renamed or copied
+//! document is not extracted again. Each path remembers the size and modification
+//! time it was hashed at; while they are unchanged the file is not even re-hashed.
+//! Clones share the same entries; the least recently used texts are dropped past the
+//! capacity.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::SystemTime;
+use anyhow::{Context, Result};
+use crate::extractor::{prefix_notice, resolve_extractor, OutputFormat};
+use crate::hash::file_sha256;
+
+/// Characters of extracted text kept by default
+pub const DEFAULT_CACHE_CHARS: usize = 50_000_000;
+
+/// What a cached text was extracted from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ContentKey {
+    sha256: String,
+    /// Lowercased extension of the file, which decides the extractor
+    extension: String,
+    format: OutputFormat,
+}
+
+/// The contents a path had when it was last hashed
+struct FileState {
+    size: u64,
+    modified: Option<SystemTime>,
+    sha256: String,
+}
+
+struct Entry {
+    text: Arc<str>,
+    chars: usize,
+    /// Value of the use counter when the entry was last read
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    texts: HashMap<ContentKey, Entry>,
+    files: HashMap<PathBuf, FileState>,
+    chars: usize,
+    uses: u64,
+}
+
+impl Entries {
+    /// Returns a cached text, marking it as recently used
+    fn get(&mut self, key: &ContentKey) -> Option<Arc<str>> {
+        self.uses += 1;
+        let uses = self.uses;
+        let entry = self.texts.get_mut(key)?;
+        entry.last_used = uses;
+        Some(entry.text.clone())
+    }
+}
+
+/// Extracted text of documents, shared between clones
+#[derive(Clone)]
+pub struct ExtractionCache {
+    inner: Arc<Mutex<Entries>>,
+    capacity: usize,
+}
+
+impl Default for ExtractionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExtractionCache {
+    /// Creates an empty cache holding up to `DEFAULT_CACHE_CHARS` characters
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CACHE_CHARS)
+    }
+
+    /// Creates an empty cache holding up to `capacity` characters of text
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { inner: Arc::new(Mutex::new(Entries::default())), capacity }
+    }
+
+    /// Returns the extracted text of a document (see `formatted`)
+    pub fn text(&self, file_path: &Path) -> Result<Arc<str>> {
+        self.formatted(file_path, OutputFormat::Text)
+    }
+
+    /// Returns a document extracted in `format`, extracting it unless the same contents
+    /// were extracted before
+    ///
+    /// Hashing and extraction run without holding the lock, so sessions reading
+    /// different documents do not wait on each other.
+    pub fn formatted(&self, file_path: &Path, format: OutputFormat) -> Result<Arc<str>> {
+        let path = file_path.canonicalize().map_err(|_| anyhow::anyhow!("File not found: {}", file_path.display()))?;
+        let metadata = fs::metadata(&path).with_context(|| format!("Failed to read file: {}", path.display()))?;
+        if !metadata.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+        let (size, modified) = (metadata.len(), metadata.modified().ok());
+        let extension = path.extension().map(|extension| extension.to_string_lossy().to_lowercase());
+        let extension = extension.unwrap_or_default();
+
+        let known_hash = self.lock().files.get(&path).and_then(|state| {
+            (state.size == size && state.modified == modified).then(|| state.sha256.clone())
+        });
+        let sha256 = match known_hash {
+            Some(sha256) => sha256,
+            None => file_sha256(&path).with_context(|| format!("Failed to read file: {}", path.display()))?,
+        };
+        let key = ContentKey { sha256: sha256.clone(), extension, format };
+
+        // The notice of a format mismatch names the file, so it is added to the cached
+        // text rather than cached with it, which would show one copy's path for another
+        let (extractor, notice) = resolve_extractor(&path)?;
+        let with_notice = |text: Arc<str>| match &notice {
+            Some(notice) => prefix_notice(notice, &text).into(),
+            None => text,
+        };
+
+        {
+            let mut cached = self.lock();
+            cached.files.insert(path.clone(), FileState { size, modified, sha256 });
+            if let Some(text) = cached.get(&key) {
+                return Ok(with_notice(text));
+            }
+        }
+
+        let text: Arc<str> = extractor.extract_formatted(&path, format)?.into();
+        self.insert(key, text.clone());
+        Ok(with_notice(text))
+    }
+
+    fn insert(&self, key: ContentKey, text: Arc<str>) {
+        let chars = text.chars().count();
+        if chars > self.capacity {
+            return;
+        }
+        let mut cached = self.lock();
+        if let Some(previous) = cached.texts.remove(&key) {
+            cached.chars -= previous.chars;
+        }
+        while cached.chars + chars > self.capacity {
+            let oldest = cached.texts.iter().min_by_key(|(_, entry)| entry.last_used);
+            let Some(oldest) = oldest.map(|(key, _)| key.clone()) else {
+                break;
+            };
+            if let Some(entry) = cached.texts.remove(&oldest) {
+                cached.chars -= entry.chars;
+            }
+        }
+        cached.uses += 1;
+        let last_used = cached.uses;
+        cached.chars += chars;
+        cached.texts.insert(key, Entry { text, chars, last_used });
+
+        // Paths whose contents have no cached text left only cost memory
+        let Entries { texts, files, .. } = &mut *cached;
+        files.retain(|_, state| texts.keys().any(|key| key.sha256 == state.sha256));
+    }
+
+    /// Number of cached texts
+    pub fn len(&self) -> usize {
+        self.lock().texts.len()
+    }
+
+    /// True when no text is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every entry
+    pub fn clear(&self) {
+        let mut cached = self.lock();
+        cached.texts.clear();
+        cached.files.clear();
+        cached.chars = 0;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        // A panic while holding the lock leaves the entries consistent, so the cache stays usable
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The cache shared by every caller in the process
+pub fn shared_cache() -> &'static ExtractionCache {
+    static CACHE: OnceLock<ExtractionCache> = OnceLock::new();
+    CACHE.get_or_init(ExtractionCache::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use crate::extractor::FORMAT_MISMATCH_NOTICE;
+
+    #[test]
+    fn test_cache_reuses_and_refreshes_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        fs::write(&path, "First version").unwrap();
+
+        let cache = ExtractionCache::new();
+        let first = cache.text(&path).unwrap();
+        assert_eq!(&*first, "First version");
+        assert!(Arc::ptr_eq(&first, &cache.text(&path).unwrap()), "The cached text should be reused");
+
+        fs::write(&path, "Second, longer version").unwrap();
+        assert_eq!(&*cache.text(&path).unwrap(), "Second, longer version");
+        assert_eq!(cache.len(), 2);
+
+        assert!(cache.text(&dir.path().join("missing.txt")).is_err());
+        assert!(cache.text(dir.path()).is_err());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_is_keyed_by_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("report.md");
+        fs::write(&original, "# Report\n\nRevenue grew.").unwrap();
+
+        let cache = ExtractionCache::new();
+        let text = cache.text(&original).unwrap();
+
+        // Rewriting the same contents may change the modification time, not the hash
+        fs::write(&original, "# Report\n\nRevenue grew.").unwrap();
+        assert!(Arc::ptr_eq(&text, &cache.text(&original).unwrap()));
+        let copy = dir.path().join("copy.md");
+        fs::copy(&original, &copy).unwrap();
+        assert!(Arc::ptr_eq(&text, &cache.text(&copy).unwrap()), "Copies should share the cached text");
+
+        let markdown = cache.formatted(&original, OutputFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# Report"), "Got: {}", markdown);
+        let renamed = dir.path().join("report.txt");
+        fs::copy(&original, &renamed).unwrap();
+        cache.text(&renamed).unwrap();
+        assert_eq!(cache.len(), 3, "Formats and extensions are cached apart");
+    }
+
+    #[test]
+    fn test_cache_keeps_format_notices_per_path() {
+        let mut policy = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        policy.push("fixtures");
+        policy.push("policy.docx");
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.pdf");
+        let second = dir.path().join("second.pdf");
+        fs::copy(&policy, &first).unwrap();
+        fs::copy(&policy, &second).unwrap();
+
+        let cache = ExtractionCache::new();
+        let first_text = cache.text(&first).unwrap();
+        let second_text = cache.text(&second).unwrap();
+        assert_eq!(cache.len(), 1, "Both copies should share one cached text");
+        let copies = [(&first_text, "first.pdf", "second.pdf"), (&second_text, "second.pdf", "first.pdf")];
+        for (text, own, other) in copies {
+            assert!(text.starts_with(FORMAT_MISMATCH_NOTICE), "Got: {}", text);
+            assert!(text.contains(own) && !text.contains(other), "Got: {}", text);
+            assert!(text.contains("Travel Expense Policy"), "Got: {}", text);
+        }
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = (1..=3).map(|n| dir.path().join(format!("{}.txt", n))).collect();
+        for (n, path) in paths.iter().enumerate() {
+            fs::write(path, format!("document {}.", n)).unwrap();
+        }
+
+        let cache = ExtractionCache::with_capacity(25);
+        let first = cache.text(&paths[0]).unwrap();
+        cache.text(&paths[1]).unwrap();
+        cache.text(&paths[0]).unwrap();
+        cache.text(&paths[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(Arc::ptr_eq(&first, &cache.text(&paths[0]).unwrap()), "The recently used entry should stay");
+
+        fs::write(&paths[1], "x".repeat(30)).unwrap();
+        cache.text(&paths[1]).unwrap();
+        assert_eq!(cache.len(), 2, "Texts larger than the capacity are not cached");
+    }
+
+    #[test]
+    fn test_cache_is_shared_between_clones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shared.txt");
+        fs::write(&path, "Shared text").unwrap();
+
+        let cache = ExtractionCache::new();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let (cache, path) = (cache.clone(), path.clone());
+                thread::spawn(move || cache.text(&path).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(&*handle.join().unwrap(), "Shared text");
+        }
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,323 @@
+//! Splitting extracted text into overlapping chunks for retrieval pipelines
+
+use std::path::Path;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::extractor::create_extractor;
+
+/// Chunk size used when none is given, in the chosen unit
+pub const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+/// Overlap between consecutive chunks used when none is given, in the chosen unit
+pub const DEFAULT_CHUNK_OVERLAP: usize = 200;
+
+/// Characters per token when estimating token counts
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Unit of the chunk size and overlap
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkUnit {
+    /// Unicode characters
+    #[default]
+    Chars,
+    /// Estimated model tokens: one per 4 characters of each word, at least one per word
+    Tokens,
+}
+
+/// A piece of a document's extracted text
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Chunk {
+    /// Identifier derived from the source name, offset and text, so it is the same
+    /// every time the document is chunked with the same settings
+    pub id: String,
+    /// 0-based position of the chunk in the document
+    pub index: usize,
+    /// Offset of the chunk in the extracted text, in characters
+    pub start: usize,
+    /// Offset just past the end of the chunk, in characters
+    pub end: usize,
+    pub text: String,
+}
+
+/// A document split into chunks
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkedDocument {
+    /// Extractor that read the document
+    pub extractor: &'static str,
+    /// Length of the whole extracted text, in characters
+    pub total_chars: usize,
+    pub chunks: Vec<Chunk>,
+}
+
+/// Splits text into chunks of at most `size` units, consecutive chunks sharing about
+/// `overlap` units
+///
+/// Chunks end at a paragraph break where one falls in the second half of the window,
+/// else at a sentence end, else between words.
+pub struct Chunker {
+    size: usize,
+    overlap: usize,
+    unit: ChunkUnit,
+}
+
+impl Default for Chunker {
+    fn default() -> Self {
+        Self { size: DEFAULT_CHUNK_SIZE, overlap: DEFAULT_CHUNK_OVERLAP, unit: ChunkUnit::Chars }
+    }
+}
+
+impl Chunker {
+    /// Creates a chunker counting in characters; `overlap` must be smaller than `size`
+    pub fn new(size: usize, overlap: usize) -> Result<Self> {
+        if size == 0 {
+            return Err(anyhow::anyhow!("Chunk size must be at least 1"));
+        }
+        if overlap >= size {
+            return Err(anyhow::anyhow!("Chunk overlap ({}) must be smaller than the chunk size ({})", overlap, size));
+        }
+        Ok(Self { size, overlap, unit: ChunkUnit::Chars })
+    }
+
+    /// Counts the size and overlap in characters or estimated tokens
+    pub fn with_unit(mut self, unit: ChunkUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Splits a text into chunks; `source` (e.g. the file name) goes into the chunk ids
+    pub fn chunk_text(&self, text: &str, source: &str) -> Vec<Chunk> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut chunks = Vec::new();
+        let mut start = 0;
+
+        while start < chars.len() {
+            let end = break_point(&chars, start, self.window_end(&chars, start)).max(start + 1);
+
+            // Whitespace at the edges is left out of the chunk and its offsets
+            let mut chunk_start = start;
+            while chunk_start < end && chars[chunk_start].is_whitespace() {
+                chunk_start += 1;
+            }
+            let mut chunk_end = end;
+            while chunk_end > chunk_start && chars[chunk_end - 1].is_whitespace() {
+                chunk_end -= 1;
+            }
+            if chunk_start < chunk_end {
+                let text: String = chars[chunk_start..chunk_end].iter().collect();
+                chunks.push(Chunk {
+                    id: chunk_id(source, chunk_start, &text),
+                    index: chunks.len(),
+                    start: chunk_start,
+                    end: chunk_end,
+                    text,
+                });
+            }
+
+            if end >= chars.len() {
+                break;
+            }
+            let next = self.overlap_start(&chars, end);
+            start = if next > start { next } else { end };
+        }
+        chunks
+    }
+
+    /// Returns the end of the longest window from `start` that fits the chunk size
+    fn window_end(&self, chars: &[char], start: usize) -> usize {
+        match self.unit {
+            ChunkUnit::Chars => (start + self.size).min(chars.len()),
+            ChunkUnit::Tokens => {
+                let mut tokens = 0;
+                let mut position = start;
+                while let Some((word_start, word_end)) = next_word(chars, position) {
+                    let cost = word_tokens(word_end - word_start);
+                    if tokens + cost > self.size {
+                        // A single word too long for a chunk is cut
+                        return if tokens == 0 { word_start + self.size * CHARS_PER_TOKEN } else { word_start };
+                    }
+                    tokens += cost;
+                    position = word_end;
+                }
+                chars.len()
+            }
+        }
+    }
+
+    /// Returns where the chunk after one ending at `end` starts, so the two overlap
+    fn overlap_start(&self, chars: &[char], end: usize) -> usize {
+        match self.unit {
+            ChunkUnit::Chars => {
+                let mut start = end.saturating_sub(self.overlap);
+                // Start on a word boundary rather than mid-word
+                if start > 0 && !chars[start - 1].is_whitespace() {
+                    while start < end && !chars[start].is_whitespace() {
+                        start += 1;
+                    }
+                }
+                start
+            }
+            ChunkUnit::Tokens => {
+                let mut tokens = 0;
+                let mut start = end;
+                while let Some((word_start, word_end)) = previous_word(chars, start) {
+                    let cost = word_tokens(word_end - word_start);
+                    if tokens + cost > self.overlap {
+                        break;
+                    }
+                    tokens += cost;
+                    start = word_start;
+                }
+                start
+            }
+        }
+    }
+}
+
+/// Estimated tokens of a word of `length` characters
+fn word_tokens(length: usize) -> usize {
+    length.div_ceil(CHARS_PER_TOKEN).max(1)
+}
+
+/// Estimates the model tokens of a text, the way `ChunkUnit::Tokens` counts them
+pub fn estimate_tokens(text: &str) -> usize {
+    text.split_whitespace().map(|word| word_tokens(word.chars().count())).sum()
+}
+
+/// Returns the bounds of the first word at or after `position`
+fn next_word(chars: &[char], position: usize) -> Option<(usize, usize)> {
+    let start = position + chars[position..].iter().position(|c| !c.is_whitespace())?;
+    let end = chars[start..].iter().position(|c| c.is_whitespace()).map_or(chars.len(), |length| start + length);
+    Some((start, end))
+}
+
+/// Returns the bounds of the last word ending at or before `position`
+fn previous_word(chars: &[char], position: usize) -> Option<(usize, usize)> {
+    let end = chars[..position].iter().rposition(|c| !c.is_whitespace())? + 1;
+    let start = chars[..end].iter().rposition(|c| c.is_whitespace()).map_or(0, |index| index + 1);
+    Some((start, end))
+}
+
+/// Moves the end of a window back to the best break in its second half
+fn break_point(chars: &[char], start: usize, end: usize) -> usize {
+    if end >= chars.len() {
+        return chars.len();
+    }
+    let earliest = start + (end - start) / 2 + 1;
+    let candidates = || (earliest..=end).rev();
+
+    let paragraph = candidates().find(|&p| p >= 2 && chars[p - 1] == '\n' && chars[p - 2] == '\n');
+    let sentence = || {
+        candidates().find(|&p| matches!(chars[p - 1], '.' | '!' | '?' | '。') && chars[p].is_whitespace())
+    };
+    let word = || candidates().find(|&p| chars[p].is_whitespace());
+    paragraph.or_else(sentence).or_else(word).unwrap_or(end)
+}
+
+/// Hashes the source, offset and text with 64-bit FNV-1a, which is stable across builds
+fn chunk_id(source: &str, start: usize, text: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = source.bytes().chain([0]).chain(start.to_le_bytes()).chain(text.bytes());
+    for byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Extracts a document and splits its text into chunks
+///
+/// Chunk ids include the file name, so they do not change when the directory is moved.
+pub fn chunk_document(file_path: &Path, chunker: &Chunker) -> Result<ChunkedDocument> {
+    let extractor = create_extractor(file_path)?;
+    let text = extractor.extract_text_from_file(file_path)?;
+    let source = file_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    Ok(ChunkedDocument {
+        extractor: extractor.extractor_type(),
+        total_chars: text.chars().count(),
+        chunks: chunker.chunk_text(&text, &source),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const POLICY: &str = "Travel policy\n\nFlights must be booked through the travel portal. Economy class applies to trips under six hours. Business class is allowed above that.\n\nHotels are reimbursed up to 180 USD per night. Receipts are required for every claim, and claims older than 90 days are rejected.";
+
+    fn assert_chunks_cover(text: &str, chunks: &[Chunk]) {
+        let chars: Vec<char> = text.chars().collect();
+        for chunk in chunks {
+            assert_eq!(chars[chunk.start..chunk.end].iter().collect::<String>(), chunk.text, "Offsets should match the text");
+        }
+        for pair in chunks.windows(2) {
+            assert!(pair[1].start < pair[0].end, "Chunks should overlap: {:?}", pair);
+            assert!(pair[1].start > pair[0].start, "Chunks should advance: {:?}", pair);
+        }
+        assert_eq!(chunks.first().map(|chunk| chunk.start), Some(0));
+        assert_eq!(chunks.last().map(|chunk| chunk.end), Some(chars.len()));
+    }
+
+    #[test]
+    fn test_chunk_text_by_chars() {
+        let chunks = Chunker::new(100, 20).unwrap().chunk_text(POLICY, "policy.txt");
+        assert_chunks_cover(POLICY, &chunks);
+        assert!(chunks.len() > 2, "Got: {:?}", chunks);
+        assert!(chunks.iter().all(|chunk| chunk.text.chars().count() <= 100), "Got: {:?}", chunks);
+        assert_eq!(
+            chunks[0].text, "Travel policy\n\nFlights must be booked through the travel portal.",
+            "A break early in the window is passed over for a later sentence end"
+        );
+        assert!(chunks[1].text.ends_with("six hours."), "Chunks should end at sentences. Got: {:?}", chunks[1]);
+        assert!(chunks[1].text.starts_with("the travel portal."), "Chunks should overlap on word boundaries");
+        assert!(chunks.iter().enumerate().all(|(index, chunk)| chunk.index == index));
+    }
+
+    #[test]
+    fn test_chunk_text_by_tokens() {
+        let chunks = Chunker::new(20, 5).unwrap().with_unit(ChunkUnit::Tokens).chunk_text(POLICY, "policy.txt");
+        assert_chunks_cover(POLICY, &chunks);
+        for chunk in &chunks {
+            let tokens = estimate_tokens(&chunk.text);
+            assert!(tokens <= 20, "Chunk over the token budget: {:?}", chunk);
+        }
+
+        let long_word = "x".repeat(50);
+        let chunks = Chunker::new(5, 1).unwrap().with_unit(ChunkUnit::Tokens).chunk_text(&long_word, "x");
+        assert_eq!(chunks.iter().map(|chunk| chunk.text.len()).collect::<Vec<_>>(), [20, 20, 10]);
+    }
+
+    #[test]
+    fn test_chunk_ids() {
+        let chunker = Chunker::new(100, 20).unwrap();
+        let first = chunker.chunk_text(POLICY, "policy.txt");
+        assert_eq!(first, chunker.chunk_text(POLICY, "policy.txt"), "Chunking should be deterministic");
+
+        let ids: std::collections::HashSet<&str> = first.iter().map(|chunk| chunk.id.as_str()).collect();
+        assert_eq!(ids.len(), first.len(), "Ids should be distinct");
+        assert_ne!(first[0].id, chunker.chunk_text(POLICY, "other.txt")[0].id, "The source is part of the id");
+        assert_eq!(chunk_id("a", 0, "b"), chunk_id("a", 0, "b"));
+    }
+
+    #[test]
+    fn test_chunker_validation() {
+        assert!(Chunker::new(0, 0).is_err());
+        assert!(Chunker::new(100, 100).is_err());
+        assert!(Chunker::default().chunk_text("", "empty").is_empty());
+        assert!(Chunker::default().chunk_text(" \n\n ", "blank").is_empty());
+    }
+
+    #[test]
+    fn test_chunk_document() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("policy.docx");
+
+        let document = chunk_document(&path, &Chunker::new(120, 30).unwrap()).unwrap();
+        assert_eq!(document.extractor, "DocxExtractor");
+        assert!(document.chunks.len() > 1);
+        assert_eq!(document.chunks.last().unwrap().end, document.total_chars);
+        assert!(chunk_document(&path.with_file_name("missing.docx"), &Chunker::default()).is_err());
+    }
+}
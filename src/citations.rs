@@ -0,0 +1,688 @@
+//! Pulling bibliography entries out of papers, LaTeX sources and BibTeX databases
+//!
+//! BibTeX fields are read as they are. Other entries are free text, split into
+//! authors, title, year and DOI by heuristics suited to the common styles (APA,
+//! IEEE, Chicago, MLA); a field that cannot be told apart is left empty, and the
+//! entry text is always kept.
+
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::constants::{BIB_EXTENSION, TEX_EXTENSION};
+use crate::extractor::create_extractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Headings that open a reference list, lowercased
+const REFERENCE_HEADINGS: &[&str] = &[
+    "references",
+    "bibliography",
+    "works cited",
+    "literature cited",
+    "references cited",
+    "reference list",
+];
+
+/// Starts of headings that close a reference list, lowercased
+const CLOSING_HEADINGS: &[&str] = &["appendix", "acknowledgment", "acknowledgement", "supplementary"];
+
+/// Headings longer than this are taken as text, not headings
+const MAX_HEADING_LEN: usize = 40;
+
+/// Numbers taken as publication years
+const YEARS: RangeInclusive<u32> = 1500..=2099;
+
+/// Abbreviations whose period does not end a sentence, lowercased
+const ABBREVIATIONS: &[&str] = &["al", "ed", "eds", "vol", "pp", "no", "jr", "sr", "st"];
+
+/// A bibliography entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Citation {
+    /// Citation key, for LaTeX and BibTeX entries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub authors: Vec<String>,
+    pub title: Option<String>,
+    pub year: Option<u32>,
+    pub doi: Option<String>,
+    /// The entry as written; `\bibitem` entries have their LaTeX markup removed
+    pub text: String,
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns true for section numbers such as `7`, `7.1.` or `VII.`
+fn is_section_number(word: &str) -> bool {
+    let word = word.trim_end_matches('.');
+    !word.is_empty()
+        && (word.chars().all(|c| c.is_ascii_digit() || c == '.') || word.chars().all(|c| "IVXLC".contains(c)))
+}
+
+/// Returns a line as a lowercased heading, without Markdown marks, section number or colon
+fn heading_text(line: &str) -> String {
+    let line = line.trim().trim_start_matches('#').trim();
+    let line = match line.split_once(' ') {
+        Some((number, rest)) if is_section_number(number) => rest,
+        _ => line,
+    };
+    line.trim().trim_end_matches(':').trim().to_lowercase()
+}
+
+/// Returns the lines of the last reference list in a text, up to an appendix or the end
+fn reference_lines(text: &str) -> Vec<&str> {
+    let lines: Vec<&str> = text.lines().collect();
+    let Some(start) = lines.iter().rposition(|line| REFERENCE_HEADINGS.contains(&heading_text(line).as_str())) else {
+        return Vec::new();
+    };
+    let section = &lines[start + 1..];
+    let end = section
+        .iter()
+        .position(|line| {
+            let heading = heading_text(line);
+            heading.len() <= MAX_HEADING_LEN && CLOSING_HEADINGS.iter().any(|closing| heading.starts_with(closing))
+        })
+        .unwrap_or(section.len());
+    section[..end].to_vec()
+}
+
+/// Splits a leading entry label such as `[12]`, `[Smi19]`, `12.` or `12)` off a line
+fn strip_marker(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let (label, rest) = if let Some(rest) = line.strip_prefix('[') {
+        let end = rest.find(']')?;
+        (&rest[..end], &rest[end + 1..])
+    } else {
+        let end = line.find(|c: char| !c.is_ascii_digit())?;
+        let rest = line[end..].strip_prefix(['.', ')'])?;
+        (&line[..end], rest)
+    };
+    let valid = !label.is_empty() && label.len() <= 12 && !label.contains(char::is_whitespace);
+    // Unbracketed labels are numbers; four digits would be a year starting a line
+    let valid = valid && (line.starts_with('[') || label.len() <= 3);
+    valid.then_some(rest.trim_start())
+}
+
+/// Joins the lines of an entry, mending words hyphenated across lines
+fn join_lines(lines: &[&str]) -> String {
+    let mut joined = String::new();
+    for line in lines {
+        let hyphenated = joined
+            .strip_suffix('-')
+            .is_some_and(|before| before.ends_with(char::is_lowercase))
+            && line.starts_with(char::is_lowercase);
+        if hyphenated {
+            joined.pop();
+        } else if !joined.is_empty() {
+            joined.push(' ');
+        }
+        joined.push_str(line);
+    }
+    normalize_whitespace(&joined)
+}
+
+/// Splits a reference list into entries: at labels when entries are labelled, otherwise at blank lines
+fn reference_entries(lines: &[&str]) -> Vec<String> {
+    let labelled = lines.iter().filter(|line| strip_marker(line).is_some()).count() >= 2;
+    let mut entries: Vec<Vec<&str>> = Vec::new();
+    let mut open = false;
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            open = labelled;
+            continue;
+        }
+        if labelled {
+            match strip_marker(line) {
+                Some(rest) => entries.push(vec![rest]),
+                // Text before the first label is not an entry
+                None => entries.last_mut().into_iter().for_each(|entry| entry.push(line)),
+            }
+            continue;
+        }
+        match entries.last_mut() {
+            Some(entry) if open => entry.push(line),
+            _ => {
+                entries.push(vec![line]);
+                open = true;
+            }
+        }
+    }
+    entries.iter().map(|lines| join_lines(lines)).filter(|entry| !entry.is_empty()).collect()
+}
+
+/// Finds a DOI (`10.<registrant>/<suffix>`), returning where it starts
+fn find_doi(text: &str) -> Option<(usize, String)> {
+    text.match_indices("10.").find_map(|(start, _)| {
+        let after_word = text[..start].chars().next_back().is_none_or(|c| !c.is_alphanumeric() && c != '.');
+        let rest = &text[start + 3..];
+        let registrant = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if !after_word || !(4..=9).contains(&registrant) || !rest[registrant..].starts_with('/') {
+            return None;
+        }
+        let end = text[start..].find(char::is_whitespace).map_or(text.len(), |end| start + end);
+        let doi = text[start..end].trim_end_matches(['.', ',', ';', ')', ']']);
+        (doi.len() > 3 + registrant + 1).then(|| (start, doi.to_string()))
+    })
+}
+
+/// Finds four-digit numbers that can be years, as `(start, end, year)` byte positions
+fn find_years(text: &str) -> Vec<(usize, usize, u32)> {
+    let bytes = text.as_bytes();
+    let mut years = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        if !bytes[position].is_ascii_digit() {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            position += 1;
+        }
+        if position - start == 4 {
+            if let Some(year) = text[start..position].parse().ok().filter(|year| YEARS.contains(year)) {
+                years.push((start, position, year));
+            }
+        }
+    }
+    years
+}
+
+/// Splits text after each period that ends a sentence, not an initial or an abbreviation
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (index, _) in text.match_indices(". ") {
+        let word = text[start..index].rsplit(|c: char| c.is_whitespace() || c == '-' || c == '.').next().unwrap_or("");
+        let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+        if letters == 1 || ABBREVIATIONS.contains(&word.to_lowercase().as_str()) {
+            continue;
+        }
+        sentences.push(text[start..=index].trim());
+        start = index + 2;
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        sentences.push(rest);
+    }
+    sentences
+}
+
+/// Finds the first quoted span, returning where its opening quote is and what it holds
+fn quoted(text: &str) -> Option<(usize, &str)> {
+    [('“', '”'), ('"', '"')].into_iter().find_map(|(open, close)| {
+        let start = text.find(open)?;
+        let inner = start + open.len_utf8();
+        let len = text[inner..].find(close)?;
+        Some((start, &text[inner..inner + len]))
+    })
+}
+
+fn clean_title(title: &str) -> Option<String> {
+    let title = title.trim().trim_end_matches([',', '.', ':', ' ']);
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Returns true for name parts made of initials, such as `J.`, `J. R.` or `J.-P.`
+fn is_initials(part: &str) -> bool {
+    part.split_whitespace().all(|word| {
+        let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+        let dotted = word.contains('.') || letters.len() == 1;
+        (1..=2).contains(&letters.len()) && letters[0].is_uppercase() && dotted
+    })
+}
+
+/// Splits an author list into names, keeping `Surname, Initials` pairs together
+fn split_authors(segment: &str) -> Vec<String> {
+    let mut segment = segment.trim().trim_end_matches([',', ':', ';']).trim();
+    // A period after a full word ends the sentence, one after an initial belongs to the name
+    if let Some(before) = segment.strip_suffix('.') {
+        let last_word = before.rsplit([' ', '.']).next().unwrap_or("");
+        if last_word.chars().filter(|c| c.is_alphabetic()).count() > 1 {
+            segment = before;
+        }
+    }
+    let segment = segment.replace(" et al.", "").replace('&', ",").replace(" and ", ", ");
+
+    let mut authors: Vec<String> = Vec::new();
+    for part in segment.split([',', ';']).map(str::trim) {
+        if part.is_empty() || part == "others" {
+            continue;
+        }
+        let first = authors.len() == 1;
+        match authors.last_mut() {
+            // Initials follow their surname (APA), and a lone surname first takes the given names
+            // after it (Chicago, MLA)
+            Some(last)
+                if !last.contains(',')
+                    && (is_initials(part) && !is_initials(last) || first && !last.contains(' ')) =>
+            {
+                last.push_str(", ");
+                last.push_str(part);
+            }
+            _ => authors.push(part.to_string()),
+        }
+    }
+    authors
+}
+
+/// Returns true for sentences that are only a year, as in Chicago author-date entries
+fn is_year_sentence(sentence: &str) -> bool {
+    let sentence = sentence.trim_end_matches('.').trim();
+    sentence.len() >= 4 && sentence.chars().take(4).all(|c| c.is_ascii_digit())
+        && sentence.chars().skip(4).all(|c| c.is_ascii_lowercase())
+}
+
+/// Splits a free-text bibliography entry into authors, title, year and DOI
+///
+/// A quoted title (IEEE) ends the authors; otherwise a parenthesized year (APA)
+/// does, and the title is the sentence after it; otherwise the authors are the
+/// first sentence and the title the next one that is not a year (Chicago, MLA).
+pub fn parse_reference(entry: &str) -> Citation {
+    let text = normalize_whitespace(strip_marker(entry).unwrap_or(entry));
+    let doi = find_doi(&text);
+    let body = &text[..doi.as_ref().map_or(text.len(), |(start, _)| *start)];
+    let years = find_years(body);
+    let parenthesized = years.iter().find(|(start, _, _)| body[..*start].trim_end().ends_with('('));
+    let year = parenthesized.or(years.last()).map(|(_, _, year)| *year);
+
+    let (authors, title) = if let Some((start, title)) = quoted(body) {
+        (&body[..start], Some(title))
+    } else if let Some((start, end, _)) = parenthesized {
+        let open = body[..*start].rfind('(').unwrap_or(*start);
+        let after = body[*end..].find(')').map_or(*end, |close| end + close + 1);
+        let rest = body[after..].trim_start_matches(['.', ',', ':', ' ']);
+        (&body[..open], sentences(rest).first().copied())
+    } else {
+        let sentences = sentences(body);
+        let title = sentences.iter().skip(1).find(|sentence| !is_year_sentence(sentence)).copied();
+        (sentences.first().copied().unwrap_or(""), title)
+    };
+    let authors = split_authors(authors);
+    let title = title.and_then(clean_title);
+
+    Citation { key: None, authors, title, year, doi: doi.map(|(_, doi)| doi), text }
+}
+
+/// LaTeX accent commands, with the letters they combine with and the accented letters, in the same order
+const ACCENTS: &[(char, &str, &str)] = &[
+    ('\'', "aeiouyAEIOUYcnszCNSZ", "áéíóúýÁÉÍÓÚÝćńśźĆŃŚŹ"),
+    ('`', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+    ('^', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+    ('"', "aeiouyAEIOU", "äëïöüÿÄËÏÖÜ"),
+    ('~', "anoANO", "ãñõÃÑÕ"),
+];
+
+/// Returns a letter with a LaTeX accent, or the bare letter when there is no such character
+fn accented(accent: char, letter: char) -> char {
+    ACCENTS
+        .iter()
+        .find(|(symbol, _, _)| *symbol == accent)
+        .and_then(|(_, letters, accented)| {
+            let index = letters.chars().position(|candidate| candidate == letter)?;
+            accented.chars().nth(index)
+        })
+        .unwrap_or(letter)
+}
+
+/// Removes LaTeX markup, keeping the text of commands' arguments
+fn clean_latex(source: &str) -> String {
+    let mut text = String::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.peek().copied() {
+                Some(next) if next.is_ascii_alphabetic() => {
+                    while chars.next_if(char::is_ascii_alphabetic).is_some() {}
+                    text.push(' ');
+                }
+                Some(next) if "&%$#_{}".contains(next) => {
+                    text.push(next);
+                    chars.next();
+                }
+                Some(accent) if ACCENTS.iter().any(|(symbol, _, _)| *symbol == accent) => {
+                    chars.next();
+                    let braced = chars.next_if_eq(&'{').is_some();
+                    if let Some(letter) = chars.next() {
+                        text.push(accented(accent, letter));
+                    }
+                    if braced {
+                        chars.next_if_eq(&'}');
+                    }
+                }
+                // `\\` breaks a line, and other symbols are spacing
+                Some(_) => {
+                    chars.next();
+                    text.push(' ');
+                }
+                None => {}
+            },
+            '{' | '}' => {}
+            '~' => text.push(' '),
+            c => text.push(c),
+        }
+    }
+    normalize_whitespace(&text)
+}
+
+/// Removes LaTeX comments, which run from an unescaped `%` to the end of the line
+fn strip_comments(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| {
+            let comment = line.match_indices('%').find(|(index, _)| !line[..*index].ends_with('\\'));
+            comment.map_or(line, |(index, _)| &line[..index])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the position of the delimiter closing the one at `open`, skipping braced groups
+fn closing_delimiter(source: &str, open: usize) -> Option<usize> {
+    let close = match source.as_bytes()[open] {
+        b'(' => b')',
+        b'"' => b'"',
+        _ => b'}',
+    };
+    let mut depth = 0;
+    for (index, &byte) in source.as_bytes().iter().enumerate().skip(open + 1) {
+        match byte {
+            b'{' => depth += 1,
+            b'}' if depth > 0 => depth -= 1,
+            byte if byte == close && depth == 0 => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads one BibTeX field value, joining parts concatenated with `#`
+fn bibtex_value(mut rest: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    loop {
+        rest = rest.trim_start();
+        let (part, remainder) = if rest.starts_with(['{', '"']) {
+            let end = closing_delimiter(rest, 0)?;
+            (&rest[1..end], &rest[end + 1..])
+        } else {
+            let end = rest.find([',', '#']).unwrap_or(rest.len());
+            (rest[..end].trim(), &rest[end..])
+        };
+        value.push_str(part);
+        rest = remainder.trim_start();
+        match rest.strip_prefix('#') {
+            Some(remainder) => rest = remainder,
+            None => return Some((value, rest)),
+        }
+    }
+}
+
+/// Reads the `name = value` fields of a BibTeX entry, with lowercased names
+fn bibtex_fields(mut rest: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        let Some(equals) = rest.find('=') else {
+            return fields;
+        };
+        let name = rest[..equals].trim().to_lowercase();
+        let Some((value, remainder)) = bibtex_value(&rest[equals + 1..]) else {
+            return fields;
+        };
+        fields.push((name, value));
+        rest = remainder;
+    }
+}
+
+/// Parses the entries of a BibTeX database, skipping `@string`, `@preamble` and `@comment` blocks
+fn parse_bibtex(source: &str) -> Vec<Citation> {
+    let mut citations = Vec::new();
+    let mut position = 0;
+    while let Some(offset) = source[position..].find('@') {
+        let start = position + offset;
+        position = start + 1;
+        let Some(open) = source[position..].find(['{', '(']).map(|open| position + open) else {
+            break;
+        };
+        let entry_type = source[position..open].trim().to_lowercase();
+        // An `@` outside an entry, such as in an email address
+        if entry_type.is_empty() || !entry_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+            continue;
+        }
+        let Some(end) = closing_delimiter(source, open) else {
+            break;
+        };
+        position = end + 1;
+        if matches!(entry_type.as_str(), "string" | "preamble" | "comment") {
+            continue;
+        }
+
+        let body = &source[open + 1..end];
+        let (key, fields) = body.split_once(',').unwrap_or((body, ""));
+        let fields = bibtex_fields(fields);
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| clean_latex(value))
+                .filter(|value| !value.is_empty())
+        };
+        let authors = field("author").or_else(|| field("editor")).map_or_else(Vec::new, |names| {
+            names.split(" and ").map(str::trim).filter(|name| !name.is_empty() && *name != "others")
+                .map(str::to_string).collect()
+        });
+        let doi = field("doi").map(|doi| match doi.find("10.") {
+            Some(start) => doi[start..].to_string(),
+            None => doi,
+        });
+        citations.push(Citation {
+            key: Some(key.trim().to_string()).filter(|key| !key.is_empty()),
+            authors,
+            title: field("title"),
+            year: field("year").and_then(|year| find_years(&year).first().map(|(_, _, year)| *year)),
+            doi,
+            text: source[start..=end].trim().to_string(),
+        });
+    }
+    citations
+}
+
+/// Returns the `.bib` files named by `\bibliography` and `\addbibresource`, relative to `directory`
+fn bibliography_files(source: &str, directory: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for command in ["\\bibliography{", "\\addbibresource{"] {
+        for (start, _) in source.match_indices(command) {
+            let names = &source[start + command.len()..];
+            let names = &names[..names.find('}').unwrap_or(names.len())];
+            for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+                let path = directory.join(name);
+                files.push(if path.extension().is_some() { path } else { path.with_extension(BIB_EXTENSION) });
+            }
+        }
+    }
+    files
+}
+
+fn read_source(file_path: &Path) -> Result<String> {
+    let bytes = fs::read(file_path).with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+    Ok(TxtExtractor::decode_bytes(&bytes))
+}
+
+/// Reads the `\bibitem` entries of a LaTeX source, or else the BibTeX databases it names
+fn latex_citations(source: &str, directory: &Path) -> Result<Vec<Citation>> {
+    let source = strip_comments(source);
+    let items: Vec<usize> = source.match_indices("\\bibitem").map(|(start, _)| start).collect();
+    let mut citations = Vec::new();
+    for (number, &start) in items.iter().enumerate() {
+        let end = items.get(number + 1).copied().unwrap_or_else(|| {
+            source[start..].find("\\end{thebibliography}").map_or(source.len(), |end| start + end)
+        });
+        let mut entry = source[start + "\\bibitem".len()..end].trim_start();
+        if entry.starts_with('[') {
+            entry = entry.find(']').map_or(entry, |close| &entry[close + 1..]).trim_start();
+        }
+        let mut key = None;
+        if let Some(close) = entry.starts_with('{').then(|| closing_delimiter(entry, 0)).flatten() {
+            key = Some(entry[1..close].trim().to_string());
+            entry = &entry[close + 1..];
+        }
+        let mut citation = parse_reference(&clean_latex(entry));
+        citation.key = key;
+        citations.push(citation);
+    }
+
+    if citations.is_empty() {
+        for file in bibliography_files(&source, directory) {
+            if !file.is_file() {
+                return Err(anyhow::anyhow!("Bibliography file not found: {}", file.display()));
+            }
+            citations.extend(parse_bibtex(&read_source(&file)?));
+        }
+    }
+    Ok(citations)
+}
+
+/// Extracts the bibliography entries of a document
+///
+/// BibTeX databases (`.bib`) are parsed field by field. LaTeX sources (`.tex`) give
+/// their `\bibitem` entries, or else the entries of the `.bib` files they name. Other
+/// documents are extracted to text, and the entries of their last reference list
+/// ("References", "Bibliography", "Works Cited", ...) are parsed with `parse_reference`.
+pub fn extract_citations(file_path: &Path) -> Result<Vec<Citation>> {
+    let metadata = fs::metadata(file_path).with_context(|| format!("File not found: {}", file_path.display()))?;
+    if !metadata.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+    }
+
+    let extension = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if extension == BIB_EXTENSION {
+        return Ok(parse_bibtex(&read_source(file_path)?));
+    }
+    if extension == TEX_EXTENSION {
+        let directory = file_path.parent().unwrap_or(Path::new("."));
+        return latex_citations(&read_source(file_path)?, directory);
+    }
+
+    let text = create_extractor(file_path)?.extract_text_from_file(file_path)?;
+    Ok(reference_entries(&reference_lines(&text)).iter().map(|entry| parse_reference(entry)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference() {
+        let apa = parse_reference(
+            "Smith, J., & Doe, A. B. (2019). Learning to read receipts. Journal of Travel, 12(3), 45–67. \
+             https://doi.org/10.1234/jt.2019.045.",
+        );
+        assert_eq!(apa.authors, ["Smith, J.", "Doe, A. B."]);
+        assert_eq!(apa.title.as_deref(), Some("Learning to read receipts"));
+        assert_eq!(apa.year, Some(2019));
+        assert_eq!(apa.doi.as_deref(), Some("10.1234/jt.2019.045"));
+
+        let ieee = parse_reference(
+            "[3] J. Smith, X. Li, and A. Doe, “Fare prediction at scale,” in Proc. ICDE, vol. 7, \
+             pp. 1100–1112, 2021.",
+        );
+        assert_eq!(ieee.authors, ["J. Smith", "X. Li", "A. Doe"]);
+        assert_eq!(ieee.title.as_deref(), Some("Fare prediction at scale"));
+        assert_eq!(ieee.year, Some(2021));
+        assert_eq!(ieee.doi, None);
+        assert!(ieee.text.starts_with("J. Smith"));
+
+        let chicago = parse_reference("Smith, John, and Jane Doe. 2018. Airports and Their Cities. Chicago: Press.");
+        assert_eq!(chicago.authors, ["Smith, John", "Jane Doe"]);
+        assert_eq!(chicago.title.as_deref(), Some("Airports and Their Cities"));
+        assert_eq!(chicago.year, Some(2018));
+
+        let unknown = parse_reference("Untitled notes");
+        assert_eq!(unknown.authors, ["Untitled notes"]);
+        assert_eq!((unknown.title, unknown.year), (None, None));
+    }
+
+    #[test]
+    fn test_reference_entries() {
+        let text = "Intro\nWe cite [1].\n\n7. References\n[1] A. Doe, “One,” 2001.\n[2] B. Roe, “Two\nparts,” \
+                    2002.\n\nAppendix A\n[3] Not an entry, 2003.\n";
+        let entries = reference_entries(&reference_lines(text));
+        assert_eq!(entries, ["A. Doe, “One,” 2001.", "B. Roe, “Two parts,” 2002."]);
+
+        let text = "# Bibliography\n\nDoe, A. (2001). One\ntwo-\nfold. Press.\n\nRoe, B. (2002). Two. Press.\n";
+        assert_eq!(
+            reference_entries(&reference_lines(text)),
+            ["Doe, A. (2001). One twofold. Press.", "Roe, B. (2002). Two. Press."]
+        );
+        assert!(reference_lines("No reference list here").is_empty());
+    }
+
+    #[test]
+    fn test_parse_bibtex() {
+        let source = r#"@string{jt = "Journal of Travel"}
+Contact: someone@example.com
+@article{smith2019,
+  author = {Smith, John and Doe, Ann and others},
+  title = {Learning to Read {PDF} Receipts},
+  journal = jt,
+  year = 2019,
+  doi = {https://doi.org/10.1234/jt.2019.045}
+}
+@Book(roe2020, title = "Airports " # "and Cities", editor = {R{\'e}my Roe}, year = {2020})"#;
+        let citations = parse_bibtex(source);
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].key.as_deref(), Some("smith2019"));
+        assert_eq!(citations[0].authors, ["Smith, John", "Doe, Ann"]);
+        assert_eq!(citations[0].title.as_deref(), Some("Learning to Read PDF Receipts"));
+        assert_eq!(citations[0].year, Some(2019));
+        assert_eq!(citations[0].doi.as_deref(), Some("10.1234/jt.2019.045"));
+        assert!(citations[0].text.starts_with("@article{smith2019,") && citations[0].text.ends_with('}'));
+        assert_eq!(citations[1].title.as_deref(), Some("Airports and Cities"));
+        assert_eq!(citations[1].authors, ["Rémy Roe"]);
+        assert_eq!(citations[1].year, Some(2020));
+    }
+
+    #[test]
+    fn test_extract_citations() {
+        let dir = tempfile::tempdir().unwrap();
+        let inline = dir.path().join("inline.tex");
+        fs::write(
+            &inline,
+            "See \\cite{doe01}.\n\\begin{thebibliography}{9}\n\
+             \\bibitem[Doe(2001)]{doe01} A.~Doe. \\newblock \\emph{Routes \\& Fares}. \\newblock Press, 2001. % old\n\
+             \\bibitem{roe02} B. Roe, ``Two,'' 2002.\n\\end{thebibliography}\nThe end.\n",
+        )
+        .unwrap();
+        let citations = extract_citations(&inline).unwrap();
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].key.as_deref(), Some("doe01"));
+        assert_eq!(citations[0].authors, ["A. Doe"]);
+        assert_eq!(citations[0].title.as_deref(), Some("Routes & Fares"));
+        assert_eq!(citations[0].year, Some(2001));
+        assert_eq!(citations[1].key.as_deref(), Some("roe02"));
+        assert!(!citations[1].text.contains("The end"));
+
+        let paper = dir.path().join("paper.tex");
+        fs::write(&paper, "Text.\n\\bibliographystyle{plain}\n\\bibliography{refs}\n").unwrap();
+        assert!(extract_citations(&paper).unwrap_err().to_string().contains("Bibliography file not found"));
+        fs::write(dir.path().join("refs.bib"), "@misc{a, title = {A}}\n").unwrap();
+        let citations = extract_citations(&paper).unwrap();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].key.as_deref(), Some("a"));
+
+        let notes = dir.path().join("notes.md");
+        let markdown = "# Notes\n\nBody.\n\n## References\n\n1. Doe, A. (2001). One. Press.\n2. Roe, B. (2002). Two.\n";
+        fs::write(&notes, markdown).unwrap();
+        let citations = extract_citations(&notes).unwrap();
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[1].title.as_deref(), Some("Two"));
+
+        assert!(extract_citations(&dir.path().join("missing.bib")).is_err());
+    }
+}
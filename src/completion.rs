@@ -0,0 +1,175 @@
+//! Completion of path arguments, for MCP's `completion/complete`
+//!
+//! Paths are completed against the file system, relative ones from the active directory.
+//! Directory arguments are also completed with the aliases and paths of the configured
+//! directories.
+
+use std::fs;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use serde::Serialize;
+use crate::config::Config;
+
+/// Most values a completion returns, as MCP allows
+pub const MAX_COMPLETIONS: usize = 100;
+
+/// What a path argument names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathKind {
+    /// A document, e.g. `file_path`; directories are offered too, to complete through them
+    File,
+    /// A directory, e.g. `directory`
+    Directory,
+}
+
+/// Values completing an argument, A to Z
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Completion {
+    pub values: Vec<String>,
+    /// Number of values found, including those beyond `MAX_COMPLETIONS`
+    pub total: usize,
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+}
+
+/// Completes a partly typed path argument
+///
+/// Directories are completed with a trailing separator so completion can continue
+/// inside them. Hidden entries are offered only once their leading `.` is typed.
+pub fn complete_path(value: &str, kind: PathKind, config: &Config) -> Completion {
+    let mut values = Vec::new();
+    if kind == PathKind::Directory {
+        values.extend(config.aliases.keys().filter(|alias| alias.starts_with(value)).cloned());
+        values.extend(
+            config
+                .directories
+                .iter()
+                .map(|directory| directory.to_string_lossy().into_owned())
+                .filter(|directory| directory.starts_with(value)),
+        );
+    }
+    values.extend(complete_entries(value, kind, config.active_directory.as_deref()));
+
+    values.sort();
+    values.dedup();
+    let total = values.len();
+    values.truncate(MAX_COMPLETIONS);
+    Completion { values, total, has_more: total > MAX_COMPLETIONS }
+}
+
+/// Lists the entries of the directory `value` points into whose names start with its last component
+fn complete_entries(value: &str, kind: PathKind, active_directory: Option<&Path>) -> Vec<String> {
+    // `value` is split where its last component starts, so completions keep what was typed
+    let split = value.rfind(['/', MAIN_SEPARATOR]).map_or(0, |position| position + 1);
+    let (typed_directory, prefix) = value.split_at(split);
+
+    let directory = match typed_directory.strip_prefix("~/") {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => home.join(rest),
+            None => return Vec::new(),
+        },
+        None => PathBuf::from(typed_directory),
+    };
+    let directory = match active_directory {
+        _ if directory.is_absolute() => directory,
+        Some(active) => active.join(directory),
+        None => return Vec::new(),
+    };
+
+    let Ok(entries) = fs::read_dir(&directory) else {
+        return Vec::new();
+    };
+    let prefix_lower = prefix.to_lowercase();
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') && !prefix.starts_with('.') {
+                return None;
+            }
+            if !name.to_lowercase().starts_with(&prefix_lower) {
+                return None;
+            }
+            // Follows symbolic links, like `Path::is_dir`
+            let is_dir = entry.path().is_dir();
+            match (is_dir, kind) {
+                (true, _) => Some(format!("{}{}{}", typed_directory, name, MAIN_SEPARATOR)),
+                (false, PathKind::File) => Some(format!("{}{}", typed_directory, name)),
+                (false, PathKind::Directory) => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["Taxes/2024/return.pdf", "Taxes/receipts.zip", "tax-notes.md", "travel.pdf", ".secrets/key.txt"] {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_complete_relative_paths() {
+        let dir = sample_directory();
+        let config = Config { active_directory: Some(dir.path().to_path_buf()), ..Config::default() };
+        let complete = |value: &str, kind: PathKind| complete_path(value, kind, &config).values;
+
+        assert_eq!(complete("ta", PathKind::File), ["Taxes/", "tax-notes.md"], "Case is ignored");
+        assert_eq!(complete("Taxes/", PathKind::File), ["Taxes/2024/", "Taxes/receipts.zip"]);
+        assert_eq!(complete("Taxes/2024/re", PathKind::File), ["Taxes/2024/return.pdf"]);
+        assert_eq!(complete("Taxes/", PathKind::Directory), ["Taxes/2024/"], "Only directories");
+        let top_level = ["Taxes/", "tax-notes.md", "travel.pdf"];
+        assert_eq!(complete("", PathKind::File), top_level, "Hidden entries are skipped");
+        assert_eq!(complete(".s", PathKind::File), [".secrets/"]);
+        assert!(complete("missing/", PathKind::File).is_empty());
+
+        // Without an active directory, only absolute paths can be completed
+        assert!(complete_path("ta", PathKind::File, &Config::default()).values.is_empty());
+    }
+
+    #[test]
+    fn test_complete_absolute_paths_and_aliases() {
+        let dir = sample_directory();
+        let taxes = dir.path().join("Taxes");
+        let mut config = Config::default();
+        config.set_alias("taxes", &taxes.to_string_lossy()).unwrap();
+        config.set_alias("travel-2025", &dir.path().to_string_lossy()).unwrap();
+        let taxes = taxes.canonicalize().unwrap().to_string_lossy().into_owned();
+
+        let completion = complete_path("t", PathKind::Directory, &config);
+        assert_eq!(completion.values, ["taxes", "travel-2025"]);
+        assert_eq!(completion.total, 2);
+        assert!(!completion.has_more);
+
+        // Configured directories are offered as well as what is on disk
+        let completion = complete_path(&taxes[..taxes.len() - 2], PathKind::Directory, &config);
+        assert_eq!(completion.values, [taxes.clone(), format!("{}/", taxes)]);
+
+        let value = format!("{}/", taxes);
+        assert_eq!(
+            complete_path(&value, PathKind::File, &config).values,
+            [format!("{}/2024/", taxes), format!("{}/receipts.zip", taxes)]
+        );
+    }
+
+    #[test]
+    fn test_completion_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        for index in 0..MAX_COMPLETIONS + 5 {
+            fs::write(dir.path().join(format!("scan-{:03}.pdf", index)), "x").unwrap();
+        }
+        let config = Config { active_directory: Some(dir.path().to_path_buf()), ..Config::default() };
+
+        let completion = complete_path("scan-", PathKind::File, &config);
+        assert_eq!(completion.values.len(), MAX_COMPLETIONS);
+        assert_eq!(completion.total, MAX_COMPLETIONS + 5);
+        assert!(completion.has_more);
+        assert_eq!(completion.values[0], "scan-000.pdf");
+    }
+}
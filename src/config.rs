@@ -0,0 +1,805 @@
+//! Persistent configuration: the document directories, their aliases and the active one,
+//! and the passwords of encrypted documents
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use anyhow::{Context, Result};
+use glob::Pattern;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::listing::GLOB_OPTIONS;
+use crate::protocol::ClientCapabilities;
+
+/// Directory under the user's configuration directory holding docu-mcp's files
+const CONFIG_DIR_NAME: &str = "docu-mcp";
+
+/// Name of the configuration file
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// Service under which document passwords are saved in the OS keychain
+const KEYCHAIN_SERVICE: &str = "docu-mcp";
+
+/// Returns the path of a file in docu-mcp's configuration directory
+pub(crate) fn config_file_path(file_name: &str) -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().context("Could not determine the user's configuration directory")?;
+    Ok(config_dir.join(CONFIG_DIR_NAME).join(file_name))
+}
+
+/// Loads a JSON file; a missing file gives the default value
+pub(crate) fn load_json<T: DeserializeOwned + Default>(path: &Path, description: &str) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}: {}", description, path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid {}: {}", description, path.display()))
+}
+
+/// Saves a JSON file, replacing it atomically so a crash cannot truncate it
+pub(crate) fn save_json<T: Serialize>(path: &Path, value: &T, description: &str) -> Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create configuration directory: {}", parent.display()))?;
+
+    let content = serde_json::to_string_pretty(value).with_context(|| format!("Failed to serialize {}", description))?;
+    let temp_path = path.with_extension("json.tmp");
+    write_private(&temp_path, content.as_bytes())
+        .with_context(|| format!("Failed to write {}: {}", description, temp_path.display()))?;
+    fs::rename(&temp_path, path).with_context(|| format!("Failed to write {}: {}", description, path.display()))
+}
+
+/// Writes a file only its owner can read on Unix, as it may hold document passwords
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files, so a temp file left by a crash is fixed too
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(content)
+}
+
+/// Document directories known to docu-mcp
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Configured directories, in the order they were added
+    #[serde(default)]
+    pub directories: Vec<PathBuf>,
+    /// Directory that directory-wide tools operate on
+    #[serde(default)]
+    pub active_directory: Option<PathBuf>,
+    /// Short names of configured directories, e.g. `taxes`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, PathBuf>,
+    /// Passwords of encrypted documents, by absolute file path or glob, e.g. `/home/me/Bank/*.pdf`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub passwords: BTreeMap<String, StoredPassword>,
+}
+
+/// Where the password of a document is kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoredPassword {
+    /// In the configuration file, in clear
+    Password(String),
+    /// In the OS keychain (macOS Keychain, or the Secret Service on Linux), under the path or glob
+    Keychain,
+}
+
+/// A configured directory, as listed by `Config::list_directories`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfiguredDirectory {
+    /// 1-based position, usable instead of the path to select the directory
+    pub index: usize,
+    pub path: PathBuf,
+    /// Aliases of the directory, A to Z
+    pub aliases: Vec<String>,
+    pub active: bool,
+    /// False when the directory no longer exists
+    pub exists: bool,
+}
+
+/// Directories removed by `Config::remove_directory`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RemovedDirectories {
+    pub removed: Vec<PathBuf>,
+    /// True when the active directory was among them and has been cleared
+    pub active_cleared: bool,
+}
+
+impl Config {
+    /// Returns the path of the configuration file (`<config dir>/docu-mcp/config.json`)
+    pub fn config_path() -> Result<PathBuf> {
+        config_file_path(CONFIG_FILE_NAME)
+    }
+
+    /// Loads the configuration from the default location
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::config_path()?)
+    }
+
+    /// Loads a configuration file; a missing file gives an empty configuration
+    pub fn load_from(path: &Path) -> Result<Self> {
+        load_json(path, "configuration")
+    }
+
+    /// Saves the configuration to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::config_path()?)
+    }
+
+    /// Saves the configuration, replacing the file atomically so a crash cannot truncate it
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        save_json(path, self, "configuration")
+    }
+
+    /// Makes `directory` the active directory, adding it to the configured ones if needed
+    pub fn set_active_directory(&mut self, directory: &Path) -> Result<PathBuf> {
+        let directory = directory
+            .canonicalize()
+            .with_context(|| format!("Directory not found: {}", directory.display()))?;
+        if !directory.is_dir() {
+            return Err(anyhow::anyhow!("Path is not a directory: {}", directory.display()));
+        }
+
+        if !self.directories.contains(&directory) {
+            self.directories.push(directory.clone());
+        }
+        self.active_directory = Some(directory.clone());
+        Ok(directory)
+    }
+
+    /// Returns the configured directories with their positions and aliases
+    pub fn list_directories(&self) -> Vec<ConfiguredDirectory> {
+        self.directories
+            .iter()
+            .enumerate()
+            .map(|(position, path)| ConfiguredDirectory {
+                index: position + 1,
+                path: path.clone(),
+                aliases: self
+                    .aliases
+                    .iter()
+                    .filter(|(_, aliased)| *aliased == path)
+                    .map(|(alias, _)| alias.clone())
+                    .collect(),
+                active: self.active_directory.as_ref() == Some(path),
+                exists: path.is_dir(),
+            })
+            .collect()
+    }
+
+    /// Resolves a directory given as a 1-based position in `directories`, an alias or a path
+    ///
+    /// Paths are returned as given, whether configured or not.
+    pub fn resolve_directory(&self, selector: &str) -> Result<PathBuf> {
+        let selector = selector.trim();
+        if selector.is_empty() {
+            return Err(anyhow::anyhow!("The directory is empty"));
+        }
+        if let Ok(index) = selector.parse::<usize>() {
+            return index
+                .checked_sub(1)
+                .and_then(|position| self.directories.get(position))
+                .cloned()
+                .ok_or_else(|| {
+                    anyhow::anyhow!("No directory {}: {} directories are configured", index, self.directories.len())
+                });
+        }
+        if let Some(directory) = self.aliases.get(selector) {
+            return Ok(directory.clone());
+        }
+        Ok(PathBuf::from(selector))
+    }
+
+    /// Makes the directory given by position, alias or path the active directory
+    pub fn switch_directory(&mut self, selector: &str) -> Result<PathBuf> {
+        let directory = self.resolve_directory(selector)?;
+        self.set_active_directory(&directory)
+    }
+
+    /// Names a directory given by position, alias or path, adding it to the configured ones if needed
+    ///
+    /// An existing alias of the same name is moved to the new directory. Aliases made
+    /// only of digits are refused, as they would read as positions.
+    pub fn set_alias(&mut self, alias: &str, selector: &str) -> Result<PathBuf> {
+        let alias = validate_alias(alias)?;
+        let directory = self
+            .resolve_directory(selector)?
+            .canonicalize()
+            .with_context(|| format!("Directory not found: {}", selector))?;
+        if !directory.is_dir() {
+            return Err(anyhow::anyhow!("Path is not a directory: {}", directory.display()));
+        }
+        if !self.directories.contains(&directory) {
+            self.directories.push(directory.clone());
+        }
+        self.aliases.insert(alias.to_string(), directory.clone());
+        Ok(directory)
+    }
+
+    /// Renames an alias, keeping the directory it names
+    pub fn rename_alias(&mut self, alias: &str, new_alias: &str) -> Result<PathBuf> {
+        let new_alias = validate_alias(new_alias)?;
+        if new_alias != alias.trim() && self.aliases.contains_key(new_alias) {
+            return Err(anyhow::anyhow!("Alias already exists: {}", new_alias));
+        }
+        let directory = self
+            .aliases
+            .remove(alias.trim())
+            .ok_or_else(|| anyhow::anyhow!("Alias not found: {}", alias))?;
+        self.aliases.insert(new_alias.to_string(), directory.clone());
+        Ok(directory)
+    }
+
+    /// Removes an alias; the directory stays configured
+    pub fn remove_alias(&mut self, alias: &str) -> Result<PathBuf> {
+        self.aliases
+            .remove(alias.trim())
+            .ok_or_else(|| anyhow::anyhow!("Alias not found: {}", alias))
+    }
+
+    /// Describes a directory by its aliases and path, e.g. `taxes (/home/me/Documents/Taxes)`
+    pub fn directory_label(&self, directory: &Path) -> String {
+        let aliases: Vec<&str> = self
+            .aliases
+            .iter()
+            .filter(|(_, aliased)| aliased.as_path() == directory)
+            .map(|(alias, _)| alias.as_str())
+            .collect();
+        if aliases.is_empty() {
+            directory.display().to_string()
+        } else {
+            format!("{} ({})", aliases.join(", "), directory.display())
+        }
+    }
+
+    /// Removes `directory` from the configured directories, clearing the active
+    /// directory if it was the one removed
+    ///
+    /// With `prune_missing`, directories that no longer exist are removed too. Errors
+    /// when `directory` is not configured and nothing was pruned.
+    pub fn remove_directory(&mut self, directory: &Path, prune_missing: bool) -> Result<RemovedDirectories> {
+        // A directory deleted from disk can no longer be canonicalized
+        let canonical = directory.canonicalize().ok();
+        let is_target = |configured: &PathBuf| configured == directory || Some(configured) == canonical.as_ref();
+
+        let (removed, kept): (Vec<PathBuf>, Vec<PathBuf>) = self
+            .directories
+            .drain(..)
+            .partition(|configured| is_target(configured) || (prune_missing && !configured.is_dir()));
+        self.directories = kept;
+
+        if !removed.iter().any(is_target) && !prune_missing {
+            return Err(anyhow::anyhow!("Directory is not configured: {}", directory.display()));
+        }
+
+        self.aliases.retain(|_, aliased| !removed.contains(aliased));
+        let active_cleared = self.active_directory.as_ref().is_some_and(|active| removed.contains(active));
+        if active_cleared {
+            self.active_directory = None;
+        }
+        Ok(RemovedDirectories { removed, active_cleared })
+    }
+
+    /// Saves the password of the documents matching a file path or glob, replacing any previous one
+    ///
+    /// Relative paths and globs are taken from the current directory and `~` is expanded.
+    /// With `keychain`, the password goes to the OS keychain and the configuration only
+    /// records that it is there. Returns the path or glob as saved.
+    pub fn set_document_password(&mut self, pattern: &str, password: &str, keychain: bool) -> Result<String> {
+        let pattern = normalize_password_pattern(pattern)?;
+        if password.is_empty() {
+            return Err(anyhow::anyhow!("The password is empty"));
+        }
+
+        let stored = if keychain {
+            keychain_command(KeychainAction::Store(password), &pattern)?;
+            StoredPassword::Keychain
+        } else {
+            // A password moved back to the configuration must not linger in the keychain
+            if self.passwords.get(&pattern) == Some(&StoredPassword::Keychain) {
+                keychain_command(KeychainAction::Delete, &pattern)?;
+            }
+            StoredPassword::Password(password.to_string())
+        };
+        self.passwords.insert(pattern.clone(), stored);
+        Ok(pattern)
+    }
+
+    /// Forgets the password saved for a file path or glob, deleting it from the OS keychain if it is there
+    pub fn remove_document_password(&mut self, pattern: &str) -> Result<String> {
+        let pattern = normalize_password_pattern(pattern)
+            .ok()
+            .filter(|pattern| self.passwords.contains_key(pattern))
+            .unwrap_or_else(|| pattern.trim().to_string());
+        match self.passwords.remove(&pattern) {
+            Some(StoredPassword::Keychain) => {
+                keychain_command(KeychainAction::Delete, &pattern)?;
+            }
+            Some(StoredPassword::Password(_)) => {}
+            None => return Err(anyhow::anyhow!("No password is saved for: {}", pattern)),
+        }
+        Ok(pattern)
+    }
+
+    /// Returns the password saved for a document, if any
+    ///
+    /// A password saved for the exact path wins over globs; among matching globs the
+    /// longest, most specific one is used.
+    pub fn document_password(&self, file_path: &Path) -> Result<Option<String>> {
+        // Globs may name a directory through a symbolic link, so the path as given is tried too
+        let paths = [file_path.canonicalize().unwrap_or_else(|_| file_path.to_path_buf()), file_path.to_path_buf()];
+        let exact = paths
+            .iter()
+            .find_map(|path| self.passwords.get_key_value(path.to_string_lossy().as_ref()));
+        let matching = exact.or_else(|| {
+            self.passwords
+                .iter()
+                .filter(|(pattern, _)| {
+                    Pattern::new(pattern)
+                        .is_ok_and(|glob| paths.iter().any(|path| glob.matches_path_with(path, GLOB_OPTIONS)))
+                })
+                .max_by_key(|(pattern, _)| pattern.len())
+        });
+        match matching {
+            None => Ok(None),
+            Some((_, StoredPassword::Password(password))) => Ok(Some(password.clone())),
+            Some((pattern, StoredPassword::Keychain)) => keychain_command(KeychainAction::Lookup, pattern).map(Some),
+        }
+    }
+}
+
+/// Configuration of one docu-mcp instance, kept in memory
+///
+/// Directory changes (active directory, directories, aliases) stay in the session until
+/// `save`, so instances attached to different projects do not overwrite each other's
+/// active directory through the shared configuration file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    config: Config,
+    path: PathBuf,
+    unsaved: bool,
+    client: ClientCapabilities,
+    /// Document passwords as last loaded or saved, to tell which ones the session changed
+    saved_passwords: BTreeMap<String, StoredPassword>,
+}
+
+impl Session {
+    /// Starts a session from the configuration in the default location
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Config::config_path()?)
+    }
+
+    /// Starts a session from a configuration file; a missing file gives an empty configuration
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let config = Config::load_from(path)?;
+        Ok(Self {
+            saved_passwords: config.passwords.clone(),
+            config,
+            path: path.to_path_buf(),
+            unsaved: false,
+            client: ClientCapabilities::default(),
+        })
+    }
+
+    /// Returns the session's configuration
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the session's configuration for changes, which stay in memory until `save`
+    pub fn config_mut(&mut self) -> &mut Config {
+        self.unsaved = true;
+        &mut self.config
+    }
+
+    /// Returns the path of the configuration file the session was loaded from and saves to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the session's active directory
+    pub fn active_directory(&self) -> Option<&Path> {
+        self.config.active_directory.as_deref()
+    }
+
+    /// Returns what the session's client declared it supports; nothing before `initialize`
+    pub fn client_capabilities(&self) -> &ClientCapabilities {
+        &self.client
+    }
+
+    /// Records the capabilities from the client's `initialize` request
+    pub fn set_client_capabilities(&mut self, capabilities: ClientCapabilities) {
+        self.client = capabilities;
+    }
+
+    /// True when the session's directories may differ from the saved ones
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved
+    }
+
+    /// Saves the session's directories, aliases and active directory, and the document
+    /// passwords it set or removed
+    ///
+    /// The rest of the file is re-read and kept as it is on disk, so document passwords
+    /// saved meanwhile, by this instance or another one, are not lost.
+    pub fn save(&mut self) -> Result<()> {
+        let mut saved = Config::load_from(&self.path)?;
+        saved.directories = self.config.directories.clone();
+        saved.active_directory = self.config.active_directory.clone();
+        saved.aliases = self.config.aliases.clone();
+        for (pattern, password) in &self.config.passwords {
+            if self.saved_passwords.get(pattern) != Some(password) {
+                saved.passwords.insert(pattern.clone(), password.clone());
+            }
+        }
+        for pattern in self.saved_passwords.keys() {
+            if !self.config.passwords.contains_key(pattern) {
+                saved.passwords.remove(pattern);
+            }
+        }
+        saved.save_to(&self.path)?;
+        self.config.passwords = saved.passwords;
+        self.saved_passwords = self.config.passwords.clone();
+        self.unsaved = false;
+        Ok(())
+    }
+}
+
+/// Makes a password path or glob absolute, resolving plain paths to the canonical file path
+fn normalize_password_pattern(pattern: &str) -> Result<String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(anyhow::anyhow!("The path is empty"));
+    }
+    let path = match pattern.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().context("Could not determine the home directory")?.join(rest),
+        None => PathBuf::from(pattern),
+    };
+    let path = if path.is_absolute() {
+        path
+    } else {
+        std::env::current_dir().context("Could not determine the current directory")?.join(path)
+    };
+
+    let is_glob = pattern.contains(['*', '?', '[']);
+    if is_glob {
+        let pattern = path.to_string_lossy().into_owned();
+        Pattern::new(&pattern).with_context(|| format!("Invalid glob: {}", pattern))?;
+        return Ok(pattern);
+    }
+    let path = path.canonicalize().with_context(|| format!("File not found: {}", path.display()))?;
+    if !path.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", path.display()));
+    }
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Tells whether an executable named `program` is in one of the `PATH` directories
+pub(crate) fn program_on_path(program: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|directory| {
+        let candidate = directory.join(program);
+        candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+    })
+}
+
+/// Tells whether passwords can be saved in the OS keychain (see `StoredPassword::Keychain`)
+pub fn keychain_available() -> bool {
+    if cfg!(target_os = "macos") {
+        program_on_path("security")
+    } else {
+        cfg!(unix) && program_on_path("secret-tool")
+    }
+}
+
+/// An operation on a password in the OS keychain
+enum KeychainAction<'a> {
+    Store(&'a str),
+    Lookup,
+    Delete,
+}
+
+/// Runs the OS keychain tool on the password saved under `account`, returning what it printed
+///
+/// Uses `security` on macOS and `secret-tool` (libsecret) on other Unix systems.
+fn keychain_command(action: KeychainAction, account: &str) -> Result<String> {
+    let (program, mut args, input) = if cfg!(target_os = "macos") {
+        let (args, input) = match action {
+            // Passed on stdin so it does not show in the process list: `-w` given last with
+            // no value makes `security` read it, then read it again to confirm
+            KeychainAction::Store(password) => {
+                (vec!["add-generic-password", "-U"], Some(format!("{}\n{}\n", password, password)))
+            }
+            KeychainAction::Lookup => (vec!["find-generic-password", "-w"], None),
+            KeychainAction::Delete => (vec!["delete-generic-password"], None),
+        };
+        ("security", args, input)
+    } else if cfg!(unix) {
+        let (args, input) = match action {
+            KeychainAction::Store(password) => (vec!["store", "--label", KEYCHAIN_SERVICE], Some(password.to_string())),
+            KeychainAction::Lookup => (vec!["lookup"], None),
+            KeychainAction::Delete => (vec!["clear"], None),
+        };
+        ("secret-tool", args, input)
+    } else {
+        return Err(anyhow::anyhow!("The OS keychain is not supported on this platform"));
+    };
+    if program == "security" {
+        args.extend(["-s", KEYCHAIN_SERVICE, "-a", account]);
+        if matches!(action, KeychainAction::Store(_)) {
+            args.push("-w");
+        }
+    } else {
+        args.extend(["service", KEYCHAIN_SERVICE, "account", account]);
+    }
+
+    let mut child = Command::new(program)
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}, needed to use the OS keychain", program))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin
+            .write_all(input.as_bytes())
+            .with_context(|| format!("Failed to pass the password to {}", program))?;
+    }
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Failed to run {}, needed to use the OS keychain", program))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.strip_suffix('\n').unwrap_or(&stdout);
+    match action {
+        // secret-tool exits successfully with no output when nothing is saved
+        KeychainAction::Lookup if !output.status.success() || stdout.is_empty() => {
+            Err(anyhow::anyhow!("No password in the OS keychain for: {}", account))
+        }
+        _ if !output.status.success() => Err(anyhow::anyhow!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        _ => Ok(stdout.to_string()),
+    }
+}
+
+/// Trims an alias and checks it is not empty and not a number
+fn validate_alias(alias: &str) -> Result<&str> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(anyhow::anyhow!("The alias is empty"));
+    }
+    if alias.chars().all(|c| c.is_ascii_digit()) {
+        return Err(anyhow::anyhow!("Aliases cannot be numbers, which select directories by position: {}", alias));
+    }
+    Ok(alias)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_and_save() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("nested").join(CONFIG_FILE_NAME);
+        assert_eq!(Config::load_from(&path).unwrap(), Config::default(), "A missing file is an empty config");
+
+        let mut config = Config::default();
+        config.set_active_directory(temp_dir.path()).unwrap();
+        config.save_to(&path).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap(), config);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "The file may hold passwords, so only its owner should read it");
+        }
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(Config::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn test_set_active_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+
+        let directory = config.set_active_directory(temp_dir.path()).unwrap();
+        config.set_active_directory(&temp_dir.path().join(".")).unwrap();
+        assert_eq!(config.directories, vec![directory.clone()], "Directories should not be added twice");
+        assert_eq!(config.active_directory, Some(directory));
+
+        assert!(config.set_active_directory(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_remove_directory() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        let first_path = config.set_active_directory(first.path()).unwrap();
+        let second_path = config.set_active_directory(second.path()).unwrap();
+
+        let result = config.remove_directory(&first_path, false).unwrap();
+        assert_eq!(result, RemovedDirectories { removed: vec![first_path.clone()], active_cleared: false });
+        assert_eq!(config.active_directory, Some(second_path.clone()));
+
+        let result = config.remove_directory(&second_path, false).unwrap();
+        assert!(result.active_cleared);
+        assert_eq!(config, Config::default());
+
+        assert!(config.remove_directory(&first_path, false).is_err(), "Unknown directories are reported");
+    }
+
+    #[test]
+    fn test_switch_directory_by_index_or_alias() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        let first_path = config.set_active_directory(first.path()).unwrap();
+        let second_path = config.set_active_directory(second.path()).unwrap();
+
+        assert_eq!(config.switch_directory("1").unwrap(), first_path);
+        assert_eq!(config.active_directory, Some(first_path.clone()));
+        assert_eq!(config.set_alias("taxes", "2").unwrap(), second_path);
+        assert_eq!(config.switch_directory(" taxes ").unwrap(), second_path);
+        assert_eq!(config.switch_directory(&first_path.to_string_lossy()).unwrap(), first_path);
+
+        let listed = config.list_directories();
+        assert_eq!(listed[1].index, 2);
+        assert_eq!(listed[1].aliases, ["taxes"]);
+        assert!(listed[0].active && !listed[1].active);
+
+        assert!(config.switch_directory("0").is_err());
+        assert!(config.switch_directory("3").is_err());
+        assert!(config.set_alias("42", "1").is_err(), "Numeric aliases would shadow positions");
+        assert!(config.set_alias(" ", "1").is_err());
+
+        config.remove_directory(&second_path, false).unwrap();
+        assert!(config.aliases.is_empty(), "Aliases of removed directories are dropped");
+    }
+
+    #[test]
+    fn test_rename_and_remove_alias() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        let path = config.set_alias("taxes", &dir.path().to_string_lossy()).unwrap();
+        config.set_alias("work", "1").unwrap();
+        assert_eq!(config.directories, vec![path.clone()], "Aliasing adds the directory once");
+        assert_eq!(config.directory_label(&path), format!("taxes, work ({})", path.display()));
+
+        assert!(config.rename_alias("taxes", "work").is_err(), "Aliases are not overwritten by renames");
+        assert_eq!(config.rename_alias("taxes", "taxes-2024").unwrap(), path);
+        assert_eq!(config.resolve_directory("taxes-2024").unwrap(), path);
+        assert!(config.rename_alias("taxes", "other").is_err());
+
+        assert_eq!(config.remove_alias("work").unwrap(), path);
+        assert!(config.remove_alias("work").is_err());
+        assert_eq!(config.directory_label(&path), format!("taxes-2024 ({})", path.display()));
+
+        let saved = dir.path().join(CONFIG_FILE_NAME);
+        config.save_to(&saved).unwrap();
+        assert_eq!(Config::load_from(&saved).unwrap(), config);
+    }
+
+    #[test]
+    fn test_remove_directory_prunes_missing() {
+        let kept = tempfile::tempdir().unwrap();
+        let deleted = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        let kept_path = config.set_active_directory(kept.path()).unwrap();
+        let deleted_path = config.set_active_directory(deleted.path()).unwrap();
+        drop(deleted);
+
+        // The removed directory itself may already be gone
+        let result = config.remove_directory(Path::new("/nonexistent/docs"), true).unwrap();
+        assert_eq!(result, RemovedDirectories { removed: vec![deleted_path], active_cleared: true });
+        assert_eq!(config.directories, vec![kept_path]);
+        assert_eq!(config.active_directory, None);
+    }
+
+    #[test]
+    fn test_document_passwords() {
+        let dir = tempfile::tempdir().unwrap();
+        let statements = dir.path().join("statements");
+        fs::create_dir(&statements).unwrap();
+        let march = statements.join("march.pdf");
+        let april = statements.join("april.pdf");
+        fs::write(&march, "%PDF-1.7").unwrap();
+        fs::write(&april, "%PDF-1.7").unwrap();
+
+        let mut config = Config::default();
+        let glob = format!("{}/*.pdf", statements.display());
+        let saved = config.set_document_password(&format!("  {}  ", glob), "account-1234", false).unwrap();
+        assert!(saved.ends_with("/statements/*.pdf"), "Unexpected pattern: {}", saved);
+        let march_path = config.set_document_password(&march.to_string_lossy(), "march-only", false).unwrap();
+        assert_eq!(march_path, march.canonicalize().unwrap().to_string_lossy());
+
+        assert_eq!(config.document_password(&march).unwrap().as_deref(), Some("march-only"), "Exact paths win");
+        assert_eq!(config.document_password(&april).unwrap().as_deref(), Some("account-1234"));
+        assert_eq!(config.document_password(&dir.path().join("other.pdf")).unwrap(), None);
+
+        assert!(config.set_document_password(&march.to_string_lossy(), "", false).is_err());
+        assert!(config.set_document_password(&statements.join("missing.pdf").to_string_lossy(), "x", false).is_err());
+        assert!(config.set_document_password(&statements.to_string_lossy(), "x", false).is_err());
+
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        config.save_to(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#""password": "account-1234""#), "Unexpected file: {}", content);
+        assert_eq!(Config::load_from(&path).unwrap(), config);
+
+        assert_eq!(config.remove_document_password(&march.to_string_lossy()).unwrap(), march_path);
+        assert_eq!(config.document_password(&march).unwrap().as_deref(), Some("account-1234"));
+        assert!(config.remove_document_password(&march.to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_sessions_do_not_share_the_active_directory() {
+        let taxes = tempfile::tempdir().unwrap();
+        let work = tempfile::tempdir().unwrap();
+        let config_dir = tempfile::tempdir().unwrap();
+        let path = config_dir.path().join(CONFIG_FILE_NAME);
+
+        let mut first = Session::load_from(&path).unwrap();
+        let mut second = Session::load_from(&path).unwrap();
+        let taxes_path = first.config_mut().set_active_directory(taxes.path()).unwrap();
+        let work_path = second.config_mut().set_active_directory(work.path()).unwrap();
+        assert!(first.has_unsaved_changes());
+        assert_eq!(first.active_directory(), Some(taxes_path.as_path()));
+        assert_eq!(second.active_directory(), Some(work_path.as_path()));
+        assert!(!path.exists(), "Nothing is written before save");
+
+        // A password saved directly to the file meanwhile survives the session's save
+        let statement = taxes.path().join("statement.pdf");
+        fs::write(&statement, "%PDF-1.7").unwrap();
+        let mut saved = Config::load_from(&path).unwrap();
+        saved.set_document_password(&statement.to_string_lossy(), "account-1234", false).unwrap();
+        saved.save_to(&path).unwrap();
+
+        first.save().unwrap();
+        assert!(!first.has_unsaved_changes());
+        assert_eq!(first.config().document_password(&statement).unwrap().as_deref(), Some("account-1234"));
+        let reloaded = Config::load_from(first.path()).unwrap();
+        assert_eq!(reloaded.active_directory, Some(taxes_path));
+        assert_eq!(reloaded.passwords.len(), 1);
+        assert_eq!(second.active_directory(), Some(work_path.as_path()), "Other sessions keep their state");
+
+        // Passwords set or removed through the session are saved with it
+        let receipt = work.path().join("receipt.pdf");
+        fs::write(&receipt, "%PDF-1.7").unwrap();
+        second.config_mut().set_document_password(&receipt.to_string_lossy(), "receipt-99", false).unwrap();
+        second.save().unwrap();
+        let reloaded = Config::load_from(&path).unwrap();
+        assert_eq!(reloaded.document_password(&receipt).unwrap().as_deref(), Some("receipt-99"));
+        assert_eq!(reloaded.document_password(&statement).unwrap().as_deref(), Some("account-1234"));
+        first.config_mut().remove_document_password(&statement.to_string_lossy()).unwrap();
+        first.save().unwrap();
+        let reloaded = Config::load_from(&path).unwrap();
+        assert_eq!(reloaded.document_password(&statement).unwrap(), None);
+        assert_eq!(reloaded.document_password(&receipt).unwrap().as_deref(), Some("receipt-99"));
+
+        let sampling = ClientCapabilities { sampling: Some(serde_json::json!({})), ..Default::default() };
+        first.set_client_capabilities(sampling);
+        assert!(first.client_capabilities().supports_sampling());
+        assert!(!second.client_capabilities().supports_sampling(), "Each session has its own client");
+    }
+}
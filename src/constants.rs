@@ -0,0 +1,200 @@
+/// File extension for PDF documents
+pub const PDF_EXTENSION: &str = "pdf";
+
+/// File extension for plain-text documents
+pub const TXT_EXTENSION: &str = "txt";
+
+/// File extensions for Markdown documents
+pub const MD_EXTENSION: &str = "md";
+pub const MARKDOWN_EXTENSION: &str = "markdown";
+
+/// File extensions for HTML documents
+pub const HTML_EXTENSION: &str = "html";
+pub const HTM_EXTENSION: &str = "htm";
+
+/// File extensions for delimited tabular data
+pub const CSV_EXTENSION: &str = "csv";
+pub const TSV_EXTENSION: &str = "tsv";
+
+/// File extension for PowerPoint presentations
+pub const PPTX_EXTENSION: &str = "pptx";
+
+/// File extensions for OpenDocument spreadsheets and presentations
+pub const ODS_EXTENSION: &str = "ods";
+pub const ODP_EXTENSION: &str = "odp";
+
+/// File extension for RFC 822 email messages
+pub const EML_EXTENSION: &str = "eml";
+
+/// File extension for mbox mailboxes
+pub const MBOX_EXTENSION: &str = "mbox";
+
+/// File extension for JSON documents
+pub const JSON_EXTENSION: &str = "json";
+
+/// File extension for XML documents
+pub const XML_EXTENSION: &str = "xml";
+
+/// File extensions for YAML and TOML configuration files
+pub const YAML_EXTENSION: &str = "yaml";
+pub const YML_EXTENSION: &str = "yml";
+pub const TOML_EXTENSION: &str = "toml";
+
+/// File extension for ZIP archives
+pub const ZIP_EXTENSION: &str = "zip";
+
+/// File extension for log files
+pub const LOG_EXTENSION: &str = "log";
+
+/// File extension for Apache Parquet files
+pub const PARQUET_EXTENSION: &str = "parquet";
+
+/// File extension for iCalendar files
+pub const ICS_EXTENSION: &str = "ics";
+
+/// File extension for vCard contact files
+pub const VCF_EXTENSION: &str = "vcf";
+
+/// File extensions for Kindle ebooks
+pub const MOBI_EXTENSION: &str = "mobi";
+pub const AZW3_EXTENSION: &str = "azw3";
+
+/// File extensions for XPS and OpenXPS documents
+pub const XPS_EXTENSION: &str = "xps";
+pub const OXPS_EXTENSION: &str = "oxps";
+
+/// File extensions for AsciiDoc documents
+pub const ADOC_EXTENSION: &str = "adoc";
+pub const ASCIIDOC_EXTENSION: &str = "asciidoc";
+
+/// File extension for Apple Pages documents
+pub const PAGES_EXTENSION: &str = "pages";
+
+/// File extension for Apple Keynote presentations
+pub const KEY_EXTENSION: &str = "key";
+
+/// File extension for Apple Numbers spreadsheets
+pub const NUMBERS_EXTENSION: &str = "numbers";
+
+/// File extensions for subtitle files
+pub const SRT_EXTENSION: &str = "srt";
+pub const VTT_EXTENSION: &str = "vtt";
+
+/// File extension for SVG drawings
+pub const SVG_EXTENSION: &str = "svg";
+
+/// File extension for Word documents
+pub const DOCX_EXTENSION: &str = "docx";
+
+/// File extension for WARC web archives, and the gzip suffix they often carry (`.warc.gz`)
+pub const WARC_EXTENSION: &str = "warc";
+pub const GZ_EXTENSION: &str = "gz";
+
+/// File extension for OneNote sections
+pub const ONE_EXTENSION: &str = "one";
+
+/// File extensions for LaTeX sources and BibTeX databases, read for their references by `citations`
+pub const TEX_EXTENSION: &str = "tex";
+pub const BIB_EXTENSION: &str = "bib";
+
+/// File extensions for source code
+pub const RS_EXTENSION: &str = "rs";
+pub const PY_EXTENSION: &str = "py";
+pub const JS_EXTENSION: &str = "js";
+pub const TS_EXTENSION: &str = "ts";
+pub const JAVA_EXTENSION: &str = "java";
+pub const GO_EXTENSION: &str = "go";
+pub const C_EXTENSION: &str = "c";
+pub const H_EXTENSION: &str = "h";
+pub const CPP_EXTENSION: &str = "cpp";
+pub const HPP_EXTENSION: &str = "hpp";
+
+/// Source-code extensions handled by `CodeExtractor`
+pub const CODE_EXTENSIONS: &[&str] = &[
+    RS_EXTENSION,
+    PY_EXTENSION,
+    JS_EXTENSION,
+    TS_EXTENSION,
+    JAVA_EXTENSION,
+    GO_EXTENSION,
+    C_EXTENSION,
+    H_EXTENSION,
+    CPP_EXTENSION,
+    HPP_EXTENSION,
+];
+
+/// Every file extension that `create_extractor` knows how to handle
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    PDF_EXTENSION,
+    TXT_EXTENSION,
+    MD_EXTENSION,
+    MARKDOWN_EXTENSION,
+    HTML_EXTENSION,
+    HTM_EXTENSION,
+    CSV_EXTENSION,
+    TSV_EXTENSION,
+    PPTX_EXTENSION,
+    ODS_EXTENSION,
+    ODP_EXTENSION,
+    EML_EXTENSION,
+    MBOX_EXTENSION,
+    JSON_EXTENSION,
+    XML_EXTENSION,
+    YAML_EXTENSION,
+    YML_EXTENSION,
+    TOML_EXTENSION,
+    ZIP_EXTENSION,
+    LOG_EXTENSION,
+    PARQUET_EXTENSION,
+    ICS_EXTENSION,
+    VCF_EXTENSION,
+    MOBI_EXTENSION,
+    AZW3_EXTENSION,
+    XPS_EXTENSION,
+    OXPS_EXTENSION,
+    ADOC_EXTENSION,
+    ASCIIDOC_EXTENSION,
+    PAGES_EXTENSION,
+    KEY_EXTENSION,
+    NUMBERS_EXTENSION,
+    SRT_EXTENSION,
+    VTT_EXTENSION,
+    SVG_EXTENSION,
+    DOCX_EXTENSION,
+    WARC_EXTENSION,
+    ONE_EXTENSION,
+    RS_EXTENSION,
+    PY_EXTENSION,
+    JS_EXTENSION,
+    TS_EXTENSION,
+    JAVA_EXTENSION,
+    GO_EXTENSION,
+    C_EXTENSION,
+    H_EXTENSION,
+    CPP_EXTENSION,
+    HPP_EXTENSION,
+];
+
+/// Extensions of formats stored as zip containers; a file detected as a plain zip
+/// may carry any of them
+pub const ZIP_CONTAINER_EXTENSIONS: &[&str] = &[
+    ZIP_EXTENSION,
+    DOCX_EXTENSION,
+    PPTX_EXTENSION,
+    ODS_EXTENSION,
+    ODP_EXTENSION,
+    XPS_EXTENSION,
+    OXPS_EXTENSION,
+    PAGES_EXTENSION,
+    KEY_EXTENSION,
+    NUMBERS_EXTENSION,
+];
+
+/// MIME type stored in the `mimetype` entry of OpenDocument spreadsheets
+pub const ODS_MIME_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// MIME type stored in the `mimetype` entry of OpenDocument presentations
+pub const ODP_MIME_TYPE: &str = "application/vnd.oasis.opendocument.presentation";
+
+/// MCP protocol versions the server speaks, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-11-25", "2025-06-18", "2025-03-26", "2024-11-05"];
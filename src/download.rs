@@ -0,0 +1,157 @@
+//! Extracting documents that are only available as links
+
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use serde::Serialize;
+use crate::extractor::{extract_text_from_bytes, is_supported_file};
+use crate::file_type::extension_for_mime_type;
+use crate::language::DetectedLanguage;
+
+/// Largest document downloaded, in bytes
+const MAX_DOWNLOAD_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Time allowed for the whole download
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Redirects followed before giving up
+const MAX_REDIRECTS: usize = 10;
+
+/// Text extracted from a downloaded document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UrlExtraction {
+    /// URL the document was finally served from, after redirects
+    pub url: String,
+    /// `Content-Type` sent by the server, without parameters
+    pub content_type: Option<String>,
+    /// Extractor that read the document
+    pub extractor: &'static str,
+    pub text: String,
+    /// Languages of the text, most used first
+    pub languages: Vec<DetectedLanguage>,
+}
+
+/// Parses a URL, accepting only HTTPS
+fn parse_https_url(url: &str) -> Result<Url> {
+    let parsed = Url::parse(url.trim()).with_context(|| format!("Invalid URL: {}", url))?;
+    if parsed.scheme() != "https" {
+        return Err(anyhow::anyhow!("Only https:// URLs can be downloaded: {}", url));
+    }
+    Ok(parsed)
+}
+
+/// Chooses the temporary file name, whose extension selects the extractor
+///
+/// A supported extension in the URL path wins, since servers often send documents as
+/// `application/octet-stream`; then the content type decides. Without either, content
+/// detection in `create_extractor` takes over.
+fn download_file_name(url: &Url, content_type: Option<&str>) -> String {
+    let from_path = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match from_path {
+        Some(extension) if is_supported_file(Path::new(&format!("download.{}", extension))) => {
+            format!("download.{}", extension)
+        }
+        _ => match content_type.and_then(extension_for_mime_type) {
+            Some(extension) => format!("download.{}", extension),
+            None => from_path.map_or("download".to_string(), |extension| format!("download.{}", extension)),
+        },
+    }
+}
+
+/// Downloads a document from an HTTPS URL and extracts its text
+///
+/// The document is extracted with `extract_text_from_bytes`, like any local file.
+/// Downloads larger than 100 MiB, plain-HTTP URLs and redirects to them are refused.
+pub fn extract_from_url(url: &str) -> Result<UrlExtraction> {
+    let url = parse_https_url(url)?;
+
+    let client = Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(concat!("docu-mcp/", env!("CARGO_PKG_VERSION")))
+        .redirect(Policy::custom(|attempt| {
+            if attempt.url().scheme() != "https" {
+                attempt.error("redirect to a non-HTTPS URL")
+            } else if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else {
+                attempt.follow()
+            }
+        }))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let response = client
+        .get(url.clone())
+        .send()
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to download: {}", url))?;
+    if response.content_length().is_some_and(|length| length > MAX_DOWNLOAD_SIZE) {
+        return Err(anyhow::anyhow!("Document is larger than {} bytes: {}", MAX_DOWNLOAD_SIZE, url));
+    }
+
+    let final_url = response.url().clone();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+
+    // The length header may be missing or wrong, so the body is capped as it is read
+    let mut body = Vec::new();
+    response
+        .take(MAX_DOWNLOAD_SIZE + 1)
+        .read_to_end(&mut body)
+        .with_context(|| format!("Failed to download: {}", url))?;
+    if body.len() as u64 > MAX_DOWNLOAD_SIZE {
+        return Err(anyhow::anyhow!("Document is larger than {} bytes: {}", MAX_DOWNLOAD_SIZE, url));
+    }
+
+    let extraction = extract_text_from_bytes(&body, &download_file_name(&final_url, content_type.as_deref()))
+        .with_context(|| format!("Failed to extract text from: {}", final_url))?;
+
+    Ok(UrlExtraction {
+        url: final_url.to_string(),
+        content_type,
+        extractor: extraction.extractor,
+        text: extraction.text,
+        languages: extraction.languages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_from_url_requires_https() {
+        for url in ["http://example.com/report.pdf", "ftp://example.com/report.pdf", "file:///etc/hosts"] {
+            let error = extract_from_url(url).unwrap_err().to_string();
+            assert!(error.starts_with("Only https:// URLs can be downloaded"), "Got: {}", error);
+        }
+        assert!(extract_from_url("not a url").unwrap_err().to_string().starts_with("Invalid URL"));
+    }
+
+    #[test]
+    fn test_download_file_name() {
+        let name = |url: &str, content_type: Option<&str>| download_file_name(&Url::parse(url).unwrap(), content_type);
+
+        assert_eq!(name("https://example.com/files/Report.PDF", Some("application/octet-stream")), "download.pdf");
+        assert_eq!(name("https://example.com/view?id=42", Some("application/pdf")), "download.pdf");
+        assert_eq!(name("https://example.com/export.php", Some("text/csv")), "download.csv");
+        assert_eq!(name("https://example.com/legacy.doc", None), "download.doc");
+        assert_eq!(name("https://example.com/", None), "download");
+    }
+}
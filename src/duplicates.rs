@@ -0,0 +1,333 @@
+//! Finding copies of the same document in a directory
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use anyhow::Result;
+use serde::Serialize;
+use crate::extractor::create_extractor;
+use crate::hash::file_sha256;
+use crate::listing::{list_files_in_directory, FileInfo, ListOptions};
+
+/// Files examined by default before the scan is cut short
+const DEFAULT_MAX_FILES: usize = 10_000;
+
+/// Share of word shingles two texts need in common to be reported as similar
+pub const DEFAULT_SIMILARITY: f64 = 0.9;
+
+/// Words per shingle when comparing texts
+const SHINGLE_WORDS: usize = 5;
+
+/// How the files of a group were found to be duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Byte-for-byte identical
+    Exact,
+    /// Different files with the same extracted text, ignoring case, punctuation and spacing
+    SameText,
+    /// Extracted texts sharing most of their wording
+    Similar,
+}
+
+/// Files that are copies of one another
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
+    /// SHA-256 of the contents, for exact duplicates
+    pub sha256: Option<String>,
+    /// Lowest text similarity between two files of the group, for similar documents
+    pub similarity: Option<f64>,
+    /// The copies, shortest name first since `report.pdf` is likelier the original
+    /// than `report (1).pdf`
+    pub files: Vec<FileInfo>,
+}
+
+/// Duplicates found in a directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateReport {
+    pub groups: Vec<DuplicateGroup>,
+    /// Number of files examined
+    pub files_scanned: usize,
+    /// Files that could not be read or extracted, by relative path
+    pub unreadable: Vec<String>,
+    /// True when the directory held more files than were examined
+    pub truncated: bool,
+}
+
+/// Settings for `find_duplicate_documents`
+pub struct DuplicateOptions {
+    list: ListOptions,
+    compare_text: bool,
+    similarity: f64,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DuplicateOptions {
+    /// Finds byte-identical files in the directory and its subdirectories
+    pub fn new() -> Self {
+        Self {
+            list: ListOptions::new().with_recursive(true).with_max_results(DEFAULT_MAX_FILES),
+            compare_text: false,
+            similarity: DEFAULT_SIMILARITY,
+        }
+    }
+
+    /// Chooses the files examined, e.g. only PDFs or only the top directory
+    pub fn with_list_options(mut self, list: ListOptions) -> Self {
+        self.list = list;
+        self
+    }
+
+    /// Also compares the extracted text of supported documents, catching copies that
+    /// differ only in their bytes (re-saved, re-exported, different metadata)
+    pub fn with_compare_text(mut self, compare_text: bool) -> Self {
+        self.compare_text = compare_text;
+        self
+    }
+
+    /// Sets the share of wording (0 to 1) two texts need in common to be reported as similar
+    pub fn with_similarity(mut self, similarity: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&similarity) || similarity == 0.0 {
+            return Err(anyhow::anyhow!("Similarity must be above 0 and at most 1: {}", similarity));
+        }
+        self.similarity = similarity;
+        Ok(self)
+    }
+}
+
+/// Lowercases a text and keeps only its words, so spacing and punctuation do not matter
+fn normalize_text(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Hashes every run of `SHINGLE_WORDS` consecutive words (the whole text when shorter)
+fn shingles(words: &[String]) -> HashSet<u64> {
+    words
+        .windows(SHINGLE_WORDS.min(words.len()).max(1))
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Share of shingles two texts have in common (Jaccard index)
+fn similarity(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let common = a.intersection(b).count();
+    let total = a.len() + b.len() - common;
+    if total == 0 {
+        return 0.0;
+    }
+    common as f64 / total as f64
+}
+
+/// Finds the root of a file's group, compressing the path on the way
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Sorts the copies so the likeliest original comes first
+fn sort_copies(files: &mut [FileInfo]) {
+    files.sort_by(|a, b| {
+        (a.relative_path.len(), &a.relative_path).cmp(&(b.relative_path.len(), &b.relative_path))
+    });
+}
+
+/// Finds groups of duplicate files in a directory
+///
+/// Files are first compared by size and SHA-256. With text comparison, supported
+/// documents are then compared by their normalized extracted text, exact copies
+/// counting once: identical texts form `same_text` groups, and texts sharing at
+/// least the similarity threshold of their five-word sequences form `similar` groups.
+pub fn find_duplicate_documents(directory: &Path, options: &DuplicateOptions) -> Result<DuplicateReport> {
+    let listing = list_files_in_directory(directory, &options.list)?;
+    let files_scanned = listing.files.len();
+    let mut unreadable = Vec::new();
+    let mut groups = Vec::new();
+
+    // Only files sharing a size can be identical, so the others are never read
+    let mut by_size: HashMap<u64, Vec<FileInfo>> = HashMap::new();
+    for file in listing.files {
+        by_size.entry(file.size).or_default().push(file);
+    }
+    // One file per set of identical contents goes on to the text comparison
+    let mut distinct: Vec<FileInfo> = Vec::new();
+    for (_, same_size) in by_size {
+        if same_size.len() == 1 {
+            distinct.extend(same_size);
+            continue;
+        }
+        let mut by_hash: HashMap<String, Vec<FileInfo>> = HashMap::new();
+        for file in same_size {
+            match file_sha256(&file.path) {
+                Ok(hash) => by_hash.entry(hash).or_default().push(file),
+                Err(_) => unreadable.push(file.relative_path),
+            }
+        }
+        for (hash, mut files) in by_hash {
+            sort_copies(&mut files);
+            distinct.push(files[0].clone());
+            if files.len() > 1 {
+                groups.push(DuplicateGroup { kind: DuplicateKind::Exact, sha256: Some(hash), similarity: None, files });
+            }
+        }
+    }
+
+    if options.compare_text {
+        distinct.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        let mut texts: Vec<(FileInfo, Vec<String>)> = Vec::new();
+        for file in distinct.into_iter().filter(|file| file.supported) {
+            let text = create_extractor(&file.path).and_then(|extractor| extractor.extract_text_from_file(&file.path));
+            match text {
+                Ok(text) => {
+                    let words = normalize_text(&text);
+                    if !words.is_empty() {
+                        texts.push((file, words));
+                    }
+                }
+                Err(_) => unreadable.push(file.relative_path),
+            }
+        }
+
+        // Identical texts first, then the remaining ones by similarity
+        let mut by_text: HashMap<&[String], Vec<usize>> = HashMap::new();
+        for (index, (_, words)) in texts.iter().enumerate() {
+            by_text.entry(words.as_slice()).or_default().push(index);
+        }
+        let mut candidates: Vec<usize> = Vec::new();
+        let mut same_text: Vec<Vec<usize>> = Vec::new();
+        for (_, indexes) in by_text {
+            candidates.push(indexes[0]);
+            if indexes.len() > 1 {
+                same_text.push(indexes);
+            }
+        }
+        for indexes in same_text {
+            let mut files: Vec<FileInfo> = indexes.iter().map(|&index| texts[index].0.clone()).collect();
+            sort_copies(&mut files);
+            groups.push(DuplicateGroup { kind: DuplicateKind::SameText, sha256: None, similarity: None, files });
+        }
+
+        candidates.sort_unstable();
+        let shingled: Vec<HashSet<u64>> = candidates.iter().map(|&index| shingles(&texts[index].1)).collect();
+        let mut parents: Vec<usize> = (0..candidates.len()).collect();
+        let mut lowest: HashMap<usize, f64> = HashMap::new();
+        for a in 0..candidates.len() {
+            for b in a + 1..candidates.len() {
+                let score = similarity(&shingled[a], &shingled[b]);
+                if score < options.similarity {
+                    continue;
+                }
+                let (root_a, root_b) = (find_root(&mut parents, a), find_root(&mut parents, b));
+                let low = [score, lowest.remove(&root_a).unwrap_or(1.0), lowest.remove(&root_b).unwrap_or(1.0)]
+                    .into_iter()
+                    .fold(1.0, f64::min);
+                parents[root_b] = root_a;
+                lowest.insert(root_a, low);
+            }
+        }
+
+        let mut similar: HashMap<usize, Vec<FileInfo>> = HashMap::new();
+        for position in 0..candidates.len() {
+            let root = find_root(&mut parents, position);
+            similar.entry(root).or_default().push(texts[candidates[position]].0.clone());
+        }
+        for (root, mut files) in similar {
+            if files.len() > 1 {
+                sort_copies(&mut files);
+                let similarity = lowest.get(&root).map(|low| (low * 100.0).round() / 100.0);
+                groups.push(DuplicateGroup { kind: DuplicateKind::Similar, sha256: None, similarity, files });
+            }
+        }
+    }
+
+    // Stable output: exact groups first, then by first file
+    groups.sort_by(|a, b| {
+        (a.kind as u8, &a.files[0].relative_path).cmp(&(b.kind as u8, &b.files[0].relative_path))
+    });
+    unreadable.sort();
+    Ok(DuplicateReport { groups, files_scanned, unreadable, truncated: listing.truncated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const POLICY: &str = "Flights must be booked through the travel portal. Economy class applies to trips under six hours. Hotels are reimbursed up to 180 USD per night and receipts are required for every claim.";
+
+    fn names(group: &DuplicateGroup) -> Vec<&str> {
+        group.files.iter().map(|file| file.relative_path.as_str()).collect()
+    }
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("old")).unwrap();
+        for (name, content) in [
+            ("invoice.txt", "Invoice 2025-114\nTotal: 320 USD".to_string()),
+            ("invoice (1).txt", "Invoice 2025-114\nTotal: 320 USD".to_string()),
+            ("old/invoice.txt", "Invoice 2025-114\nTotal: 320 USD".to_string()),
+            ("policy.txt", POLICY.to_string()),
+            ("policy.md", POLICY.replace(". ", ".\n\n").to_uppercase()),
+            ("policy-v2.txt", POLICY.replace("180 USD", "200 USD")),
+            ("notes.txt", "Nothing to see here".to_string()),
+        ] {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_find_exact_duplicates() {
+        let dir = sample_directory();
+        let report = find_duplicate_documents(dir.path(), &DuplicateOptions::new()).unwrap();
+
+        assert_eq!(report.files_scanned, 7);
+        assert_eq!(report.groups.len(), 1, "Got: {:?}", report.groups);
+        let group = &report.groups[0];
+        assert_eq!(group.kind, DuplicateKind::Exact);
+        assert_eq!(names(group), ["invoice.txt", "invoice (1).txt", "old/invoice.txt"]);
+        assert_eq!(group.sha256.as_ref().map(String::len), Some(64));
+    }
+
+    #[test]
+    fn test_find_text_duplicates() {
+        let dir = sample_directory();
+        let options = DuplicateOptions::new().with_compare_text(true).with_similarity(0.6).unwrap();
+        let report = find_duplicate_documents(dir.path(), &options).unwrap();
+
+        let kinds: Vec<DuplicateKind> = report.groups.iter().map(|group| group.kind).collect();
+        assert_eq!(kinds, [DuplicateKind::Exact, DuplicateKind::SameText, DuplicateKind::Similar], "Got: {:?}", report.groups);
+        assert_eq!(names(&report.groups[1]), ["policy.md", "policy.txt"]);
+        assert_eq!(names(&report.groups[2]), ["policy.md", "policy-v2.txt"], "Same-text copies count once");
+        assert!(report.groups[2].similarity.is_some_and(|similarity| (0.6..1.0).contains(&similarity)));
+        assert!(report.unreadable.is_empty());
+    }
+
+    #[test]
+    fn test_similarity() {
+        let words = normalize_text("One, two -- THREE four five six.");
+        assert_eq!(words, ["one", "two", "three", "four", "five", "six"]);
+        assert_eq!(similarity(&shingles(&words), &shingles(&words)), 1.0);
+        assert_eq!(similarity(&shingles(&words), &shingles(&normalize_text("seven eight"))), 0.0);
+        assert_eq!(shingles(&normalize_text("short text")).len(), 1, "Short texts are one shingle");
+
+        assert!(DuplicateOptions::new().with_similarity(1.5).is_err());
+        assert!(DuplicateOptions::new().with_similarity(0.0).is_err());
+    }
+}
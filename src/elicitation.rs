@@ -0,0 +1,226 @@
+//! Asking the user for a missing password or a confirmation through the client
+//!
+//! MCP elicitation (`elicitation/create`) lets the server ask the user for input in the
+//! middle of a request. The client is reached through a callback, which the server
+//! only uses when the client declared the `elicitation` capability; otherwise it runs
+//! the operation directly and reports the error as before.
+
+use std::path::Path;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use crate::rpc_error::{classify, ErrorKind};
+
+/// Times the user is asked for a password before the error is returned
+pub const MAX_PASSWORD_ATTEMPTS: usize = 3;
+
+/// Parameters of an `elicitation/create` request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ElicitationRequest {
+    pub message: String,
+    /// JSON schema of the requested input: an object of flat, primitive properties
+    #[serde(rename = "requestedSchema")]
+    pub requested_schema: Value,
+}
+
+/// What the user did with an elicitation request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElicitationAction {
+    /// Submitted the input
+    Accept,
+    /// Refused to give the input
+    Decline,
+    /// Dismissed the request without choosing
+    Cancel,
+}
+
+/// Result of an `elicitation/create` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElicitationResponse {
+    pub action: ElicitationAction,
+    /// The submitted input, when accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Map<String, Value>>,
+}
+
+impl ElicitationResponse {
+    /// A submitted string field, when the user accepted and the field is not blank
+    fn string(&self, field: &str) -> Option<&str> {
+        match self.action {
+            ElicitationAction::Accept => self.content.as_ref()?.get(field)?.as_str().filter(|value| !value.is_empty()),
+            _ => None,
+        }
+    }
+
+    /// A submitted boolean field, when the user accepted
+    fn boolean(&self, field: &str) -> Option<bool> {
+        match self.action {
+            ElicitationAction::Accept => self.content.as_ref()?.get(field)?.as_bool(),
+            _ => None,
+        }
+    }
+}
+
+/// Request asking for the password of an encrypted document
+pub fn password_request(file_path: &Path, retry: bool) -> ElicitationRequest {
+    let name = file_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let message = if retry {
+        format!("The password for {} was incorrect. Enter it again to open the document.", name)
+    } else {
+        format!("{} is password-protected. Enter its password to open it.", name)
+    };
+    ElicitationRequest {
+        message,
+        requested_schema: json!({
+            "type": "object",
+            "properties": {
+                "password": { "type": "string", "title": "Password", "description": "Used once; not saved" }
+            },
+            "required": ["password"]
+        }),
+    }
+}
+
+/// Request asking the user to confirm an operation, e.g. writing files
+pub fn confirmation_request(message: &str) -> ElicitationRequest {
+    ElicitationRequest {
+        message: message.to_string(),
+        requested_schema: json!({
+            "type": "object",
+            "properties": {
+                "confirm": { "type": "boolean", "title": "Proceed", "default": false }
+            },
+            "required": ["confirm"]
+        }),
+    }
+}
+
+/// Runs an operation on a document, asking the user for its password when it turns
+/// out to be encrypted
+///
+/// `attempt` is run without a password first, then with each password the user
+/// enters, up to `MAX_PASSWORD_ATTEMPTS` times. When the user declines or cancels, or
+/// the error is not about a password, the error of the last attempt is returned.
+pub fn with_elicited_password<T, A, E>(file_path: &Path, mut attempt: A, mut elicit: E) -> Result<T>
+where
+    A: FnMut(Option<&str>) -> Result<T>,
+    E: FnMut(&ElicitationRequest) -> Result<ElicitationResponse>,
+{
+    let mut error = match attempt(None) {
+        Ok(result) => return Ok(result),
+        Err(error) => error,
+    };
+    for retry in 0..MAX_PASSWORD_ATTEMPTS {
+        if classify(&error) != ErrorKind::PasswordRequired {
+            break;
+        }
+        let response = elicit(&password_request(file_path, retry > 0))?;
+        let Some(password) = response.string("password") else {
+            break;
+        };
+        error = match attempt(Some(password)) {
+            Ok(result) => return Ok(result),
+            Err(error) => error,
+        };
+    }
+    Err(error)
+}
+
+/// Asks the user to confirm an operation; true only when they accepted and confirmed
+pub fn confirm<E>(message: &str, mut elicit: E) -> Result<bool>
+where
+    E: FnMut(&ElicitationRequest) -> Result<ElicitationResponse>,
+{
+    let response = elicit(&confirmation_request(message))?;
+    Ok(response.boolean("confirm").unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWORD: &str = "s3cret";
+
+    fn accept(content: Value) -> ElicitationResponse {
+        ElicitationResponse { action: ElicitationAction::Accept, content: content.as_object().cloned() }
+    }
+
+    /// Opens a fake encrypted document
+    fn open(password: Option<&str>) -> Result<String> {
+        match password {
+            None => Err(anyhow::anyhow!("PDF is password-protected; a password is required")),
+            Some(PASSWORD) => Ok("Secret text".to_string()),
+            Some(_) => Err(anyhow::anyhow!("Incorrect password for the PDF")),
+        }
+    }
+
+    #[test]
+    fn test_elicitation_serialization() {
+        let request = password_request(Path::new("/tmp/report.pdf"), false);
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["message"], "report.pdf is password-protected. Enter its password to open it.");
+        assert_eq!(value["requestedSchema"]["required"], json!(["password"]));
+
+        let response: ElicitationResponse =
+            serde_json::from_value(json!({ "action": "accept", "content": { "password": PASSWORD } })).unwrap();
+        assert_eq!(response.string("password"), Some(PASSWORD));
+        let response: ElicitationResponse = serde_json::from_value(json!({ "action": "cancel" })).unwrap();
+        assert_eq!(response, ElicitationResponse { action: ElicitationAction::Cancel, content: None });
+    }
+
+    #[test]
+    fn test_with_elicited_password() {
+        let path = Path::new("report.pdf");
+        let mut requests = Vec::new();
+        let mut answers = vec![accept(json!({ "password": PASSWORD })), accept(json!({ "password": "wrong" }))];
+        let text = with_elicited_password(path, open, |request| {
+            requests.push(request.message.clone());
+            Ok(answers.pop().unwrap())
+        })
+        .unwrap();
+        assert_eq!(text, "Secret text");
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].contains("was incorrect"), "Got: {}", requests[1]);
+    }
+
+    #[test]
+    fn test_with_elicited_password_failures() {
+        let path = Path::new("report.pdf");
+        let decline =
+            |_: &ElicitationRequest| Ok(ElicitationResponse { action: ElicitationAction::Decline, content: None });
+        let error = with_elicited_password(path, open, decline).unwrap_err();
+        assert_eq!(classify(&error), ErrorKind::PasswordRequired);
+
+        let mut asked = 0;
+        let error = with_elicited_password(path, open, |_| {
+            asked += 1;
+            Ok(accept(json!({ "password": "wrong" })))
+        })
+        .unwrap_err();
+        assert_eq!(asked, MAX_PASSWORD_ATTEMPTS);
+        assert!(error.to_string().starts_with("Incorrect password"));
+
+        let never = |_: &ElicitationRequest| -> Result<ElicitationResponse> { panic!("The user should not be asked") };
+        assert_eq!(with_elicited_password(path, |_| Ok(1), never).unwrap(), 1);
+        let error = with_elicited_password(path, |_| Err::<(), _>(anyhow::anyhow!("File not found: x")), never);
+        assert_eq!(classify(&error.unwrap_err()), ErrorKind::FileNotFound);
+    }
+
+    #[test]
+    fn test_confirm() {
+        let message = "Write 12 text files to /tmp/export?";
+        let mut requests = Vec::new();
+        assert!(confirm(message, |request| {
+            requests.push(request.clone());
+            Ok(accept(json!({ "confirm": true })))
+        })
+        .unwrap());
+        assert_eq!(requests[0].message, message);
+
+        assert!(!confirm(message, |_| Ok(accept(json!({ "confirm": false })))).unwrap());
+        assert!(!confirm(message, |_| Ok(ElicitationResponse { action: ElicitationAction::Cancel, content: None }))
+            .unwrap());
+        assert!(confirm(message, |_| Err(anyhow::anyhow!("client refused"))).is_err());
+    }
+}
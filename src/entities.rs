@@ -0,0 +1,491 @@
+//! Finding structured values in extracted text: emails, phone numbers, dates, money
+//! amounts and IBANs, with their positions
+//!
+//! Emails and phone numbers use the scanners of `redact`. Dates are read in ISO,
+//! numeric and English month-name forms; amounts need a currency symbol or ISO code
+//! next to the number; IBANs must pass their mod-97 checksum.
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::extractor::{create_extractor, OutputFormat};
+use crate::redact::{find_emails, find_phones, is_bounded, starts_word};
+
+/// A kind of structured value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Email,
+    Phone,
+    Date,
+    Amount,
+    Iban,
+}
+
+impl EntityKind {
+    /// Every kind, in the order overlapping matches of the same span are resolved
+    pub const ALL: [EntityKind; 5] =
+        [EntityKind::Iban, EntityKind::Email, EntityKind::Date, EntityKind::Amount, EntityKind::Phone];
+}
+
+/// A value found in a text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Entity {
+    pub kind: EntityKind,
+    /// The value as written
+    pub text: String,
+    /// The value in a standard form: ISO 8601 dates, amounts as `1234.50 EUR`, lowercased emails,
+    /// phone numbers and IBANs without separators. Absent for numeric dates where day and month
+    /// could be swapped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Char offset where the value starts in the text
+    pub start: usize,
+    /// Char offset just past the value
+    pub end: usize,
+}
+
+/// English month names; three-letter abbreviations and `Sept` are also read
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Currency symbols and the ISO 4217 codes they are read as; `$` is taken as US dollars
+const CURRENCY_SYMBOLS: &[(char, &str)] = &[('$', "USD"), ('€', "EUR"), ('£', "GBP"), ('¥', "JPY"), ('₹', "INR")];
+
+/// ISO 4217 codes read next to amounts
+const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CHF", "CAD", "AUD", "NZD", "CNY", "INR", "SEK", "NOK", "DKK", "PLN", "CZK", "HUF",
+    "SGD", "HKD", "MXN", "BRL", "ZAR",
+];
+
+/// Lengths of IBANs across countries
+const IBAN_LENGTHS: std::ops::RangeInclusive<usize> = 15..=34;
+
+/// Longest IBAN written in groups of four: 34 characters and 8 spaces
+const MAX_IBAN_WRITTEN_LEN: usize = 42;
+
+/// Reads a run of ASCII digits, returning its value, digit count and end
+fn read_number(chars: &[char], position: usize) -> Option<(u32, usize, usize)> {
+    let end = chars[position.min(chars.len())..]
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .map_or(chars.len(), |len| position + len);
+    let digits = end.checked_sub(position).filter(|&digits| digits > 0 && digits <= 9)?;
+    let value = chars[position..end].iter().fold(0, |value, c| value * 10 + c.to_digit(10).unwrap_or(0));
+    Some((value, digits, end))
+}
+
+/// Reads a run of letters, returning it lowercased with its end
+fn read_word(chars: &[char], position: usize) -> Option<(String, usize)> {
+    let end = chars[position.min(chars.len())..]
+        .iter()
+        .position(|c| !c.is_alphabetic())
+        .map_or(chars.len(), |len| position + len);
+    (end > position).then(|| (chars[position..end].iter().collect::<String>().to_lowercase(), end))
+}
+
+fn skip_spaces(chars: &[char], mut position: usize) -> usize {
+    while chars.get(position) == Some(&' ') {
+        position += 1;
+    }
+    position
+}
+
+fn skip_char(chars: &[char], position: usize, c: char) -> usize {
+    if chars.get(position) == Some(&c) { position + 1 } else { position }
+}
+
+/// Returns the 1-based month for a name or its abbreviation
+fn month_number(word: &str) -> Option<u32> {
+    if word == "sept" {
+        return Some(9);
+    }
+    MONTHS
+        .iter()
+        .position(|month| *month == word || (word.len() == 3 && month.starts_with(word)))
+        .map(|index| index as u32 + 1)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Formats a date as ISO 8601, or `None` when it does not exist
+fn iso_date(year: u32, month: u32, day: u32) -> Option<String> {
+    let valid = (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day);
+    valid.then(|| format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+/// Skips an ordinal suffix written right after a day, as in `15th`
+fn skip_ordinal(chars: &[char], position: usize) -> usize {
+    match read_word(chars, position) {
+        Some((suffix, end)) if matches!(suffix.as_str(), "st" | "nd" | "rd" | "th") => end,
+        _ => position,
+    }
+}
+
+/// Reads a date in numeric form (`2024-01-15`, `15/01/2024`, `01.15.2024`), returning its end
+/// and its value when day and month can be told apart
+fn numeric_date(chars: &[char], start: usize) -> Option<(usize, Option<String>)> {
+    let (first, first_digits, end) = read_number(chars, start)?;
+    let separator = *chars.get(end).filter(|c| matches!(c, '-' | '/' | '.'))?;
+    let (second, second_digits, end) = read_number(chars, end + 1)?;
+    if chars.get(end) != Some(&separator) || !(1..=2).contains(&second_digits) {
+        return None;
+    }
+    let (third, third_digits, end) = read_number(chars, end + 1)?;
+    if first_digits == 4 && (1..=2).contains(&third_digits) {
+        return Some((end, Some(iso_date(first, second, third)?)));
+    }
+    if !(1..=2).contains(&first_digits) || third_digits != 4 {
+        return None;
+    }
+    // The day is whichever of the first two numbers is above 12; equal numbers read the same either way
+    let day_first = iso_date(third, second, first);
+    let month_first = iso_date(third, first, second);
+    match (day_first, month_first) {
+        (None, None) => None,
+        (Some(date), None) | (None, Some(date)) => Some((end, Some(date))),
+        (Some(date), Some(_)) if first == second => Some((end, Some(date))),
+        (Some(_), Some(_)) => Some((end, None)),
+    }
+}
+
+/// Reads a date written with a month name (`15 March 2024`, `March 15th, 2024`, `Mar. 2024`)
+fn named_date(chars: &[char], start: usize) -> Option<(usize, Option<String>)> {
+    let read_month = |position: usize| -> Option<(u32, usize)> {
+        let (word, end) = read_word(chars, position)?;
+        let month = month_number(&word)?;
+        Some((month, skip_spaces(chars, skip_char(chars, end, '.'))))
+    };
+    if chars[start].is_ascii_digit() {
+        // Day, month, year
+        let (day, digits, end) = read_number(chars, start)?;
+        if digits > 2 {
+            return None;
+        }
+        let (month, position) = read_month(skip_spaces(chars, skip_ordinal(chars, end)))?;
+        let (year, 4, end) = read_number(chars, skip_spaces(chars, skip_char(chars, position, ',')))? else {
+            return None;
+        };
+        return Some((end, Some(iso_date(year, month, day)?)));
+    }
+
+    let (month, position) = read_month(start)?;
+    match read_number(chars, position)? {
+        (year, 4, end) => Some((end, Some(format!("{:04}-{:02}", year, month)))),
+        (day, 1..=2, end) => {
+            let position = skip_spaces(chars, skip_char(chars, skip_ordinal(chars, end), ','));
+            let (year, 4, end) = read_number(chars, position)? else {
+                return None;
+            };
+            Some((end, Some(iso_date(year, month, day)?)))
+        }
+        _ => None,
+    }
+}
+
+/// Finds dates, returning their spans and ISO values
+fn find_dates(chars: &[char]) -> Vec<(usize, usize, Option<String>)> {
+    let mut found = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        if !chars[position].is_alphanumeric() || !starts_word(chars, position) {
+            position += 1;
+            continue;
+        }
+        let date = numeric_date(chars, position).or_else(|| named_date(chars, position));
+        match date {
+            Some((end, value)) if is_bounded(chars, position, end) => {
+                found.push((position, end, value));
+                position = end;
+            }
+            _ => position += 1,
+        }
+    }
+    found
+}
+
+/// Reads a number with `,`, `.` or space grouping and decimals (`1,234.50`, `1.234,50`,
+/// `5 000`, `12`), returning it with a `.` decimal point and its end
+fn read_amount(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let (_, leading_digits, mut end) = read_number(chars, start)?;
+    // Separators as (position, char, digits after)
+    let mut separators = Vec::new();
+    while let Some(&separator) = chars.get(end).filter(|c| matches!(c, ',' | '.' | ' ')) {
+        let Some((_, digits, next)) = read_number(chars, end + 1) else {
+            break;
+        };
+        // Spaces only group thousands, after a short leading group
+        if separator == ' ' && (digits != 3 || leading_digits > 3 || chars.get(next).is_some_and(|c| *c == ',')) {
+            break;
+        }
+        separators.push((end, separator, digits));
+        end = next;
+    }
+    // The last separator is the decimal point when another kind comes before it, or
+    // when it is the only one and not followed by a group of three digits
+    let decimal = separators.last().filter(|&&(_, last, digits)| {
+        let mixed = separators.iter().any(|(_, separator, _)| *separator != last);
+        last != ' ' && (mixed || separators.len() == 1 && digits != 3)
+    });
+    let mut amount = String::new();
+    for (position, c) in chars.iter().enumerate().take(end).skip(start) {
+        if c.is_ascii_digit() {
+            amount.push(*c);
+        } else if decimal.is_some_and(|(decimal, _, _)| *decimal == position) {
+            amount.push('.');
+        }
+    }
+    Some((amount, end))
+}
+
+/// Reads a currency symbol or code at a position, returning its ISO code and end
+fn read_currency(chars: &[char], position: usize) -> Option<(&'static str, usize)> {
+    let c = *chars.get(position)?;
+    if let Some((_, code)) = CURRENCY_SYMBOLS.iter().find(|(symbol, _)| *symbol == c) {
+        return Some((code, position + 1));
+    }
+    let end = position + 3;
+    let word: String = chars.get(position..end)?.iter().collect();
+    let code = CURRENCY_CODES.iter().find(|code| **code == word)?;
+    is_bounded(chars, position, end).then_some((code, end))
+}
+
+/// Finds money amounts: a number with a currency symbol or code before or after it
+fn find_amounts(chars: &[char]) -> Vec<(usize, usize, Option<String>)> {
+    let mut found = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        let amount = if chars[position].is_ascii_digit() && starts_word(chars, position) {
+            read_amount(chars, position).and_then(|(amount, end)| {
+                let (code, end) = read_currency(chars, skip_spaces(chars, end))?;
+                Some((amount, code, end))
+            })
+        } else {
+            read_currency(chars, position).and_then(|(code, end)| {
+                let number = skip_spaces(chars, end);
+                let (amount, end) = read_amount(chars, number)?;
+                is_bounded(chars, number, end).then_some((amount, code, end))
+            })
+        };
+        match amount {
+            Some((amount, code, end)) => {
+                found.push((position, end, Some(format!("{} {}", amount, code))));
+                position = end;
+            }
+            None => position += 1,
+        }
+    }
+    found
+}
+
+/// Returns true when an IBAN, without spaces, passes the mod-97 check
+fn iban_valid(iban: &[char]) -> bool {
+    if !IBAN_LENGTHS.contains(&iban.len()) {
+        return false;
+    }
+    let shape = iban[..2].iter().all(char::is_ascii_uppercase)
+        && iban[2..4].iter().all(char::is_ascii_digit)
+        && iban[4..].iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    let remainder = iban[4..].iter().chain(&iban[..4]).fold(0u32, |remainder, c| match c.to_digit(36) {
+        Some(value) if value >= 10 => (remainder * 100 + value) % 97,
+        Some(value) => (remainder * 10 + value) % 97,
+        None => remainder,
+    });
+    shape && remainder == 1
+}
+
+/// Finds IBANs, written together or in groups of four separated by spaces
+fn find_ibans(chars: &[char]) -> Vec<(usize, usize, Option<String>)> {
+    let is_iban_char = |c: &char| c.is_ascii_uppercase() || c.is_ascii_digit();
+    let mut found = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        if !chars[position].is_ascii_uppercase() || !starts_word(chars, position) {
+            position += 1;
+            continue;
+        }
+        // Collect the longest run of IBAN characters and single spaces, then try it
+        // shorter at each group boundary, so a word written after the IBAN is left out
+        let mut ends = Vec::new();
+        let mut end = position;
+        while end < chars.len() && end - position < MAX_IBAN_WRITTEN_LEN {
+            if is_iban_char(&chars[end]) {
+                end += 1;
+            } else if chars[end] == ' ' && chars.get(end + 1).is_some_and(is_iban_char) {
+                ends.push(end);
+                end += 1;
+            } else {
+                break;
+            }
+        }
+        ends.push(end);
+        let iban = ends.iter().rev().find_map(|&end| {
+            let compact: Vec<char> = chars[position..end].iter().copied().filter(|c| *c != ' ').collect();
+            (is_bounded(chars, position, end) && iban_valid(&compact)).then(|| (end, compact.into_iter().collect()))
+        });
+        match iban {
+            Some((end, value)) => {
+                found.push((position, end, Some(value)));
+                position = end;
+            }
+            None => position += 1,
+        }
+    }
+    found
+}
+
+/// Finds the chosen kinds of values in a text, in text order
+///
+/// Where matches overlap, the one starting first wins, then the longest, then the
+/// kind listed first in `EntityKind::ALL`.
+pub fn find_entities(text: &str, kinds: &[EntityKind]) -> Vec<Entity> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    for kind in EntityKind::ALL.into_iter().filter(|kind| kinds.contains(kind)) {
+        let found = match kind {
+            EntityKind::Email => {
+                find_emails(&chars).into_iter().map(|(start, end)| (start, end, None)).collect()
+            }
+            EntityKind::Phone => find_phones(&chars)
+                .into_iter()
+                .map(|(start, end)| {
+                    let value = chars[start..end].iter().filter(|c| c.is_ascii_digit() || **c == '+').collect();
+                    (start, end, Some(value))
+                })
+                .collect(),
+            EntityKind::Date => find_dates(&chars),
+            EntityKind::Amount => find_amounts(&chars),
+            EntityKind::Iban => find_ibans(&chars),
+        };
+        spans.extend(found.into_iter().map(|(start, end, value)| (kind, start, end, value)));
+    }
+    // A stable sort keeps the order of `EntityKind::ALL` among equal spans
+    spans.sort_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)));
+
+    let mut entities = Vec::new();
+    let mut position = 0;
+    for (kind, start, end, value) in spans {
+        if start < position {
+            continue;
+        }
+        let text: String = chars[start..end].iter().collect();
+        let value = if kind == EntityKind::Email { Some(text.to_lowercase()) } else { value };
+        entities.push(Entity { kind, text, value, start, end });
+        position = end;
+    }
+    entities
+}
+
+/// Extracts a document and finds the chosen kinds of values in its text
+///
+/// Offsets are in the text extracted with `format`, as `extract_text_slice` returns it.
+pub fn extract_entities(file_path: &Path, kinds: &[EntityKind], format: OutputFormat) -> Result<Vec<Entity>> {
+    let text = create_extractor(file_path)?
+        .extract_formatted(file_path, format)
+        .with_context(|| format!("Failed to extract text: {}", file_path.display()))?;
+    Ok(find_entities(&text, kinds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(text: &str, kind: EntityKind) -> Vec<(String, Option<String>)> {
+        find_entities(text, &[kind]).into_iter().map(|entity| (entity.text, entity.value)).collect()
+    }
+
+    fn found(text: &str, value: Option<&str>) -> (String, Option<String>) {
+        (text.to_string(), value.map(str::to_string))
+    }
+
+    #[test]
+    fn test_find_dates() {
+        let text = "Booked 2024-02-29, flying 15/03/2024 or 03/15/2024, back 04/05/2024. \
+                    Paid on 1st March 2024 and March 15th, 2024; valid until Dec. 2025. Not 2023-02-29 or 12345.";
+        assert_eq!(
+            values(text, EntityKind::Date),
+            [
+                found("2024-02-29", Some("2024-02-29")),
+                found("15/03/2024", Some("2024-03-15")),
+                found("03/15/2024", Some("2024-03-15")),
+                found("04/05/2024", None),
+                found("1st March 2024", Some("2024-03-01")),
+                found("March 15th, 2024", Some("2024-03-15")),
+                found("Dec. 2025", Some("2025-12")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_amounts() {
+        let text = "Fare $1,234.50, tax €12,50, fee 1.234,00 EUR, refund GBP 80 and 5 000 JPY; 3 bags, USDC 4.";
+        assert_eq!(
+            values(text, EntityKind::Amount),
+            [
+                found("$1,234.50", Some("1234.50 USD")),
+                found("€12,50", Some("12.50 EUR")),
+                found("1.234,00 EUR", Some("1234.00 EUR")),
+                found("GBP 80", Some("80 GBP")),
+                found("5 000 JPY", Some("5000 JPY")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_ibans() {
+        let text = "Pay DE89 3704 0044 0532 0130 00 BIC COBADEFFXXX, or GB82WEST12345698765432. \
+                    Not GB82WEST12345698765433.";
+        assert_eq!(
+            values(text, EntityKind::Iban),
+            [
+                found("DE89 3704 0044 0532 0130 00", Some("DE89370400440532013000")),
+                found("GB82WEST12345698765432", Some("GB82WEST12345698765432")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_entities() {
+        let text = "Call +44 20 7946 0958 or mail Ops@Example.com by 2024-03-15 about €20.";
+        let entities = find_entities(text, &EntityKind::ALL);
+        let kinds: Vec<EntityKind> = entities.iter().map(|entity| entity.kind).collect();
+        assert_eq!(kinds, [EntityKind::Phone, EntityKind::Email, EntityKind::Date, EntityKind::Amount]);
+        assert_eq!(entities[0].value.as_deref(), Some("+442079460958"));
+        assert_eq!(entities[1].value.as_deref(), Some("ops@example.com"));
+        let date = &entities[2];
+        assert_eq!(text.chars().skip(date.start).take(date.end - date.start).collect::<String>(), "2024-03-15");
+
+        assert!(find_entities(text, &[]).is_empty());
+        assert_eq!(find_entities(text, &[EntityKind::Email]).len(), 1);
+    }
+
+    #[test]
+    fn test_extract_entities() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invoice.txt");
+        std::fs::write(&path, "Invoice dated 2 January 2025, total €1.250,00.\n").unwrap();
+        let entities = extract_entities(&path, &EntityKind::ALL, OutputFormat::Text).unwrap();
+        let values: Vec<_> = entities.iter().map(|entity| entity.value.as_deref()).collect();
+        assert_eq!(values, [Some("2025-01-02"), Some("1250.00 EUR")]);
+        assert!(extract_entities(&dir.path().join("missing.txt"), &EntityKind::ALL, OutputFormat::Text).is_err());
+    }
+}
@@ -0,0 +1,379 @@
+//! Writing the extracted text of documents to files, e.g. to mirror an archive as plain
+//! text or to feed a JSONL corpus to other tools
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::extractor::{create_extractor, DocumentMetadata, OutputFormat};
+use crate::listing::{list_files_in_directory, FileInfo, ListOptions};
+
+/// Documents exported by default before the export is cut short
+const DEFAULT_MAX_FILES: usize = 10_000;
+
+/// A document written to the output folder
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedFile {
+    /// Path of the document relative to the exported directory, with `/` separators
+    pub relative_path: String,
+    pub output: PathBuf,
+    /// Length of the written text, in characters
+    pub chars: usize,
+}
+
+/// A document that could not be exported
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportFailure {
+    pub relative_path: String,
+    pub error: String,
+}
+
+/// Result of `export_extracted_text`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportReport {
+    pub output_dir: PathBuf,
+    pub exported: Vec<ExportedFile>,
+    /// Documents whose export is already newer than the document, by relative path
+    pub up_to_date: Vec<String>,
+    pub failed: Vec<ExportFailure>,
+    /// True when the directory held more documents than were exported
+    pub truncated: bool,
+}
+
+/// One line of a corpus written by `export_corpus`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CorpusRecord {
+    /// Path of the document relative to the exported directory, with `/` separators
+    pub path: String,
+    /// Last modification time of the document, as an ISO 8601 UTC timestamp
+    pub mtime: Option<String>,
+    pub text: String,
+    /// Descriptive metadata, for formats that have it
+    pub metadata: Option<DocumentMetadata>,
+}
+
+/// Result of `export_corpus`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CorpusReport {
+    pub output: PathBuf,
+    /// Number of documents written
+    pub documents: usize,
+    pub failed: Vec<ExportFailure>,
+    /// True when the directory held more documents than were exported
+    pub truncated: bool,
+}
+
+/// Settings for `export_extracted_text` and `export_corpus`
+pub struct ExportOptions {
+    list: ListOptions,
+    format: OutputFormat,
+    overwrite: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExportOptions {
+    /// Exports every supported document of the directory and its subdirectories as plain text
+    pub fn new() -> Self {
+        Self {
+            list: ListOptions::new()
+                .with_recursive(true)
+                .with_supported_only(true)
+                .with_max_results(DEFAULT_MAX_FILES),
+            format: OutputFormat::Text,
+            overwrite: false,
+        }
+    }
+
+    /// Chooses the documents exported from a directory, e.g. only PDFs
+    pub fn with_list_options(mut self, list: ListOptions) -> Self {
+        self.list = list;
+        self
+    }
+
+    /// Writes Markdown (`.md` files) instead of plain text (`.txt` files)
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Exports documents again even when their export is newer than the document; for
+    /// `export_corpus`, replaces an existing corpus file
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    fn output_extension(&self) -> &'static str {
+        match self.format {
+            OutputFormat::Text => "txt",
+            OutputFormat::Markdown => "md",
+        }
+    }
+}
+
+/// Extracts a document, or the documents of a directory, into text files in `output_dir`
+///
+/// Each document is written to its relative path plus `.txt` (or `.md`), so
+/// `2024/taxes.pdf` becomes `2024/taxes.pdf.txt` and documents differing only in
+/// extension do not collide. Running the export again only re-extracts documents
+/// modified since, which keeps a mirror current cheaply. An output folder inside the
+/// exported directory is not exported itself.
+pub fn export_extracted_text(source: &Path, output_dir: &Path, options: &ExportOptions) -> Result<ExportReport> {
+    let (files, truncated) = if source.is_file() {
+        let file_name = source
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Path is not a file: {}", source.display()))?
+            .to_string_lossy()
+            .into_owned();
+        let file = FileInfo {
+            path: source.to_path_buf(),
+            relative_path: file_name,
+            supported: true,
+            size: 0,
+            modified: None,
+        };
+        (vec![file], false)
+    } else if source.is_dir() {
+        let listing = list_files_in_directory(source, &options.list)?;
+        (listing.files, listing.truncated)
+    } else {
+        return Err(anyhow::anyhow!("File not found: {}", source.display()));
+    };
+
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", output_dir.display()))?;
+    let output_dir = output_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve output directory: {}", output_dir.display()))?;
+    // Earlier exports inside the source directory must not be exported again
+    let nested_output = source
+        .canonicalize()
+        .ok()
+        .and_then(|source| output_dir.strip_prefix(source).ok().map(Path::to_path_buf))
+        .filter(|nested| !nested.as_os_str().is_empty());
+
+    let mut report = ExportReport {
+        output_dir: output_dir.clone(),
+        exported: Vec::new(),
+        up_to_date: Vec::new(),
+        failed: Vec::new(),
+        truncated,
+    };
+    for file in files {
+        if nested_output.as_ref().is_some_and(|nested| Path::new(&file.relative_path).starts_with(nested)) {
+            continue;
+        }
+
+        let output = output_dir.join(format!("{}.{}", file.relative_path, options.output_extension()));
+        if !options.overwrite && is_up_to_date(&file.path, &output) {
+            report.up_to_date.push(file.relative_path);
+            continue;
+        }
+
+        match export_file(&file.path, &output, options.format) {
+            Ok(chars) => report.exported.push(ExportedFile { relative_path: file.relative_path, output, chars }),
+            Err(e) => {
+                report.failed.push(ExportFailure { relative_path: file.relative_path, error: format!("{:#}", e) })
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Returns true when `output` exists and was written after `source` last changed
+fn is_up_to_date(source: &Path, output: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    match (modified(source), modified(output)) {
+        (Some(source), Some(output)) => output >= source,
+        _ => false,
+    }
+}
+
+/// Extracts one document and writes its text, returning the number of characters written
+fn export_file(file_path: &Path, output: &Path, format: OutputFormat) -> Result<usize> {
+    let text = create_extractor(file_path)?.extract_formatted(file_path, format)?;
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(output, &text).with_context(|| format!("Failed to write: {}", output.display()))?;
+    Ok(text.chars().count())
+}
+
+/// Extracts the documents of a directory into a JSONL file, one `CorpusRecord` per line
+///
+/// The file is written next to its final location and renamed once complete, so an
+/// interrupted export never leaves a partial corpus behind. Documents that cannot be
+/// extracted are reported and left out; the corpus file itself is never exported.
+pub fn export_corpus(directory: &Path, output: &Path, options: &ExportOptions) -> Result<CorpusReport> {
+    if output.exists() && !options.overwrite {
+        return Err(anyhow::anyhow!("Output file already exists: {}", output.display()));
+    }
+    let listing = list_files_in_directory(directory, &options.list)?;
+    let output_path = output.canonicalize().ok();
+
+    if let Some(parent) = output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let temp_path = output.with_extension("jsonl.tmp");
+    let mut writer = BufWriter::new(
+        File::create(&temp_path).with_context(|| format!("Failed to write: {}", temp_path.display()))?,
+    );
+
+    let mut report =
+        CorpusReport { output: output.to_path_buf(), documents: 0, failed: Vec::new(), truncated: listing.truncated };
+    for file in listing.files {
+        let is_output = file.path == temp_path
+            || output_path.is_some() && file.path.canonicalize().ok() == output_path;
+        if is_output {
+            continue;
+        }
+
+        let extracted = create_extractor(&file.path).and_then(|extractor| {
+            let text = extractor.extract_formatted(&file.path, options.format)?;
+            Ok(CorpusRecord {
+                path: file.relative_path.clone(),
+                mtime: file.modified.clone(),
+                text,
+                metadata: extractor.extract_metadata(&file.path).ok(),
+            })
+        });
+        match extracted {
+            Ok(record) => {
+                serde_json::to_writer(&mut writer, &record).context("Failed to serialize corpus record")?;
+                writer.write_all(b"\n").with_context(|| format!("Failed to write: {}", temp_path.display()))?;
+                report.documents += 1;
+            }
+            Err(e) => {
+                report.failed.push(ExportFailure { relative_path: file.relative_path, error: format!("{:#}", e) })
+            }
+        }
+    }
+
+    writer.flush().with_context(|| format!("Failed to write: {}", temp_path.display()))?;
+    drop(writer);
+    fs::rename(&temp_path, output).with_context(|| format!("Failed to write: {}", output.display()))?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("2024")).unwrap();
+        fs::write(dir.path().join("notes.txt"), "Call the bank.").unwrap();
+        fs::write(dir.path().join("2024/taxes.md"), "# Taxes\n\nFile by **April**.").unwrap();
+        fs::write(dir.path().join("photo.heic"), "not a document").unwrap();
+        dir
+    }
+
+    fn relative_paths(files: &[ExportedFile]) -> Vec<&str> {
+        files.iter().map(|file| file.relative_path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_export_directory() {
+        let dir = sample_directory();
+        let output = tempfile::tempdir().unwrap();
+
+        let report = export_extracted_text(dir.path(), output.path(), &ExportOptions::new()).unwrap();
+        assert_eq!(relative_paths(&report.exported), ["2024/taxes.md", "notes.txt"]);
+        assert!(report.failed.is_empty(), "Got: {:?}", report.failed);
+        assert_eq!(fs::read_to_string(output.path().join("notes.txt.txt")).unwrap(), "Call the bank.");
+        assert_eq!(report.exported[1].chars, "Call the bank.".len());
+        assert!(output.path().join("2024/taxes.md.txt").is_file());
+        assert!(!output.path().join("photo.heic.txt").exists(), "Unsupported files are skipped");
+
+        let markdown = ExportOptions::new().with_format(OutputFormat::Markdown);
+        let report = export_extracted_text(dir.path(), output.path(), &markdown).unwrap();
+        assert_eq!(report.exported.len(), 2);
+        assert!(output.path().join("2024/taxes.md.md").is_file());
+    }
+
+    #[test]
+    fn test_export_skips_up_to_date_files() {
+        let dir = sample_directory();
+        let output = tempfile::tempdir().unwrap();
+        export_extracted_text(dir.path(), output.path(), &ExportOptions::new()).unwrap();
+
+        // Make the exported notes older than the document, as if it changed since
+        let old = SystemTime::now() - Duration::from_secs(3600);
+        let exported = fs::File::options().write(true).open(output.path().join("notes.txt.txt")).unwrap();
+        exported.set_modified(old).unwrap();
+
+        let report = export_extracted_text(dir.path(), output.path(), &ExportOptions::new()).unwrap();
+        assert_eq!(relative_paths(&report.exported), ["notes.txt"]);
+        assert_eq!(report.up_to_date, ["2024/taxes.md"]);
+
+        let overwrite = ExportOptions::new().with_overwrite(true);
+        let report = export_extracted_text(dir.path(), output.path(), &overwrite).unwrap();
+        assert_eq!(report.exported.len(), 2);
+        assert!(report.up_to_date.is_empty());
+    }
+
+    #[test]
+    fn test_export_into_source_directory() {
+        let dir = sample_directory();
+        let output = dir.path().join("mirror");
+
+        export_extracted_text(dir.path(), &output, &ExportOptions::new()).unwrap();
+        let report = export_extracted_text(dir.path(), &output, &ExportOptions::new().with_overwrite(true)).unwrap();
+        assert_eq!(
+            relative_paths(&report.exported),
+            ["2024/taxes.md", "notes.txt"],
+            "The mirror must not export itself"
+        );
+    }
+
+    #[test]
+    fn test_export_corpus() {
+        let dir = sample_directory();
+        fs::write(dir.path().join("broken.docx"), "not a zip").unwrap();
+        let output = dir.path().join("corpus.jsonl");
+
+        let report = export_corpus(dir.path(), &output, &ExportOptions::new()).unwrap();
+        assert_eq!(report.documents, 2);
+        assert_eq!(report.failed[0].relative_path, "broken.docx");
+
+        let lines: Vec<serde_json::Value> = fs::read_to_string(&output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["path"], "notes.txt");
+        assert_eq!(lines[1]["text"], "Call the bank.");
+        assert!(lines[1]["mtime"].as_str().is_some_and(|mtime| mtime.ends_with('Z')), "Got: {}", lines[1]);
+        assert!(lines[1]["metadata"].is_null());
+
+        assert!(export_corpus(dir.path(), &output, &ExportOptions::new()).is_err(), "Existing corpora are kept");
+        let report = export_corpus(dir.path(), &output, &ExportOptions::new().with_overwrite(true)).unwrap();
+        assert_eq!(report.documents, 2, "The corpus must not export itself");
+        assert!(!dir.path().join("corpus.jsonl.tmp").exists());
+    }
+
+    #[test]
+    fn test_export_single_file() {
+        let dir = sample_directory();
+        let output = tempfile::tempdir().unwrap();
+
+        let options = ExportOptions::new();
+        let report = export_extracted_text(&dir.path().join("2024/taxes.md"), output.path(), &options).unwrap();
+        assert_eq!(relative_paths(&report.exported), ["taxes.md"]);
+        assert!(output.path().join("taxes.md.txt").is_file());
+
+        fs::write(dir.path().join("broken.docx"), "not a zip").unwrap();
+        let report = export_extracted_text(&dir.path().join("broken.docx"), output.path(), &options).unwrap();
+        assert_eq!(report.failed[0].relative_path, "broken.docx");
+        assert!(report.exported.is_empty());
+        assert!(export_extracted_text(&dir.path().join("missing.pdf"), output.path(), &options).is_err());
+    }
+}
@@ -1,7 +1,307 @@
+use std::fs;
+use std::ops::RangeInclusive;
 use std::path::Path;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
 
+use crate::cache::shared_cache;
+use crate::config::{keychain_available, Config};
+use crate::constants::{
+    ADOC_EXTENSION, ASCIIDOC_EXTENSION, AZW3_EXTENSION, CODE_EXTENSIONS, CSV_EXTENSION,
+    DOCX_EXTENSION, EML_EXTENSION, HTM_EXTENSION, HTML_EXTENSION, ICS_EXTENSION, JSON_EXTENSION,
+    KEY_EXTENSION, LOG_EXTENSION, MARKDOWN_EXTENSION, MBOX_EXTENSION, MD_EXTENSION, MOBI_EXTENSION,
+    NUMBERS_EXTENSION, ODP_EXTENSION, ODS_EXTENSION, OXPS_EXTENSION, PAGES_EXTENSION,
+    PARQUET_EXTENSION, PDF_EXTENSION, PPTX_EXTENSION, SRT_EXTENSION,
+    SVG_EXTENSION, TOML_EXTENSION, TSV_EXTENSION, TXT_EXTENSION, VCF_EXTENSION, VTT_EXTENSION,
+    GZ_EXTENSION, ONE_EXTENSION, SUPPORTED_EXTENSIONS, WARC_EXTENSION, XML_EXTENSION, XPS_EXTENSION, YAML_EXTENSION,
+    YML_EXTENSION, ZIP_EXTENSION,
+};
+use crate::extractors::adoc_extractor::AdocExtractor;
+use crate::extractors::code_extractor::CodeExtractor;
+use crate::extractors::csv_extractor::CsvExtractor;
+use crate::extractors::docx_extractor::DocxExtractor;
+use crate::extractors::eml_extractor::EmlExtractor;
+use crate::extractors::generic_extractor::GenericExtractor;
+use crate::extractors::html_extractor::HtmlExtractor;
+use crate::extractors::ics_extractor::IcsExtractor;
+use crate::extractors::json_extractor::JsonExtractor;
+use crate::extractors::keynote_extractor::KeynoteExtractor;
+use crate::extractors::log_extractor::LogExtractor;
+use crate::extractors::markdown_extractor::MarkdownExtractor;
+use crate::extractors::mbox_extractor::MboxExtractor;
+use crate::extractors::mobi_extractor::MobiExtractor;
+use crate::extractors::numbers_extractor::NumbersExtractor;
+use crate::extractors::odp_extractor::OdpExtractor;
+use crate::extractors::ods_extractor::OdsExtractor;
+use crate::extractors::pages_extractor::PagesExtractor;
+use crate::extractors::parquet_extractor::ParquetExtractor;
 use crate::extractors::pdf_extractor::PdfExtractor;
+use crate::extractors::pptx_extractor::PptxExtractor;
+use crate::extractors::subtitle_extractor::SubtitleExtractor;
+use crate::extractors::svg_extractor::SvgExtractor;
+use crate::extractors::toml_extractor::TomlExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+use crate::extractors::vcf_extractor::VcfExtractor;
+use crate::extractors::onenote_extractor::OneNoteExtractor;
+use crate::extractors::warc_extractor::WarcExtractor;
+use crate::extractors::xml_extractor::XmlExtractor;
+use crate::extractors::xps_extractor::XpsExtractor;
+use crate::extractors::yaml_extractor::YamlExtractor;
+use crate::extractors::zip_extractor::ZipExtractor;
+use crate::file_type::{detect_extension, extension_matches, looks_like_text};
+use crate::language::{detect_languages, DetectedLanguage};
+use crate::ocr::ocr_available;
+use crate::timestamp::{format_unix_time, unix_seconds};
+
+/// First words of the notice put before text extracted by content-detected format
+pub const FORMAT_MISMATCH_NOTICE: &str = "[Format]";
+
+/// A table as rows of cell text; the first row is usually the header
+///
+/// Serializes to JSON as an array of rows, each an array of strings.
+pub type Table = Vec<Vec<String>>;
+
+/// A table with the page it was found on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageTable {
+    /// 1-based page, for formats with pages
+    pub page: Option<usize>,
+    pub rows: Table,
+}
+
+/// How `get_document_tables` returns each table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    /// Rows of cell strings
+    #[default]
+    Json,
+    /// A CSV string, quoted where needed
+    Csv,
+}
+
+/// A table of a document, as returned by `get_document_tables`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtractedTable {
+    /// 0-based position of the table in the document
+    pub index: usize,
+    /// 1-based page, for formats with pages
+    pub page: Option<usize>,
+    /// Rows of cells, for `TableFormat::Json`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Table>,
+    /// The table as CSV, for `TableFormat::Csv`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csv: Option<String>,
+}
+
+/// Largest embedded image that is extracted, in bytes (decoded, for PDF pixel data)
+pub const MAX_IMAGE_SIZE: usize = 20 * 1024 * 1024;
+
+/// Images returned by `get_document_images` unless the caller asks otherwise
+pub const DEFAULT_MAX_IMAGES: usize = 20;
+
+/// An image embedded in a document, in a format image viewers understand
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedImage {
+    /// 1-based page (slide, for presentations) the image first appears on
+    pub page: Option<usize>,
+    /// Name of the image in the document: archive entry or PDF resource name
+    pub name: Option<String>,
+    pub mime_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+/// An image of a document, as returned by `get_document_images`
+///
+/// `data` and `mime_type` are the content of an MCP image content block.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtractedImage {
+    /// 0-based position of the image in the document
+    pub index: usize,
+    /// 1-based page (slide, for presentations) the image first appears on
+    pub page: Option<usize>,
+    pub name: Option<String>,
+    pub mime_type: String,
+    /// Size of the image, in bytes
+    pub size: usize,
+    /// The image bytes, base64-encoded
+    pub data: String,
+}
+
+/// A heading of a document outline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Heading {
+    /// Nesting depth, starting at 1 for top-level headings
+    pub level: usize,
+    pub title: String,
+    /// 1-based page the heading points to, for formats with pages
+    pub page: Option<usize>,
+    /// Character offset of the heading's line in the extracted text, for `extract_text_slice`
+    pub offset: Option<usize>,
+}
+
+/// A hyperlink of a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Link {
+    /// Target of the link, as written in the document
+    pub url: String,
+    /// Text the link is set on; PDF links are areas of a page, so they have none
+    pub text: Option<String>,
+    /// 1-based page the link is on, for formats with pages
+    pub page: Option<usize>,
+    /// Title of the heading the link comes under
+    pub section: Option<String>,
+}
+
+/// How extracted text is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Linearized plain text
+    #[default]
+    Text,
+    /// Markdown keeping headings, lists and tables
+    Markdown,
+}
+
+/// Shape of the result of `extract_document`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    /// Linearized plain text
+    #[default]
+    Plain,
+    /// Markdown keeping headings, lists and tables
+    Markdown,
+    /// A `StructuredDocument`: text with metadata, pages and sections
+    Json,
+}
+
+/// A section of a document, from its heading to the next one
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocumentSection {
+    #[serde(flatten)]
+    pub heading: Heading,
+    /// Text of the section, for headings anchored to an offset in the text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// A document extracted with its structure, for `DocumentFormat::Json`
+///
+/// Parts the format does not support are `None` (or empty, for sections).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StructuredDocument {
+    /// Extractor that handled the file
+    pub extractor: &'static str,
+    pub metadata: Option<DocumentMetadata>,
+    /// Text of each page, for formats with pages
+    pub pages: Option<Vec<PageText>>,
+    pub sections: Vec<DocumentSection>,
+    pub text: String,
+}
+
+/// Result of `extract_document`: a string, or the structured document for JSON output
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ExtractedDocument {
+    Text(String),
+    Structured(StructuredDocument),
+}
+
+/// Descriptive metadata of a document; fields the file does not record are `None`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// Creation date, in ISO 8601 form where the format allows it
+    pub created: Option<String>,
+    /// Last modification date, in ISO 8601 form where the format allows it
+    pub modified: Option<String>,
+    /// Number of pages, or slides for presentations
+    pub page_count: Option<usize>,
+    /// Application that produced the file
+    pub producer: Option<String>,
+}
+
+/// What a document's pages are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageUnit {
+    Page,
+    Slide,
+}
+
+/// Number of pages of a document, as returned by `count_pages`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageCount {
+    pub count: usize,
+    pub unit: PageUnit,
+    /// False when the count is the one the saving application recorded (Word files),
+    /// which can be stale or depend on the printer layout
+    pub exact: bool,
+}
+
+/// Text of one page of a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageText {
+    /// 1-based page number
+    pub page: usize,
+    pub text: String,
+}
+
+/// File system facts about a document, together with its descriptive metadata
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocumentInfo {
+    /// File size in bytes
+    pub size: u64,
+    /// Last modification time of the file, as an ISO 8601 UTC timestamp
+    pub file_modified: Option<String>,
+    /// Extractor that handles the file
+    pub extractor: &'static str,
+    #[serde(flatten)]
+    pub metadata: DocumentMetadata,
+    /// Why the descriptive metadata is missing, when it could not be read
+    pub metadata_error: Option<String>,
+    /// Languages of the extracted text, most used first; empty when the text cannot be read
+    pub languages: Vec<DetectedLanguage>,
+}
+
+/// A window of a document's extracted text, for reading large documents in slices
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TextSlice {
+    pub text: String,
+    /// Offset of the slice in the extracted text, in characters
+    pub offset: usize,
+    /// Length of the whole extracted text, in characters
+    pub total_chars: usize,
+    /// Offset to pass for the next slice; `None` once the end is reached
+    pub next_offset: Option<usize>,
+    /// Languages of the whole extracted text, most used first
+    pub languages: Vec<DetectedLanguage>,
+}
+
+impl TextSlice {
+    /// Cuts up to `max_chars` characters starting at `offset` out of `text`
+    ///
+    /// An offset past the end gives an empty slice rather than an error, so a client
+    /// can tell from `total_chars` how far off it was.
+    pub fn from_text(text: &str, offset: usize, max_chars: usize) -> Self {
+        let total_chars = text.chars().count();
+        let slice: String = text.chars().skip(offset).take(max_chars).collect();
+        let end = offset.saturating_add(max_chars);
+        Self {
+            text: slice,
+            offset,
+            total_chars,
+            next_offset: (end < total_chars).then_some(end),
+            languages: detect_languages(text),
+        }
+    }
+}
 
 /// Trait for extracting text from various document formats
 pub trait DocumentExtractor {
@@ -17,32 +317,669 @@ pub trait DocumentExtractor {
 
     /// Returns the name/type of this extractor (e.g., "PdfExtractor", "DocxExtractor")
     fn extractor_type(&self) -> &'static str;
+
+    /// Extracts the content as Markdown, keeping headings, lists and tables
+    ///
+    /// Formats without such structure return their plain text.
+    fn extract_markdown(&self, file_path: &Path) -> Result<String> {
+        self.extract_text_from_file(file_path)
+    }
+
+    /// Extracts the content in the requested output format
+    fn extract_formatted(&self, file_path: &Path, format: OutputFormat) -> Result<String> {
+        match format {
+            OutputFormat::Text => self.extract_text_from_file(file_path),
+            OutputFormat::Markdown => self.extract_markdown(file_path),
+        }
+    }
+
+    /// Extracts the tables of a document, in document order
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Table>)` - The tables found (possibly none)
+    /// * `Err` - Error if extraction fails or the format has no table support
+    fn extract_tables(&self, file_path: &Path) -> Result<Vec<Table>> {
+        Err(anyhow::anyhow!(
+            "Table extraction is not supported by {}: {}",
+            self.extractor_type(),
+            file_path.display()
+        ))
+    }
+
+    /// Extracts the tables of a document with the page each is on, in document order
+    ///
+    /// Formats without pages report no page numbers.
+    fn extract_page_tables(&self, file_path: &Path) -> Result<Vec<PageTable>> {
+        let tables = self.extract_tables(file_path)?;
+        Ok(tables.into_iter().map(|rows| PageTable { page: None, rows }).collect())
+    }
+
+    /// Reads the heading hierarchy of a document, in document order
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Heading>)` - The headings found (possibly none)
+    /// * `Err` - Error if reading fails or the format has no outline support
+    fn extract_outline(&self, file_path: &Path) -> Result<Vec<Heading>> {
+        Err(anyhow::anyhow!(
+            "Outline extraction is not supported by {}: {}",
+            self.extractor_type(),
+            file_path.display()
+        ))
+    }
+
+    /// Reads the hyperlinks of a document, in document order
+    ///
+    /// Links to places inside the document are left out.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Link>)` - The links found (possibly none)
+    /// * `Err` - Error if reading fails or the format has no link support
+    fn extract_links(&self, file_path: &Path) -> Result<Vec<Link>> {
+        Err(anyhow::anyhow!(
+            "Link extraction is not supported by {}: {}",
+            self.extractor_type(),
+            file_path.display()
+        ))
+    }
+
+    /// Extracts the images embedded in a document, in document order
+    ///
+    /// # Returns
+    /// * `Ok(Vec<EmbeddedImage>)` - The images found (possibly none)
+    /// * `Err` - Error if extraction fails or the format has no image support
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        Err(anyhow::anyhow!(
+            "Image extraction is not supported by {}: {}",
+            self.extractor_type(),
+            file_path.display()
+        ))
+    }
+
+    /// Reads the document's metadata (title, author, dates, page count, producer)
+    ///
+    /// # Returns
+    /// * `Ok(DocumentMetadata)` - The metadata found (possibly all `None`)
+    /// * `Err` - Error if reading fails or the format has no metadata support
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        Err(anyhow::anyhow!(
+            "Metadata extraction is not supported by {}: {}",
+            self.extractor_type(),
+            file_path.display()
+        ))
+    }
+
+    /// Extracts the text of a range of pages (1-based, inclusive), in page order
+    ///
+    /// Pages past the end of the document are left out, so `1..=usize::MAX` returns
+    /// every page.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PageText>)` - One entry per existing page of the range
+    /// * `Err` - Error if extraction fails, the range starts past the last page, or the
+    ///   format has no pages
+    fn extract_pages(&self, file_path: &Path, _pages: RangeInclusive<usize>) -> Result<Vec<PageText>> {
+        Err(anyhow::anyhow!(
+            "Page extraction is not supported by {}: {}",
+            self.extractor_type(),
+            file_path.display()
+        ))
+    }
+}
+
+/// Clamps a 1-based page range to a document with `page_count` pages
+///
+/// Errors when the range is empty, starts at page 0 or starts past the last page.
+pub fn clamp_page_range(pages: &RangeInclusive<usize>, page_count: usize) -> Result<RangeInclusive<usize>> {
+    if *pages.start() == 0 {
+        return Err(anyhow::anyhow!("Page numbers start at 1"));
+    }
+    if pages.is_empty() {
+        return Err(anyhow::anyhow!("Empty page range: {}-{}", pages.start(), pages.end()));
+    }
+    if *pages.start() > page_count {
+        return Err(anyhow::anyhow!(
+            "Page {} is out of range; the document has {} page{}",
+            pages.start(),
+            page_count,
+            if page_count == 1 { "" } else { "s" }
+        ));
+    }
+    Ok(*pages.start()..=(*pages.end()).min(page_count))
+}
+
+/// Returns the extractor for a (lowercase) file extension, if it is supported
+fn extractor_for_extension(extension: &str) -> Option<Box<dyn DocumentExtractor>> {
+    let extractor: Box<dyn DocumentExtractor> = match extension {
+        PDF_EXTENSION => match Config::config_path() {
+            Ok(config_path) => Box::new(PdfExtractor::new().with_password_store(config_path)),
+            Err(_) => Box::new(PdfExtractor::new()),
+        },
+        TXT_EXTENSION => Box::new(TxtExtractor),
+        MD_EXTENSION | MARKDOWN_EXTENSION => Box::new(MarkdownExtractor),
+        HTML_EXTENSION | HTM_EXTENSION => Box::new(HtmlExtractor::new()),
+        CSV_EXTENSION | TSV_EXTENSION => Box::new(CsvExtractor::new()),
+        PPTX_EXTENSION => Box::new(PptxExtractor),
+        ODS_EXTENSION => Box::new(OdsExtractor::new()),
+        ODP_EXTENSION => Box::new(OdpExtractor),
+        EML_EXTENSION => Box::new(EmlExtractor),
+        MBOX_EXTENSION => Box::new(MboxExtractor::new()),
+        JSON_EXTENSION => Box::new(JsonExtractor::new()),
+        XML_EXTENSION => Box::new(XmlExtractor),
+        YAML_EXTENSION | YML_EXTENSION => Box::new(YamlExtractor),
+        TOML_EXTENSION => Box::new(TomlExtractor),
+        ZIP_EXTENSION => Box::new(ZipExtractor::new()),
+        LOG_EXTENSION => Box::new(LogExtractor::new()),
+        PARQUET_EXTENSION => Box::new(ParquetExtractor::new()),
+        ICS_EXTENSION => Box::new(IcsExtractor),
+        VCF_EXTENSION => Box::new(VcfExtractor),
+        MOBI_EXTENSION | AZW3_EXTENSION => Box::new(MobiExtractor),
+        XPS_EXTENSION | OXPS_EXTENSION => Box::new(XpsExtractor),
+        ADOC_EXTENSION | ASCIIDOC_EXTENSION => Box::new(AdocExtractor),
+        PAGES_EXTENSION => Box::new(PagesExtractor),
+        KEY_EXTENSION => Box::new(KeynoteExtractor),
+        NUMBERS_EXTENSION => Box::new(NumbersExtractor),
+        SRT_EXTENSION | VTT_EXTENSION => Box::new(SubtitleExtractor::new()),
+        SVG_EXTENSION => Box::new(SvgExtractor),
+        DOCX_EXTENSION => Box::new(DocxExtractor::new()),
+        WARC_EXTENSION => Box::new(WarcExtractor::new()),
+        ONE_EXTENSION => Box::new(OneNoteExtractor),
+        ext if CODE_EXTENSIONS.contains(&ext) => Box::new(CodeExtractor::new()),
+        _ => return None,
+    };
+    Some(extractor)
+}
+
+/// Returns the lowercase extension that selects the extractor
+///
+/// Compressed formats with their own extractor count by their inner extension,
+/// e.g. `site.warc.gz` is dispatched as `warc`.
+fn dispatch_extension(file_path: &Path) -> Option<String> {
+    let extension = file_path.extension()?.to_str()?.to_lowercase();
+    if extension == GZ_EXTENSION {
+        let inner = file_path
+            .file_stem()
+            .and_then(|stem| Path::new(stem).extension())
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        if inner.as_deref() == Some(WARC_EXTENSION) {
+            return inner;
+        }
+    }
+    Some(extension)
+}
+
+/// Returns true when the file's extension has a dedicated extractor
+///
+/// Unlike `create_extractor`, this never falls back to `GenericExtractor`, so
+/// containers (archives, PDF attachments) can skip members they cannot read well.
+pub fn is_supported_file(file_path: &Path) -> bool {
+    dispatch_extension(file_path).is_some_and(|ext| extractor_for_extension(&ext).is_some())
+}
+
+/// A file extension with a dedicated extractor, as listed by `get_supported_formats`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SupportedFormat {
+    /// Lowercase extension without the dot, e.g. `pdf`
+    pub extension: &'static str,
+    /// Extractor that handles the extension, e.g. `PdfExtractor`
+    pub extractor: &'static str,
+}
+
+/// What this build of docu-mcp can read, as returned by `get_supported_formats`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SupportedFormats {
+    pub formats: Vec<SupportedFormat>,
+    /// Extractor tried for every other file; it reads what Apache Tika recognizes
+    pub fallback_extractor: &'static str,
+    /// Compressed suffixes read through the extension before them, e.g. `gz` for `site.warc.gz`
+    pub compressed_extensions: Vec<&'static str>,
+    /// True when Tesseract is installed, for scanned PDFs, images and `ocr_file`
+    pub ocr: bool,
+    /// Password-protected PDFs can be decrypted, with a password given or saved in the configuration
+    pub pdf_passwords: bool,
+    /// True when saved passwords can be kept in the OS keychain
+    pub keychain: bool,
+}
+
+/// Lists the extensions with a dedicated extractor and the optional features available
+///
+/// OCR and the keychain depend on programs installed on the machine, so they are
+/// checked each time.
+pub fn get_supported_formats() -> SupportedFormats {
+    let formats = SUPPORTED_EXTENSIONS
+        .iter()
+        .filter_map(|&extension| {
+            extractor_for_extension(extension).map(|extractor| SupportedFormat {
+                extension,
+                extractor: extractor.extractor_type(),
+            })
+        })
+        .collect();
+    SupportedFormats {
+        formats,
+        fallback_extractor: GenericExtractor.extractor_type(),
+        compressed_extensions: vec![GZ_EXTENSION],
+        ocr: ocr_available(),
+        pdf_passwords: true,
+        keychain: keychain_available(),
+    }
+}
+
+/// Puts a format mismatch notice before extracted text
+pub(crate) fn prefix_notice(notice: &str, text: &str) -> String {
+    format!("{}\n\n{}", notice, text)
+}
+
+/// Extractor chosen from the file content rather than its extension
+///
+/// Extracted text is prefixed with a notice, so the mismatch is visible to the reader.
+struct DetectedFormatExtractor {
+    inner: Box<dyn DocumentExtractor>,
+    notice: String,
+}
+
+impl DocumentExtractor for DetectedFormatExtractor {
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        Ok(prefix_notice(&self.notice, &self.inner.extract_text_from_file(file_path)?))
+    }
+
+    fn extractor_type(&self) -> &'static str {
+        self.inner.extractor_type()
+    }
+
+    fn extract_markdown(&self, file_path: &Path) -> Result<String> {
+        Ok(prefix_notice(&self.notice, &self.inner.extract_markdown(file_path)?))
+    }
+
+    fn extract_tables(&self, file_path: &Path) -> Result<Vec<Table>> {
+        self.inner.extract_tables(file_path)
+    }
+
+    fn extract_page_tables(&self, file_path: &Path) -> Result<Vec<PageTable>> {
+        self.inner.extract_page_tables(file_path)
+    }
+
+    fn extract_outline(&self, file_path: &Path) -> Result<Vec<Heading>> {
+        self.inner.extract_outline(file_path)
+    }
+
+    fn extract_links(&self, file_path: &Path) -> Result<Vec<Link>> {
+        self.inner.extract_links(file_path)
+    }
+
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        self.inner.extract_images(file_path)
+    }
+
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        self.inner.extract_metadata(file_path)
+    }
+
+    fn extract_pages(&self, file_path: &Path, pages: RangeInclusive<usize>) -> Result<Vec<PageText>> {
+        self.inner.extract_pages(file_path, pages)
+    }
 }
 
 /// Creates an appropriate document extractor based on the file extension
 ///
+/// When the file exists, its content is checked too (see `file_type::detect_extension`).
+/// A file without a supported extension, or whose content is clearly another format
+/// (a PDF saved as `.tmp`, a DOCX named `.pdf`), gets the extractor for its content;
+/// its extracted text then starts with a notice explaining the mismatch. A text file
+/// without an extension (`README`, `LICENSE`, `Makefile`) is read as plain text.
+/// Anything else goes to `GenericExtractor`, which lets Tika detect and read the format.
+///
 /// # Arguments
 /// * `file_path` - Path to the document file
 ///
 /// # Returns
 /// * `Ok(Box<dyn DocumentExtractor>)` - Appropriate extractor for the file type
-/// * `Err` - Error if the file format is not supported
+/// * `Err` - Error if no extractor exists for the detected content (should not happen)
 ///
 /// # Supported Formats
 /// * `.pdf` - PDF documents (Phase 1)
+/// * `.txt` - Plain-text documents in any common encoding
+/// * `.md`, `.markdown` - Markdown documents with optional YAML front matter
+/// * `.html`, `.htm` - Web pages, with scripts/styles stripped and links listed
+/// * `.csv`, `.tsv` - Delimited data rendered as an aligned table
+/// * `.pptx` - PowerPoint decks, labeled per slide with speaker notes
+/// * `.ods` - OpenDocument spreadsheets, one aligned table per sheet
+/// * `.odp` - OpenDocument presentations, labeled per slide with speaker notes
+/// * `.eml` - Email messages: headers, body text and attachment names
+/// * `.mbox` - Mailboxes, listed message by message (use `MboxExtractor` directly to select one)
+/// * `.json` - JSON documents flattened into `path.to.key: value` lines
+/// * `.xml` - XML documents as `element/path: text` lines
+/// * `.yaml`, `.yml`, `.toml` - Configuration files, validated and rendered as indented text
+/// * `.zip` - Archives: member listing plus the text of every supported member
+/// * `.log` - Log files, trimmed to the last 500 lines (use `LogExtractor` directly for other windows)
+/// * `.parquet` - Parquet files: schema summary plus the first rows as a table
+/// * `.ics` - iCalendar files: events and to-dos in chronological order
+/// * `.vcf` - vCard contacts as name/phone/email/organization lines
+/// * `.mobi`, `.azw3` - Kindle ebooks (without DRM), split into chapters
+/// * `.xps`, `.oxps` - XPS fixed documents, page by page
+/// * `.adoc`, `.asciidoc` - AsciiDoc documents with markup stripped
+/// * `.pages` - Apple Pages documents (files or package directories)
+/// * `.key` - Apple Keynote presentations, labeled per slide with presenter notes
+/// * `.numbers` - Apple Numbers spreadsheets: sheets, tables and text cells
+/// * `.srt`, `.vtt` - Subtitles and captions as dialog lines
+/// * `.svg` - SVG drawings: title, description and text labels
+/// * `.docx` - Word documents with tables, footnotes and endnotes
+/// * `.warc`, `.warc.gz` - Web archives: captured responses listed by URL, plus the text of
+///   HTML and plain-text pages (use `WarcExtractor` directly to select one URL)
+/// * `.one` - OneNote sections, labeled per page with the page title
+/// * `.rs`, `.py`, `.js`, `.ts`, `.java`, `.go`, `.c`, `.h`, `.cpp`, `.hpp` - Source code in a
+///   language-tagged fence
 pub fn create_extractor(file_path: &Path) -> Result<Box<dyn DocumentExtractor>> {
-    let extension = file_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .ok_or_else(|| anyhow::anyhow!("File has no extension: {}", file_path.display()))?;
+    let (extractor, notice) = resolve_extractor(file_path)?;
+    Ok(match notice {
+        Some(notice) => Box::new(DetectedFormatExtractor { inner: extractor, notice }),
+        None => extractor,
+    })
+}
+
+/// Chooses the extractor for a file as `create_extractor` does, returning the notice
+/// about a format mismatch separately instead of wrapping the extractor with it
+///
+/// The notice names the file, so text cached by content must not include it.
+pub(crate) fn resolve_extractor(file_path: &Path) -> Result<(Box<dyn DocumentExtractor>, Option<String>)> {
+    let extension = dispatch_extension(file_path);
+    let detected = detect_extension(file_path);
+
+    let (detected, notice) = match (extension.as_deref(), detected) {
+        (Some(extension), detected) => match (extractor_for_extension(extension), detected) {
+            (Some(extractor), None) => return Ok((extractor, None)),
+            (Some(extractor), Some(detected)) if extension_matches(extension, detected) => {
+                return Ok((extractor, None));
+            }
+            (Some(_), Some(detected)) => (
+                detected,
+                format!(
+                    "{} The content of {} is .{} data, not .{}; it was extracted as .{}.",
+                    FORMAT_MISMATCH_NOTICE,
+                    file_path.display(),
+                    detected,
+                    extension,
+                    detected
+                ),
+            ),
+            (None, None) => return Ok((Box::new(GenericExtractor), None)),
+            (None, Some(detected)) => (
+                detected,
+                format!(
+                    "{} {} has an unsupported extension; its content was detected as .{}.",
+                    FORMAT_MISMATCH_NOTICE,
+                    file_path.display(),
+                    detected
+                ),
+            ),
+        },
+        // README, LICENSE, Makefile, exports without an extension, ...
+        (None, None) if looks_like_text(file_path) => return Ok((Box::new(TxtExtractor), None)),
+        (None, None) => return Ok((Box::new(GenericExtractor), None)),
+        (None, Some(detected)) => (
+            detected,
+            format!(
+                "{} {} has no extension; its content was detected as .{}.",
+                FORMAT_MISMATCH_NOTICE,
+                file_path.display(),
+                detected
+            ),
+        ),
+    };
+
+    let inner = extractor_for_extension(detected)
+        .ok_or_else(|| anyhow::anyhow!("No extractor for detected format: {}", detected))?;
+    Ok((inner, Some(notice)))
+}
+
+/// Reads the metadata of a document with the extractor for its format
+///
+/// Supported for PDFs, Word/PowerPoint files, OpenDocument spreadsheets and
+/// presentations, Kindle ebooks, and (through Tika) formats without a dedicated
+/// extractor; other formats return an error.
+pub fn get_document_metadata(file_path: &Path) -> Result<DocumentMetadata> {
+    create_extractor(file_path)?.extract_metadata(file_path)
+}
+
+/// Counts the pages (slides, for presentations) of a document without extracting its text
+///
+/// PDFs and presentations are counted from their structure; Word files report the
+/// count saved in their properties. Errors when the format records no page count.
+pub fn count_pages(file_path: &Path) -> Result<PageCount> {
+    let extractor = create_extractor(file_path)?;
+    let count = extractor
+        .extract_metadata(file_path)?
+        .page_count
+        .ok_or_else(|| anyhow::anyhow!("The page count is not recorded in: {}", file_path.display()))?;
+
+    let (unit, exact) = match extractor.extractor_type() {
+        "PdfExtractor" => (PageUnit::Page, true),
+        "PptxExtractor" | "OdpExtractor" => (PageUnit::Slide, true),
+        _ => (PageUnit::Page, false),
+    };
+    Ok(PageCount { count, unit, exact })
+}
+
+/// Writes a table as CSV, quoting cells that need it
+fn table_to_csv(rows: &Table) -> Result<String> {
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+    for row in rows {
+        writer.write_record(row).context("Failed to write CSV row")?;
+    }
+    let bytes = writer.into_inner().context("Failed to write CSV")?;
+    String::from_utf8(bytes).context("CSV output is not valid UTF-8")
+}
+
+/// Extracts the tables of a document, with page numbers for paged formats
+///
+/// Supported for PDFs (tables inferred from text layout) and Word documents; other
+/// formats return an error.
+pub fn get_document_tables(file_path: &Path, format: TableFormat) -> Result<Vec<ExtractedTable>> {
+    let tables = create_extractor(file_path)?.extract_page_tables(file_path)?;
+    tables
+        .into_iter()
+        .enumerate()
+        .map(|(index, table)| {
+            let (rows, csv) = match format {
+                TableFormat::Json => (Some(table.rows), None),
+                TableFormat::Csv => (None, Some(table_to_csv(&table.rows)?)),
+            };
+            Ok(ExtractedTable { index, page: table.page, rows, csv })
+        })
+        .collect()
+}
+
+/// Returns the outline of a document: PDF bookmarks, Word heading styles or Markdown headers
+///
+/// PDF headings point to a page (read it with `get_document_pages`); the others to
+/// a character offset in the extracted text (read from it with `extract_text_slice`).
+/// Other formats return an error.
+pub fn get_document_outline(file_path: &Path) -> Result<Vec<Heading>> {
+    let extractor = create_extractor(file_path)?;
+    let mut headings = extractor.extract_outline(file_path)?;
+    if headings.iter().any(|heading| heading.page.is_none() && heading.offset.is_none()) {
+        let text = extractor.extract_text_from_file(file_path)?;
+        anchor_headings(&text, &mut headings);
+    }
+    Ok(headings)
+}
+
+/// Returns the hyperlinks of a document: PDF link annotations, Word hyperlinks, HTML
+/// anchors or Markdown links, with the page or section each is in
+///
+/// Other formats return an error.
+pub fn get_document_links(file_path: &Path) -> Result<Vec<Link>> {
+    create_extractor(file_path)?.extract_links(file_path)
+}
+
+/// Sets the offset of headings without an anchor to the start of the line where their
+/// title next appears in the text, searching from the previous heading onwards
+fn anchor_headings(text: &str, headings: &mut [Heading]) {
+    // Search position in bytes, and the number of characters before it
+    let mut position = 0;
+    let mut chars_before = 0;
+    for heading in headings.iter_mut().filter(|heading| heading.page.is_none() && heading.offset.is_none()) {
+        let Some(found) = text[position..].find(&heading.title).map(|found| position + found) else {
+            continue;
+        };
+        let line_start = text[..found].rfind('\n').map_or(0, |newline| newline + 1).max(position);
+        chars_before += text[position..line_start].chars().count();
+        heading.offset = Some(chars_before);
+
+        let end = found + heading.title.len();
+        chars_before += text[line_start..end].chars().count();
+        position = end;
+    }
+}
+
+/// Extracts a document as plain text, Markdown, or a structured document for `DocumentFormat::Json`
+pub fn extract_document(file_path: &Path, format: DocumentFormat) -> Result<ExtractedDocument> {
+    let extractor = create_extractor(file_path)?;
+    let text = match format {
+        DocumentFormat::Plain => return extractor.extract_text_from_file(file_path).map(ExtractedDocument::Text),
+        DocumentFormat::Markdown => return extractor.extract_markdown(file_path).map(ExtractedDocument::Text),
+        DocumentFormat::Json => extractor.extract_text_from_file(file_path)?,
+    };
+
+    // Structure the format does not support is left out rather than failing the extraction
+    let metadata = extractor.extract_metadata(file_path).ok();
+    let pages = extractor.extract_pages(file_path, 1..=usize::MAX).ok();
+    let mut headings = extractor.extract_outline(file_path).unwrap_or_default();
+    anchor_headings(&text, &mut headings);
+
+    // Char offsets of the anchored headings, to cut the text into sections
+    let starts: Vec<usize> = headings.iter().filter_map(|heading| heading.offset).collect();
+    let byte_positions: Vec<usize> = text.char_indices().map(|(position, _)| position).collect();
+    let byte_at = |offset: usize| byte_positions.get(offset).copied().unwrap_or(text.len());
+    let sections = headings
+        .into_iter()
+        .map(|heading| {
+            let text = heading.offset.map(|start| {
+                let end = starts.iter().copied().find(|&next| next > start).unwrap_or(usize::MAX);
+                text[byte_at(start)..byte_at(end)].trim_end().to_string()
+            });
+            DocumentSection { heading, text }
+        })
+        .collect();
+
+    Ok(ExtractedDocument::Structured(StructuredDocument {
+        extractor: extractor.extractor_type(),
+        metadata,
+        pages,
+        sections,
+        text,
+    }))
+}
+
+/// Extracts up to `max_images` embedded images of a document, base64-encoded
+///
+/// Supported for PDFs (JPEG and JPEG 2000 images as stored, 8-bit RGB and grayscale
+/// images as PNG), Word, PowerPoint and OpenDocument files; other formats return an error.
+pub fn get_document_images(file_path: &Path, max_images: usize) -> Result<Vec<ExtractedImage>> {
+    let images = create_extractor(file_path)?.extract_images(file_path)?;
+    Ok(images
+        .into_iter()
+        .take(max_images)
+        .enumerate()
+        .map(|(index, image)| ExtractedImage {
+            index,
+            page: image.page,
+            name: image.name,
+            mime_type: image.mime_type.to_string(),
+            size: image.data.len(),
+            data: STANDARD.encode(&image.data),
+        })
+        .collect())
+}
+
+/// Describes a document: size, modification time, the languages of its text and,
+/// where the format records them, title, author, dates and page count
+///
+/// Unlike `get_document_metadata`, formats without metadata support are not an
+/// error; their metadata fields are left empty and `metadata_error` says why.
+pub fn get_document_info(file_path: &Path) -> Result<DocumentInfo> {
+    let file = fs::metadata(file_path).with_context(|| format!("File not found: {}", file_path.display()))?;
+    if !file.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+    }
+
+    let extractor = create_extractor(file_path)?;
+    let (metadata, metadata_error) = match extractor.extract_metadata(file_path) {
+        Ok(metadata) => (metadata, None),
+        Err(e) => (DocumentMetadata::default(), Some(format!("{:#}", e))),
+    };
+    let file_modified = file
+        .modified()
+        .ok()
+        .and_then(unix_seconds)
+        .map(format_unix_time);
+    let languages = extractor
+        .extract_text_from_file(file_path)
+        .map(|text| detect_languages(&text))
+        .unwrap_or_default();
 
-    match extension.to_lowercase().as_str() {
-        "pdf" => Ok(Box::new(PdfExtractor)),
-        _ => Err(anyhow::anyhow!(
-            "Unsupported file format: {}. Only PDF files are supported in Phase 1.",
-            extension
-        )),
+    Ok(DocumentInfo {
+        size: file.len(),
+        file_modified,
+        extractor: extractor.extractor_type(),
+        metadata,
+        metadata_error,
+        languages,
+    })
+}
+
+/// Text extracted from a document held in memory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BytesExtraction {
+    /// Extractor that read the document
+    pub extractor: &'static str,
+    pub text: String,
+    /// Languages of the text, most used first
+    pub languages: Vec<DetectedLanguage>,
+}
+
+/// Extracts a document held in memory, choosing the extractor by `file_name`
+///
+/// The bytes are written to a temporary file named `file_name` (only its final
+/// component is used), which is removed afterwards; without a telling extension,
+/// the content decides as for any file.
+pub fn extract_text_from_bytes(data: &[u8], file_name: &str) -> Result<BytesExtraction> {
+    let file_name = Path::new(file_name)
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", file_name))?;
+    let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let file_path = temp_dir.path().join(file_name);
+    fs::write(&file_path, data).context("Failed to write document to a temporary file")?;
+
+    let extractor = create_extractor(&file_path)?;
+    let text = extractor.extract_text_from_file(&file_path)?;
+    Ok(BytesExtraction { extractor: extractor.extractor_type(), languages: detect_languages(&text), text })
+}
+
+/// Extracts a document in `format` and returns up to `max_chars` characters starting at `offset`
+///
+/// The document is extracted in full once and kept in the shared extraction cache, so
+/// reading the following slices of an unchanged document does not extract it again.
+pub fn extract_text_slice(file_path: &Path, offset: usize, max_chars: usize, format: OutputFormat) -> Result<TextSlice> {
+    if max_chars == 0 {
+        return Err(anyhow::anyhow!("max_chars must be at least 1"));
     }
+    let text = shared_cache().formatted(file_path, format)?;
+    Ok(TextSlice::from_text(&text, offset, max_chars))
+}
+
+/// Extracts a range of pages of a document with the extractor for its format
+///
+/// Only PDFs are supported for now; other formats return an error.
+pub fn get_document_pages(file_path: &Path, pages: RangeInclusive<usize>) -> Result<Vec<PageText>> {
+    create_extractor(file_path)?.extract_pages(file_path, pages)
+}
+
+/// Extracts every page of a document, in page order, so any page can be read by number
+///
+/// Only PDFs are supported for now; other formats return an error.
+pub fn split_by_pages(file_path: &Path) -> Result<Vec<PageText>> {
+    get_document_pages(file_path, 1..=usize::MAX)
 }
 
 #[cfg(test)]
@@ -50,6 +987,25 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_get_supported_formats() {
+        let supported = get_supported_formats();
+        assert_eq!(supported.formats.len(), SUPPORTED_EXTENSIONS.len(), "Every listed extension has an extractor");
+        let extractor_of = |extension: &str| {
+            supported
+                .formats
+                .iter()
+                .find(|format| format.extension == extension)
+                .map(|format| format.extractor)
+        };
+        assert_eq!(extractor_of("pdf"), Some("PdfExtractor"));
+        assert_eq!(extractor_of("docx"), Some("DocxExtractor"));
+        assert_eq!(extractor_of("rs"), Some("CodeExtractor"));
+        assert_eq!(extractor_of("gz"), None);
+        assert_eq!(supported.fallback_extractor, "GenericExtractor");
+        assert!(supported.pdf_passwords);
+    }
+
     #[test]
     fn test_create_extractor_for_pdf() {
         // Get the path to the test PDF
@@ -77,28 +1033,571 @@ mod tests {
     }
 
     #[test]
-    fn test_create_extractor_for_unsupported_format() {
+    fn test_create_extractor_for_txt() {
         let mut txt_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         txt_path.push("fixtures");
-        txt_path.push("test.txt");
+        txt_path.push("sample_utf8.txt");
 
-        // Test factory function with unsupported format
-        let result = create_extractor(&txt_path);
-        assert!(result.is_err(), "Factory should return error for unsupported formats");
-        
-        if let Err(e) = result {
-            let error_msg = e.to_string();
-            assert!(error_msg.contains("Unsupported file format"), 
-                    "Error message should mention unsupported format. Got: {}", error_msg);
+        let extractor = create_extractor(&txt_path).expect("Factory should create extractor for TXT files");
+        assert_eq!(extractor.extractor_type(), "TxtExtractor");
+
+        let text = extractor.extract_text_from_file(&txt_path).expect("Extractor should read TXT files");
+        assert!(text.contains("Café crème"), "Extracted text should be decoded. Got: {}", text);
+    }
+
+    #[test]
+    fn test_create_extractor_for_markdown() {
+        for name in ["note.md", "NOTES.MARKDOWN"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for Markdown files");
+            assert_eq!(extractor.extractor_type(), "MarkdownExtractor");
         }
     }
 
+    #[test]
+    fn test_create_extractor_for_html() {
+        for name in ["page.html", "saved.htm"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for HTML files");
+            assert_eq!(extractor.extractor_type(), "HtmlExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_csv() {
+        for name in ["flights.csv", "export.tsv"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for delimited files");
+            assert_eq!(extractor.extractor_type(), "CsvExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_pptx() {
+        let mut pptx_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pptx_path.push("fixtures");
+        pptx_path.push("deck.pptx");
+
+        let extractor = create_extractor(&pptx_path).expect("Factory should create extractor for PPTX files");
+        assert_eq!(extractor.extractor_type(), "PptxExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_opendocument() {
+        let cases = [("budget.ods", "OdsExtractor"), ("slides.odp", "OdpExtractor")];
+        for (name, expected_type) in cases {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for OpenDocument files");
+            assert_eq!(extractor.extractor_type(), expected_type);
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_eml() {
+        let path = PathBuf::from("thread.eml");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for EML files");
+        assert_eq!(extractor.extractor_type(), "EmlExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_mbox() {
+        let path = PathBuf::from("archive.mbox");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for mbox files");
+        assert_eq!(extractor.extractor_type(), "MboxExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_json() {
+        let path = PathBuf::from("export.json");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for JSON files");
+        assert_eq!(extractor.extractor_type(), "JsonExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_xml() {
+        let path = PathBuf::from("invoice.xml");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for XML files");
+        assert_eq!(extractor.extractor_type(), "XmlExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_config_files() {
+        let cases = [
+            ("deployment.yaml", "YamlExtractor"),
+            ("compose.yml", "YamlExtractor"),
+            ("infra.toml", "TomlExtractor"),
+        ];
+        for (name, expected_type) in cases {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for config files");
+            assert_eq!(extractor.extractor_type(), expected_type);
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_zip() {
+        let path = PathBuf::from("bundle.zip");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for ZIP files");
+        assert_eq!(extractor.extractor_type(), "ZipExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_log() {
+        let path = PathBuf::from("service.log");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for LOG files");
+        assert_eq!(extractor.extractor_type(), "LogExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_parquet() {
+        let path = PathBuf::from("flights.parquet");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for PARQUET files");
+        assert_eq!(extractor.extractor_type(), "ParquetExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_ics() {
+        let path = PathBuf::from("trip.ics");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for ICS files");
+        assert_eq!(extractor.extractor_type(), "IcsExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_vcf() {
+        let path = PathBuf::from("contacts.vcf");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for VCF files");
+        assert_eq!(extractor.extractor_type(), "VcfExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_mobi() {
+        for name in ["isthmus.mobi", "isthmus.azw3"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for Kindle files");
+            assert_eq!(extractor.extractor_type(), "MobiExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_xps() {
+        for name in ["passport_form.xps", "passport_form.oxps"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for XPS files");
+            assert_eq!(extractor.extractor_type(), "XpsExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_adoc() {
+        for name in ["runbook.adoc", "runbook.asciidoc"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for AsciiDoc files");
+            assert_eq!(extractor.extractor_type(), "AdocExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_pages() {
+        let path = PathBuf::from("memo.pages");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for PAGES files");
+        assert_eq!(extractor.extractor_type(), "PagesExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_keynote() {
+        let path = PathBuf::from("briefing.key");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for KEY files");
+        assert_eq!(extractor.extractor_type(), "KeynoteExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_numbers() {
+        let path = PathBuf::from("trips.numbers");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for NUMBERS files");
+        assert_eq!(extractor.extractor_type(), "NumbersExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_subtitle() {
+        for name in ["briefing.srt", "training.vtt"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for subtitle files");
+            assert_eq!(extractor.extractor_type(), "SubtitleExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_svg() {
+        let path = PathBuf::from("network.svg");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for SVG files");
+        assert_eq!(extractor.extractor_type(), "SvgExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_docx() {
+        let path = PathBuf::from("policy.docx");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for DOCX files");
+        assert_eq!(extractor.extractor_type(), "DocxExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_warc() {
+        for name in ["site.warc", "site.warc.gz", "SITE.WARC.GZ"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for WARC files");
+            assert_eq!(extractor.extractor_type(), "WarcExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_onenote() {
+        let path = PathBuf::from("travel.one");
+        let extractor = create_extractor(&path).expect("Factory should create extractor for OneNote files");
+        assert_eq!(extractor.extractor_type(), "OneNoteExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_source_code() {
+        for name in ["main.rs", "fare.py", "app.ts", "Main.java", "lib.cpp"] {
+            let path = PathBuf::from(name);
+            let extractor = create_extractor(&path).expect("Factory should create extractor for source files");
+            assert_eq!(extractor.extractor_type(), "CodeExtractor");
+        }
+    }
+
+    #[test]
+    fn test_create_extractor_for_unsupported_format() {
+        let mut unsupported_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        unsupported_path.push("fixtures");
+        unsupported_path.push("test.xyz");
+
+        // Unknown formats fall back to the generic extractor
+        let extractor = create_extractor(&unsupported_path).expect("Factory should fall back for unknown formats");
+        assert_eq!(extractor.extractor_type(), "GenericExtractor");
+
+        let error_msg = extractor.extract_text_from_file(&unsupported_path).unwrap_err().to_string();
+        assert!(error_msg.contains("File not found"), "Missing files should still be reported. Got: {}", error_msg);
+    }
+
     #[test]
     fn test_create_extractor_for_file_without_extension() {
         let path = PathBuf::from("somefile");
 
         // Test factory function with file without extension
-        let result = create_extractor(&path);
-        assert!(result.is_err(), "Factory should return error for files without extension");
+        let extractor = create_extractor(&path).expect("Factory should fall back for files without extension");
+        assert_eq!(extractor.extractor_type(), "GenericExtractor");
+    }
+
+    #[test]
+    fn test_create_extractor_for_extensionless_files() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let readme = dir.path().join("README");
+        std::fs::write(&readme, "docu-mcp\n\nExtracts text from documents.\n").unwrap();
+        let extractor = create_extractor(&readme).expect("Text files without extension should be readable");
+        assert_eq!(extractor.extractor_type(), "TxtExtractor");
+        assert_eq!(extractor.extract_text_from_file(&readme).unwrap(), "docu-mcp\n\nExtracts text from documents.\n");
+
+        let mut fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        fixtures.push("fixtures");
+        let export = dir.path().join("export");
+        std::fs::copy(fixtures.join("budget.ods"), &export).unwrap();
+        let extractor = create_extractor(&export).expect("Known formats should be detected");
+        assert_eq!(extractor.extractor_type(), "OdsExtractor");
+
+        let blob = dir.path().join("blob");
+        std::fs::write(&blob, [0x00, 0x01, 0x02, 0xff]).unwrap();
+        let extractor = create_extractor(&blob).expect("Binary files should fall back");
+        assert_eq!(extractor.extractor_type(), "GenericExtractor");
+    }
+
+    #[test]
+    fn test_get_document_info() {
+        let mut docx_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        docx_path.push("fixtures");
+        docx_path.push("policy.docx");
+
+        let info = get_document_info(&docx_path).expect("Should describe DOCX files");
+        assert_eq!(info.size, std::fs::metadata(&docx_path).unwrap().len());
+        assert!(info.file_modified.as_deref().is_some_and(|date| date.ends_with('Z')), "Got: {:?}", info);
+        assert_eq!(info.extractor, "DocxExtractor");
+        assert_eq!(info.metadata.title.as_deref(), Some("Travel Expense Policy"));
+        assert_eq!(info.metadata_error, None);
+        assert_eq!(info.languages.first().map(|language| language.code), Some("en"), "Got: {:?}", info.languages);
+
+        let mut txt_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        txt_path.push("fixtures");
+        txt_path.push("sample_utf8.txt");
+
+        let info = get_document_info(&txt_path).expect("Formats without metadata are still described");
+        assert_eq!(info.metadata, DocumentMetadata::default());
+        assert!(info.metadata_error.is_some_and(|error| error.contains("not supported by TxtExtractor")));
+
+        let json = serde_json::to_value(get_document_info(&docx_path).unwrap()).unwrap();
+        assert_eq!(json["author"], "Thomas Plantin", "Metadata fields should be flattened. Got: {}", json);
+
+        assert!(get_document_info(Path::new("missing.docx")).is_err());
+    }
+
+    #[test]
+    fn test_extract_text_from_bytes() {
+        let extraction = extract_text_from_bytes(b"# Notes\n\nBring the adapter.", "../notes.md").unwrap();
+        assert_eq!(extraction.extractor, "MarkdownExtractor");
+        assert!(extraction.text.contains("Bring the adapter."), "Got: {}", extraction.text);
+
+        assert!(extract_text_from_bytes(b"text", "..").is_err());
+    }
+
+    #[test]
+    fn test_text_slice() {
+        let text = "Café au lait";
+        let first = TextSlice::from_text(text, 0, 5);
+        assert_eq!(first, TextSlice { text: "Café ".to_string(), offset: 0, total_chars: 12, next_offset: Some(5), languages: Vec::new() });
+
+        let last = TextSlice::from_text(text, 5, 100);
+        assert_eq!(last.text, "au lait");
+        assert_eq!(last.next_offset, None);
+
+        let past_end = TextSlice::from_text(text, 50, 5);
+        assert_eq!(past_end.text, "");
+        assert_eq!(past_end.next_offset, None);
+    }
+
+    #[test]
+    fn test_extract_formatted() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+
+        let docx = fixtures.join("policy.docx");
+        let extractor = create_extractor(&docx).unwrap();
+        let markdown = extractor.extract_formatted(&docx, OutputFormat::Markdown).unwrap();
+        assert!(markdown.contains("| Category | Daily limit |"), "Got: {}", markdown);
+        assert_eq!(
+            extractor.extract_formatted(&docx, OutputFormat::Text).unwrap(),
+            extractor.extract_text_from_file(&docx).unwrap()
+        );
+
+        // Formats without structure fall back to their plain text
+        let txt = fixtures.join("sample_utf8.txt");
+        let extractor = create_extractor(&txt).unwrap();
+        assert_eq!(
+            extractor.extract_formatted(&txt, OutputFormat::Markdown).unwrap(),
+            extractor.extract_text_from_file(&txt).unwrap()
+        );
+        assert_eq!(serde_json::from_str::<OutputFormat>("\"markdown\"").unwrap(), OutputFormat::Markdown);
+    }
+
+    #[test]
+    fn test_extract_text_slice() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("sample_utf8.txt");
+
+        let full = create_extractor(&path).unwrap().extract_text_from_file(&path).unwrap();
+        let mut offset = Some(0);
+        let mut pieces = String::new();
+        while let Some(start) = offset {
+            let slice = extract_text_slice(&path, start, 10, OutputFormat::Text).expect("Should slice text files");
+            assert_eq!(slice.total_chars, full.chars().count());
+            pieces.push_str(&slice.text);
+            offset = slice.next_offset;
+        }
+        assert_eq!(pieces, full, "Slices should cover the text exactly once");
+
+        assert!(extract_text_slice(&path, 0, 0, OutputFormat::Text).is_err());
+    }
+
+    #[test]
+    fn test_get_document_tables() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let docx = fixtures.join("policy.docx");
+
+        let tables = get_document_tables(&docx, TableFormat::Json).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].page, None, "Word documents have no pages");
+        assert_eq!(tables[0].rows.as_ref().unwrap()[1], ["Meals", "60 USD"]);
+        assert_eq!(tables[0].csv, None);
+
+        let tables = get_document_tables(&docx, TableFormat::Csv).unwrap();
+        assert_eq!(tables[0].csv.as_deref(), Some("Category,Daily limit\nMeals,60 USD\n"));
+        assert_eq!(table_to_csv(&vec![vec!["a,b".to_string(), "say \"hi\"".to_string()]]).unwrap(), "\"a,b\",\"say \"\"hi\"\"\"\n");
+
+        assert!(get_document_tables(&fixtures.join("sample_utf8.txt"), TableFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_get_document_images() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let pdf = fixtures.join("boardingPass.pdf");
+
+        let images = get_document_images(&pdf, 2).unwrap();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images.iter().map(|image| image.index).collect::<Vec<_>>(), [0, 1]);
+        let decoded = STANDARD.decode(&images[0].data).unwrap();
+        assert_eq!(decoded.len(), images[0].size);
+        assert_eq!(crate::file_type::image_mime_type(&decoded), Some(images[0].mime_type.as_str()));
+
+        assert_eq!(get_document_images(&fixtures.join("policy.docx"), DEFAULT_MAX_IMAGES).unwrap(), []);
+        assert!(get_document_images(&fixtures.join("sample_utf8.txt"), DEFAULT_MAX_IMAGES).is_err());
+    }
+
+    #[test]
+    fn test_get_document_outline() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+
+        let outline = get_document_outline(&fixtures.join("cabinManual.pdf")).unwrap();
+        assert_eq!(outline[1].title, "2. Installation");
+        assert_eq!((outline[1].page, outline[1].offset), (Some(2), None));
+
+        let note = fixtures.join("note.md");
+        let outline = get_document_outline(&note).unwrap();
+        assert_eq!(outline.len(), 2);
+        for heading in &outline {
+            let slice = extract_text_slice(&note, heading.offset.unwrap(), 40, OutputFormat::Text).unwrap();
+            assert!(slice.text.trim_start_matches('#').trim_start().starts_with(&heading.title), "Got: {}", slice.text);
+        }
+
+        let outline = get_document_outline(&fixtures.join("policy.docx")).unwrap();
+        assert_eq!(outline[0].title, "Travel Expense Policy");
+        assert_eq!(outline[1].title, "Booking");
+        assert!(outline.iter().any(|heading| heading.title == "Limits" && heading.level == 2), "Got: {:?}", outline);
+        assert!(outline.iter().all(|heading| heading.offset.is_some()), "Got: {:?}", outline);
+
+        let error = get_document_outline(&fixtures.join("sample_utf8.txt")).unwrap_err().to_string();
+        assert!(error.contains("Outline extraction is not supported by TxtExtractor"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_extract_document() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let docx = fixtures.join("policy.docx");
+
+        let ExtractedDocument::Text(markdown) = extract_document(&docx, DocumentFormat::Markdown).unwrap() else {
+            panic!("Markdown output should be a string");
+        };
+        assert!(markdown.starts_with("# Travel Expense Policy"), "Got: {}", markdown);
+
+        let ExtractedDocument::Structured(document) = extract_document(&docx, DocumentFormat::Json).unwrap() else {
+            panic!("JSON output should be structured");
+        };
+        assert_eq!(document.extractor, "DocxExtractor");
+        assert_eq!(document.metadata.unwrap().title.as_deref(), Some("Travel Expense Policy"));
+        assert_eq!(document.pages, None, "Word files have no fixed pages");
+        let booking = document.sections.iter().find(|section| section.heading.title == "Booking").unwrap();
+        let text = booking.text.as_deref().unwrap();
+        assert!(text.contains("Booking") && !text.contains("Limits"), "Got: {}", text);
+
+        let json = serde_json::to_value(extract_document(&fixtures.join("note.md"), DocumentFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["sections"][0]["title"], "Itinerary");
+        assert!(json["metadata"].is_null());
+        assert_eq!(serde_json::from_str::<DocumentFormat>("\"plain\"").unwrap(), DocumentFormat::Plain);
+    }
+
+    #[test]
+    fn test_anchor_headings() {
+        let heading = |title: &str| Heading { level: 1, title: title.to_string(), page: None, offset: None };
+        let mut headings = [heading("Été"), heading("Missing"), heading("Été"), heading("Fin")];
+        anchor_headings("# Été\nau\n# Été\n## Fin", &mut headings);
+        let offsets: Vec<_> = headings.iter().map(|heading| heading.offset).collect();
+        assert_eq!(offsets, [Some(0), None, Some(9), Some(15)]);
+    }
+
+    #[test]
+    fn test_get_document_metadata() {
+        let mut docx_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        docx_path.push("fixtures");
+        docx_path.push("policy.docx");
+
+        let metadata = get_document_metadata(&docx_path).expect("Should read DOCX metadata");
+        assert_eq!(metadata.title.as_deref(), Some("Travel Expense Policy"));
+
+        let mut txt_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        txt_path.push("fixtures");
+        txt_path.push("sample_utf8.txt");
+
+        let error = get_document_metadata(&txt_path).unwrap_err().to_string();
+        assert!(error.contains("Metadata extraction is not supported by TxtExtractor"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_get_document_pages() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("fareTable.pdf");
+
+        let pages = get_document_pages(&pdf_path, 2..=2).expect("Should extract PDF pages");
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].page, 2);
+
+        let mut txt_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        txt_path.push("fixtures");
+        txt_path.push("sample_utf8.txt");
+
+        let error = get_document_pages(&txt_path, 1..=1).unwrap_err().to_string();
+        assert!(error.contains("Page extraction is not supported by TxtExtractor"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_count_pages() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+
+        let pdf = count_pages(&fixtures.join("cabinManual.pdf")).unwrap();
+        assert_eq!(pdf, PageCount { count: 5, unit: PageUnit::Page, exact: true });
+        let docx = count_pages(&fixtures.join("policy.docx")).unwrap();
+        assert_eq!(docx, PageCount { count: 2, unit: PageUnit::Page, exact: false });
+
+        let error = count_pages(&fixtures.join("sample_utf8.txt")).unwrap_err().to_string();
+        assert!(error.contains("Metadata extraction is not supported by TxtExtractor"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_split_by_pages() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+
+        let pages = split_by_pages(&fixtures.join("cabinManual.pdf")).unwrap();
+        assert_eq!(pages.iter().map(|page| page.page).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert!(pages[1].text.contains("Installation"), "Got: {}", pages[1].text);
+
+        assert!(split_by_pages(&fixtures.join("policy.docx")).is_err());
+    }
+
+    #[test]
+    fn test_clamp_page_range() {
+        assert_eq!(clamp_page_range(&(1..=usize::MAX), 3).unwrap(), 1..=3);
+        assert_eq!(clamp_page_range(&(2..=2), 3).unwrap(), 2..=2);
+        assert!(clamp_page_range(&(0..=2), 3).is_err());
+        assert!(clamp_page_range(&RangeInclusive::new(3, 2), 3).is_err());
+
+        let error = clamp_page_range(&(4..=5), 3).unwrap_err().to_string();
+        assert_eq!(error, "Page 4 is out of range; the document has 3 pages");
+    }
+
+    #[test]
+    fn test_create_extractor_detects_content_type() {
+        let mut fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        fixtures.push("fixtures");
+        let dir = tempfile::tempdir().unwrap();
+
+        // A PDF with an unsupported extension
+        let renamed_pdf = dir.path().join("download.tmp");
+        std::fs::copy(fixtures.join("boardingPass.pdf"), &renamed_pdf).unwrap();
+        let extractor = create_extractor(&renamed_pdf).expect("Content should identify the PDF");
+        assert_eq!(extractor.extractor_type(), "PdfExtractor");
+
+        // A Word document with the wrong extension
+        let misnamed_docx = dir.path().join("policy.pdf");
+        std::fs::copy(fixtures.join("policy.docx"), &misnamed_docx).unwrap();
+        let extractor = create_extractor(&misnamed_docx).expect("Content should identify the DOCX");
+        assert_eq!(extractor.extractor_type(), "DocxExtractor");
+        let text = extractor.extract_text_from_file(&misnamed_docx).unwrap();
+        assert!(text.starts_with(FORMAT_MISMATCH_NOTICE), "Mismatch should be reported. Got: {}", text);
+        assert!(text.contains("is .docx data, not .pdf"), "Got: {}", text);
+        assert!(text.contains("Travel Expense Policy"), "Got: {}", text);
+
+        // Content that agrees with the extension needs no notice
+        let text = create_extractor(&fixtures.join("policy.docx"))
+            .unwrap()
+            .extract_text_from_file(&fixtures.join("policy.docx"))
+            .unwrap();
+        assert!(!text.starts_with(FORMAT_MISMATCH_NOTICE), "Got: {}", text);
     }
 }
@@ -0,0 +1,381 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Admonition labels recognized both inline (`NOTE: ...`) and as block styles (`[NOTE]`)
+const ADMONITIONS: &[&str] = &["NOTE", "TIP", "IMPORTANT", "WARNING", "CAUTION"];
+
+/// Delimited blocks whose content is kept verbatim
+const VERBATIM_DELIMITERS: &[&str] = &["----", "...."];
+
+/// Delimited blocks whose content is rendered like regular paragraphs
+const COMPOUND_DELIMITERS: &[&str] = &["====", "****", "____", "--"];
+
+/// URL-like prefixes recognized by the inline link handling
+const LINK_PREFIXES: &[&str] = &["https://", "http://", "mailto:", "link:", "xref:"];
+
+/// AsciiDoc extractor that strips markup while keeping section titles, lists and admonitions
+pub struct AdocExtractor;
+
+impl AdocExtractor {
+    /// Finds `mark` closing a span that opened just before `from`
+    ///
+    /// Constrained (single) marks must also end at a word boundary.
+    fn find_closing(chars: &[char], from: usize, mark: &[char], constrained: bool) -> Option<usize> {
+        (from + 1..=chars.len().checked_sub(mark.len())?).find(|&index| {
+            chars[index..index + mark.len()] == *mark
+                && !chars[index - 1].is_whitespace()
+                && (!constrained || chars.get(index + mark.len()).is_none_or(|c| !c.is_alphanumeric()))
+        })
+    }
+
+    /// Renders a link target and its optional bracketed text
+    fn render_link(prefix: &str, target: &str, text: &str) -> String {
+        let text = Self::render_inline(text);
+        match prefix {
+            "xref:" if text.is_empty() => target.to_string(),
+            "xref:" => text,
+            _ => {
+                let url = if prefix == "link:" { target.to_string() } else { format!("{}{}", prefix, target) };
+                if text.is_empty() || text == url {
+                    url
+                } else {
+                    format!("{} ({})", text, url)
+                }
+            }
+        }
+    }
+
+    /// Strips inline markup: formatting marks, monospace, links and cross references
+    pub fn render_inline(line: &str) -> String {
+        let chars: Vec<char> = line.chars().collect();
+        let mut output = String::with_capacity(line.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let at_word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+            let rest: String = chars[i..].iter().collect();
+
+            // Cross references: <<id>> and <<id,text>>
+            if rest.starts_with("<<") {
+                if let Some(end) = rest.find(">>") {
+                    let inner = &rest[2..end];
+                    let label = inner.split_once(',').map(|(_, text)| text.trim()).unwrap_or(inner);
+                    output.push_str(&Self::render_inline(label));
+                    i += rest[..end + 2].chars().count();
+                    continue;
+                }
+            }
+
+            // Links: https://host[text], link:path[text], xref:doc.adoc[text]
+            if at_word_start {
+                if let Some(prefix) = LINK_PREFIXES.iter().find(|prefix| rest.starts_with(**prefix)) {
+                    let after = &rest[prefix.len()..];
+                    let target_len = after.find(|c: char| c.is_whitespace() || c == '[').unwrap_or(after.len());
+                    let target = &after[..target_len];
+                    let bracket = after[target_len..].strip_prefix('[').and_then(|s| s.find(']').map(|end| &s[..end]));
+                    if let Some(text) = bracket {
+                        output.push_str(&Self::render_link(prefix, target, text));
+                        i += prefix.chars().count() + target.chars().count() + text.chars().count() + 2;
+                        continue;
+                    }
+                    if !target.is_empty() && (prefix.starts_with("http") || *prefix == "mailto:") {
+                        output.push_str(prefix);
+                        output.push_str(target);
+                        i += prefix.chars().count() + target.chars().count();
+                        continue;
+                    }
+                }
+            }
+
+            // Monospace is literal: its content is not processed further
+            if c == '`' {
+                if let Some(end) = chars[i + 1..].iter().position(|&ch| ch == '`') {
+                    output.extend(&chars[i + 1..i + 1 + end]);
+                    i += end + 2;
+                    continue;
+                }
+            }
+
+            // Unconstrained (**bold**) and constrained (*bold*, _italic_, #highlight#) formatting
+            if matches!(c, '*' | '_' | '#') {
+                let doubled = chars.get(i + 1) == Some(&c);
+                let mark: &[char] = if doubled { &chars[i..i + 2] } else { &chars[i..i + 1] };
+                let content_start = i + mark.len();
+                let opens = chars.get(content_start).is_some_and(|next| !next.is_whitespace());
+                if opens && (doubled || at_word_start) {
+                    if let Some(end) = Self::find_closing(&chars, content_start, mark, !doubled) {
+                        let inner: String = chars[content_start..end].iter().collect();
+                        output.push_str(&Self::render_inline(&inner));
+                        i = end + mark.len();
+                        continue;
+                    }
+                }
+            }
+
+            output.push(c);
+            i += 1;
+        }
+
+        output
+    }
+
+    /// Returns the inner text of a `[...]` block attribute line
+    fn block_attribute(line: &str) -> Option<&str> {
+        line.strip_prefix('[')?.strip_suffix(']')
+    }
+
+    /// Renders a block macro such as `image::diagram.png[Alt text]`; `None` drops the line
+    fn render_block_macro(line: &str) -> Option<Option<String>> {
+        let (name, rest) = line.split_once("::")?;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) || !rest.ends_with(']') {
+            return None;
+        }
+        let (target, attributes) = rest[..rest.len() - 1].split_once('[')?;
+        Some(match name {
+            "image" | "video" | "audio" => {
+                let alt = attributes.split(',').next().unwrap_or("").trim();
+                let label = if alt.is_empty() { target } else { alt };
+                let kind = format!("{}{}", name[..1].to_uppercase(), &name[1..]);
+                Some(format!("[{}: {}]", kind, label))
+            }
+            _ => None,
+        })
+    }
+
+    /// Converts AsciiDoc source into readable plain text
+    pub fn extract_from_str(source: &str) -> String {
+        let mut output: Vec<String> = Vec::new();
+        // Closing delimiter of the block being read, and whether its content is verbatim
+        let mut open_block: Option<(String, bool)> = None;
+        let mut in_comment_block = false;
+        let mut in_table = false;
+        let mut in_header = false;
+        let mut pending_admonition: Option<String> = None;
+        let mut ordered_counters: Vec<usize> = Vec::new();
+
+        for raw_line in source.lines() {
+            let line = raw_line.trim_end();
+
+            if in_comment_block {
+                in_comment_block = line != "////";
+                continue;
+            }
+
+            if let Some((delimiter, verbatim)) = &open_block {
+                if line == delimiter {
+                    open_block = None;
+                    continue;
+                }
+                if *verbatim {
+                    output.push(raw_line.to_string());
+                    continue;
+                }
+            } else if VERBATIM_DELIMITERS.contains(&line) {
+                open_block = Some((line.to_string(), true));
+                continue;
+            }
+
+            if line == "////" {
+                in_comment_block = true;
+                continue;
+            }
+            if COMPOUND_DELIMITERS.contains(&line) {
+                open_block = Some((line.to_string(), false));
+                continue;
+            }
+            if line == "|===" {
+                in_table = !in_table;
+                continue;
+            }
+
+            if line.is_empty() {
+                in_header = false;
+                output.push(String::new());
+                continue;
+            }
+
+            // Comments, attribute entries and explicit line continuations carry no text
+            if line.starts_with("//")
+                || line == "+"
+                || (line.starts_with(':') && line[1..].contains(':') && !line[1..].starts_with(' '))
+            {
+                continue;
+            }
+
+            if in_table {
+                if let Some(cells) = line.strip_prefix('|') {
+                    let cells: Vec<String> = cells.split('|').map(|cell| Self::render_inline(cell.trim())).collect();
+                    output.push(cells.join(" | "));
+                } else {
+                    output.push(Self::render_inline(line));
+                }
+                continue;
+            }
+
+            if let Some(inner) = Self::block_attribute(line) {
+                let style = inner.split(',').next().unwrap_or("").trim();
+                if ADMONITIONS.contains(&style) {
+                    pending_admonition = Some(style.to_string());
+                }
+                continue;
+            }
+
+            if let Some(rendered) = Self::render_block_macro(line) {
+                output.extend(rendered);
+                continue;
+            }
+
+            // Section titles: `= Title` (document), `== Section`, ...
+            let level = line.chars().take_while(|&c| c == '=').count();
+            if level > 0 && line[level..].starts_with(' ') {
+                output.push(format!("{} {}", "#".repeat(level), Self::render_inline(line[level..].trim())));
+                in_header = level == 1;
+                ordered_counters.clear();
+                continue;
+            }
+            // Author and revision lines directly below the document title
+            if in_header {
+                output.push(line.to_string());
+                continue;
+            }
+
+            // Block titles: `.Title`
+            if let Some(title) = line.strip_prefix('.') {
+                if title.starts_with(|c: char| !c.is_whitespace() && c != '.') {
+                    output.push(Self::render_inline(title));
+                    continue;
+                }
+            }
+
+            // Lists: `* item` / `** nested`, `. step` / `.. nested step`, `term:: definition`
+            let marker = line.chars().next().unwrap_or(' ');
+            let depth = line.chars().take_while(|&c| c == marker).count();
+            let is_list_item = matches!(marker, '*' | '-' | '.') && line[depth..].starts_with(' ');
+            if is_list_item {
+                let indent = "  ".repeat(depth - 1);
+                let text = Self::render_inline(line[depth..].trim());
+                if marker == '.' {
+                    ordered_counters.resize(depth, 0);
+                    ordered_counters[depth - 1] += 1;
+                    output.push(format!("{}{}. {}", indent, ordered_counters[depth - 1], text));
+                } else {
+                    output.push(format!("{}- {}", indent, text));
+                }
+                continue;
+            }
+            ordered_counters.clear();
+
+            if let Some((term, definition)) = line.split_once(":: ").or_else(|| line.strip_suffix("::").map(|term| (term, ""))) {
+                output.push(format!("{}: {}", Self::render_inline(term.trim()), Self::render_inline(definition.trim())).trim_end().to_string());
+                continue;
+            }
+
+            let text = Self::render_inline(line);
+            match pending_admonition.take() {
+                Some(label) => output.push(format!("{}: {}", label, text)),
+                None => output.push(text),
+            }
+        }
+
+        // Collapse the blank lines left behind by removed markup
+        let mut text = String::new();
+        let mut previous_blank = true;
+        for line in output {
+            let blank = line.trim().is_empty();
+            if blank && previous_blank {
+                continue;
+            }
+            text.push_str(&line);
+            text.push('\n');
+            previous_blank = blank;
+        }
+        text.trim_end().to_string()
+    }
+}
+
+impl DocumentExtractor for AdocExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "AdocExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read AsciiDoc file: {}", file_path.display()))?;
+
+        let source = TxtExtractor::decode_bytes(&file_bytes);
+        Ok(Self::extract_from_str(&source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_adoc() {
+        let mut adoc_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        adoc_path.push("fixtures");
+        adoc_path.push("runbook.adoc");
+
+        let result = AdocExtractor.extract_text_from_file(&adoc_path);
+        assert!(result.is_ok(), "Failed to extract AsciiDoc: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "# Booking Service Runbook
+Jane Traveler <jane@example.com>
+
+This runbook covers on-call procedures for the booking service.
+See the status page (https://status.example.com) and Escalation.
+
+## Restarting the service
+
+1. Drain traffic from the node.
+2. Restart with systemctl restart booking.
+  1. Wait for the health check.
+3. Re-enable traffic.
+
+NOTE: Restarts take about 30 seconds.
+
+WARNING: Never restart both nodes at once.
+
+Useful commands
+journalctl -u booking --since \"10 min ago\"
+
+## Escalation
+
+- Page the secondary after 15 minutes.
+  - Use the #booking-oncall channel.
+Primary: Jane Traveler
+Secondary: Wei Chen
+
+Severity | Response time
+
+SEV1 | 15 minutes
+SEV2 | 1 hour
+
+[Image: Service architecture]";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_render_inline() {
+        assert_eq!(AdocExtractor::render_inline("a **b**c and snake_case_name"), "a bc and snake_case_name");
+        assert_eq!(AdocExtractor::render_inline("see xref:setup.adoc[Setup] or <<faq>>"), "see Setup or faq");
+        assert_eq!(AdocExtractor::render_inline("mail mailto:ops@example.com[ops]"), "mail ops (mailto:ops@example.com)");
+    }
+}
@@ -0,0 +1,278 @@
+//! Shared helpers for zip-based document formats (OOXML, OpenDocument, ...)
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use zip::ZipArchive;
+use crate::extractor::{DocumentMetadata, EmbeddedImage, MAX_IMAGE_SIZE};
+use crate::file_type::image_mime_type;
+
+/// Package relationship types of the OOXML core and extended (application) properties
+const CORE_PROPERTIES_TYPE: &str = "/metadata/core-properties";
+const EXTENDED_PROPERTIES_TYPE: &str = "/extended-properties";
+
+/// Largest uncompressed size read from a single archive entry, in bytes
+///
+/// Declared sizes can lie, so the limit is enforced while decompressing; a few-KB
+/// document cannot inflate to gigabytes in memory.
+pub const MAX_ZIP_ENTRY_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Opens a zip-based document for reading
+pub fn open_zip(file_path: &Path) -> Result<ZipArchive<File>> {
+    let file = File::open(file_path)
+        .with_context(|| format!("Failed to open file: {}", file_path.display()))?;
+    ZipArchive::new(file)
+        .with_context(|| format!("Failed to read zip container: {}", file_path.display()))
+}
+
+/// Reads a UTF-8 entry from the archive, returning `None` when it does not exist
+///
+/// Errors when the entry decompresses past `MAX_ZIP_ENTRY_SIZE`.
+pub fn read_zip_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<String>> {
+    read_zip_entry_capped(archive, name, MAX_ZIP_ENTRY_SIZE)
+}
+
+fn read_zip_entry_capped<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+    limit: u64,
+) -> Result<Option<String>> {
+    let entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open archive entry: {}", name)),
+    };
+
+    let content = read_capped(entry, limit).with_context(|| format!("Failed to read archive entry: {}", name))?;
+    String::from_utf8(content)
+        .map(Some)
+        .with_context(|| format!("Archive entry is not valid UTF-8: {}", name))
+}
+
+/// Reads at most `limit` bytes, erroring when there are more
+pub fn read_capped<R: Read>(reader: R, limit: u64) -> Result<Vec<u8>> {
+    let mut content = Vec::new();
+    reader.take(limit.saturating_add(1)).read_to_end(&mut content)?;
+    if content.len() as u64 > limit {
+        return Err(anyhow::anyhow!("entry decompresses past {} bytes", limit));
+    }
+    Ok(content)
+}
+
+/// Parses an OOXML relationships part (`_rels/*.rels`) into `Id -> (Type, Target)`
+pub fn parse_relationships(xml: &str) -> Result<HashMap<String, (String, String)>> {
+    let mut reader = Reader::from_str(xml);
+    let mut relationships = HashMap::new();
+
+    loop {
+        match reader.read_event().context("Failed to parse relationships XML")? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                let mut id = None;
+                let mut rel_type = String::new();
+                let mut target = None;
+                for attr in e.attributes().flatten() {
+                    let value = attr.unescape_value()?.into_owned();
+                    match attr.key.local_name().as_ref() {
+                        b"Id" => id = Some(value),
+                        b"Type" => rel_type = value,
+                        b"Target" => target = Some(value),
+                        _ => {}
+                    }
+                }
+                if let (Some(id), Some(target)) = (id, target) {
+                    relationships.insert(id, (rel_type, target));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(relationships)
+}
+
+/// Resolves a relationship target relative to the directory of the part that references it
+///
+/// For example `resolve_part_path("ppt/slides", "../notesSlides/notesSlide1.xml")`
+/// yields `ppt/notesSlides/notesSlide1.xml`.
+pub fn resolve_part_path(base_dir: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+
+    let mut parts: Vec<&str> = base_dir.split('/').filter(|part| !part.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+/// Collects the text of leaf elements by local name, e.g. `<dc:title>` under `title`
+///
+/// Meant for flat property parts such as OOXML `docProps/core.xml` or ODF `meta.xml`.
+/// The first non-empty occurrence of each name wins.
+pub fn property_elements(xml: &str) -> Result<HashMap<String, String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut properties = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+
+    loop {
+        match reader.read_event().context("Failed to parse properties XML")? {
+            Event::Start(e) => {
+                current = Some((String::from_utf8_lossy(e.local_name().as_ref()).into_owned(), String::new()));
+            }
+            Event::Text(e) => {
+                if let Some((_, text)) = current.as_mut() {
+                    text.push_str(&e.unescape()?);
+                }
+            }
+            Event::End(_) => {
+                if let Some((name, text)) = current.take() {
+                    let text = text.trim();
+                    if !text.is_empty() {
+                        properties.entry(name).or_insert_with(|| text.to_string());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(properties)
+}
+
+/// Reads the raster images stored under `prefix` (e.g. `word/media/`), in archive order
+///
+/// Entries are recognized by their content, so vector images (EMF, WMF, SVG) and
+/// entries larger than `MAX_IMAGE_SIZE` are skipped. Pages are left unset.
+pub fn read_zip_images<R: Read + Seek>(archive: &mut ZipArchive<R>, prefix: &str) -> Result<Vec<EmbeddedImage>> {
+    let mut images = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .with_context(|| format!("Failed to open archive entry #{}", index))?;
+        let name = entry.name().context("Failed to read archive entry name")?.into_owned();
+        if !entry.is_file() || !name.starts_with(prefix) || entry.size() > MAX_IMAGE_SIZE as u64 {
+            continue;
+        }
+
+        // An entry whose declared size lied is skipped like one declared too large
+        let Ok(data) = read_capped(entry, MAX_IMAGE_SIZE as u64) else {
+            continue;
+        };
+        if let Some(mime_type) = image_mime_type(&data) {
+            images.push(EmbeddedImage { page: None, name: Some(name), mime_type, data });
+        }
+    }
+    Ok(images)
+}
+
+/// Reads the OOXML document properties (`docProps/core.xml` and `docProps/app.xml`)
+///
+/// The page count comes from the `Pages` or `Slides` statistic, which is only as
+/// current as the last save by an application that maintains it.
+pub fn read_core_properties<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<DocumentMetadata> {
+    let relationships = match read_zip_entry(archive, "_rels/.rels")? {
+        Some(xml) => parse_relationships(&xml)?,
+        None => HashMap::new(),
+    };
+    let part_path = |rel_type: &str, default: &str| {
+        relationships
+            .values()
+            .find(|(target_type, _)| target_type.ends_with(rel_type))
+            .map(|(_, target)| resolve_part_path("", target))
+            .unwrap_or_else(|| default.to_string())
+    };
+    let core_path = part_path(CORE_PROPERTIES_TYPE, "docProps/core.xml");
+    let app_path = part_path(EXTENDED_PROPERTIES_TYPE, "docProps/app.xml");
+
+    let mut core = match read_zip_entry(archive, &core_path)? {
+        Some(xml) => property_elements(&xml).with_context(|| format!("Failed to parse {}", core_path))?,
+        None => HashMap::new(),
+    };
+    let mut app = match read_zip_entry(archive, &app_path)? {
+        Some(xml) => property_elements(&xml).with_context(|| format!("Failed to parse {}", app_path))?,
+        None => HashMap::new(),
+    };
+
+    Ok(DocumentMetadata {
+        title: core.remove("title"),
+        author: core.remove("creator"),
+        created: core.remove("created"),
+        modified: core.remove("modified"),
+        page_count: ["Pages", "Slides"]
+            .iter()
+            .find_map(|name| app.get(*name)?.parse().ok()),
+        producer: app.remove("Application"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_part_path() {
+        assert_eq!(resolve_part_path("ppt", "slides/slide1.xml"), "ppt/slides/slide1.xml");
+        assert_eq!(
+            resolve_part_path("ppt/slides", "../notesSlides/notesSlide1.xml"),
+            "ppt/notesSlides/notesSlide1.xml"
+        );
+        assert_eq!(resolve_part_path("word", "/word/footnotes.xml"), "word/footnotes.xml");
+    }
+
+    #[test]
+    fn test_read_zip_entry_is_capped() {
+        use std::io::{Cursor, Write};
+        use zip::write::SimpleFileOptions;
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("word/document.xml", SimpleFileOptions::default()).unwrap();
+        writer.write_all(&[b' '; 2000]).unwrap();
+        let mut archive = ZipArchive::new(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(read_zip_entry_capped(&mut archive, "word/document.xml", 2000).unwrap().unwrap().len(), 2000);
+        let error = read_zip_entry_capped(&mut archive, "word/document.xml", 1999).unwrap_err();
+        assert!(format!("{:#}", error).contains("decompresses past 1999 bytes"), "Got: {:#}", error);
+        assert!(read_zip_entry(&mut archive, "word/missing.xml").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_relationships() {
+        let xml = r#"<?xml version="1.0"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId2" Type="http://example.com/slide" Target="slides/slide1.xml"/>
+</Relationships>"#;
+        let relationships = parse_relationships(xml).unwrap();
+        assert_eq!(
+            relationships.get("rId2"),
+            Some(&("http://example.com/slide".to_string(), "slides/slide1.xml".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_property_elements() {
+        let xml = r#"<?xml version="1.0"?>
+<cp:coreProperties xmlns:cp="urn:cp" xmlns:dc="urn:dc">
+  <dc:title>Fare rules &amp; fees</dc:title>
+  <dc:subject/>
+  <dc:creator> Thomas Plantin </dc:creator>
+</cp:coreProperties>"#;
+        let properties = property_elements(xml).unwrap();
+        assert_eq!(properties.get("title").map(String::as_str), Some("Fare rules & fees"));
+        assert_eq!(properties.get("creator").map(String::as_str), Some("Thomas Plantin"));
+        assert!(!properties.contains_key("subject"));
+        assert!(!properties.contains_key("coreProperties"));
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::constants::{
+    CPP_EXTENSION, C_EXTENSION, GO_EXTENSION, HPP_EXTENSION, H_EXTENSION, JAVA_EXTENSION,
+    JS_EXTENSION, PY_EXTENSION, RS_EXTENSION, TS_EXTENSION,
+};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Fence language tag for each supported source extension
+const LANGUAGES: &[(&str, &str)] = &[
+    (RS_EXTENSION, "rust"),
+    (PY_EXTENSION, "python"),
+    (JS_EXTENSION, "javascript"),
+    (TS_EXTENSION, "typescript"),
+    (JAVA_EXTENSION, "java"),
+    (GO_EXTENSION, "go"),
+    (C_EXTENSION, "c"),
+    (H_EXTENSION, "c"),
+    (CPP_EXTENSION, "cpp"),
+    (HPP_EXTENSION, "cpp"),
+];
+
+/// Source-code extractor that wraps file content in a language-tagged Markdown fence
+pub struct CodeExtractor {
+    /// Prefix every line with its 1-based line number
+    line_numbers: bool,
+}
+
+impl Default for CodeExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeExtractor {
+    /// Creates a code extractor without line numbers
+    pub fn new() -> Self {
+        Self { line_numbers: false }
+    }
+
+    /// Enables or disables line numbers
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Returns the fence language tag for a file, based on its extension
+    pub fn detect_language(file_path: &Path) -> Option<&'static str> {
+        let extension = file_path.extension()?.to_str()?.to_lowercase();
+        LANGUAGES
+            .iter()
+            .find(|(ext, _)| *ext == extension)
+            .map(|(_, language)| *language)
+    }
+
+    /// Wraps source text in a fence tagged with `language`
+    pub fn render(&self, source: &str, language: &str) -> String {
+        // The fence must be longer than any backtick run inside the code
+        let longest_run = source
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or(0);
+        let fence = "`".repeat(longest_run.max(2) + 1);
+
+        let body = if self.line_numbers {
+            let lines: Vec<&str> = source.lines().collect();
+            let width = lines.len().to_string().len();
+            lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| format!("{:>width$} | {}", index + 1, line, width = width))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            source.trim_end_matches(['\r', '\n']).to_string()
+        };
+
+        format!("{}{}\n{}\n{}", fence, language, body, fence)
+    }
+}
+
+impl DocumentExtractor for CodeExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "CodeExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read source file: {}", file_path.display()))?;
+
+        let source = TxtExtractor::decode_bytes(&file_bytes);
+        let language = Self::detect_language(file_path).unwrap_or("");
+        Ok(self.render(&source, language))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn source_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("fare.py");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_source_file() {
+        let result = CodeExtractor::new().extract_text_from_file(&source_path());
+        assert!(result.is_ok(), "Failed to extract source: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("```python\ndef total_fare("), "Got: {}", text);
+        assert!(text.ends_with("    return base + taxes\n```"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_with_line_numbers() {
+        let text = CodeExtractor::new()
+            .with_line_numbers(true)
+            .extract_text_from_file(&source_path())
+            .unwrap();
+        assert!(text.contains("\n1 | def total_fare(base, taxes):\n"), "Got: {}", text);
+        assert!(text.contains("\n3 |     return base + taxes\n"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_detect_language_and_fence_length() {
+        assert_eq!(CodeExtractor::detect_language(Path::new("main.RS")), Some("rust"));
+        assert_eq!(CodeExtractor::detect_language(Path::new("vec.hpp")), Some("cpp"));
+        assert_eq!(CodeExtractor::detect_language(Path::new("README")), None);
+
+        let rendered = CodeExtractor::new().render("let doc = \"```\";", "rust");
+        assert!(rendered.starts_with("````rust\n"), "Fence should outgrow inner backticks. Got: {}", rendered);
+    }
+}
@@ -0,0 +1,305 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use csv::ReaderBuilder;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Default number of data rows rendered before the output is truncated
+pub const DEFAULT_MAX_ROWS: usize = 100;
+
+/// Cells longer than this are shortened so one value cannot blow up the table width
+const MAX_CELL_WIDTH: usize = 60;
+
+/// Number of lines inspected when sniffing the dialect
+const SNIFF_LINES: usize = 20;
+
+/// Delimiters considered when sniffing, in order of preference on ties
+const CANDIDATE_DELIMITERS: &[u8] = b",\t;|";
+
+/// CSV/TSV extractor that sniffs the dialect and renders rows as an aligned table
+pub struct CsvExtractor {
+    /// Maximum number of data rows (excluding the header) to render
+    max_rows: usize,
+}
+
+/// Delimiter and quote character detected from a sample of the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    pub quote: u8,
+}
+
+impl Default for CsvExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvExtractor {
+    /// Creates a CSV extractor rendering at most `DEFAULT_MAX_ROWS` rows
+    pub fn new() -> Self {
+        Self { max_rows: DEFAULT_MAX_ROWS }
+    }
+
+    /// Sets the maximum number of data rows to render
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Guesses the delimiter and quote character from the first lines of `text`
+    ///
+    /// The delimiter that splits the sampled lines into the most consistent
+    /// number of fields (more than one) wins; `default_delimiter` is used when
+    /// no candidate produces a consistent split.
+    pub fn sniff_dialect(text: &str, default_delimiter: u8) -> CsvDialect {
+        let sample: String = text.lines().take(SNIFF_LINES).collect::<Vec<_>>().join("\n");
+
+        let quote = if Self::count_quoted_fields(&sample, b'\'') > Self::count_quoted_fields(&sample, b'"') {
+            b'\''
+        } else {
+            b'"'
+        };
+
+        let mut best: Option<(usize, usize, u8)> = None;
+        for &delimiter in CANDIDATE_DELIMITERS {
+            let field_counts: Vec<usize> = ReaderBuilder::new()
+                .delimiter(delimiter)
+                .quote(quote)
+                .has_headers(false)
+                .flexible(true)
+                .from_reader(sample.as_bytes())
+                .records()
+                .filter_map(|record| record.ok())
+                .map(|record| record.len())
+                .collect();
+
+            let Some(&first) = field_counts.first() else {
+                continue;
+            };
+            if first < 2 {
+                continue;
+            }
+
+            // Score: how many rows agree with the header's field count, then field count
+            let consistent = field_counts.iter().filter(|&&count| count == first).count();
+            let score = (consistent, first, delimiter);
+            if best.is_none_or(|(c, f, _)| (consistent, first) > (c, f)) {
+                best = Some(score);
+            }
+        }
+
+        CsvDialect {
+            delimiter: best.map_or(default_delimiter, |(_, _, delimiter)| delimiter),
+            quote,
+        }
+    }
+
+    /// Counts fields that look wrapped in `quote` (start of line or after a common delimiter)
+    fn count_quoted_fields(sample: &str, quote: u8) -> usize {
+        let bytes = sample.as_bytes();
+        bytes
+            .iter()
+            .enumerate()
+            .filter(|&(i, &b)| {
+                b == quote && (i == 0 || bytes[i - 1] == b'\n' || CANDIDATE_DELIMITERS.contains(&bytes[i - 1]))
+            })
+            .count()
+    }
+
+    /// Renders delimited text as an aligned table
+    pub fn extract_from_str(&self, text: &str, default_delimiter: u8) -> Result<String> {
+        self.render(text, default_delimiter, Self::render_table)
+    }
+
+    /// Renders delimited text as a Markdown table
+    pub fn markdown_from_str(&self, text: &str, default_delimiter: u8) -> Result<String> {
+        self.render(text, default_delimiter, Self::render_markdown_table)
+    }
+
+    /// Parses delimited text and renders the rows kept by the row limit with `render_table`
+    fn render(&self, text: &str, default_delimiter: u8, render_table: fn(&[Vec<String>]) -> String) -> Result<String> {
+        let dialect = Self::sniff_dialect(text, default_delimiter);
+        let mut reader = ReaderBuilder::new()
+            .delimiter(dialect.delimiter)
+            .quote(dialect.quote)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut omitted_rows = 0;
+        for record in reader.records() {
+            let record = record.context("Failed to parse delimited row")?;
+            // The first row is the header and is always shown
+            if rows.len() > self.max_rows {
+                omitted_rows += 1;
+                continue;
+            }
+            rows.push(record.iter().map(Self::clean_cell).collect());
+        }
+
+        if rows.is_empty() {
+            return Ok(String::new());
+        }
+
+        let mut output = render_table(&rows);
+        if omitted_rows > 0 {
+            output.push_str(&format!(
+                "\n... {} more rows not shown (limit: {} rows)",
+                omitted_rows, self.max_rows
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Flattens embedded newlines and shortens overly long values
+    pub(crate) fn clean_cell(cell: &str) -> String {
+        let cell = cell.split_whitespace().collect::<Vec<_>>().join(" ");
+        if cell.chars().count() > MAX_CELL_WIDTH {
+            let truncated: String = cell.chars().take(MAX_CELL_WIDTH - 1).collect();
+            format!("{}…", truncated)
+        } else {
+            cell
+        }
+    }
+
+    /// Pads every column to its widest cell and underlines the header row
+    pub(crate) fn render_table(rows: &[Vec<String>]) -> String {
+        let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut widths = vec![0; column_count];
+        for row in rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+
+        let render_row = |row: &[String]| -> String {
+            let cells: Vec<String> = (0..column_count)
+                .map(|i| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or("");
+                    format!("{}{}", cell, " ".repeat(widths[i] - cell.chars().count()))
+                })
+                .collect();
+            cells.join(" | ").trim_end().to_string()
+        };
+
+        let mut lines = vec![render_row(&rows[0])];
+        lines.push(widths.iter().map(|&w| "-".repeat(w.max(1))).collect::<Vec<_>>().join("-+-"));
+        lines.extend(rows[1..].iter().map(|row| render_row(row)));
+        lines.join("\n")
+    }
+
+    /// Renders rows as a Markdown pipe table, the first row being the header
+    pub(crate) fn render_markdown_table(rows: &[Vec<String>]) -> String {
+        let column_count = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        let render_row = |row: &[String]| -> String {
+            let cells: Vec<String> = (0..column_count)
+                .map(|i| row.get(i).map_or(String::new(), |cell| cell.replace('|', "\\|")))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![render_row(&rows[0])];
+        lines.push(format!("|{}", " --- |".repeat(column_count)));
+        lines.extend(rows[1..].iter().map(|row| render_row(row)));
+        lines.join("\n")
+    }
+
+    /// Reads a delimited file, returning its text and the delimiter its extension suggests
+    fn read_file(file_path: &Path) -> Result<(String, u8)> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read delimited file: {}", file_path.display()))?;
+
+        // The extension is only a hint; the sniffed dialect takes precedence
+        let is_tsv = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv"));
+        let default_delimiter = if is_tsv { b'\t' } else { b',' };
+
+        Ok((TxtExtractor::decode_bytes(&file_bytes), default_delimiter))
+    }
+}
+
+impl DocumentExtractor for CsvExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "CsvExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        let (text, default_delimiter) = Self::read_file(file_path)?;
+        self.extract_from_str(&text, default_delimiter)
+            .with_context(|| format!("Failed to extract table from: {}", file_path.display()))
+    }
+
+    fn extract_markdown(&self, file_path: &Path) -> Result<String> {
+        let (text, default_delimiter) = Self::read_file(file_path)?;
+        self.markdown_from_str(&text, default_delimiter)
+            .with_context(|| format!("Failed to extract table from: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_csv() {
+        let mut csv_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        csv_path.push("fixtures");
+        csv_path.push("flights.csv");
+
+        let result = CsvExtractor::new().extract_text_from_file(&csv_path);
+        assert!(result.is_ok(), "Failed to extract CSV: {:?}", result.err());
+
+        let text = result.unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "flight | from | to  | notes");
+        assert_eq!(lines[1], "-------+------+-----+------------------------");
+        assert_eq!(lines[2], "CM 716 | AUS  | PTY | Window seat; early; 6am");
+        assert_eq!(lines[3], "CM 155 | PTY  | MDE | Connection, tight");
+    }
+
+    #[test]
+    fn test_extract_markdown_from_csv() {
+        let text = CsvExtractor::new().markdown_from_str("name,note\nA,\"two\nlines\"\nC\n", b',').unwrap();
+        assert_eq!(text, "| name | note |\n| --- | --- |\n| A | two lines |\n| C |  |");
+
+        let rows = vec![vec!["fare|class".to_string()]];
+        assert_eq!(CsvExtractor::render_markdown_table(&rows), "| fare\\|class |\n| --- |", "Pipes are escaped");
+    }
+
+    #[test]
+    fn test_sniff_dialect() {
+        let semicolons = "name;amount\n\"Smith, J\";12,50\nDoe;3,00\n";
+        assert_eq!(CsvExtractor::sniff_dialect(semicolons, b','), CsvDialect { delimiter: b';', quote: b'"' });
+
+        let tabs = "a\tb\tc\n1\t2\t3\n";
+        assert_eq!(CsvExtractor::sniff_dialect(tabs, b',').delimiter, b'\t');
+
+        let single_quoted = "'a'|'b'\n'1, 2'|'3'\n";
+        assert_eq!(CsvExtractor::sniff_dialect(single_quoted, b','), CsvDialect { delimiter: b'|', quote: b'\'' });
+    }
+
+    #[test]
+    fn test_extract_from_str_respects_row_limit() {
+        let text = "n\n1\n2\n3\n4\n";
+        let output = CsvExtractor::new().with_max_rows(2).extract_from_str(text, b',').unwrap();
+        assert!(output.contains("1\n2"), "First rows should be rendered. Got: {}", output);
+        assert!(!output.contains('3'), "Rows past the limit should be omitted. Got: {}", output);
+        assert!(output.ends_with("... 2 more rows not shown (limit: 2 rows)"), "Got: {}", output);
+    }
+}
@@ -0,0 +1,711 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use zip::ZipArchive;
+use crate::extractor::{DocumentExtractor, DocumentMetadata, EmbeddedImage, Heading, Link, OutputFormat, Table};
+use crate::extractors::archive::{
+    open_zip, parse_relationships, read_core_properties, read_zip_entry, read_zip_images, resolve_part_path,
+};
+use crate::extractors::csv_extractor::CsvExtractor;
+use crate::extractors::markdown_extractor::markdown_headings;
+use crate::extractors::odf::string_attribute;
+
+/// Where footnotes and endnotes appear in the extracted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotePlacement {
+    /// Numbered markers (`[1]`, `[E1]`) in the text, notes listed in labeled sections at the end
+    #[default]
+    Append,
+    /// The note text in brackets at the point where it is referenced
+    Inline,
+}
+
+/// Word (.docx) extractor for body text, tables, footnotes and endnotes
+///
+/// Headings (`Title`, `Heading1`..`Heading9` styles) are marked with `#`, tables are
+/// rendered like CSV tables and deleted tracked changes are left out. Markdown output
+/// also marks list items and renders tables as pipe tables.
+pub struct DocxExtractor {
+    notes: NotePlacement,
+}
+
+/// Footnote and endnote texts by `w:id`
+#[derive(Debug, Default)]
+struct Notes {
+    footnotes: HashMap<String, String>,
+    endnotes: HashMap<String, String>,
+}
+
+/// A table being read; tables can be nested inside cells
+#[derive(Debug, Default)]
+struct OpenTable {
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: String,
+}
+
+impl Default for DocxExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocxExtractor {
+    /// Creates a DOCX extractor that appends notes after the body
+    pub fn new() -> Self {
+        Self { notes: NotePlacement::default() }
+    }
+
+    /// Chooses whether notes are appended in sections or inlined at their reference
+    pub fn with_notes(mut self, notes: NotePlacement) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    /// Returns the heading level of a paragraph style id, if it is a heading style
+    fn heading_level(style: &str) -> Option<usize> {
+        if style == "Title" {
+            return Some(1);
+        }
+        style
+            .strip_prefix("Heading")
+            .and_then(|level| level.parse().ok())
+            .filter(|level| (1..=9).contains(level))
+    }
+
+    /// Parses `word/footnotes.xml` or `word/endnotes.xml` (`element` is `footnote`/`endnote`)
+    fn parse_notes(xml: &str, element: &[u8]) -> Result<HashMap<String, String>> {
+        let mut reader = Reader::from_str(xml);
+        let mut notes = HashMap::new();
+        let mut current: Option<(String, Vec<String>)> = None;
+        let mut paragraph = String::new();
+        let mut in_text = false;
+
+        loop {
+            match reader.read_event().context("Failed to parse notes XML")? {
+                Event::Start(e) if e.local_name().as_ref() == element => {
+                    // Separator notes carry a type; real notes have none (or `normal`)
+                    let is_note = string_attribute(&e, b"type").is_none_or(|t| t == "normal");
+                    current = string_attribute(&e, b"id")
+                        .filter(|_| is_note)
+                        .map(|id| (id, Vec::new()));
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"t" => in_text = true,
+                Event::Empty(e) if e.local_name().as_ref() == b"tab" => paragraph.push(' '),
+                Event::Text(e) if in_text => paragraph.push_str(&e.unescape()?),
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"t" => in_text = false,
+                    b"p" => {
+                        let text = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+                        if let (Some((_, paragraphs)), false) = (current.as_mut(), text.is_empty()) {
+                            paragraphs.push(text);
+                        }
+                        paragraph.clear();
+                    }
+                    name if name == element => {
+                        if let Some((id, paragraphs)) = current.take() {
+                            notes.insert(id, paragraphs.join(" "));
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(notes)
+    }
+
+    /// Reads the footnotes and endnotes parts referenced by the main document
+    fn read_notes(archive: &mut ZipArchive<File>, document_path: &str) -> Result<Notes> {
+        let document_dir = document_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let relationships = Self::document_relationships(archive, document_path)?;
+
+        let mut notes = Notes::default();
+        for (rel_type, target) in relationships.values() {
+            let (element, map) = if rel_type.ends_with("/footnotes") {
+                (b"footnote".as_slice(), &mut notes.footnotes)
+            } else if rel_type.ends_with("/endnotes") {
+                (b"endnote".as_slice(), &mut notes.endnotes)
+            } else {
+                continue;
+            };
+            let part_path = resolve_part_path(document_dir, target);
+            if let Some(xml) = read_zip_entry(archive, &part_path)? {
+                *map = Self::parse_notes(&xml, element)
+                    .with_context(|| format!("Failed to parse {}", part_path))?;
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Reads the relationships of the main document part, by id
+    fn document_relationships(
+        archive: &mut ZipArchive<File>,
+        document_path: &str,
+    ) -> Result<HashMap<String, (String, String)>> {
+        let (document_dir, document_file) = document_path.rsplit_once('/').unwrap_or(("", document_path));
+        let rels_path = format!("{}/_rels/{}.rels", document_dir, document_file);
+        match read_zip_entry(archive, &rels_path)? {
+            Some(xml) => parse_relationships(&xml),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Returns the main document part, following the package relationships
+    fn document_path(archive: &mut ZipArchive<File>) -> Result<String> {
+        let target = match read_zip_entry(archive, "_rels/.rels")? {
+            Some(xml) => parse_relationships(&xml)?
+                .into_values()
+                .find(|(rel_type, _)| rel_type.ends_with("/officeDocument"))
+                .map(|(_, target)| resolve_part_path("", &target)),
+            None => None,
+        };
+        Ok(target.unwrap_or_else(|| "word/document.xml".to_string()))
+    }
+
+    /// Renders the body of `word/document.xml`, placing notes as configured
+    ///
+    /// Also returns the top-level tables as rows of cells.
+    fn render_document(&self, xml: &str, notes: &Notes, format: OutputFormat) -> Result<(String, Vec<Table>)> {
+        let markdown = format == OutputFormat::Markdown;
+        let mut reader = Reader::from_str(xml);
+        let mut blocks: Vec<String> = Vec::new();
+        let mut tables: Vec<OpenTable> = Vec::new();
+        let mut finished_tables: Vec<Table> = Vec::new();
+        let mut paragraph: Option<String> = None;
+        let mut heading: Option<usize> = None;
+        // Nesting level (`w:ilvl`) of a numbered or bulleted paragraph
+        let mut list_level: Option<usize> = None;
+        let mut in_text = false;
+        // Appended notes in reference order: (marker, text)
+        let mut footnotes: Vec<(String, String)> = Vec::new();
+        let mut endnotes: Vec<(String, String)> = Vec::new();
+
+        loop {
+            match reader.read_event().context("Failed to parse word/document.xml")? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"p" => {
+                        paragraph = Some(String::new());
+                        heading = None;
+                        list_level = None;
+                    }
+                    b"t" => in_text = true,
+                    b"tbl" => tables.push(OpenTable::default()),
+                    b"tr" => {
+                        if let Some(table) = tables.last_mut() {
+                            table.row.clear();
+                        }
+                    }
+                    b"tc" => {
+                        if let Some(table) = tables.last_mut() {
+                            table.cell.clear();
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"pStyle" => heading = string_attribute(&e, b"val").and_then(|style| Self::heading_level(&style)),
+                    b"ilvl" => list_level = string_attribute(&e, b"val").and_then(|level| level.parse().ok()),
+                    // Numbering id 0 removes the numbering a style would apply
+                    b"numId" => match string_attribute(&e, b"val").as_deref() {
+                        Some("0") => list_level = None,
+                        _ => list_level = list_level.or(Some(0)),
+                    },
+                    b"tab" => push_text(&mut paragraph, "\t"),
+                    b"br" | b"cr" => push_text(&mut paragraph, "\n"),
+                    b"footnoteReference" | b"endnoteReference" => {
+                        let marker = self.note_reference(&e, notes, &mut footnotes, &mut endnotes);
+                        push_text(&mut paragraph, &marker);
+                    }
+                    _ => {}
+                },
+                Event::Text(e) if in_text => push_text(&mut paragraph, &e.unescape()?),
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"t" => in_text = false,
+                    b"p" => {
+                        let Some(text) = paragraph.take() else {
+                            continue;
+                        };
+                        let text = text.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+                        let text = text.trim();
+                        if text.is_empty() {
+                            continue;
+                        }
+                        match (tables.last_mut(), heading) {
+                            (Some(table), _) => {
+                                table.cell.push_str(text);
+                                table.cell.push(' ');
+                            }
+                            (None, Some(level)) => blocks.push(format!("{} {}", "#".repeat(level), text)),
+                            (None, None) => match list_level.filter(|_| markdown) {
+                                Some(level) => blocks.push(format!("{}- {}", "  ".repeat(level), text)),
+                                None => blocks.push(text.to_string()),
+                            },
+                        }
+                    }
+                    b"tc" => {
+                        if let Some(table) = tables.last_mut() {
+                            let cell = CsvExtractor::clean_cell(&table.cell);
+                            table.row.push(cell);
+                        }
+                    }
+                    b"tr" => {
+                        if let Some(table) = tables.last_mut() {
+                            let row = std::mem::take(&mut table.row);
+                            table.rows.push(row);
+                        }
+                    }
+                    b"tbl" => {
+                        let Some(table) = tables.pop() else {
+                            continue;
+                        };
+                        if table.rows.is_empty() {
+                            continue;
+                        }
+                        match tables.last_mut() {
+                            // A nested table is flattened into the enclosing cell
+                            Some(outer) => {
+                                for row in &table.rows {
+                                    outer.cell.push_str(&row.join(" "));
+                                    outer.cell.push(' ');
+                                }
+                            }
+                            None => {
+                                blocks.push(if markdown {
+                                    CsvExtractor::render_markdown_table(&table.rows)
+                                } else {
+                                    CsvExtractor::render_table(&table.rows)
+                                });
+                                finished_tables.push(table.rows);
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        let mut output = blocks.join("\n\n");
+        for (label, notes) in [("Footnotes", footnotes), ("Endnotes", endnotes)] {
+            if notes.is_empty() {
+                continue;
+            }
+            let lines: Vec<String> = notes
+                .into_iter()
+                .map(|(marker, text)| format!("{} {}", marker, text))
+                .collect();
+            output.push_str(&format!("\n\n{}:\n{}", label, lines.join("\n")));
+        }
+        Ok((output, finished_tables))
+    }
+
+    /// Reads the hyperlinks of `word/document.xml`, with the heading each comes under
+    ///
+    /// Both `w:hyperlink` elements, whose target is in the document relationships, and
+    /// `HYPERLINK` fields are read. Links to bookmarks in the document are left out.
+    fn parse_links(xml: &str, relationships: &HashMap<String, (String, String)>) -> Result<Vec<Link>> {
+        let mut reader = Reader::from_str(xml);
+        let mut links = Vec::new();
+        let mut section: Option<String> = None;
+        let mut paragraph = String::new();
+        let mut heading = false;
+        let mut in_text = false;
+        let mut in_instruction = false;
+        let mut instruction = String::new();
+        // Links being read: (url, text)
+        let mut hyperlink: Option<(String, String)> = None;
+        let mut field: Option<(String, String)> = None;
+
+        loop {
+            match reader.read_event().context("Failed to parse word/document.xml")? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"p" => {
+                        paragraph.clear();
+                        heading = false;
+                    }
+                    b"t" => in_text = true,
+                    b"instrText" => in_instruction = true,
+                    b"hyperlink" => {
+                        let target = string_attribute(&e, b"id")
+                            .and_then(|id| relationships.get(&id))
+                            .filter(|(rel_type, _)| rel_type.ends_with("/hyperlink"))
+                            .map(|(_, target)| target.clone());
+                        hyperlink = target.map(|target| match string_attribute(&e, b"anchor") {
+                            Some(anchor) => (format!("{}#{}", target, anchor), String::new()),
+                            None => (target, String::new()),
+                        });
+                    }
+                    _ => {}
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"pStyle" => {
+                        heading = string_attribute(&e, b"val").and_then(|style| Self::heading_level(&style)).is_some();
+                    }
+                    b"fldChar" => match string_attribute(&e, b"fldCharType").as_deref() {
+                        Some("begin") => {
+                            instruction.clear();
+                            field = None;
+                        }
+                        Some("separate") => {
+                            let instruction = instruction.trim();
+                            // `\l` points the field at a bookmark in the document
+                            if instruction.starts_with("HYPERLINK") && !instruction.contains("\\l") {
+                                let url = instruction.split('"').nth(1).unwrap_or_default().trim();
+                                field = (!url.is_empty()).then(|| (url.to_string(), String::new()));
+                            }
+                        }
+                        Some("end") => {
+                            if let Some(link) = field.take() {
+                                links.push(Self::link(link, &section));
+                            }
+                        }
+                        _ => {}
+                    },
+                    _ => {}
+                },
+                Event::Text(e) if in_text || in_instruction => {
+                    let text = e.unescape()?;
+                    if in_instruction {
+                        instruction.push_str(&text);
+                        continue;
+                    }
+                    paragraph.push_str(&text);
+                    for (_, link_text) in hyperlink.iter_mut().chain(field.iter_mut()) {
+                        link_text.push_str(&text);
+                    }
+                }
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"t" => in_text = false,
+                    b"instrText" => in_instruction = false,
+                    b"hyperlink" => {
+                        if let Some(link) = hyperlink.take() {
+                            links.push(Self::link(link, &section));
+                        }
+                    }
+                    b"p" => {
+                        let text = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+                        if heading && !text.is_empty() {
+                            section = Some(text);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(links)
+    }
+
+    fn link((url, text): (String, String), section: &Option<String>) -> Link {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        Link { url, text: (!text.is_empty()).then_some(text), page: None, section: section.clone() }
+    }
+
+    /// Reads `word/document.xml` and, when requested, the footnotes and endnotes
+    fn read_document(file_path: &Path, with_notes: bool) -> Result<(String, Notes)> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        let document_path = Self::document_path(&mut archive)?;
+        let document = read_zip_entry(&mut archive, &document_path)?.ok_or_else(|| {
+            anyhow::anyhow!("Missing {}; not a Word document: {}", document_path, file_path.display())
+        })?;
+        let notes = if with_notes {
+            Self::read_notes(&mut archive, &document_path)
+                .with_context(|| format!("Failed to read notes from: {}", file_path.display()))?
+        } else {
+            Notes::default()
+        };
+        Ok((document, notes))
+    }
+
+    /// Returns the text that replaces a `w:footnoteReference`/`w:endnoteReference`
+    fn note_reference(
+        &self,
+        e: &BytesStart,
+        notes: &Notes,
+        footnotes: &mut Vec<(String, String)>,
+        endnotes: &mut Vec<(String, String)>,
+    ) -> String {
+        let is_footnote = e.local_name().as_ref() == b"footnoteReference";
+        let (texts, collected, label, prefix) = if is_footnote {
+            (&notes.footnotes, footnotes, "Footnote", "")
+        } else {
+            (&notes.endnotes, endnotes, "Endnote", "E")
+        };
+        let Some(text) = string_attribute(e, b"id").and_then(|id| texts.get(&id)) else {
+            return String::new();
+        };
+
+        match self.notes {
+            NotePlacement::Inline => format!(" [{}: {}]", label, text),
+            NotePlacement::Append => {
+                let marker = format!("[{}{}]", prefix, collected.len() + 1);
+                collected.push((marker.clone(), text.clone()));
+                marker
+            }
+        }
+    }
+}
+
+/// Appends text to the open paragraph, if any
+fn push_text(paragraph: &mut Option<String>, text: &str) {
+    if let Some(paragraph) = paragraph.as_mut() {
+        paragraph.push_str(text);
+    }
+}
+
+impl DocumentExtractor for DocxExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "DocxExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        let (document, notes) = Self::read_document(file_path, true)?;
+        let (text, _) = self
+            .render_document(&document, &notes, OutputFormat::Text)
+            .with_context(|| format!("Failed to extract DOCX: {}", file_path.display()))?;
+        Ok(text)
+    }
+
+    fn extract_markdown(&self, file_path: &Path) -> Result<String> {
+        let (document, notes) = Self::read_document(file_path, true)?;
+        let (markdown, _) = self
+            .render_document(&document, &notes, OutputFormat::Markdown)
+            .with_context(|| format!("Failed to extract DOCX: {}", file_path.display()))?;
+        Ok(markdown)
+    }
+
+    fn extract_tables(&self, file_path: &Path) -> Result<Vec<Table>> {
+        // Without notes, references inside cells leave no markers behind
+        let (document, notes) = Self::read_document(file_path, false)?;
+        let (_, tables) = self
+            .render_document(&document, &notes, OutputFormat::Text)
+            .with_context(|| format!("Failed to extract tables from DOCX: {}", file_path.display()))?;
+        Ok(tables)
+    }
+
+    /// Reads the paragraphs styled as headings, through their Markdown rendering
+    fn extract_outline(&self, file_path: &Path) -> Result<Vec<Heading>> {
+        let (document, notes) = Self::read_document(file_path, false)?;
+        let (markdown, _) = self
+            .render_document(&document, &notes, OutputFormat::Markdown)
+            .with_context(|| format!("Failed to extract DOCX: {}", file_path.display()))?;
+        Ok(markdown_headings(&markdown)
+            .into_iter()
+            .map(|(level, title)| Heading { level, title, page: None, offset: None })
+            .collect())
+    }
+
+    fn extract_links(&self, file_path: &Path) -> Result<Vec<Link>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        let document_path = Self::document_path(&mut archive)?;
+        let document = read_zip_entry(&mut archive, &document_path)?.ok_or_else(|| {
+            anyhow::anyhow!("Missing {}; not a Word document: {}", document_path, file_path.display())
+        })?;
+        let relationships = Self::document_relationships(&mut archive, &document_path)?;
+        Self::parse_links(&document, &relationships)
+            .with_context(|| format!("Failed to read DOCX links: {}", file_path.display()))
+    }
+
+    /// Reads the images in `word/media`; Word files have no fixed pages, so none are set
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        read_zip_images(&mut archive, "word/media/")
+            .with_context(|| format!("Failed to read DOCX images: {}", file_path.display()))
+    }
+
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        read_core_properties(&mut archive)
+            .with_context(|| format!("Failed to read DOCX properties: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn policy_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("policy.docx");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_docx() {
+        let result = DocxExtractor::new().extract_text_from_file(&policy_path());
+        assert!(result.is_ok(), "Failed to extract DOCX: {:?}", result.err());
+
+        let expected = "# Travel Expense Policy
+
+# Booking
+
+Flights must be booked through the travel portal.[1] Economy class applies to trips under six hours.[2]
+
+## Limits
+
+Category | Daily limit
+---------+------------
+Meals    | 60 USD
+
+Receipts are required\tfor every claim.[E1]
+Claims older than 90 days are rejected.
+
+Footnotes:
+[1] Bookings made elsewhere are not reimbursed.
+[2] Measured gate to gate, including layovers.
+
+Endnotes:
+[E1] Scanned copies are accepted.";
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_extract_text_from_docx_with_inline_notes() {
+        let text = DocxExtractor::new()
+            .with_notes(NotePlacement::Inline)
+            .extract_text_from_file(&policy_path())
+            .unwrap();
+
+        assert!(
+            text.contains("travel portal. [Footnote: Bookings made elsewhere are not reimbursed.] Economy class"),
+            "Footnote should be inlined at its reference. Got: {}",
+            text
+        );
+        assert!(
+            text.contains("for every claim. [Endnote: Scanned copies are accepted.]\n"),
+            "Endnote should be inlined at its reference. Got: {}",
+            text
+        );
+        assert!(!text.contains("Footnotes:"), "Inlined notes should not be appended. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_tables_from_docx() {
+        let tables = DocxExtractor::new().extract_tables(&policy_path()).unwrap();
+        assert_eq!(tables, vec![vec![vec!["Category", "Daily limit"], vec!["Meals", "60 USD"]]]);
+        assert_eq!(
+            serde_json::to_string(&tables).unwrap(),
+            r#"[[["Category","Daily limit"],["Meals","60 USD"]]]"#
+        );
+    }
+
+    #[test]
+    fn test_extract_markdown_from_docx() {
+        let markdown = DocxExtractor::new().extract_markdown(&policy_path()).unwrap();
+        assert!(markdown.starts_with("# Travel Expense Policy\n\n# Booking\n\n"), "Got: {}", markdown);
+        assert!(
+            markdown.contains("## Limits\n\n| Category | Daily limit |\n| --- | --- |\n| Meals | 60 USD |\n\n"),
+            "Tables should be pipe tables. Got: {}",
+            markdown
+        );
+    }
+
+    #[test]
+    fn test_render_document_lists_as_markdown() {
+        let xml = r#"<w:document xmlns:w="w"><w:body>
+            <w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="3"/></w:numPr></w:pPr><w:r><w:t>Passport</w:t></w:r></w:p>
+            <w:p><w:pPr><w:numPr><w:ilvl w:val="1"/><w:numId w:val="3"/></w:numPr></w:pPr><w:r><w:t>Valid six months</w:t></w:r></w:p>
+            <w:p><w:pPr><w:numPr><w:numId w:val="0"/></w:numPr></w:pPr><w:r><w:t>Not a list item</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let extractor = DocxExtractor::new();
+
+        let (markdown, _) = extractor.render_document(xml, &Notes::default(), OutputFormat::Markdown).unwrap();
+        assert_eq!(markdown, "- Passport\n\n  - Valid six months\n\nNot a list item");
+
+        let (text, _) = extractor.render_document(xml, &Notes::default(), OutputFormat::Text).unwrap();
+        assert_eq!(text, "Passport\n\nValid six months\n\nNot a list item", "Plain text is unchanged");
+    }
+
+    #[test]
+    fn test_extract_links_from_docx() {
+        let links = DocxExtractor::new().extract_links(&policy_path()).unwrap();
+        assert_eq!(
+            links,
+            [Link {
+                url: "https://portal.example.com".to_string(),
+                text: Some("travel portal".to_string()),
+                page: None,
+                section: Some("Booking".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_links_from_fields() {
+        let xml = r#"<w:document xmlns:w="w"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Contacts</w:t></w:r></w:p>
+            <w:p><w:r><w:fldChar w:fldCharType="begin"/></w:r>
+            <w:r><w:instrText xml:space="preserve"> HYPERLINK "https://example.com/desk" </w:instrText></w:r>
+            <w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>Help desk</w:t></w:r>
+            <w:r><w:fldChar w:fldCharType="end"/></w:r>
+            <w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText>HYPERLINK \l "top"</w:instrText></w:r>
+            <w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>Top</w:t></w:r>
+            <w:r><w:fldChar w:fldCharType="end"/></w:r>
+            <w:hyperlink w:anchor="top"><w:r><w:t>Back</w:t></w:r></w:hyperlink></w:p>
+            </w:body></w:document>"#;
+        let links = DocxExtractor::parse_links(xml, &HashMap::new()).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com/desk");
+        assert_eq!(links[0].text.as_deref(), Some("Help desk"));
+        assert_eq!(links[0].section.as_deref(), Some("Contacts"));
+    }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(DocxExtractor::heading_level("Title"), Some(1));
+        assert_eq!(DocxExtractor::heading_level("Heading3"), Some(3));
+        assert_eq!(DocxExtractor::heading_level("Normal"), None);
+        assert_eq!(DocxExtractor::heading_level("HeadingX"), None);
+    }
+
+    #[test]
+    fn test_extract_metadata_from_docx() {
+        let metadata = DocxExtractor::new().extract_metadata(&policy_path()).unwrap();
+        assert_eq!(
+            metadata,
+            DocumentMetadata {
+                title: Some("Travel Expense Policy".to_string()),
+                author: Some("Thomas Plantin".to_string()),
+                created: Some("2025-08-20T09:00:00Z".to_string()),
+                modified: Some("2025-08-28T16:45:00Z".to_string()),
+                page_count: Some(2),
+                producer: Some("Microsoft Office Word".to_string()),
+            }
+        );
+    }
+}
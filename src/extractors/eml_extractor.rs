@@ -0,0 +1,160 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use mail_parser::{Address, Message, MessageParser, MimeHeaders, PartType};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::html_extractor::HtmlExtractor;
+
+/// RFC 822 email (.eml) extractor returning headers, body text and attachment names
+pub struct EmlExtractor;
+
+impl EmlExtractor {
+    /// Parses raw RFC 822 bytes into a message
+    pub fn parse_message(bytes: &[u8]) -> Result<Message<'_>> {
+        MessageParser::default()
+            .parse(bytes)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse email message"))
+    }
+
+    /// Formats an address header as `Name <address>, ...`
+    fn format_address(address: &Address) -> String {
+        address
+            .iter()
+            .map(|addr| match (addr.name(), addr.address()) {
+                (Some(name), Some(email)) => format!("{} <{}>", name, email),
+                (None, Some(email)) => email.to_string(),
+                (Some(name), None) => name.to_string(),
+                (None, None) => String::new(),
+            })
+            .filter(|entry| !entry.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns the message body as plain text, converting HTML-only bodies
+    fn body_text(message: &Message) -> String {
+        if let Some(part) = message.text_part(0) {
+            if let PartType::Text(text) = &part.body {
+                return text.trim().to_string();
+            }
+        }
+
+        if let Some(part) = message.html_part(0) {
+            if let PartType::Html(html) = &part.body {
+                return HtmlExtractor::new().with_links(false).extract_from_str(html);
+            }
+        }
+
+        message.body_text(0).map(|text| text.trim().to_string()).unwrap_or_default()
+    }
+
+    /// Renders a parsed message as headers, body and an attachment list
+    pub fn render_message(message: &Message) -> String {
+        let mut output = String::new();
+
+        let headers = [
+            ("From", message.from().map(Self::format_address)),
+            ("To", message.to().map(Self::format_address)),
+            ("Cc", message.cc().map(Self::format_address)),
+            ("Subject", message.subject().map(str::to_string)),
+            ("Date", message.date().map(|date| date.to_rfc822())),
+        ];
+        for (name, value) in headers {
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                output.push_str(&format!("{}: {}\n", name, value));
+            }
+        }
+
+        let body = Self::body_text(message);
+        if !body.is_empty() {
+            output.push('\n');
+            output.push_str(&body);
+            output.push('\n');
+        }
+
+        let attachments: Vec<String> = message
+            .attachments()
+            .map(|part| {
+                let name = part.attachment_name().unwrap_or("(unnamed)");
+                let content_type = part
+                    .content_type()
+                    .map(|ct| match ct.subtype() {
+                        Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                        None => ct.ctype().to_string(),
+                    })
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                format!("- {} ({}, {} bytes)", name, content_type, part.contents().len())
+            })
+            .collect();
+        if !attachments.is_empty() {
+            output.push_str("\nAttachments:\n");
+            output.push_str(&attachments.join("\n"));
+            output.push('\n');
+        }
+
+        output.trim_end().to_string()
+    }
+}
+
+impl DocumentExtractor for EmlExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "EmlExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read email file: {}", file_path.display()))?;
+
+        let message = Self::parse_message(&file_bytes)
+            .with_context(|| format!("Failed to parse email: {}", file_path.display()))?;
+
+        Ok(Self::render_message(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_eml() {
+        let mut eml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        eml_path.push("fixtures");
+        eml_path.push("itinerary.eml");
+
+        let result = EmlExtractor.extract_text_from_file(&eml_path);
+        assert!(result.is_ok(), "Failed to extract EML: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("From: Copa Airlines <noreply@copaair.com>\n"), "Got: {}", text);
+        assert!(text.contains("To: Thomas Plantin <thomas@example.com>\n"), "Got: {}", text);
+        assert!(text.contains("Subject: Your itinerary BDJVMN\n"), "Got: {}", text);
+        assert!(text.contains("Date: Thu, 28 Aug 2025 06:00:00 -0500\n"), "Got: {}", text);
+        assert!(text.contains("Your flight CM 716 departs at 06:10."), "Plain body should be used. Got: {}", text);
+        assert!(!text.contains("<b>"), "HTML alternative should not leak. Got: {}", text);
+        assert!(
+            text.contains("Attachments:\n- boardingPass.pdf (application/pdf, 9 bytes)"),
+            "Attachments should be listed. Got: {}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_render_message_with_html_only_body() {
+        let raw = b"From: a@example.com\r\nSubject: Hi\r\nContent-Type: text/html\r\n\r\n<p>Hello <b>there</b></p><script>x()</script>\r\n";
+        let message = EmlExtractor::parse_message(raw).unwrap();
+        let text = EmlExtractor::render_message(&message);
+        assert_eq!(text, "From: a@example.com\nSubject: Hi\n\nHello there");
+    }
+}
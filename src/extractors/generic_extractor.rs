@@ -0,0 +1,157 @@
+use std::path::Path;
+use anyhow::Result;
+use extractous::{Extractor, Metadata};
+use crate::constants::SUPPORTED_EXTENSIONS;
+use crate::extractor::{DocumentExtractor, DocumentMetadata, OutputFormat};
+use crate::extractors::html_extractor::HtmlExtractor;
+
+/// Fallback extractor for formats without a dedicated extractor
+///
+/// Hands the file to Apache Tika (through extractous), which detects the content type
+/// itself and covers many more formats (RTF, legacy Office, EPUB, ...). The detected
+/// content type is reported on the first line, since the extension said nothing useful.
+pub struct GenericExtractor;
+
+impl GenericExtractor {
+    /// Returns the first value of a Tika metadata key
+    fn first<'a>(metadata: &'a Metadata, key: &str) -> Option<&'a str> {
+        metadata
+            .get(key)
+            .and_then(|values| values.first())
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Returns the content type Tika detected, without parameters such as `charset`
+    pub fn content_type(metadata: &Metadata) -> Option<&str> {
+        Self::first(metadata, "Content-Type").and_then(|value| value.split(';').next()).map(str::trim)
+    }
+
+    /// Maps Tika metadata keys onto `DocumentMetadata`
+    pub fn document_metadata(metadata: &Metadata) -> DocumentMetadata {
+        let any = |keys: &[&str]| keys.iter().find_map(|key| Self::first(metadata, key)).map(str::to_string);
+        DocumentMetadata {
+            title: any(&["dc:title"]),
+            author: any(&["dc:creator", "meta:author"]),
+            created: any(&["dcterms:created"]),
+            modified: any(&["dcterms:modified"]),
+            page_count: any(&["xmpTPg:NPages", "meta:page-count", "meta:slide-count"]).and_then(|count| count.parse().ok()),
+            producer: any(&["pdf:producer", "extended-properties:Application", "xmp:CreatorTool"]),
+        }
+    }
+
+    /// Runs Tika on the file, returning its text (XHTML for Markdown output) and metadata
+    fn parse(file_path: &Path, format: OutputFormat) -> Result<(String, Metadata)> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let path = file_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Path is not valid UTF-8: {}", file_path.display()))?;
+        Extractor::new()
+            .set_xml_output(format == OutputFormat::Markdown)
+            .extract_file_to_string(path)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Unsupported file format: {} could not be read ({}). Formats with dedicated support: {}",
+                    file_path.display(),
+                    e,
+                    SUPPORTED_EXTENSIONS.join(", ")
+                )
+            })
+    }
+
+    /// Extracts the file, prefixing the detected content type
+    fn extract(file_path: &Path, format: OutputFormat) -> Result<String> {
+        let (content, metadata) = Self::parse(file_path, format)?;
+        let content_type = Self::content_type(&metadata).unwrap_or("unknown");
+
+        let text = match format {
+            OutputFormat::Text => content.trim().to_string(),
+            OutputFormat::Markdown => HtmlExtractor::new().markdown_from_str(&content),
+        };
+        if text.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No text found in {} (detected content type: {})",
+                file_path.display(),
+                content_type
+            ));
+        }
+        Ok(format!("Content type: {}\n\n{}", content_type, text))
+    }
+}
+
+impl DocumentExtractor for GenericExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "GenericExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        Self::extract(file_path, OutputFormat::Text)
+    }
+
+    /// Converts Tika's XHTML output, which keeps headings, lists and tables
+    fn extract_markdown(&self, file_path: &Path) -> Result<String> {
+        Self::extract(file_path, OutputFormat::Markdown)
+    }
+
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        let (_, metadata) = Self::parse(file_path, OutputFormat::Text)?;
+        Ok(Self::document_metadata(&metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn tika_metadata(entries: &[(&str, &str)]) -> Metadata {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+            .collect()
+    }
+
+    #[test]
+    fn test_document_metadata() {
+        let metadata = tika_metadata(&[
+            ("Content-Type", "application/rtf; charset=windows-1252"),
+            ("dc:title", "Crew roster"),
+            ("dc:creator", "Hugo Flores"),
+            ("dcterms:created", "2025-08-01T08:00:00Z"),
+            ("meta:page-count", "3"),
+            ("extended-properties:Application", " "),
+        ]);
+
+        assert_eq!(GenericExtractor::content_type(&metadata), Some("application/rtf"));
+        assert_eq!(
+            GenericExtractor::document_metadata(&metadata),
+            DocumentMetadata {
+                title: Some("Crew roster".to_string()),
+                author: Some("Hugo Flores".to_string()),
+                created: Some("2025-08-01T08:00:00Z".to_string()),
+                modified: None,
+                page_count: Some(3),
+                producer: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_text_from_missing_file() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("missing.rtf");
+
+        let error = GenericExtractor.extract_text_from_file(&path).unwrap_err().to_string();
+        assert!(error.contains("File not found"), "Got: {}", error);
+    }
+}
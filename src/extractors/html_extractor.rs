@@ -0,0 +1,467 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use scraper::{ElementRef, Html, Node};
+use crate::extractor::{DocumentExtractor, Link};
+use crate::extractors::csv_extractor::CsvExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// HTML extractor that strips markup while keeping readable text, headings and links
+pub struct HtmlExtractor {
+    /// Whether to append a numbered list of hyperlinks after the text
+    include_links: bool,
+}
+
+/// Elements whose content is never meaningful text
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "object", "svg", "canvas", "head",
+];
+
+/// Elements that start and end on their own line
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "dd", "div", "dl", "dt", "fieldset",
+    "figcaption", "figure", "footer", "form", "header", "hr", "li", "main", "nav", "ol", "p",
+    "pre", "section", "table", "tr", "ul", "title", "caption",
+];
+
+impl Default for HtmlExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlExtractor {
+    /// Creates an HTML extractor that appends a hyperlink appendix
+    pub fn new() -> Self {
+        Self { include_links: true }
+    }
+
+    /// Enables or disables the hyperlink appendix
+    pub fn with_links(mut self, include_links: bool) -> Self {
+        self.include_links = include_links;
+        self
+    }
+
+    /// Converts an HTML document into readable text
+    pub fn extract_from_str(&self, html: &str) -> String {
+        self.render(html, false)
+    }
+
+    /// Converts an HTML document into Markdown, with pipe tables, numbered lists and inline links
+    pub fn markdown_from_str(&self, html: &str) -> String {
+        self.render(html, true)
+    }
+
+    /// Reads the hyperlinks of an HTML document, with the heading each comes under
+    pub fn links_from_str(&self, html: &str) -> Vec<Link> {
+        let document = Html::parse_document(html);
+        let mut links = Vec::new();
+        let mut section = None;
+        for element in document.root_element().descendants().filter_map(ElementRef::wrap) {
+            let name = element.value().name();
+            let skipped = element
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|ancestor| SKIPPED_ELEMENTS.contains(&ancestor.value().name()));
+            if skipped || SKIPPED_ELEMENTS.contains(&name) {
+                continue;
+            }
+            match name {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    let heading = collapse_whitespace(&element.text().collect::<String>());
+                    if !heading.is_empty() {
+                        section = Some(heading);
+                    }
+                }
+                "a" => {
+                    let Some(href) = element.value().attr("href").map(str::trim).filter(|href| is_link_target(href))
+                    else {
+                        continue;
+                    };
+                    let text = collapse_whitespace(&element.text().collect::<String>());
+                    links.push(Link {
+                        url: href.to_string(),
+                        text: (!text.is_empty()).then_some(text),
+                        page: None,
+                        section: section.clone(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        links
+    }
+
+    fn render(&self, html: &str, markdown: bool) -> String {
+        let document = Html::parse_document(html);
+        let mut renderer = TextRenderer { markdown, ..Default::default() };
+
+        // <title> lives in <head>, which is otherwise skipped
+        if let Some(title) = document
+            .root_element()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|element| element.value().name() == "title")
+        {
+            let title = collapse_whitespace(&title.text().collect::<String>());
+            if !title.is_empty() {
+                renderer.push_line(&title);
+                renderer.blank_line();
+            }
+        }
+
+        renderer.walk(document.root_element());
+
+        let mut text = renderer.finish();
+        // Markdown keeps links inline instead
+        if self.include_links && !markdown && !renderer.links.is_empty() {
+            text.push_str("\n\nLinks:\n");
+            for (index, (label, href)) in renderer.links.iter().enumerate() {
+                text.push_str(&format!("[{}] {} - {}\n", index + 1, label, href));
+            }
+        }
+        text
+    }
+}
+
+impl DocumentExtractor for HtmlExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "HtmlExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read HTML file: {}", file_path.display()))?;
+
+        let html = TxtExtractor::decode_bytes(&file_bytes);
+        Ok(self.extract_from_str(&html))
+    }
+
+    fn extract_markdown(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read HTML file: {}", file_path.display()))?;
+
+        let html = TxtExtractor::decode_bytes(&file_bytes);
+        Ok(self.markdown_from_str(&html))
+    }
+
+    fn extract_links(&self, file_path: &Path) -> Result<Vec<Link>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read HTML file: {}", file_path.display()))?;
+
+        let html = TxtExtractor::decode_bytes(&file_bytes);
+        Ok(self.links_from_str(&html))
+    }
+}
+
+/// Returns true for hrefs that lead somewhere else: not empty, a fragment of this page or a script
+fn is_link_target(href: &str) -> bool {
+    !href.is_empty() && !href.starts_with('#') && !href.starts_with("javascript:")
+}
+
+/// Collapses every run of whitespace into a single space and trims the ends
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Collects the rows of a table as cell text, leaving out rows of nested tables
+fn table_rows(table: ElementRef) -> Vec<Vec<String>> {
+    table
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|row| row.value().name() == "tr")
+        .filter(|row| {
+            row.ancestors()
+                .filter_map(ElementRef::wrap)
+                .find(|ancestor| ancestor.value().name() == "table")
+                .is_some_and(|enclosing| enclosing.id() == table.id())
+        })
+        .map(|row| {
+            row.children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .map(|cell| CsvExtractor::clean_cell(&cell.text().collect::<String>()))
+                .collect::<Vec<_>>()
+        })
+        .filter(|cells| !cells.is_empty())
+        .collect()
+}
+
+/// Accumulates rendered text while walking the DOM
+#[derive(Default)]
+struct TextRenderer {
+    output: String,
+    /// A space is owed before the next inline text
+    pending_space: bool,
+    /// Depth of enclosing <pre> elements, where whitespace is preserved
+    preformatted: usize,
+    /// Collected (anchor text, href) pairs in document order
+    links: Vec<(String, String)>,
+    /// Render Markdown rather than plain text
+    markdown: bool,
+    /// Enclosing lists, innermost last: the next item number for `<ol>`, `None` for `<ul>`
+    lists: Vec<Option<usize>>,
+}
+
+impl TextRenderer {
+    fn walk(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child_element) = ElementRef::wrap(child) {
+                        self.visit_element(child_element);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            return;
+        }
+
+        match name {
+            "br" => self.newline(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                let heading = collapse_whitespace(&element.text().collect::<String>());
+                if !heading.is_empty() {
+                    self.blank_line();
+                    self.push_line(&format!("{} {}", "#".repeat(level), heading));
+                    self.blank_line();
+                }
+            }
+            "ul" | "ol" if self.markdown => {
+                let start = element.value().attr("start").and_then(|start| start.trim().parse().ok());
+                self.lists.push((name == "ol").then_some(start.unwrap_or(1)));
+                self.newline();
+                self.walk(element);
+                self.newline();
+                self.lists.pop();
+            }
+            "li" => {
+                self.newline();
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.output.push_str(&"  ".repeat(depth));
+                self.output.push_str(&marker);
+                self.walk(element);
+                self.newline();
+            }
+            "table" if self.markdown => {
+                let rows = table_rows(element);
+                if !rows.is_empty() {
+                    self.blank_line();
+                    self.output.push_str(&CsvExtractor::render_markdown_table(&rows));
+                    self.blank_line();
+                }
+            }
+            "td" | "th" => {
+                if !self.output.ends_with('\n') && !self.output.is_empty() {
+                    self.output.push_str(" | ");
+                }
+                self.pending_space = false;
+                self.walk(element);
+            }
+            "pre" => {
+                self.blank_line();
+                if self.markdown {
+                    self.output.push_str("```\n");
+                }
+                self.preformatted += 1;
+                self.walk(element);
+                self.preformatted -= 1;
+                if self.markdown {
+                    self.newline();
+                    self.output.push_str("```");
+                }
+                self.blank_line();
+            }
+            "a" if self.markdown => {
+                let href = element.value().attr("href").map(str::trim).unwrap_or_default();
+                if !is_link_target(href) {
+                    self.walk(element);
+                } else {
+                    self.push_text("[");
+                    self.walk(element);
+                    self.output.push_str(&format!("]({})", href));
+                }
+            }
+            "a" => {
+                self.walk(element);
+                if let Some(href) = element.value().attr("href") {
+                    let href = href.trim();
+                    if is_link_target(href) {
+                        let label = collapse_whitespace(&element.text().collect::<String>());
+                        self.links.push((label, href.to_string()));
+                    }
+                }
+            }
+            "p" | "blockquote" | "table" | "section" | "article" => {
+                self.blank_line();
+                self.walk(element);
+                self.blank_line();
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => {
+                self.newline();
+                self.walk(element);
+                self.newline();
+            }
+            _ => self.walk(element),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.preformatted > 0 {
+            self.output.push_str(text);
+            return;
+        }
+
+        if text.starts_with(char::is_whitespace) {
+            self.pending_space = true;
+        }
+        let collapsed = collapse_whitespace(text);
+        if !collapsed.is_empty() {
+            if self.pending_space && !self.output.is_empty() && !self.output.ends_with(['\n', ' ']) {
+                self.output.push(' ');
+            }
+            self.output.push_str(&collapsed);
+            self.pending_space = text.ends_with(char::is_whitespace);
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        self.newline();
+        self.output.push_str(line);
+        self.newline();
+    }
+
+    fn newline(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+        self.pending_space = false;
+    }
+
+    fn blank_line(&mut self) {
+        self.newline();
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") {
+            self.output.push('\n');
+        }
+    }
+
+    fn finish(&self) -> String {
+        let lines: Vec<&str> = self.output.lines().map(str::trim_end).collect();
+        lines.join("\n").trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_html() {
+        let mut html_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        html_path.push("fixtures");
+        html_path.push("page.html");
+
+        let result = HtmlExtractor::new().extract_text_from_file(&html_path);
+        assert!(result.is_ok(), "Failed to extract HTML: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Flight Status"), "Title should come first. Got: {}", text);
+        assert!(text.contains("# Flight CM 716"), "Headings should be kept. Got: {}", text);
+        assert!(text.contains("- Departs AUS at 06:10"), "List items should be kept. Got: {}", text);
+        assert!(text.contains("Check the airline site for updates."), "Paragraphs should be kept. Got: {}", text);
+        assert!(!text.contains("trackVisitor"), "Scripts should be stripped. Got: {}", text);
+        assert!(!text.contains("font-family"), "Styles should be stripped. Got: {}", text);
+        assert!(!text.contains("<p>"), "Tags should be stripped. Got: {}", text);
+        assert!(
+            text.contains("[1] airline site - https://www.copaair.com/status"),
+            "Links should be listed in the appendix. Got: {}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_markdown_from_str() {
+        let html = r#"<h2>Fares</h2>
+            <ol><li>Book <a href="https://example.com/book">online</a></li><li>Pay<ul><li>Card</li></ul></li></ol>
+            <table><tr><th>Class</th><th>Fare</th></tr><tr><td>Economy</td><td>320 USD</td></tr></table>"#;
+        let markdown = HtmlExtractor::new().markdown_from_str(html);
+        assert_eq!(
+            markdown,
+            "## Fares\n\n1. Book [online](https://example.com/book)\n2. Pay\n  - Card\n\n| Class | Fare |\n| --- | --- |\n| Economy | 320 USD |"
+        );
+    }
+
+    #[test]
+    fn test_links_from_str() {
+        let html = r##"<p>Intro <a href="https://example.com/a"> First  link </a></p>
+            <h2>Fares</h2><p><a href="#top">Top</a> <a href="javascript:void(0)">Menu</a>
+            <a href="mailto:desk@example.com"></a></p>
+            <noscript><a href="https://example.com/hidden">Hidden</a></noscript>"##;
+        let links = HtmlExtractor::new().links_from_str(html);
+        assert_eq!(
+            links,
+            [
+                Link {
+                    url: "https://example.com/a".into(),
+                    text: Some("First link".into()),
+                    page: None,
+                    section: None,
+                },
+                Link {
+                    url: "mailto:desk@example.com".into(),
+                    text: None,
+                    page: None,
+                    section: Some("Fares".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_str_without_links() {
+        let html = r#"<p>See <a href="https://example.com">the docs</a>.</p>"#;
+        let text = HtmlExtractor::new().with_links(false).extract_from_str(html);
+        assert_eq!(text, "See the docs.");
+    }
+}
@@ -0,0 +1,260 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Calendar components rendered by the extractor
+const COMPONENTS: &[&str] = &["VEVENT", "VTODO"];
+
+/// Uppercased property parameters, e.g. `("TZID", "America/Panama")`
+type Params = Vec<(String, String)>;
+
+/// A VEVENT or VTODO with the properties that are rendered
+#[derive(Debug, Default)]
+struct CalendarEntry {
+    kind: &'static str,
+    summary: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    due: Option<String>,
+    location: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+}
+
+impl CalendarEntry {
+    /// Date the entry is ordered by: the start of events, the due date of to-dos
+    fn sort_key(&self) -> Option<&str> {
+        self.start.as_deref().or(self.due.as_deref())
+    }
+}
+
+/// iCalendar extractor that lists events and to-dos in chronological order
+pub struct IcsExtractor;
+
+impl IcsExtractor {
+    /// Joins folded lines (continuations start with a space or tab)
+    pub(crate) fn unfold(source: &str) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for line in source.lines() {
+            match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+                (Some(continuation), Some(last)) => last.push_str(continuation),
+                _ => lines.push(line.to_string()),
+            }
+        }
+        lines
+    }
+
+    /// Splits `NAME;PARAM=x:value` into the uppercased name, its parameters and the value
+    fn split_property(line: &str) -> Option<(String, Params, &str)> {
+        let (head, value) = line.split_once(':')?;
+        let mut parts = head.split(';');
+        let name = parts.next()?.to_uppercase();
+        let params = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_string()))
+            .collect();
+        Some((name, params, value))
+    }
+
+    /// Resolves the backslash escapes allowed in TEXT values
+    pub(crate) fn unescape(value: &str) -> String {
+        let mut text = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n' | 'N') => text.push('\n'),
+                Some(other) => text.push(other),
+                None => text.push('\\'),
+            }
+        }
+        text.trim().to_string()
+    }
+
+    /// Formats `20250828` / `20250828T110000[Z]` as `2025-08-28` / `2025-08-28 11:00 [UTC]`
+    ///
+    /// The time zone named in a `TZID` parameter is appended in parentheses.
+    fn format_date(value: &str, params: &[(String, String)]) -> String {
+        let value = value.trim();
+        let digits = |range: std::ops::Range<usize>| {
+            value.get(range).filter(|part| part.bytes().all(|b| b.is_ascii_digit()))
+        };
+        let (Some(year), Some(month), Some(day)) = (digits(0..4), digits(4..6), digits(6..8)) else {
+            return value.to_string();
+        };
+
+        let mut formatted = format!("{}-{}-{}", year, month, day);
+        if let (Some(hour), Some(minute)) = (digits(9..11), digits(11..13)) {
+            formatted.push_str(&format!(" {}:{}", hour, minute));
+            if value.ends_with('Z') {
+                formatted.push_str(" UTC");
+            } else if let Some((_, tzid)) = params.iter().find(|(key, _)| key == "TZID") {
+                formatted.push_str(&format!(" ({})", tzid));
+            }
+        }
+        formatted
+    }
+
+    /// Parses the VEVENT and VTODO components of an iCalendar document
+    fn parse_entries(source: &str) -> Vec<CalendarEntry> {
+        let mut entries = Vec::new();
+        let mut current: Option<CalendarEntry> = None;
+        // Nested components (e.g. VALARM) must not overwrite the parent's properties
+        let mut nested_depth = 0usize;
+
+        for line in Self::unfold(source) {
+            let Some((name, params, value)) = Self::split_property(&line) else {
+                continue;
+            };
+            let value_upper = value.trim().to_uppercase();
+
+            match name.as_str() {
+                "BEGIN" if current.is_some() => nested_depth += 1,
+                "BEGIN" => {
+                    if let Some(kind) = COMPONENTS.iter().find(|kind| **kind == value_upper) {
+                        current = Some(CalendarEntry { kind, ..CalendarEntry::default() });
+                    }
+                }
+                "END" if nested_depth > 0 => nested_depth -= 1,
+                "END" => {
+                    if let Some(entry) = current.take() {
+                        entries.push(entry);
+                    }
+                }
+                _ if nested_depth > 0 => {}
+                property => {
+                    let Some(entry) = current.as_mut() else {
+                        continue;
+                    };
+                    match property {
+                        "SUMMARY" => entry.summary = Some(Self::unescape(value)),
+                        "DTSTART" => entry.start = Some(Self::format_date(value, &params)),
+                        "DTEND" => entry.end = Some(Self::format_date(value, &params)),
+                        "DUE" => entry.due = Some(Self::format_date(value, &params)),
+                        "LOCATION" => entry.location = Some(Self::unescape(value)),
+                        "DESCRIPTION" => entry.description = Some(Self::unescape(value)),
+                        "STATUS" => entry.status = Some(value_upper),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        entries
+    }
+
+    /// Renders events and to-dos chronologically; undated entries come last
+    pub fn extract_from_str(source: &str) -> String {
+        let mut entries = Self::parse_entries(source);
+        entries.sort_by(|a, b| match (a.sort_key(), b.sort_key()) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        entries
+            .iter()
+            .map(|entry| {
+                let label = if entry.kind == "VTODO" { "To-do" } else { "Event" };
+                let mut lines = vec![format!(
+                    "{}: {}",
+                    label,
+                    entry.summary.as_deref().unwrap_or("(no summary)")
+                )];
+                let fields = [
+                    ("Start", &entry.start),
+                    ("End", &entry.end),
+                    ("Due", &entry.due),
+                    ("Status", &entry.status),
+                    ("Location", &entry.location),
+                ];
+                for (name, value) in fields {
+                    if let Some(value) = value {
+                        lines.push(format!("  {}: {}", name, value));
+                    }
+                }
+                if let Some(description) = entry.description.as_deref().filter(|d| !d.is_empty()) {
+                    lines.push("  Description:".to_string());
+                    lines.extend(description.lines().map(|line| format!("    {}", line)));
+                }
+                lines.join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl DocumentExtractor for IcsExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "IcsExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read calendar file: {}", file_path.display()))?;
+
+        let source = TxtExtractor::decode_bytes(&file_bytes);
+        if !source.trim_start().to_uppercase().starts_with("BEGIN:VCALENDAR") {
+            return Err(anyhow::anyhow!("Not an iCalendar file: {}", file_path.display()));
+        }
+        Ok(Self::extract_from_str(&source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_ics() {
+        let mut ics_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        ics_path.push("fixtures");
+        ics_path.push("trip.ics");
+
+        let result = IcsExtractor.extract_text_from_file(&ics_path);
+        assert!(result.is_ok(), "Failed to extract calendar: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "To-do: Check in online
+  Due: 2025-08-27
+  Status: NEEDS-ACTION
+
+Event: Flight CM 716 to Panama City
+  Start: 2025-08-28 11:00 UTC
+  End: 2025-08-28 16:00 UTC
+  Location: Terminal 4
+  Description:
+    Seat 14C.
+    Bring passport and boarding pass; gate closes 20 minutes before departure.
+
+Event: Dinner with the Panama team
+  Start: 2025-08-28 19:30 (America/Panama)
+  End: 2025-08-28 21:30 (America/Panama)
+  Location: Casco Viejo, Panama City";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_undated_entries_come_last() {
+        let source = "BEGIN:VCALENDAR\nBEGIN:VTODO\nSUMMARY:Someday\nEND:VTODO\nBEGIN:VEVENT\nSUMMARY:Launch\nDTSTART:20250101\nEND:VEVENT\nEND:VCALENDAR";
+        let text = IcsExtractor::extract_from_str(source);
+        assert_eq!(text, "Event: Launch\n  Start: 2025-01-01\n\nTo-do: Someday");
+    }
+}
@@ -0,0 +1,322 @@
+//! Shared helpers for Apple iWork packages (Pages, Keynote, Numbers)
+//!
+//! Since iWork '13, documents are stored as IWA archives: Snappy-compressed
+//! streams of protobuf messages. Only the handful of message types that carry
+//! text are decoded here; everything else is skipped by length.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use zip::ZipArchive;
+use crate::extractors::archive::{open_zip, read_capped, MAX_ZIP_ENTRY_SIZE};
+use crate::extractors::pdf_extractor::PdfExtractor;
+
+/// Message type of `TSWP.StorageArchive`, the container for a run of text
+pub const STORAGE_ARCHIVE_TYPE: u32 = 2001;
+
+/// `TSWP.StorageArchive.KindType` values
+pub const STORAGE_KIND_BODY: u64 = 0;
+pub const STORAGE_KIND_HEADER: u64 = 1;
+pub const STORAGE_KIND_FOOTNOTE: u64 = 2;
+pub const STORAGE_KIND_TEXTBOX: u64 = 3;
+pub const STORAGE_KIND_NOTE: u64 = 4;
+
+/// Preview PDFs embedded by older iWork versions
+const PREVIEW_PDF_PATHS: &[&str] = &["QuickLook/Preview.pdf", "preview.pdf"];
+
+/// An iWork document, either a single-file zip or a package directory
+pub struct IworkPackage {
+    archive: Option<ZipArchive<File>>,
+    bundle_dir: Option<PathBuf>,
+}
+
+/// One protobuf message stored in an IWA archive
+#[derive(Debug)]
+pub struct IwaMessage {
+    /// Identifier of the object the message belongs to
+    pub object_id: u64,
+    /// Registry type of the message (e.g. `STORAGE_ARCHIVE_TYPE`)
+    pub type_id: u32,
+    pub payload: Vec<u8>,
+}
+
+/// A decoded protobuf field value
+#[derive(Debug, Clone, Copy)]
+pub enum ProtoValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl IworkPackage {
+    /// Opens a `.pages`/`.key`/`.numbers` file or package directory
+    ///
+    /// Package directories keep their IWA files in `Index.zip`.
+    pub fn open(file_path: &Path) -> Result<Self> {
+        if !file_path.is_dir() {
+            return Ok(Self { archive: Some(open_zip(file_path)?), bundle_dir: None });
+        }
+
+        let index_zip = file_path.join("Index.zip");
+        let archive = if index_zip.is_file() { Some(open_zip(&index_zip)?) } else { None };
+        Ok(Self { archive, bundle_dir: Some(file_path.to_path_buf()) })
+    }
+
+    /// Reads an entry by its package path (e.g. `Index/Document.iwa`)
+    pub fn read(&mut self, name: &str) -> Result<Option<Vec<u8>>> {
+        if let Some(dir) = &self.bundle_dir {
+            let path = dir.join(name);
+            if path.is_file() {
+                return fs::read(&path)
+                    .map(Some)
+                    .with_context(|| format!("Failed to read package file: {}", path.display()));
+            }
+        }
+
+        let Some(archive) = self.archive.as_mut() else {
+            return Ok(None);
+        };
+        // Inside a package's Index.zip the `Index/` prefix may be missing
+        let candidates = [Some(name), name.strip_prefix("Index/")];
+        for candidate in candidates.into_iter().flatten() {
+            match archive.by_name(candidate) {
+                Ok(entry) => {
+                    let bytes = read_capped(entry, MAX_ZIP_ENTRY_SIZE)
+                        .with_context(|| format!("Failed to read package entry: {}", name))?;
+                    return Ok(Some(bytes));
+                }
+                Err(zip::result::ZipError::FileNotFound) => continue,
+                Err(e) => return Err(e).with_context(|| format!("Failed to open package entry: {}", name)),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Lists the IWA files under `Index/`, sorted by path
+    pub fn iwa_names(&mut self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        if let Some(archive) = self.archive.as_mut() {
+            for index in 0..archive.len() {
+                let entry = archive.by_index(index).context("Failed to read package entry")?;
+                let name = entry.name().context("Failed to read package entry name")?;
+                if name.ends_with(".iwa") {
+                    let name = if name.starts_with("Index/") { name.into_owned() } else { format!("Index/{}", name) };
+                    names.push(name);
+                }
+            }
+        }
+        if let Some(dir) = &self.bundle_dir {
+            let mut pending = vec![dir.join("Index")];
+            while let Some(current) = pending.pop() {
+                let Ok(entries) = fs::read_dir(&current) else {
+                    continue;
+                };
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.is_dir() {
+                        pending.push(path);
+                    } else if path.extension().is_some_and(|ext| ext == "iwa") {
+                        if let Ok(relative) = path.strip_prefix(dir) {
+                            let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+                            names.push(parts.join("/"));
+                        }
+                    }
+                }
+            }
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Reads and decodes one IWA file, returning `None` when it does not exist
+    pub fn read_iwa(&mut self, name: &str) -> Result<Option<Vec<IwaMessage>>> {
+        let Some(bytes) = self.read(name)? else {
+            return Ok(None);
+        };
+        let stream = decompress_iwa(&bytes).with_context(|| format!("Failed to decompress {}", name))?;
+        parse_iwa(&stream).with_context(|| format!("Failed to parse {}", name)).map(Some)
+    }
+
+    /// Reads and decodes every IWA file in the package, in path order
+    pub fn read_all_iwa(&mut self) -> Result<Vec<IwaMessage>> {
+        let mut messages = Vec::new();
+        for name in self.iwa_names()? {
+            messages.extend(self.read_iwa(&name)?.unwrap_or_default());
+        }
+        Ok(messages)
+    }
+
+    /// Extracts the text of the preview PDF older iWork versions embed, if there is one
+    pub fn preview_pdf_text(&mut self) -> Result<Option<String>> {
+        for path in PREVIEW_PDF_PATHS {
+            if let Some(bytes) = self.read(path)? {
+                return PdfExtractor::extract_from_bytes(&bytes).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Reads a base-128 varint, advancing `pos`
+fn read_varint(data: &[u8], pos: &mut usize) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos).context("Truncated varint")?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(anyhow::anyhow!("Varint is too long"))
+}
+
+fn take<'a>(data: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let bytes = data
+        .get(*pos..pos.saturating_add(len))
+        .with_context(|| format!("Truncated data: need {} bytes at offset {}", len, pos))?;
+    *pos += len;
+    Ok(bytes)
+}
+
+/// Decodes the top-level fields of a protobuf message, in wire order
+pub fn proto_fields(data: &[u8]) -> Result<Vec<(u32, ProtoValue<'_>)>> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let key = read_varint(data, &mut pos)?;
+        let number = (key >> 3) as u32;
+        let value = match key & 0x7 {
+            0 => ProtoValue::Varint(read_varint(data, &mut pos)?),
+            1 => {
+                let bytes = take(data, &mut pos, 8)?;
+                ProtoValue::Fixed64(u64::from_le_bytes(bytes.try_into()?))
+            }
+            2 => {
+                let len = read_varint(data, &mut pos)? as usize;
+                ProtoValue::Bytes(take(data, &mut pos, len)?)
+            }
+            5 => {
+                let bytes = take(data, &mut pos, 4)?;
+                ProtoValue::Fixed32(u32::from_le_bytes(bytes.try_into()?))
+            }
+            wire_type => return Err(anyhow::anyhow!("Unsupported protobuf wire type {}", wire_type)),
+        };
+        fields.push((number, value));
+    }
+    Ok(fields)
+}
+
+/// Returns the first varint value of field `number`
+pub fn proto_varint(fields: &[(u32, ProtoValue<'_>)], number: u32) -> Option<u64> {
+    fields.iter().find_map(|(n, value)| match value {
+        ProtoValue::Varint(v) if *n == number => Some(*v),
+        _ => None,
+    })
+}
+
+/// Returns every length-delimited value of field `number`
+pub fn proto_bytes<'a>(fields: &[(u32, ProtoValue<'a>)], number: u32) -> Vec<&'a [u8]> {
+    fields
+        .iter()
+        .filter_map(|(n, value)| match value {
+            ProtoValue::Bytes(bytes) if *n == number => Some(*bytes),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the object identifier a `TSP.Reference` message points to
+pub fn reference_id(reference: &[u8]) -> Option<u64> {
+    proto_varint(&proto_fields(reference).ok()?, 1)
+}
+
+/// Undoes IWA framing: chunks of `0x00`, a 3-byte little-endian length and a raw Snappy block
+pub fn decompress_iwa(data: &[u8]) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut decoder = snap::raw::Decoder::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let header = take(data, &mut pos, 4)?;
+        if header[0] != 0 {
+            return Err(anyhow::anyhow!("Unexpected IWA chunk type {}", header[0]));
+        }
+        let len = u32::from_le_bytes([header[1], header[2], header[3], 0]) as usize;
+        let block = take(data, &mut pos, len)?;
+        output.extend(decoder.decompress_vec(block).context("Invalid Snappy block")?);
+    }
+    Ok(output)
+}
+
+/// Splits a decompressed IWA stream into its messages
+///
+/// The stream is a sequence of length-prefixed `ArchiveInfo` headers, each followed
+/// by the payloads of the messages it describes.
+pub fn parse_iwa(stream: &[u8]) -> Result<Vec<IwaMessage>> {
+    let mut messages = Vec::new();
+    let mut pos = 0;
+    while pos < stream.len() {
+        let info_len = read_varint(stream, &mut pos)? as usize;
+        let info = proto_fields(take(stream, &mut pos, info_len)?)?;
+        let object_id = proto_varint(&info, 1).unwrap_or(0);
+
+        for message_info in proto_bytes(&info, 2) {
+            let message_info = proto_fields(message_info)?;
+            let type_id = proto_varint(&message_info, 1).unwrap_or(0) as u32;
+            let length = proto_varint(&message_info, 3).unwrap_or(0) as usize;
+            let payload = take(stream, &mut pos, length)?.to_vec();
+            messages.push(IwaMessage { object_id, type_id, payload });
+        }
+    }
+    Ok(messages)
+}
+
+/// Decodes a `TSWP.StorageArchive` into its kind and text
+pub fn storage_text(message: &IwaMessage) -> Result<Option<(u64, String)>> {
+    if message.type_id != STORAGE_ARCHIVE_TYPE {
+        return Ok(None);
+    }
+    let fields = proto_fields(&message.payload)?;
+    let kind = proto_varint(&fields, 1).unwrap_or(STORAGE_KIND_BODY);
+    let text: String = proto_bytes(&fields, 3)
+        .into_iter()
+        .map(String::from_utf8_lossy)
+        .collect();
+    Ok(Some((kind, clean_text(&text))))
+}
+
+/// Drops attachment placeholders and normalizes iWork's paragraph and line separators
+pub fn clean_text(text: &str) -> String {
+    text.replace('\u{fffc}', "")
+        .replace(['\u{2028}', '\u{2029}', '\r'], "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proto_fields() {
+        // field 1 = 150 (varint), field 3 = "hi" (bytes)
+        let data = [0x08, 0x96, 0x01, 0x1a, 0x02, b'h', b'i'];
+        let fields = proto_fields(&data).unwrap();
+        assert_eq!(proto_varint(&fields, 1), Some(150));
+        assert_eq!(proto_bytes(&fields, 3), vec![b"hi".as_slice()]);
+    }
+
+    #[test]
+    fn test_decompress_iwa() {
+        // One chunk holding a literal-only Snappy block for "abc"
+        let data = [0x00, 0x05, 0x00, 0x00, 0x03, 0x08, b'a', b'b', b'c'];
+        assert_eq!(decompress_iwa(&data).unwrap(), b"abc");
+        assert!(decompress_iwa(&[0x01, 0x00, 0x00, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Default nesting depth rendered before values are summarized
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Default maximum number of characters returned
+pub const DEFAULT_MAX_CHARS: usize = 200_000;
+
+/// How JSON documents are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonOutputMode {
+    /// One `path.to[0].key: value` line per leaf value
+    Flatten,
+    /// Indented JSON
+    Pretty,
+}
+
+/// JSON extractor rendering documents as key/value lines or pretty-printed JSON
+pub struct JsonExtractor {
+    mode: JsonOutputMode,
+    /// Containers nested deeper than this are summarized (`{3 keys}`, `[12 items]`)
+    max_depth: usize,
+    /// Output is truncated after this many characters
+    max_chars: usize,
+}
+
+impl Default for JsonExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonExtractor {
+    /// Creates a JSON extractor that flattens documents with the default limits
+    pub fn new() -> Self {
+        Self {
+            mode: JsonOutputMode::Flatten,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_chars: DEFAULT_MAX_CHARS,
+        }
+    }
+
+    /// Sets the output mode
+    pub fn with_mode(mut self, mode: JsonOutputMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the nesting depth rendered before values are summarized
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum number of characters returned
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    /// Short description of a container that is too deep to render
+    fn summarize(value: &Value) -> String {
+        match value {
+            Value::Object(map) => format!("{{{} keys}}", map.len()),
+            Value::Array(items) => format!("[{} items]", items.len()),
+            other => other.to_string(),
+        }
+    }
+
+    /// Renders a leaf value; strings are shown without quotes and on one line
+    fn render_leaf(value: &Value) -> String {
+        match value {
+            Value::String(s) => s.replace(['\r', '\n'], " "),
+            other => other.to_string(),
+        }
+    }
+
+    fn flatten(&self, value: &Value, path: &str, depth: usize, lines: &mut Vec<String>) {
+        let label = if path.is_empty() { "(root)" } else { path };
+        match value {
+            Value::Object(map) if depth < self.max_depth && !map.is_empty() => {
+                for (key, child) in map {
+                    let child_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                    self.flatten(child, &child_path, depth + 1, lines);
+                }
+            }
+            Value::Array(items) if depth < self.max_depth && !items.is_empty() => {
+                for (index, child) in items.iter().enumerate() {
+                    self.flatten(child, &format!("{}[{}]", path, index), depth + 1, lines);
+                }
+            }
+            Value::Object(_) | Value::Array(_) => lines.push(format!("{}: {}", label, Self::summarize(value))),
+            leaf => lines.push(format!("{}: {}", label, Self::render_leaf(leaf))),
+        }
+    }
+
+    /// Replaces containers deeper than `max_depth` with their summary
+    fn prune(&self, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::Object(_) | Value::Array(_) if depth >= self.max_depth => Value::String(Self::summarize(value)),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, child)| (key.clone(), self.prune(child, depth + 1)))
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|child| self.prune(child, depth + 1)).collect()),
+            leaf => leaf.clone(),
+        }
+    }
+
+    /// Renders JSON source text according to the configured mode and limits
+    pub fn extract_from_str(&self, json: &str) -> Result<String> {
+        let value: Value = serde_json::from_str(json).context("Failed to parse JSON")?;
+
+        let mut output = match self.mode {
+            JsonOutputMode::Flatten => {
+                let mut lines = Vec::new();
+                self.flatten(&value, "", 0, &mut lines);
+                lines.join("\n")
+            }
+            JsonOutputMode::Pretty => serde_json::to_string_pretty(&self.prune(&value, 0))?,
+        };
+
+        if output.chars().count() > self.max_chars {
+            output = output.chars().take(self.max_chars).collect();
+            output.push_str(&format!("\n... (truncated at {} characters)", self.max_chars));
+        }
+        Ok(output)
+    }
+}
+
+impl DocumentExtractor for JsonExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "JsonExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read JSON file: {}", file_path.display()))?;
+
+        let json = TxtExtractor::decode_bytes(&file_bytes);
+        self.extract_from_str(&json)
+            .with_context(|| format!("Failed to extract JSON: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn json_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("export.json");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_json() {
+        let result = JsonExtractor::new().extract_text_from_file(&json_path());
+        assert!(result.is_ok(), "Failed to extract JSON: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.contains("channel: travel\n"), "Got: {}", text);
+        assert!(text.contains("messages[0].user: thomas\n"), "Got: {}", text);
+        assert!(text.contains("messages[1].text: Landed in MDE! See you soon\n"), "Newlines should be flattened. Got: {}", text);
+        assert!(text.contains("messages[1].reactions[0].count: 3"), "Got: {}", text);
+        assert!(text.contains("archived: false"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_with_depth_and_size_limits() {
+        let shallow = JsonExtractor::new()
+            .with_max_depth(1)
+            .extract_text_from_file(&json_path())
+            .unwrap();
+        assert!(shallow.contains("messages: [2 items]"), "Deep values should be summarized. Got: {}", shallow);
+
+        let pretty = JsonExtractor::new()
+            .with_mode(JsonOutputMode::Pretty)
+            .with_max_depth(2)
+            .extract_from_str(r#"{"a": {"b": {"c": 1}}, "d": [1, 2]}"#)
+            .unwrap();
+        assert!(pretty.contains("\"b\": \"{1 keys}\""), "Got: {}", pretty);
+        assert!(pretty.contains("\"d\": [\n    1,"), "Got: {}", pretty);
+
+        let truncated = JsonExtractor::new().with_max_chars(10).extract_text_from_file(&json_path()).unwrap();
+        assert!(truncated.ends_with("... (truncated at 10 characters)"), "Got: {}", truncated);
+    }
+
+    #[test]
+    fn test_extract_from_invalid_json() {
+        assert!(JsonExtractor::new().extract_from_str("{ not json").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::Path;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::iwork::{
+    storage_text, IworkPackage, STORAGE_KIND_BODY, STORAGE_KIND_NOTE, STORAGE_KIND_TEXTBOX,
+};
+
+/// Apple Keynote (.key) extractor that labels each slide and includes presenter notes
+///
+/// Each slide is stored in its own `Index/Slide-<id>.iwa` file. Slides are ordered
+/// by that identifier, which follows creation order and can differ from the show
+/// order when slides were rearranged. The first text on a slide is used as its title.
+pub struct KeynoteExtractor;
+
+impl KeynoteExtractor {
+    /// Orders slide IWA files by the numeric identifier in their name
+    fn slide_sort_key(name: &str) -> u64 {
+        name.trim_end_matches(".iwa")
+            .rsplit(['-', '/'])
+            .next()
+            .and_then(|id| id.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Renders one slide in the same layout as the PowerPoint extractor
+    fn render_slide(package: &mut IworkPackage, name: &str, number: usize) -> Result<String> {
+        let messages = package.read_iwa(name)?.unwrap_or_default();
+
+        let mut texts = Vec::new();
+        let mut notes = Vec::new();
+        for message in &messages {
+            let Some((kind, text)) = storage_text(message)? else {
+                continue;
+            };
+            if text.is_empty() {
+                continue;
+            }
+            match kind {
+                STORAGE_KIND_NOTE => notes.push(text),
+                STORAGE_KIND_BODY | STORAGE_KIND_TEXTBOX => texts.push(text),
+                _ => {}
+            }
+        }
+
+        let mut section = match texts.split_first() {
+            Some((title, [])) => format!("Slide {}: {}", number, title),
+            Some((title, body)) => format!("Slide {}: {}\n{}", number, title, body.join("\n")),
+            None => format!("Slide {}", number),
+        };
+        if !notes.is_empty() {
+            section.push_str(&format!("\n\nSpeaker notes:\n{}", notes.join("\n")));
+        }
+        Ok(section)
+    }
+}
+
+impl DocumentExtractor for KeynoteExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "KeynoteExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Keynote documents can also be package directories, so only the existence check applies
+        let mut package = IworkPackage::open(file_path)?;
+        let mut slide_names: Vec<String> = package
+            .iwa_names()?
+            .into_iter()
+            .filter(|name| name.starts_with("Index/Slide"))
+            .collect();
+        slide_names.sort_by_key(|name| Self::slide_sort_key(name));
+
+        if slide_names.is_empty() {
+            return package
+                .preview_pdf_text()
+                .with_context(|| format!("Failed to read preview PDF of: {}", file_path.display()))?
+                .ok_or_else(|| anyhow::anyhow!("No slides or preview PDF found in: {}", file_path.display()));
+        }
+
+        let mut sections = Vec::new();
+        for (index, name) in slide_names.iter().enumerate() {
+            let section = Self::render_slide(&mut package, name, index + 1)
+                .with_context(|| format!("Failed to read slide {} of: {}", index + 1, file_path.display()))?;
+            sections.push(section);
+        }
+        Ok(sections.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_keynote() {
+        let mut key_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        key_path.push("fixtures");
+        key_path.push("briefing.key");
+
+        let result = KeynoteExtractor.extract_text_from_file(&key_path);
+        assert!(result.is_ok(), "Failed to extract Keynote: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "Slide 1: Welcome Aboard
+Agenda
+Routes
+Fleet update
+
+Speaker notes:
+Mention the new lounge in Panama City.
+
+Slide 2: Questions?";
+        assert_eq!(text, expected);
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+
+/// Number of trailing lines kept when no explicit window is configured
+const DEFAULT_TAIL_LINES: usize = 500;
+
+/// Log file extractor that streams the file and keeps only a bounded window of lines
+///
+/// Lines can be restricted to a timestamp range first; lines without a leading
+/// timestamp (stack traces, wrapped messages) follow the entry they belong to.
+/// When both `head_lines` and `tail_lines` are set, the first and last lines are
+/// shown with an omission marker in between.
+pub struct LogExtractor {
+    head_lines: Option<usize>,
+    tail_lines: Option<usize>,
+    since: Option<String>,
+    until: Option<String>,
+}
+
+impl Default for LogExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogExtractor {
+    /// Creates a log extractor that keeps the last 500 lines
+    pub fn new() -> Self {
+        Self {
+            head_lines: None,
+            tail_lines: Some(DEFAULT_TAIL_LINES),
+            since: None,
+            until: None,
+        }
+    }
+
+    /// Keeps the first `lines` lines (`None` disables the head window)
+    pub fn with_head_lines(mut self, lines: Option<usize>) -> Self {
+        self.head_lines = lines;
+        self
+    }
+
+    /// Keeps the last `lines` lines (`None` disables the tail window)
+    pub fn with_tail_lines(mut self, lines: Option<usize>) -> Self {
+        self.tail_lines = lines;
+        self
+    }
+
+    /// Keeps entries stamped at or after `since` (`YYYY-MM-DD` or `YYYY-MM-DD HH:MM[:SS]`)
+    pub fn with_since(mut self, since: &str) -> Self {
+        self.since = Some(since.to_string());
+        self
+    }
+
+    /// Keeps entries stamped at or before `until` (`YYYY-MM-DD` or `YYYY-MM-DD HH:MM[:SS]`)
+    pub fn with_until(mut self, until: &str) -> Self {
+        self.until = Some(until.to_string());
+        self
+    }
+
+    /// Normalizes a `YYYY-MM-DD[T ]HH:MM[:SS]` prefix into `YYYY-MM-DD HH:MM:SS`
+    ///
+    /// The normalized form sorts lexicographically, so timestamps can be compared as strings.
+    /// A bare date is accepted only when `allow_date_only` is set and maps to midnight.
+    fn normalize_timestamp(text: &str, allow_date_only: bool) -> Option<String> {
+        let bytes = text.as_bytes();
+        let digits = |range: std::ops::Range<usize>| {
+            bytes.get(range.clone()).is_some_and(|b| b.iter().all(u8::is_ascii_digit))
+        };
+
+        if !(digits(0..4) && bytes.get(4) == Some(&b'-') && digits(5..7) && bytes.get(7) == Some(&b'-') && digits(8..10)) {
+            return None;
+        }
+        let date = &text[..10];
+
+        let has_time = matches!(bytes.get(10), Some(b'T' | b' '))
+            && digits(11..13)
+            && bytes.get(13) == Some(&b':')
+            && digits(14..16);
+        if !has_time {
+            return allow_date_only.then(|| format!("{} 00:00:00", date));
+        }
+
+        let seconds = if bytes.get(16) == Some(&b':') && digits(17..19) {
+            &text[17..19]
+        } else {
+            "00"
+        };
+        Some(format!("{} {}:{}", date, &text[11..16], seconds))
+    }
+
+    /// Returns the normalized timestamp a log line starts with, if any
+    fn line_timestamp(line: &str) -> Option<String> {
+        let line = line.strip_prefix('[').unwrap_or(line);
+        Self::normalize_timestamp(line, false)
+    }
+
+    fn parse_bound(bound: &Option<String>, name: &str) -> Result<Option<String>> {
+        bound
+            .as_deref()
+            .map(|value| {
+                Self::normalize_timestamp(value.trim(), true)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid {} timestamp: {}", name, value))
+            })
+            .transpose()
+    }
+}
+
+impl DocumentExtractor for LogExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "LogExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let since = Self::parse_bound(&self.since, "since")?;
+        let until = Self::parse_bound(&self.until, "until")?;
+        let filtered = since.is_some() || until.is_some();
+
+        let file = File::open(file_path)
+            .with_context(|| format!("Failed to open log file: {}", file_path.display()))?;
+        let mut reader = BufReader::new(file);
+
+        let mut head = Vec::new();
+        let mut tail = VecDeque::new();
+        let mut matching = 0usize;
+        let mut in_range = !filtered;
+        let mut buffer = Vec::new();
+
+        loop {
+            buffer.clear();
+            let read = reader
+                .read_until(b'\n', &mut buffer)
+                .with_context(|| format!("Failed to read log file: {}", file_path.display()))?;
+            if read == 0 {
+                break;
+            }
+            let line = String::from_utf8_lossy(&buffer);
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if let Some(timestamp) = Self::line_timestamp(line).filter(|_| filtered) {
+                in_range = since.as_ref().is_none_or(|s| &timestamp >= s)
+                    && until.as_ref().is_none_or(|u| &timestamp <= u);
+            }
+            if !in_range {
+                continue;
+            }
+
+            matching += 1;
+            if self.head_lines.is_some_and(|limit| head.len() < limit) {
+                head.push(line.to_string());
+                continue;
+            }
+            match self.tail_lines {
+                Some(0) => {}
+                Some(limit) => {
+                    if tail.len() == limit {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line.to_string());
+                }
+                // Without a tail window everything past the head is omitted,
+                // unless there is no head window either
+                None if self.head_lines.is_none() => tail.push_back(line.to_string()),
+                None => {}
+            }
+        }
+
+        let omitted = matching - head.len() - tail.len();
+        let mut lines = head;
+        if omitted > 0 {
+            lines.push(format!("... {} of {} lines omitted ...", omitted, matching));
+        }
+        lines.extend(tail);
+        Ok(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn log_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("service.log");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_log() {
+        let result = LogExtractor::new().extract_text_from_file(&log_path());
+        assert!(result.is_ok(), "Failed to extract log: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert_eq!(text.lines().count(), 10, "Small logs should be returned whole. Got: {}", text);
+        assert!(text.ends_with("2025-08-28 06:30:00 INFO  Shutting down"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_head_and_tail_windows() {
+        let text = LogExtractor::new()
+            .with_head_lines(Some(2))
+            .with_tail_lines(Some(1))
+            .extract_text_from_file(&log_path())
+            .unwrap();
+        let expected = "2025-08-28 06:00:01 INFO  Starting booking service v2.4.1
+2025-08-28 06:00:02 INFO  Connected to db.internal:5432
+... 7 of 10 lines omitted ...
+2025-08-28 06:30:00 INFO  Shutting down";
+        assert_eq!(text, expected);
+
+        let text = LogExtractor::new()
+            .with_head_lines(Some(1))
+            .with_tail_lines(None)
+            .extract_text_from_file(&log_path())
+            .unwrap();
+        assert_eq!(text, "2025-08-28 06:00:01 INFO  Starting booking service v2.4.1\n... 9 of 10 lines omitted ...");
+    }
+
+    #[test]
+    fn test_time_range_filter() {
+        let text = LogExtractor::new()
+            .with_since("2025-08-28T06:05")
+            .with_until("2025-08-28 06:15:30")
+            .extract_text_from_file(&log_path())
+            .unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 6, "Got: {}", text);
+        assert!(lines[0].contains("Slow query"), "Got: {}", text);
+        assert!(lines[2].contains("at gateway::charge"), "Continuation lines should follow their entry. Got: {}", text);
+        assert!(lines[5].contains("Payment captured"), "Bracketed timestamps should be recognized. Got: {}", text);
+    }
+
+    #[test]
+    fn test_invalid_time_bound() {
+        let result = LogExtractor::new().with_since("yesterday").extract_text_from_file(&log_path());
+        assert!(result.is_err(), "Unparseable bounds should be rejected");
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+use crate::extractor::{DocumentExtractor, Heading, Link};
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Markdown extractor that keeps the document structure and surfaces YAML front matter
+pub struct MarkdownExtractor;
+
+/// Front matter fields rendered at the top of the extracted text, in order
+const HEADER_FIELDS: &[(&str, &str)] = &[("title", "Title"), ("tags", "Tags"), ("date", "Date")];
+
+/// A line of Markdown text, as far as headings and code are concerned
+enum MarkdownLine<'a> {
+    /// Inside a fenced code block, or one of its fences
+    Code,
+    /// A heading, as `(level, title)`; a setext underline becomes an empty `Text`
+    Heading(usize, String),
+    Text(&'a str),
+}
+
+/// Sorts the lines of Markdown text into headings, fenced code and other text
+fn markdown_lines(text: &str) -> Vec<MarkdownLine<'_>> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut previous: Option<&str> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            lines.push(MarkdownLine::Code);
+            continue;
+        }
+        if indent < 4 && (trimmed.starts_with("```") || trimmed.starts_with("~~~")) {
+            fence = Some(&trimmed[..3]);
+            previous = None;
+            lines.push(MarkdownLine::Code);
+            continue;
+        }
+
+        if indent < 4 {
+            if let Some((level, title)) = atx_heading(trimmed) {
+                lines.push(MarkdownLine::Heading(level, title));
+                previous = None;
+                continue;
+            }
+            let underline = trimmed.trim_end();
+            let level = if !underline.is_empty() && underline.chars().all(|c| c == '=') {
+                Some(1)
+            } else if !underline.is_empty() && underline.chars().all(|c| c == '-') {
+                Some(2)
+            } else {
+                None
+            };
+            if let (Some(level), Some(title)) = (level, previous) {
+                // `previous` is only set right after its own line was pushed as text
+                if let Some(last) = lines.last_mut() {
+                    *last = MarkdownLine::Heading(level, title.trim().to_string());
+                }
+                lines.push(MarkdownLine::Text(""));
+                previous = None;
+                continue;
+            }
+        }
+        previous = (!trimmed.is_empty()).then_some(line);
+        lines.push(MarkdownLine::Text(line));
+    }
+    lines
+}
+
+/// Reads the ATX (`# Title`) and setext (`Title` over `===`/`---`) headings of Markdown
+/// text as `(level, title)`, skipping fenced code blocks
+pub fn markdown_headings(text: &str) -> Vec<(usize, String)> {
+    markdown_lines(text)
+        .into_iter()
+        .filter_map(|line| match line {
+            MarkdownLine::Heading(level, title) => Some((level, title)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Reads a reference definition line, `[label]: url "title"`, as its lowercased label and url
+fn link_definition(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() >= 4 {
+        return None;
+    }
+    let (label, rest) = trimmed.strip_prefix('[')?.split_once("]:")?;
+    let url = rest.split_whitespace().next()?;
+    let url = url.strip_prefix('<').and_then(|url| url.strip_suffix('>')).unwrap_or(url);
+    (!label.trim().is_empty() && !url.is_empty()).then(|| (label.trim().to_lowercase(), url.to_string()))
+}
+
+/// Returns the position of the `close` matching the `open` at `start`, counting nesting
+fn matching(chars: &[char], start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (index, &c) in chars.iter().enumerate().skip(start) {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// Reads the links of one line as `(text, url)`: inline, reference and shortcut links,
+/// `<...>` autolinks and bare `http(s)://` URLs; images and code spans are skipped
+fn line_links(line: &str, definitions: &HashMap<String, String>) -> Vec<(Option<String>, String)> {
+    let chars: Vec<char> = line.chars().collect();
+    let collect = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+    let mut links = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        match chars[position] {
+            '`' => {
+                let ticks = chars[position..].iter().take_while(|c| **c == '`').count();
+                let after = position + ticks;
+                // A code span ends at the next run of as many backticks; an unclosed run is literal
+                let run_at = |end: usize| chars[end..].iter().take_while(|c| **c == '`').count() == ticks;
+                position = (after..chars.len())
+                    .find(|&end| chars[end - 1] != '`' && run_at(end))
+                    .map_or(after, |end| end + ticks);
+            }
+            '[' => {
+                let image = position > 0 && chars[position - 1] == '!';
+                let Some(close) = matching(&chars, position, '[', ']') else {
+                    position += 1;
+                    continue;
+                };
+                let label = collect(position + 1..close);
+                let text = Some(label.trim().to_string()).filter(|text| !text.is_empty());
+                let after = close + 1;
+                let target = match chars.get(after) {
+                    Some('(') => matching(&chars, after, '(', ')').map(|end| {
+                        let target = collect(after + 1..end);
+                        let url = target.split_whitespace().next().unwrap_or("");
+                        let url = url.strip_prefix('<').and_then(|url| url.strip_suffix('>')).unwrap_or(url);
+                        (Some(url.to_string()), end + 1)
+                    }),
+                    Some('[') => matching(&chars, after, '[', ']').map(|end| {
+                        let reference = collect(after + 1..end);
+                        let key = if reference.trim().is_empty() { &label } else { &reference };
+                        (definitions.get(&key.trim().to_lowercase()).cloned(), end + 1)
+                    }),
+                    _ => definitions.get(&label.trim().to_lowercase()).map(|url| (Some(url.clone()), after)),
+                };
+                match target {
+                    Some((url, end)) => {
+                        if let Some(url) = url.filter(|url| !image && !url.is_empty() && !url.starts_with('#')) {
+                            links.push((text, url));
+                        }
+                        position = end;
+                    }
+                    None => position += 1,
+                }
+            }
+            '<' => {
+                let end = chars[position..]
+                    .iter()
+                    .position(|c| *c == '>' || c.is_whitespace())
+                    .map(|end| position + end);
+                match end.filter(|end| chars[*end] == '>') {
+                    Some(end) if collect(position + 1..end).contains(':') => {
+                        links.push((None, collect(position + 1..end)));
+                        position = end + 1;
+                    }
+                    _ => position += 1,
+                }
+            }
+            'h' if position == 0 || !chars[position - 1].is_alphanumeric() => {
+                let rest = collect(position..chars.len());
+                if !rest.starts_with("http://") && !rest.starts_with("https://") {
+                    position += 1;
+                    continue;
+                }
+                let len = rest.find(|c: char| c.is_whitespace() || c == '<').unwrap_or(rest.len());
+                let mut url = &rest[..len];
+                // Sentence punctuation after a URL is not part of it
+                url = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_']);
+                if url.ends_with(')') && !url.contains('(') {
+                    url = url.trim_end_matches(')');
+                }
+                links.push((None, url.to_string()));
+                position += url.chars().count();
+            }
+            _ => position += 1,
+        }
+    }
+    links
+}
+
+/// Reads the links of Markdown text, with the heading each comes under
+///
+/// Links in fenced code are skipped, as are links to anchors in the same document.
+pub fn markdown_links(text: &str) -> Vec<Link> {
+    let lines = markdown_lines(text);
+    let definitions: HashMap<String, String> = lines
+        .iter()
+        .filter_map(|line| match line {
+            MarkdownLine::Text(line) => link_definition(line),
+            _ => None,
+        })
+        .collect();
+
+    let mut links = Vec::new();
+    let mut section: Option<String> = None;
+    for line in &lines {
+        let found = match line {
+            MarkdownLine::Heading(_, title) => {
+                section = Some(title.clone());
+                line_links(title, &definitions)
+            }
+            MarkdownLine::Text(line) if link_definition(line).is_none() => line_links(line, &definitions),
+            _ => continue,
+        };
+        links.extend(
+            found.into_iter().map(|(text, url)| Link { url, text, page: None, section: section.clone() }),
+        );
+    }
+    links
+}
+
+/// Parses `## Title ##` into `(2, "Title")`
+fn atx_heading(line: &str) -> Option<(usize, String)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, title.to_string()))
+}
+
+impl MarkdownExtractor {
+    /// Splits a Markdown document into its YAML front matter (if any) and body
+    ///
+    /// Front matter must start on the first line with `---` and end with a line
+    /// containing only `---` or `...`.
+    fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+        let Some(rest) = text
+            .strip_prefix("---\n")
+            .or_else(|| text.strip_prefix("---\r\n"))
+        else {
+            return (None, text);
+        };
+
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            let trimmed = line.trim_end();
+            if trimmed == "---" || trimmed == "..." {
+                return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+            }
+            offset += line.len();
+        }
+
+        // No closing delimiter: treat the whole file as body
+        (None, text)
+    }
+
+    /// Renders a front matter value as a single readable line
+    fn render_value(value: &Value) -> Option<String> {
+        match value {
+            Value::Null => None,
+            Value::Bool(b) => Some(b.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::String(s) => Some(s.trim().to_string()),
+            Value::Sequence(items) => {
+                let items: Vec<String> = items.iter().filter_map(Self::render_value).collect();
+                Some(items.join(", "))
+            }
+            Value::Mapping(_) | Value::Tagged(_) => serde_yaml::to_string(value)
+                .ok()
+                .map(|s| s.trim().replace('\n', "; ")),
+        }
+    }
+
+    /// Builds the structured header from parsed front matter
+    fn render_header(front_matter: &Value) -> String {
+        let mut header = String::new();
+        for (key, label) in HEADER_FIELDS {
+            if let Some(rendered) = front_matter.get(*key).and_then(Self::render_value) {
+                if !rendered.is_empty() {
+                    header.push_str(&format!("{}: {}\n", label, rendered));
+                }
+            }
+        }
+        header
+    }
+
+    /// Converts Markdown source into the extracted text representation
+    pub fn extract_from_str(text: &str) -> String {
+        let (front_matter, body) = Self::split_front_matter(text);
+
+        // Malformed front matter is left in place rather than failing the whole note
+        let parsed = front_matter.and_then(|yaml| serde_yaml::from_str::<Value>(yaml).ok());
+        match parsed {
+            Some(front_matter) => {
+                let header = Self::render_header(&front_matter);
+                if header.is_empty() {
+                    body.to_string()
+                } else {
+                    format!("{}---\n{}", header, body)
+                }
+            }
+            None if front_matter.is_some() => text.to_string(),
+            None => body.to_string(),
+        }
+    }
+}
+
+impl DocumentExtractor for MarkdownExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "MarkdownExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read Markdown file: {}", file_path.display()))?;
+
+        let text = TxtExtractor::decode_bytes(&file_bytes);
+        Ok(Self::extract_from_str(&text))
+    }
+
+    /// Reads the headers of the body; front matter is not part of the outline
+    fn extract_outline(&self, file_path: &Path) -> Result<Vec<Heading>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read Markdown file: {}", file_path.display()))?;
+        let text = TxtExtractor::decode_bytes(&file_bytes);
+        let (_, body) = Self::split_front_matter(&text);
+        Ok(markdown_headings(body)
+            .into_iter()
+            .map(|(level, title)| Heading { level, title, page: None, offset: None })
+            .collect())
+    }
+
+    /// Reads the links of the body; front matter is not part of the document text
+    fn extract_links(&self, file_path: &Path) -> Result<Vec<Link>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read Markdown file: {}", file_path.display()))?;
+        let text = TxtExtractor::decode_bytes(&file_bytes);
+        let (_, body) = Self::split_front_matter(&text);
+        Ok(markdown_links(body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_markdown_with_front_matter() {
+        let mut md_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        md_path.push("fixtures");
+        md_path.push("note.md");
+
+        let result = MarkdownExtractor.extract_text_from_file(&md_path);
+        assert!(result.is_ok(), "Failed to extract Markdown: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Title: Trip to Medellín\n"), "Header should start with title. Got: {}", text);
+        assert!(text.contains("Tags: travel, colombia\n"), "Tags should be listed. Got: {}", text);
+        assert!(text.contains("Date: 2025-08-28\n"), "Date should be present. Got: {}", text);
+        assert!(text.contains("# Itinerary"), "Headings should be preserved. Got: {}", text);
+        assert!(text.contains("- AUS to PTY"), "Lists should be preserved. Got: {}", text);
+        assert!(!text.contains("layout: post"), "Raw front matter should not leak. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_outline_from_markdown() {
+        let mut md_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        md_path.push("fixtures");
+        md_path.push("note.md");
+
+        let outline = MarkdownExtractor.extract_outline(&md_path).unwrap();
+        let titles: Vec<(usize, &str)> = outline.iter().map(|h| (h.level, h.title.as_str())).collect();
+        assert_eq!(titles, [(1, "Itinerary"), (2, "Notes")]);
+    }
+
+    #[test]
+    fn test_markdown_headings() {
+        let source = "Title\n=====\n\nIntro\n\n## Setup ##\n\n```sh\n# not a heading\n```\n\
+                      #hashtag\n\nUsage\n-----\n\n    # indented code\n";
+        assert_eq!(
+            markdown_headings(source),
+            [(1, "Title".to_string()), (2, "Setup".to_string()), (2, "Usage".to_string())]
+        );
+        assert!(markdown_headings("- item\n\n---\n").is_empty(), "A rule after a blank line is not a heading");
+    }
+
+    #[test]
+    fn test_markdown_links() {
+        let source = "Intro with <https://example.com/auto> and `[code](https://example.com/code)`.\n\n\
+                      # Booking\n\nUse the [portal](https://portal.example.com \"Portal\") or [desk][help].\n\
+                      ![logo](logo.png) [Back](#top) [help] and https://example.com/bare).\n\n\
+                      ```\n[fenced](https://example.com/fenced)\n```\n\n[help]: <mailto:help@example.com>\n";
+        let found = markdown_links(source);
+        let found: Vec<_> = found
+            .iter()
+            .map(|link| (link.text.as_deref(), link.url.as_str(), link.section.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (None, "https://example.com/auto", None),
+                (Some("portal"), "https://portal.example.com", Some("Booking")),
+                (Some("desk"), "mailto:help@example.com", Some("Booking")),
+                (Some("help"), "mailto:help@example.com", Some("Booking")),
+                (None, "https://example.com/bare", Some("Booking")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_links_from_markdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("links.md");
+        fs::write(&path, "---\ntitle: Links\n---\n## Sources\n\nSee [docs](https://example.com/docs).\n").unwrap();
+        let links = MarkdownExtractor.extract_links(&path).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].section.as_deref(), Some("Sources"));
+        assert!(MarkdownExtractor.extract_links(&dir.path().join("missing.md")).is_err());
+    }
+
+    #[test]
+    fn test_extract_from_str_without_front_matter() {
+        let source = "# Heading\n\nSome text.\n";
+        assert_eq!(MarkdownExtractor::extract_from_str(source), source);
+    }
+
+    #[test]
+    fn test_extract_from_str_with_malformed_front_matter() {
+        let source = "---\ntitle: [unclosed\n---\nBody\n";
+        assert_eq!(
+            MarkdownExtractor::extract_from_str(source),
+            source,
+            "Malformed front matter should be kept verbatim"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::eml_extractor::EmlExtractor;
+
+/// Selects a single message inside a mailbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageSelector {
+    /// Zero-based position of the message in the mailbox
+    Index(usize),
+    /// Value of the `Message-ID` header, with or without angle brackets
+    MessageId(String),
+}
+
+/// Summary of a message, as shown in the mailbox listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MboxEntry {
+    pub index: usize,
+    pub message_id: Option<String>,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub date: Option<String>,
+}
+
+/// mbox mailbox extractor
+///
+/// Without a selector the extractor lists the messages in the mailbox; with one it
+/// extracts only the selected message. The file is streamed, so only the headers
+/// of each message (and the body of the selected one) are held in memory.
+#[derive(Default)]
+pub struct MboxExtractor {
+    selector: Option<MessageSelector>,
+}
+
+/// Streams raw messages out of an mbox file
+struct MboxReader<R> {
+    reader: R,
+    /// The reader is positioned right after a `From ` separator line
+    at_message_start: bool,
+}
+
+impl<R: BufRead> MboxReader<R> {
+    fn new(reader: R) -> Self {
+        Self { reader, at_message_start: false }
+    }
+
+    fn read_line(&mut self, line: &mut Vec<u8>) -> Result<bool> {
+        line.clear();
+        let read = self.reader.read_until(b'\n', line).context("Failed to read mailbox")?;
+        Ok(read > 0)
+    }
+
+    /// Reads the next message, returning its raw headers and (if `keep_body` accepts
+    /// the headers) its body with mboxrd `>From ` quoting removed
+    fn next_message(&mut self, keep_body: impl FnOnce(&[u8]) -> bool) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+        let mut line = Vec::new();
+
+        if !self.at_message_start {
+            loop {
+                if !self.read_line(&mut line)? {
+                    return Ok(None);
+                }
+                if line.starts_with(b"From ") {
+                    break;
+                }
+            }
+        }
+        self.at_message_start = false;
+
+        let mut headers = Vec::new();
+        while self.read_line(&mut line)? {
+            if line == b"\n" || line == b"\r\n" {
+                break;
+            }
+            headers.extend_from_slice(&line);
+        }
+
+        let keep = keep_body(&headers);
+        let mut body = Vec::new();
+        let mut previous_blank = true;
+        while self.read_line(&mut line)? {
+            let is_blank = line == b"\n" || line == b"\r\n";
+            if previous_blank && line.starts_with(b"From ") {
+                self.at_message_start = true;
+                break;
+            }
+            if keep {
+                let quoted = line.iter().take_while(|&&b| b == b'>').count();
+                if quoted > 0 && line[quoted..].starts_with(b"From ") {
+                    body.extend_from_slice(&line[1..]);
+                } else {
+                    body.extend_from_slice(&line);
+                }
+            }
+            previous_blank = is_blank;
+        }
+
+        Ok(Some((headers, body)))
+    }
+}
+
+/// Strips surrounding whitespace and angle brackets from a Message-ID
+fn normalize_message_id(id: &str) -> &str {
+    id.trim().trim_start_matches('<').trim_end_matches('>')
+}
+
+impl MboxExtractor {
+    /// Creates an extractor that lists the messages in the mailbox
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extracts only the message at `index` (zero-based)
+    pub fn with_message_index(mut self, index: usize) -> Self {
+        self.selector = Some(MessageSelector::Index(index));
+        self
+    }
+
+    /// Extracts only the message with the given `Message-ID`
+    pub fn with_message_id(mut self, message_id: &str) -> Self {
+        self.selector = Some(MessageSelector::MessageId(message_id.to_string()));
+        self
+    }
+
+    fn open(file_path: &Path) -> Result<MboxReader<BufReader<File>>> {
+        let file = File::open(file_path)
+            .with_context(|| format!("Failed to open mailbox: {}", file_path.display()))?;
+        Ok(MboxReader::new(BufReader::new(file)))
+    }
+
+    /// Summarizes message headers for the listing
+    fn summarize(index: usize, headers: &[u8]) -> MboxEntry {
+        let mut raw = headers.to_vec();
+        raw.extend_from_slice(b"\r\n");
+        let parsed = EmlExtractor::parse_message(&raw).ok();
+
+        let from = parsed.as_ref().and_then(|message| {
+            let addr = message.from()?.first()?;
+            addr.address().or(addr.name()).map(str::to_string)
+        });
+
+        MboxEntry {
+            index,
+            message_id: parsed.as_ref().and_then(|m| m.message_id()).map(str::to_string),
+            from,
+            subject: parsed.as_ref().and_then(|m| m.subject()).map(str::to_string),
+            date: parsed.as_ref().and_then(|m| m.date()).map(|d| d.to_rfc3339()),
+        }
+    }
+
+    /// Lists every message in the mailbox without keeping message bodies in memory
+    pub fn list_messages(&self, file_path: &Path) -> Result<Vec<MboxEntry>> {
+        let mut reader = Self::open(file_path)?;
+        let mut entries = Vec::new();
+        while let Some((headers, _)) = reader.next_message(|_| false)? {
+            entries.push(Self::summarize(entries.len(), &headers));
+        }
+        Ok(entries)
+    }
+
+    /// Returns the raw RFC 822 bytes of the selected message
+    pub fn read_message(&self, file_path: &Path, selector: &MessageSelector) -> Result<Vec<u8>> {
+        let mut reader = Self::open(file_path)?;
+        let mut index = 0;
+        loop {
+            let mut selected = false;
+            let message = reader.next_message(|headers| {
+                selected = match selector {
+                    MessageSelector::Index(wanted) => index == *wanted,
+                    MessageSelector::MessageId(wanted) => Self::summarize(index, headers)
+                        .message_id
+                        .is_some_and(|id| normalize_message_id(&id) == normalize_message_id(wanted)),
+                };
+                selected
+            })?;
+
+            let Some((mut headers, body)) = message else {
+                return Err(anyhow::anyhow!(
+                    "Message not found in {}: {:?}",
+                    file_path.display(),
+                    selector
+                ));
+            };
+            if selected {
+                headers.extend_from_slice(b"\r\n");
+                headers.extend_from_slice(&body);
+                return Ok(headers);
+            }
+            index += 1;
+        }
+    }
+}
+
+impl DocumentExtractor for MboxExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "MboxExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        match &self.selector {
+            Some(selector) => {
+                let raw = self.read_message(file_path, selector)?;
+                let message = EmlExtractor::parse_message(&raw)
+                    .with_context(|| format!("Failed to parse message {:?} in: {}", selector, file_path.display()))?;
+                Ok(EmlExtractor::render_message(&message))
+            }
+            None => {
+                let entries = self.list_messages(file_path)?;
+                let mut output = format!("Mailbox with {} messages\n", entries.len());
+                for entry in entries {
+                    output.push_str(&format!(
+                        "\n[{}] {} | From: {} | Subject: {} | Message-ID: {}",
+                        entry.index,
+                        entry.date.as_deref().unwrap_or("(no date)"),
+                        entry.from.as_deref().unwrap_or("(unknown)"),
+                        entry.subject.as_deref().unwrap_or("(no subject)"),
+                        entry.message_id.as_deref().unwrap_or("(none)"),
+                    ));
+                }
+                Ok(output)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn mbox_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("archive.mbox");
+        path
+    }
+
+    #[test]
+    fn test_list_messages_in_mbox() {
+        let text = MboxExtractor::new()
+            .extract_text_from_file(&mbox_path())
+            .expect("Mailbox listing should succeed");
+
+        assert!(text.starts_with("Mailbox with 3 messages\n"), "Got: {}", text);
+        assert!(text.contains("[0] 2025-08-01T09:00:00-05:00 | From: ana@example.com | Subject: Visa paperwork"), "Got: {}", text);
+        assert!(text.contains("[2] "), "Got: {}", text);
+        assert!(text.contains("Message-ID: hotel-3@example.com"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_message_by_index_and_id() {
+        let by_index = MboxExtractor::new()
+            .with_message_index(1)
+            .extract_text_from_file(&mbox_path())
+            .expect("Message 1 should be extracted");
+        assert!(by_index.contains("Subject: Flight change"), "Got: {}", by_index);
+        assert!(by_index.contains("From the desk of the airline:"), "Quoted From lines should be unescaped. Got: {}", by_index);
+        assert!(!by_index.contains("Visa paperwork"), "Other messages should not leak. Got: {}", by_index);
+
+        let by_id = MboxExtractor::new()
+            .with_message_id("<hotel-3@example.com>")
+            .extract_text_from_file(&mbox_path())
+            .expect("Message should be found by Message-ID");
+        assert!(by_id.contains("Subject: Hotel booking"), "Got: {}", by_id);
+
+        let missing = MboxExtractor::new().with_message_index(7).extract_text_from_file(&mbox_path());
+        assert!(missing.is_err(), "Out-of-range index should be an error");
+    }
+}
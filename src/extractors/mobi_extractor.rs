@@ -0,0 +1,376 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::extractor::{DocumentExtractor, DocumentMetadata};
+use crate::extractors::html_extractor::HtmlExtractor;
+
+/// Size of the PalmDB header preceding the record list
+const PALMDB_HEADER_LEN: usize = 78;
+
+/// Text encoding code for UTF-8 in the MOBI header (anything else is CP1252)
+const MOBI_UTF8: u32 = 65001;
+
+/// Bit of the MOBI header's EXTH flags that marks an EXTH block after the header
+const EXTH_PRESENT: u32 = 0x40;
+
+/// EXTH record types read as metadata
+const EXTH_AUTHOR: u32 = 100;
+const EXTH_PUBLISHING_DATE: u32 = 106;
+const EXTH_CONTRIBUTOR: u32 = 108;
+const EXTH_UPDATED_TITLE: u32 = 503;
+
+/// Mobipocket/Kindle (.mobi, .azw3) extractor that decompresses the text records
+/// and returns the book split into chapters
+///
+/// Only PalmDOC-compressed and uncompressed books are supported; DRM-protected
+/// books and the rare HUFF/CDIC compression are rejected with an error.
+pub struct MobiExtractor;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+impl MobiExtractor {
+    /// Returns the byte slices of every PalmDB record
+    fn records(data: &[u8]) -> Result<Vec<&[u8]>> {
+        let count = read_u16(data, 76).context("Truncated PalmDB header")? as usize;
+        let offsets = (0..count)
+            .map(|index| read_u32(data, PALMDB_HEADER_LEN + index * 8).map(|offset| offset as usize))
+            .collect::<Option<Vec<_>>>()
+            .context("Truncated PalmDB record list")?;
+
+        offsets
+            .iter()
+            .enumerate()
+            .map(|(index, &start)| {
+                let end = offsets.get(index + 1).copied().unwrap_or(data.len());
+                data.get(start..end)
+                    .with_context(|| format!("PalmDB record {} is out of bounds", index))
+            })
+            .collect()
+    }
+
+    /// Decompresses a PalmDOC (LZ77 variant) record
+    pub fn palmdoc_decompress(data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len() * 2);
+        let mut i = 0;
+        while i < data.len() {
+            let byte = data[i];
+            i += 1;
+            match byte {
+                0x00 | 0x09..=0x7f => output.push(byte),
+                // 1-8: copy that many literal bytes
+                0x01..=0x08 => {
+                    let end = (i + byte as usize).min(data.len());
+                    output.extend_from_slice(&data[i..end]);
+                    i = end;
+                }
+                // Two-byte back-reference: 11 bits of distance, 3 bits of length
+                0x80..=0xbf => {
+                    let Some(&next) = data.get(i) else { break };
+                    i += 1;
+                    let pair = u16::from_be_bytes([byte, next]);
+                    let distance = ((pair >> 3) & 0x07ff) as usize;
+                    let length = (pair & 0x0007) as usize + 3;
+                    if distance == 0 || distance > output.len() {
+                        continue;
+                    }
+                    // Byte by byte, since the source may overlap the bytes being written
+                    let start = output.len() - distance;
+                    for offset in 0..length {
+                        output.push(output[start + offset]);
+                    }
+                }
+                // A space followed by an ASCII character
+                0xc0..=0xff => {
+                    output.push(b' ');
+                    output.push(byte ^ 0x80);
+                }
+            }
+        }
+        output
+    }
+
+    /// Size of one trailing entry, stored as a backwards variable-width integer
+    fn trailing_entry_size(data: &[u8]) -> usize {
+        let mut size = 0usize;
+        let mut shift = 0;
+        for &byte in data.iter().rev().take(4) {
+            size |= ((byte & 0x7f) as usize) << shift;
+            shift += 7;
+            if byte & 0x80 != 0 {
+                break;
+            }
+        }
+        size
+    }
+
+    /// Removes the extra data MOBI appends to text records, as described by `extra_flags`
+    fn strip_trailing_entries(mut record: &[u8], extra_flags: u16) -> &[u8] {
+        for _ in 0..(extra_flags >> 1).count_ones() {
+            let size = Self::trailing_entry_size(record).min(record.len());
+            record = &record[..record.len() - size];
+        }
+        if extra_flags & 1 != 0 {
+            if let Some(&last) = record.last() {
+                let size = ((last & 0x3) as usize + 1).min(record.len());
+                record = &record[..record.len() - size];
+            }
+        }
+        record
+    }
+
+    /// Splits the book's HTML into chapters at page breaks, or at document boundaries for KF8 books
+    fn split_chapters(html: &str) -> Vec<&str> {
+        let lower = html.to_ascii_lowercase();
+        let marker = if lower.contains("<mbp:pagebreak") { "<mbp:pagebreak" } else { "<html" };
+
+        let mut boundaries: Vec<usize> = lower.match_indices(marker).map(|(index, _)| index).collect();
+        boundaries.insert(0, 0);
+        boundaries.push(html.len());
+        boundaries
+            .windows(2)
+            .map(|pair| &html[pair[0]..pair[1]])
+            .collect()
+    }
+
+    /// Returns the book title and its decoded HTML text
+    pub fn read_book(data: &[u8]) -> Result<(Option<String>, String)> {
+        let records = Self::records(data)?;
+        let header = *records.first().context("Book has no header record")?;
+
+        let compression = read_u16(header, 0).context("Truncated PalmDOC header")?;
+        let text_length = read_u32(header, 4).context("Truncated PalmDOC header")? as usize;
+        let text_record_count = read_u16(header, 8).context("Truncated PalmDOC header")? as usize;
+        let encryption = read_u16(header, 12).context("Truncated PalmDOC header")?;
+
+        if encryption != 0 {
+            return Err(anyhow::anyhow!("Book is DRM-protected"));
+        }
+        if compression == 17480 {
+            return Err(anyhow::anyhow!("HUFF/CDIC compressed books are not supported"));
+        }
+        if compression != 1 && compression != 2 {
+            return Err(anyhow::anyhow!("Unknown compression type: {}", compression));
+        }
+
+        // The MOBI header is optional in plain PalmDOC files
+        let has_mobi_header = header.get(16..20) == Some(b"MOBI");
+        let mobi_header_len = if has_mobi_header { read_u32(header, 20).unwrap_or(0) as usize } else { 0 };
+        let utf8 = has_mobi_header && read_u32(header, 28) == Some(MOBI_UTF8);
+        let extra_flags = if mobi_header_len >= 0xe4 { read_u16(header, 16 + 0xe2).unwrap_or(0) } else { 0 };
+
+        let title = if has_mobi_header { Self::full_name(header, utf8) } else { None };
+
+        let mut text = Vec::with_capacity(text_length);
+        for record in records.iter().skip(1).take(text_record_count) {
+            let record = Self::strip_trailing_entries(record, extra_flags);
+            if compression == 2 {
+                text.extend(Self::palmdoc_decompress(record));
+            } else {
+                text.extend_from_slice(record);
+            }
+        }
+        text.truncate(text_length);
+
+        Ok((title, Self::decode(&text, utf8)))
+    }
+
+    /// Returns the "full name" (title) stored in the MOBI header
+    fn full_name(header: &[u8], utf8: bool) -> Option<String> {
+        let offset = read_u32(header, 84).unwrap_or(0) as usize;
+        let length = read_u32(header, 88).unwrap_or(0) as usize;
+        header
+            .get(offset..offset + length)
+            .map(|bytes| Self::decode(bytes, utf8))
+            .filter(|title| !title.trim().is_empty())
+    }
+
+    /// Returns the `(type, data)` records of the EXTH block following the MOBI header
+    fn exth_records(header: &[u8]) -> Vec<(u32, &[u8])> {
+        let mobi_header_len = read_u32(header, 20).unwrap_or(0) as usize;
+        let start = 16 + mobi_header_len;
+        if read_u32(header, 0x80).unwrap_or(0) & EXTH_PRESENT == 0 || header.get(start..start + 4) != Some(b"EXTH") {
+            return Vec::new();
+        }
+
+        let count = read_u32(header, start + 8).unwrap_or(0) as usize;
+        let mut records = Vec::new();
+        let mut offset = start + 12;
+        for _ in 0..count {
+            let (Some(kind), Some(length)) = (read_u32(header, offset), read_u32(header, offset + 4)) else {
+                break;
+            };
+            // The length includes the 8-byte record header
+            let Some(data) = header.get(offset + 8..offset + length as usize) else {
+                break;
+            };
+            records.push((kind, data));
+            offset += length as usize;
+        }
+        records
+    }
+
+    /// Reads the title, authors, publishing date and producing tool of a book
+    ///
+    /// Books are reflowable, so there is no page count. The producer is the EXTH
+    /// contributor, which conversion tools such as calibre fill with their name.
+    pub fn read_metadata(data: &[u8]) -> Result<DocumentMetadata> {
+        let records = Self::records(data)?;
+        let header = *records.first().context("Book has no header record")?;
+
+        let mut metadata = DocumentMetadata::default();
+        if header.get(16..20) != Some(b"MOBI") {
+            return Ok(metadata);
+        }
+        let utf8 = read_u32(header, 28) == Some(MOBI_UTF8);
+        metadata.title = Self::full_name(header, utf8);
+
+        for (kind, value) in Self::exth_records(header) {
+            let value = Self::decode(value, utf8).trim().to_string();
+            if value.is_empty() {
+                continue;
+            }
+            match kind {
+                // Each author has its own record
+                EXTH_AUTHOR => match metadata.author.as_mut() {
+                    Some(authors) => {
+                        authors.push_str(", ");
+                        authors.push_str(&value);
+                    }
+                    None => metadata.author = Some(value),
+                },
+                EXTH_UPDATED_TITLE => metadata.title = Some(value),
+                EXTH_PUBLISHING_DATE => {
+                    metadata.created.get_or_insert(value);
+                }
+                EXTH_CONTRIBUTOR => {
+                    metadata.producer.get_or_insert(value);
+                }
+                _ => {}
+            }
+        }
+        Ok(metadata)
+    }
+
+    fn decode(bytes: &[u8], utf8: bool) -> String {
+        if utf8 {
+            String::from_utf8_lossy(bytes).into_owned()
+        } else {
+            encoding_rs::WINDOWS_1252.decode(bytes).0.into_owned()
+        }
+    }
+}
+
+impl DocumentExtractor for MobiExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "MobiExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read ebook file: {}", file_path.display()))?;
+
+        let (title, html) = Self::read_book(&file_bytes)
+            .with_context(|| format!("Failed to extract ebook: {}", file_path.display()))?;
+
+        let html_extractor = HtmlExtractor::new().with_links(false);
+        let chapters: Vec<String> = Self::split_chapters(&html)
+            .into_iter()
+            .map(|chapter| html_extractor.extract_from_str(chapter).trim().to_string())
+            .filter(|chapter| !chapter.is_empty())
+            .collect();
+
+        let mut sections = Vec::new();
+        if let Some(title) = title {
+            sections.push(format!("Title: {}", title));
+        }
+        for (index, chapter) in chapters.iter().enumerate() {
+            sections.push(format!("=== Chapter {} ===\n{}", index + 1, chapter));
+        }
+        Ok(sections.join("\n\n"))
+    }
+
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read ebook file: {}", file_path.display()))?;
+        Self::read_metadata(&file_bytes)
+            .with_context(|| format!("Failed to read ebook metadata: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_mobi() {
+        let mut mobi_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        mobi_path.push("fixtures");
+        mobi_path.push("isthmus.mobi");
+
+        let result = MobiExtractor.extract_text_from_file(&mobi_path);
+        assert!(result.is_ok(), "Failed to extract MOBI: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Title: Notes from the Isthmus\n\n=== Chapter 1 ===\n"), "Got: {}", text);
+        assert!(text.contains("The flight to Panama City left at dawn."), "Got: {}", text);
+        assert!(text.contains("=== Chapter 2 ===\n# Chapter Two: Arrival"), "Got: {}", text);
+        assert!(text.contains("Café con leche followed."), "Non-ASCII text should survive. Got: {}", text);
+        assert!(!text.contains("=== Chapter 3 ==="), "Empty trailing chapters should be dropped. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_metadata_from_mobi() {
+        let mut mobi_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        mobi_path.push("fixtures");
+        mobi_path.push("isthmus.mobi");
+
+        let metadata = MobiExtractor.extract_metadata(&mobi_path).unwrap();
+        assert_eq!(
+            metadata,
+            DocumentMetadata {
+                title: Some("Notes from the Isthmus".to_string()),
+                author: Some("Thomas Plantin, Hugo Flores".to_string()),
+                created: Some("2025-08-30T00:00:00+00:00".to_string()),
+                modified: None,
+                page_count: None,
+                producer: Some("calibre (8.6.0) [https://calibre-ebook.com]".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_palmdoc_decompress() {
+        // "ab" literal, back-reference (distance 2, length 4), space pair " c", escaped 0xE9
+        let compressed = [b'a', b'b', 0x80, 0x11, 0xe3, 0x01, 0xe9];
+        assert_eq!(MobiExtractor::palmdoc_decompress(&compressed), b"ababab c\xe9");
+    }
+
+    #[test]
+    fn test_strip_trailing_entries() {
+        let record = [b'x', b'y', 0xaa, 0xbb, 0x83];
+        assert_eq!(MobiExtractor::strip_trailing_entries(&record, 0b10), b"xy");
+        assert_eq!(MobiExtractor::strip_trailing_entries(&[b'x', b'y', 0x01], 0b01), b"x");
+    }
+}
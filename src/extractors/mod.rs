@@ -1 +1,37 @@
+pub mod adoc_extractor;
+pub mod archive;
+pub mod code_extractor;
+pub mod csv_extractor;
+pub mod docx_extractor;
+pub mod eml_extractor;
+pub mod generic_extractor;
+pub mod html_extractor;
+pub mod ics_extractor;
+pub mod iwork;
+pub mod json_extractor;
+pub mod keynote_extractor;
+pub mod log_extractor;
+pub mod markdown_extractor;
+pub mod mbox_extractor;
+pub mod mobi_extractor;
+pub mod numbers_extractor;
+pub mod odf;
+pub mod odp_extractor;
+pub mod ods_extractor;
+pub mod onenote_extractor;
+pub mod pages_extractor;
+pub mod parquet_extractor;
 pub mod pdf_extractor;
+pub mod pdf_layout;
+pub mod png;
+pub mod pptx_extractor;
+pub mod subtitle_extractor;
+pub mod svg_extractor;
+pub mod toml_extractor;
+pub mod txt_extractor;
+pub mod vcf_extractor;
+pub mod warc_extractor;
+pub mod xml_extractor;
+pub mod xps_extractor;
+pub mod yaml_extractor;
+pub mod zip_extractor;
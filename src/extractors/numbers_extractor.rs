@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::iwork::{
+    proto_bytes, proto_fields, proto_varint, reference_id, IwaMessage, IworkPackage,
+};
+
+/// `TN.DocumentArchive`, which lists the sheets in order
+const DOCUMENT_ARCHIVE_TYPE: u32 = 1;
+/// `TN.SheetArchive`
+const SHEET_ARCHIVE_TYPE: u32 = 2;
+/// `TST.TableInfoArchive`, the drawable that places a table on a sheet
+const TABLE_INFO_ARCHIVE_TYPE: u32 = 6000;
+/// `TST.TableModelArchive`
+const TABLE_MODEL_ARCHIVE_TYPE: u32 = 6001;
+/// `TST.TableDataList`, a table's string (and other value) pool
+const TABLE_DATA_LIST_TYPE: u32 = 6005;
+/// `TST.TableDataList.ListType.STRING`
+const LIST_TYPE_STRING: u64 = 1;
+
+/// Apple Numbers (.numbers) extractor listing sheets, tables and their text cells
+///
+/// Text cells are read from each table's string pool. Numbers, dates and formula
+/// results live in binary tile storage and are not decoded.
+pub struct NumbersExtractor;
+
+impl NumbersExtractor {
+    /// Returns the first message of `type_id` stored for `object_id`
+    fn find<'a>(objects: &'a HashMap<u64, Vec<&IwaMessage>>, object_id: u64, type_id: u32) -> Option<&'a IwaMessage> {
+        objects.get(&object_id)?.iter().copied().find(|message| message.type_id == type_id)
+    }
+
+    /// Renders one table: its name, size and the strings of its string pool in key order
+    fn render_table(objects: &HashMap<u64, Vec<&IwaMessage>>, model: &IwaMessage) -> Result<String> {
+        let fields = proto_fields(&model.payload)?;
+        let name = proto_bytes(&fields, 8)
+            .first()
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .unwrap_or_else(|| "(unnamed table)".to_string());
+        let rows = proto_varint(&fields, 6).unwrap_or(0);
+        let columns = proto_varint(&fields, 7).unwrap_or(0);
+
+        // TableModelArchive.data_store (4) -> DataStore.stringTable (4)
+        let string_table = proto_bytes(&fields, 4)
+            .first()
+            .and_then(|data_store| proto_fields(data_store).ok())
+            .and_then(|data_store| proto_bytes(&data_store, 4).first().and_then(|r| reference_id(r)))
+            .and_then(|id| Self::find(objects, id, TABLE_DATA_LIST_TYPE));
+
+        let mut strings: Vec<(u64, String)> = Vec::new();
+        if let Some(list) = string_table {
+            let list_fields = proto_fields(&list.payload)?;
+            if proto_varint(&list_fields, 1) == Some(LIST_TYPE_STRING) {
+                for entry in proto_bytes(&list_fields, 3) {
+                    let entry = proto_fields(entry)?;
+                    let key = proto_varint(&entry, 1).unwrap_or(0);
+                    if let Some(text) = proto_bytes(&entry, 3).first() {
+                        strings.push((key, String::from_utf8_lossy(text).trim().to_string()));
+                    }
+                }
+            }
+        }
+        strings.sort_by_key(|(key, _)| *key);
+
+        let mut section = format!("Table: {} ({} rows x {} columns)", name, rows, columns);
+        if !strings.is_empty() {
+            let strings: Vec<String> = strings.into_iter().map(|(_, text)| text).filter(|t| !t.is_empty()).collect();
+            section.push_str(&format!("\nText cells: {}", strings.join(", ")));
+        }
+        Ok(section)
+    }
+
+    /// Renders a sheet and the tables placed on it
+    fn render_sheet(objects: &HashMap<u64, Vec<&IwaMessage>>, sheet: &IwaMessage) -> Result<String> {
+        let fields = proto_fields(&sheet.payload)?;
+        let name = proto_bytes(&fields, 1)
+            .first()
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .unwrap_or_default();
+
+        let mut lines = vec![format!("Sheet: {}", name)];
+        // SheetArchive.drawable_infos (2) -> TableInfoArchive.tableModel (2)
+        for drawable in proto_bytes(&fields, 2).into_iter().filter_map(reference_id) {
+            let Some(info) = Self::find(objects, drawable, TABLE_INFO_ARCHIVE_TYPE) else {
+                continue;
+            };
+            let info_fields = proto_fields(&info.payload)?;
+            let model = proto_bytes(&info_fields, 2)
+                .first()
+                .and_then(|r| reference_id(r))
+                .and_then(|id| Self::find(objects, id, TABLE_MODEL_ARCHIVE_TYPE));
+            if let Some(model) = model {
+                lines.push(Self::render_table(objects, model)?);
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Renders every sheet, in document order when the document archive lists them
+    fn render_document(messages: &[IwaMessage]) -> Result<String> {
+        let mut objects: HashMap<u64, Vec<&IwaMessage>> = HashMap::new();
+        for message in messages {
+            objects.entry(message.object_id).or_default().push(message);
+        }
+
+        let listed_sheets: Vec<u64> = match messages.iter().find(|m| m.type_id == DOCUMENT_ARCHIVE_TYPE) {
+            Some(document) => proto_bytes(&proto_fields(&document.payload)?, 1)
+                .into_iter()
+                .filter_map(reference_id)
+                .collect(),
+            None => Vec::new(),
+        };
+        let sheets: Vec<&IwaMessage> = if listed_sheets.is_empty() {
+            messages.iter().filter(|m| m.type_id == SHEET_ARCHIVE_TYPE).collect()
+        } else {
+            listed_sheets
+                .into_iter()
+                .filter_map(|id| Self::find(&objects, id, SHEET_ARCHIVE_TYPE))
+                .collect()
+        };
+
+        let sections = sheets
+            .into_iter()
+            .map(|sheet| Self::render_sheet(&objects, sheet))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(sections.join("\n\n"))
+    }
+}
+
+impl DocumentExtractor for NumbersExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "NumbersExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Numbers documents can also be package directories, so only the existence check applies
+        let mut package = IworkPackage::open(file_path)?;
+        let messages = package
+            .read_all_iwa()
+            .with_context(|| format!("Failed to read Numbers document: {}", file_path.display()))?;
+
+        if messages.is_empty() {
+            return package
+                .preview_pdf_text()
+                .with_context(|| format!("Failed to read preview PDF of: {}", file_path.display()))?
+                .ok_or_else(|| anyhow::anyhow!("No sheets or preview PDF found in: {}", file_path.display()));
+        }
+
+        Self::render_document(&messages)
+            .with_context(|| format!("Failed to decode Numbers document: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_numbers() {
+        let mut numbers_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        numbers_path.push("fixtures");
+        numbers_path.push("trips.numbers");
+
+        let result = NumbersExtractor.extract_text_from_file(&numbers_path);
+        assert!(result.is_ok(), "Failed to extract Numbers: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "Sheet: Summary
+
+Sheet: Trips 2025
+Table: Flights (3 rows x 2 columns)
+Text cells: Destination, Panama City, Bogota";
+        assert_eq!(text, expected);
+    }
+}
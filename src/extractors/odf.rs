@@ -0,0 +1,113 @@
+//! Shared helpers for OpenDocument (ODF) packages
+
+use std::fs::File;
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use zip::ZipArchive;
+use crate::extractor::{DocumentMetadata, EmbeddedImage};
+use crate::extractors::archive::{open_zip, property_elements, read_zip_entry, read_zip_images};
+
+/// Opens an OpenDocument package after checking its `mimetype` entry
+fn open_package(file_path: &Path, expected_mime_type: &str) -> Result<ZipArchive<File>> {
+    let mut archive = open_zip(file_path)?;
+
+    let mime_type = read_zip_entry(&mut archive, "mimetype")?.unwrap_or_default();
+    if mime_type.trim() != expected_mime_type {
+        return Err(anyhow::anyhow!(
+            "Unexpected OpenDocument type '{}' in {} (expected '{}')",
+            mime_type.trim(),
+            file_path.display(),
+            expected_mime_type
+        ));
+    }
+    Ok(archive)
+}
+
+/// Opens an OpenDocument package, checks its `mimetype` entry and returns `content.xml`
+pub fn read_content_xml(file_path: &Path, expected_mime_type: &str) -> Result<String> {
+    let mut archive = open_package(file_path, expected_mime_type)?;
+    read_zip_entry(&mut archive, "content.xml")?
+        .ok_or_else(|| anyhow::anyhow!("Missing content.xml in: {}", file_path.display()))
+}
+
+/// Reads the document metadata from `meta.xml`; a package without it has none
+///
+/// The author is the initial creator (`dc:creator` holds whoever saved last).
+/// The page count comes from the `meta:document-statistic` element, when present.
+pub fn read_metadata(file_path: &Path, expected_mime_type: &str) -> Result<DocumentMetadata> {
+    let mut archive = open_package(file_path, expected_mime_type)?;
+    let Some(xml) = read_zip_entry(&mut archive, "meta.xml")? else {
+        return Ok(DocumentMetadata::default());
+    };
+
+    let mut properties = property_elements(&xml)
+        .with_context(|| format!("Failed to parse meta.xml in: {}", file_path.display()))?;
+    let mut page_count = None;
+    let mut reader = Reader::from_str(&xml);
+    loop {
+        match reader.read_event().context("Failed to parse meta.xml")? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"document-statistic" => {
+                page_count = usize_attribute(&e, b"page-count");
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(DocumentMetadata {
+        title: properties.remove("title"),
+        author: properties.remove("initial-creator").or_else(|| properties.remove("creator")),
+        created: properties.remove("creation-date"),
+        modified: properties.remove("date"),
+        page_count,
+        producer: properties.remove("generator"),
+    })
+}
+
+/// Opens an OpenDocument package, checks its `mimetype` entry and returns the raster
+/// images stored in `Pictures/`
+pub fn read_pictures(file_path: &Path, expected_mime_type: &str) -> Result<Vec<EmbeddedImage>> {
+    let mut archive = open_package(file_path, expected_mime_type)?;
+    read_zip_images(&mut archive, "Pictures/")
+        .with_context(|| format!("Failed to read pictures from: {}", file_path.display()))
+}
+
+/// Reads an integer attribute such as `table:number-columns-repeated`
+pub fn usize_attribute(e: &BytesStart, local_name: &[u8]) -> Option<usize> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == local_name)
+        .and_then(|attr| attr.unescape_value().ok()?.parse().ok())
+}
+
+/// Reads a string attribute by local name, ignoring the namespace prefix
+pub fn string_attribute(e: &BytesStart, local_name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == local_name)
+        .and_then(|attr| Some(attr.unescape_value().ok()?.into_owned()))
+}
+
+/// Appends the text equivalent of ODF whitespace elements (`text:s`, `text:tab`, `text:line-break`)
+///
+/// Returns `true` when the element was one of them.
+pub fn push_whitespace_element(e: &BytesStart, text: &mut String) -> bool {
+    match e.local_name().as_ref() {
+        b"s" => {
+            let count = usize_attribute(e, b"c").unwrap_or(1);
+            text.push_str(&" ".repeat(count));
+            true
+        }
+        b"tab" => {
+            text.push('\t');
+            true
+        }
+        b"line-break" => {
+            text.push('\n');
+            true
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use crate::constants::ODP_MIME_TYPE;
+use crate::extractor::{DocumentExtractor, DocumentMetadata, EmbeddedImage};
+use crate::extractors::odf::{push_whitespace_element, read_content_xml, read_metadata, read_pictures, string_attribute};
+
+/// Presentation classes that only carry slide chrome
+const IGNORED_CLASSES: &[&str] = &["page-number", "date-time", "footer", "header"];
+
+/// OpenDocument presentation (.odp) extractor that labels each slide and includes speaker notes
+pub struct OdpExtractor;
+
+/// A parsed slide (`draw:page`)
+#[derive(Debug, Default)]
+struct Slide {
+    title: Vec<String>,
+    body: Vec<String>,
+    notes: Vec<String>,
+}
+
+impl OdpExtractor {
+    /// Parses `content.xml` of a presentation into slides
+    fn parse_slides(xml: &str) -> Result<Vec<Slide>> {
+        let mut reader = Reader::from_str(xml);
+        let mut slides = Vec::new();
+        let mut slide: Option<Slide> = None;
+        let mut frame_class: Option<String> = None;
+        let mut in_notes = false;
+        let mut paragraph: Option<String> = None;
+
+        loop {
+            match reader.read_event().context("Failed to parse presentation content.xml")? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"page" => slide = Some(Slide::default()),
+                    b"frame" => frame_class = string_attribute(&e, b"class"),
+                    b"notes" => in_notes = true,
+                    b"p" | b"h" => paragraph = Some(String::new()),
+                    _ => {}
+                },
+                Event::Empty(e) => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        push_whitespace_element(&e, paragraph);
+                    }
+                }
+                Event::Text(e) => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.push_str(&e.unescape()?);
+                    }
+                }
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"p" | b"h" => {
+                        let text = paragraph.take().unwrap_or_default();
+                        let text = text.trim();
+                        let class = frame_class.as_deref().unwrap_or("");
+                        if let Some(slide) = slide.as_mut().filter(|_| !text.is_empty()) {
+                            if in_notes {
+                                if class == "notes" {
+                                    slide.notes.push(text.to_string());
+                                }
+                            } else if class == "title" {
+                                slide.title.push(text.to_string());
+                            } else if !IGNORED_CLASSES.contains(&class) {
+                                slide.body.push(text.to_string());
+                            }
+                        }
+                    }
+                    b"frame" => frame_class = None,
+                    b"notes" => in_notes = false,
+                    b"page" => {
+                        if let Some(finished) = slide.take() {
+                            slides.push(finished);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(slides)
+    }
+}
+
+impl DocumentExtractor for OdpExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "OdpExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let content = read_content_xml(file_path, ODP_MIME_TYPE)?;
+        let slides = Self::parse_slides(&content)
+            .with_context(|| format!("Failed to parse presentation: {}", file_path.display()))?;
+
+        let sections: Vec<String> = slides
+            .iter()
+            .enumerate()
+            .map(|(index, slide)| {
+                let mut section = if slide.title.is_empty() {
+                    format!("Slide {}", index + 1)
+                } else {
+                    format!("Slide {}: {}", index + 1, slide.title.join(" "))
+                };
+                for paragraph in &slide.body {
+                    section.push('\n');
+                    section.push_str(paragraph);
+                }
+                if !slide.notes.is_empty() {
+                    section.push_str("\n\nSpeaker notes:\n");
+                    section.push_str(&slide.notes.join("\n"));
+                }
+                section
+            })
+            .collect();
+
+        Ok(sections.join("\n\n"))
+    }
+
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        read_pictures(file_path, ODP_MIME_TYPE)
+    }
+
+    /// Reads `meta.xml`; the slide count comes from the slides themselves
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let mut metadata = read_metadata(file_path, ODP_MIME_TYPE)?;
+        let content = read_content_xml(file_path, ODP_MIME_TYPE)?;
+        let slides = Self::parse_slides(&content)
+            .with_context(|| format!("Failed to parse presentation: {}", file_path.display()))?;
+        metadata.page_count = Some(slides.len());
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_odp() {
+        let mut odp_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        odp_path.push("fixtures");
+        odp_path.push("slides.odp");
+
+        let result = OdpExtractor.extract_text_from_file(&odp_path);
+        assert!(result.is_ok(), "Failed to extract ODP: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Slide 1: Team Offsite\n"), "First slide should be labeled. Got: {}", text);
+        assert!(text.contains("Agenda\tDay 1"), "Tabs should be preserved. Got: {}", text);
+        assert!(text.contains("Speaker notes:\nWelcome everyone."), "Notes should be included. Got: {}", text);
+        assert!(text.contains("\n\nSlide 2\nWrap-up"), "Untitled slides should still be labeled. Got: {}", text);
+        assert!(!text.contains("<number>"), "Page number fields should be skipped. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_metadata_from_odp() {
+        let mut odp_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        odp_path.push("fixtures");
+        odp_path.push("slides.odp");
+
+        // The package has no meta.xml; only the slide count is known
+        let metadata = OdpExtractor.extract_metadata(&odp_path).unwrap();
+        assert_eq!(metadata, DocumentMetadata { page_count: Some(2), ..Default::default() });
+    }
+}
@@ -0,0 +1,300 @@
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use crate::constants::ODS_MIME_TYPE;
+use crate::extractor::{DocumentExtractor, DocumentMetadata, EmbeddedImage};
+use crate::extractors::csv_extractor::CsvExtractor;
+use crate::extractors::odf::{
+    push_whitespace_element, read_content_xml, read_metadata, read_pictures, string_attribute, usize_attribute,
+};
+
+/// Upper bound for `number-rows-repeated` / `number-columns-repeated` expansion
+///
+/// Spreadsheets routinely mark the remaining million empty rows as one repeated row.
+const MAX_REPEAT: usize = 1024;
+
+/// OpenDocument spreadsheet (.ods) extractor rendering each sheet as an aligned table
+pub struct OdsExtractor {
+    /// Show each formula next to its computed value, e.g. `1140 [=SUM(B2:B3)]`
+    include_formulas: bool,
+}
+
+/// A parsed sheet: its name and rows of cell text
+#[derive(Debug, Default)]
+struct Sheet {
+    name: String,
+    rows: Vec<Vec<String>>,
+}
+
+impl Default for OdsExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OdsExtractor {
+    /// Creates an ODS extractor that shows computed values only
+    pub fn new() -> Self {
+        Self { include_formulas: false }
+    }
+
+    /// Enables or disables showing formulas alongside the computed values
+    pub fn with_formulas(mut self, include_formulas: bool) -> Self {
+        self.include_formulas = include_formulas;
+        self
+    }
+
+    /// Converts an OpenFormula expression to the notation shown in spreadsheet apps
+    ///
+    /// `of:=SUM([.B2:.B3])` becomes `=SUM(B2:B3)` and `[$Rates.A1]` becomes `$Rates.A1`.
+    fn display_formula(formula: &str) -> String {
+        // Drop the namespace prefix (`of:`, `oooc:`, `msoxl:`)
+        let formula = match formula.split_once(':') {
+            Some((prefix, rest)) if !prefix.contains(['=', '[', '"']) => rest,
+            _ => formula,
+        };
+
+        let mut output = String::with_capacity(formula.len());
+        let mut chars = formula.chars();
+        let mut in_string = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    in_string = !in_string;
+                    output.push(c);
+                }
+                '[' if !in_string => {
+                    let reference: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                    let parts: Vec<&str> = reference.split(':').map(|part| part.trim_start_matches('.')).collect();
+                    output.push_str(&parts.join(":"));
+                }
+                _ => output.push(c),
+            }
+        }
+        output
+    }
+
+    /// Parses `content.xml` of a spreadsheet into sheets
+    fn parse_sheets(&self, xml: &str) -> Result<Vec<Sheet>> {
+        let mut reader = Reader::from_str(xml);
+        let mut sheets = Vec::new();
+        let mut sheet: Option<Sheet> = None;
+        let mut row: Vec<String> = Vec::new();
+        let mut row_repeat = 1;
+        let mut cell: Option<(String, usize)> = None;
+        let mut formula: Option<String> = None;
+        let mut in_paragraph = false;
+
+        loop {
+            match reader.read_event().context("Failed to parse spreadsheet content.xml")? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"table" => {
+                        sheet = Some(Sheet {
+                            name: string_attribute(&e, b"name").unwrap_or_default(),
+                            rows: Vec::new(),
+                        })
+                    }
+                    b"table-row" => {
+                        row.clear();
+                        row_repeat = usize_attribute(&e, b"number-rows-repeated").unwrap_or(1);
+                    }
+                    b"table-cell" | b"covered-table-cell" => {
+                        let repeat = usize_attribute(&e, b"number-columns-repeated").unwrap_or(1);
+                        cell = Some((String::new(), repeat));
+                        formula = string_attribute(&e, b"formula").filter(|_| self.include_formulas);
+                    }
+                    b"p" if cell.is_some() => {
+                        if let Some((text, _)) = cell.as_mut() {
+                            if !text.is_empty() {
+                                text.push(' ');
+                            }
+                        }
+                        in_paragraph = true;
+                    }
+                    _ => {}
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"table-cell" | b"covered-table-cell" => {
+                        let repeat = usize_attribute(&e, b"number-columns-repeated").unwrap_or(1);
+                        row.extend(std::iter::repeat_n(String::new(), repeat.min(MAX_REPEAT)));
+                    }
+                    _ => {
+                        if let Some((text, _)) = cell.as_mut().filter(|_| in_paragraph) {
+                            push_whitespace_element(&e, text);
+                        }
+                    }
+                },
+                Event::Text(e) if in_paragraph => {
+                    if let Some((text, _)) = cell.as_mut() {
+                        text.push_str(&e.unescape()?);
+                    }
+                }
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"p" => in_paragraph = false,
+                    b"table-cell" | b"covered-table-cell" => {
+                        if let Some((mut text, repeat)) = cell.take() {
+                            if let Some(formula) = formula.take() {
+                                text = format!("{} [{}]", text.trim(), Self::display_formula(&formula));
+                            }
+                            let text = CsvExtractor::clean_cell(&text);
+                            row.extend(std::iter::repeat_n(text, repeat.min(MAX_REPEAT)));
+                        }
+                    }
+                    b"table-row" => {
+                        while row.last().is_some_and(|cell| cell.trim().is_empty()) {
+                            row.pop();
+                        }
+                        if let Some(sheet) = sheet.as_mut() {
+                            for _ in 0..row_repeat.min(MAX_REPEAT) {
+                                sheet.rows.push(row.clone());
+                            }
+                        }
+                    }
+                    b"table" => {
+                        if let Some(mut finished) = sheet.take() {
+                            while finished.rows.last().is_some_and(Vec::is_empty) {
+                                finished.rows.pop();
+                            }
+                            sheets.push(finished);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(sheets)
+    }
+}
+
+impl DocumentExtractor for OdsExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "OdsExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let content = read_content_xml(file_path, ODS_MIME_TYPE)?;
+        let sheets = self.parse_sheets(&content)
+            .with_context(|| format!("Failed to parse spreadsheet: {}", file_path.display()))?;
+
+        let sections: Vec<String> = sheets
+            .iter()
+            .map(|sheet| {
+                if sheet.rows.is_empty() {
+                    format!("Sheet: {}\n(empty)", sheet.name)
+                } else {
+                    format!("Sheet: {}\n{}", sheet.name, CsvExtractor::render_table(&sheet.rows))
+                }
+            })
+            .collect();
+
+        Ok(sections.join("\n\n"))
+    }
+
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        read_pictures(file_path, ODS_MIME_TYPE)
+    }
+
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        read_metadata(file_path, ODS_MIME_TYPE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_ods() {
+        let mut ods_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        ods_path.push("fixtures");
+        ods_path.push("budget.ods");
+
+        let result = OdsExtractor::new().extract_text_from_file(&ods_path);
+        assert!(result.is_ok(), "Failed to extract ODS: {:?}", result.err());
+
+        let text = result.unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Sheet: Expenses");
+        assert_eq!(lines[1], "Item    | Amount");
+        assert_eq!(lines[3], "Flights | 840");
+        assert_eq!(lines[4], "Hotel   | 300");
+        assert_eq!(lines[5], "Total   | 1140");
+        assert!(text.contains("\n\nSheet: Notes\nPaid by card"), "Second sheet should follow. Got: {}", text);
+        assert_eq!(text.lines().count(), 10, "Repeated empty rows should be dropped. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_text_from_ods_with_formulas() {
+        let mut ods_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        ods_path.push("fixtures");
+        ods_path.push("budget.ods");
+
+        let text = OdsExtractor::new().with_formulas(true).extract_text_from_file(&ods_path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[3], "Flights | 840");
+        assert_eq!(lines[5], "Total   | 1140 [=SUM(B2:B3)]");
+    }
+
+    #[test]
+    fn test_extract_metadata_from_ods() {
+        let mut ods_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        ods_path.push("fixtures");
+        ods_path.push("budget.ods");
+
+        let metadata = OdsExtractor::new().extract_metadata(&ods_path).unwrap();
+        assert_eq!(
+            metadata,
+            DocumentMetadata {
+                title: Some("Trip budget".to_string()),
+                author: Some("Thomas Plantin".to_string()),
+                created: Some("2025-08-18T10:12:00".to_string()),
+                modified: Some("2025-08-27T21:03:44".to_string()),
+                page_count: Some(2),
+                producer: Some("LibreOffice/24.8.0.3$Linux_X86_64 LibreOffice_project/0bdf1299c94fe897b119f97f3c613e9dca6be583".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_display_formula() {
+        assert_eq!(OdsExtractor::display_formula("of:=SUM([.B2:.B3])"), "=SUM(B2:B3)");
+        assert_eq!(OdsExtractor::display_formula("of:=[.A1]*[$Rates.$B$2]"), "=A1*$Rates.$B$2");
+        assert_eq!(OdsExtractor::display_formula("of:=IF([.A1]>0;\"[ok]\";\"no\")"), "=IF(A1>0;\"[ok]\";\"no\")");
+        assert_eq!(OdsExtractor::display_formula("=A1+1"), "=A1+1");
+    }
+
+    #[test]
+    fn test_extract_text_rejects_other_opendocument_types() {
+        let mut odp_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        odp_path.push("fixtures");
+        odp_path.push("slides.odp");
+
+        let result = OdsExtractor::new().extract_text_from_file(&odp_path);
+        assert!(result.is_err(), "A presentation should not be read as a spreadsheet");
+    }
+}
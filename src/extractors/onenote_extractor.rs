@@ -0,0 +1,459 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use encoding_rs::WINDOWS_1252;
+use crate::extractor::DocumentExtractor;
+
+/// `guidFileType` of OneNote section files (`.one`), stored little-endian
+pub const ONE_FILE_TYPE: [u8; 16] = [
+    0xe4, 0x52, 0x5c, 0x7b, 0x8c, 0xd8, 0xa7, 0x4d, 0xae, 0xb1, 0x53, 0x78, 0xd0, 0x29, 0x96, 0xd3,
+];
+
+/// `guidFileType` of OneNote table of contents files (`.onetoc2`)
+const ONETOC2_FILE_TYPE: [u8; 16] = [
+    0xa1, 0x2f, 0xff, 0x43, 0xd9, 0xef, 0x76, 0x4c, 0x9e, 0xe2, 0x10, 0xea, 0x57, 0x22, 0x76, 0x5f,
+];
+
+/// Offset of `fcrFileNodeListRoot` in the file header
+const ROOT_LIST_OFFSET: usize = 172;
+
+/// Markers at the start and end of every file node list fragment
+const FRAGMENT_MAGIC: u64 = 0xa456_7ab1_f5f7_f4c4;
+const FRAGMENT_FOOTER: u64 = 0x8bc2_15c3_8233_ba4b;
+
+/// Size of a fragment header (magic, list ID, sequence) and trailer (next fragment, footer)
+const FRAGMENT_HEADER_LEN: usize = 16;
+const FRAGMENT_TRAILER_LEN: usize = 20;
+
+/// File node IDs the extractor acts on
+const OBJECT_SPACE_MANIFEST_LIST_REFERENCE: u16 = 0x008;
+const GLOBAL_ID_TABLE_START: u16 = 0x021;
+const GLOBAL_ID_TABLE_START_2: u16 = 0x022;
+const GLOBAL_ID_TABLE_ENTRY: u16 = 0x024;
+const OBJECT_DECLARATION_2_REF_COUNT: u16 = 0x0a4;
+const OBJECT_DECLARATION_2_LARGE_REF_COUNT: u16 = 0x0a5;
+const READ_ONLY_OBJECT_DECLARATION_2_REF_COUNT: u16 = 0x0c4;
+const READ_ONLY_OBJECT_DECLARATION_2_LARGE_REF_COUNT: u16 = 0x0c5;
+const CHUNK_TERMINATOR: u16 = 0x0ff;
+
+/// File node base types: the node references a data chunk, or another file node list
+const BASE_TYPE_DATA: u32 = 1;
+const BASE_TYPE_LIST: u32 = 2;
+
+/// Nesting of file node lists is at most four levels deep in valid files
+const MAX_LIST_DEPTH: usize = 8;
+
+/// `jcidPageMetaData`, the object holding a page's cached title
+const JCID_PAGE_METADATA: u32 = 0x0002_0030;
+
+/// Property IDs read from objects
+const CACHED_TITLE_STRING: u32 = 0x1c00_1cf3;
+const RICH_EDIT_TEXT_UNICODE: u32 = 0x1c00_1c22;
+const TEXT_EXTENDED_ASCII: u32 = 0x1c00_3498;
+
+/// Property type carrying a length-prefixed value, the only one holding text
+const PROPERTY_TYPE_BYTES: u32 = 0x7;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset + 8).map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+}
+
+/// An entry of a file node list
+struct FileNode<'a> {
+    id: u16,
+    base_type: u32,
+    /// The chunk referenced by data and list nodes
+    reference: Option<(u64, u64)>,
+    /// Node content after the reference
+    body: &'a [u8],
+}
+
+/// An object declared in an object space: its type (JCID) and its text-valued properties
+struct OneNoteObject {
+    jcid: u32,
+    properties: Vec<(u32, Vec<u8>)>,
+}
+
+impl OneNoteObject {
+    fn property(&self, id: u32) -> Option<&[u8]> {
+        self.properties.iter().find(|(prid, _)| *prid == id).map(|(_, value)| value.as_slice())
+    }
+
+    /// Returns the paragraph text of rich text objects
+    fn text(&self) -> Option<String> {
+        let text = if let Some(unicode) = self.property(RICH_EDIT_TEXT_UNICODE) {
+            utf16_string(unicode)
+        } else {
+            WINDOWS_1252.decode_without_bom_handling(self.property(TEXT_EXTENDED_ASCII)?).0.into_owned()
+        };
+        // Vertical tabs are soft line breaks inside a paragraph
+        let text = text.trim_end_matches('\0').replace('\u{b}', "\n");
+        Some(text).filter(|text| !text.trim().is_empty())
+    }
+}
+
+fn utf16_string(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// The objects of one object space, keyed by their extended GUID; later revisions
+/// replace the objects they redeclare
+#[derive(Default)]
+struct ObjectSpace {
+    objects: Vec<OneNoteObject>,
+    index: HashMap<([u8; 16], u8), usize>,
+}
+
+impl ObjectSpace {
+    fn declare(&mut self, id: ([u8; 16], u8), object: OneNoteObject) {
+        match self.index.get(&id) {
+            Some(&position) => self.objects[position] = object,
+            None => {
+                self.index.insert(id, self.objects.len());
+                self.objects.push(object);
+            }
+        }
+    }
+}
+
+/// OneNote section (.one) extractor that returns every page with its title and paragraphs
+///
+/// Walks the revision store (MS-ONESTORE) file node lists and reads the rich text
+/// objects of each page's object space, in declaration order. Pages are listed in
+/// file order, which usually but not always matches their order in OneNote. Images,
+/// ink and embedded files are skipped, and table of contents files (`.onetoc2`) and
+/// the older OneNote 2007 format are not supported.
+pub struct OneNoteExtractor;
+
+impl OneNoteExtractor {
+    /// Returns the chunk a file chunk reference points to
+    fn chunk(data: &[u8], (offset, size): (u64, u64)) -> Result<&[u8]> {
+        let start = usize::try_from(offset).ok();
+        let end = start.zip(usize::try_from(size).ok()).and_then(|(start, size)| start.checked_add(size));
+        start
+            .zip(end)
+            .and_then(|(start, end)| data.get(start..end))
+            .with_context(|| format!("Chunk at offset {} ({} bytes) is out of bounds", offset, size))
+    }
+
+    /// Reads a file node, returning it and its size
+    fn file_node(data: &[u8]) -> Option<(FileNode<'_>, usize)> {
+        let header = read_u32(data, 0)?;
+        let size = ((header >> 10) & 0x1fff) as usize;
+        let node = data.get(4..size)?;
+        let base_type = (header >> 27) & 0xf;
+
+        let mut body = node;
+        let mut reference = None;
+        if base_type == BASE_TYPE_DATA || base_type == BASE_TYPE_LIST {
+            // Offsets and sizes come in several widths; compressed ones count 8-byte units
+            let (offset, offset_len) = match (header >> 23) & 0x3 {
+                0 => (read_u64(node, 0)?, 8),
+                1 => (read_u32(node, 0)? as u64, 4),
+                2 => (read_u16(node, 0)? as u64 * 8, 2),
+                _ => (read_u32(node, 0)? as u64 * 8, 4),
+            };
+            let (size, size_len) = match (header >> 25) & 0x3 {
+                0 => (read_u32(node, offset_len)? as u64, 4),
+                1 => (read_u64(node, offset_len)?, 8),
+                2 => (*node.get(offset_len)? as u64 * 8, 1),
+                _ => (read_u16(node, offset_len)? as u64 * 8, 2),
+            };
+            reference = Some((offset, size));
+            body = node.get(offset_len + size_len..)?;
+        }
+        let node = FileNode { id: (header & 0x3ff) as u16, base_type, reference, body };
+        Some((node, size))
+    }
+
+    /// Reads the nodes of a file node list, following its fragments
+    fn file_nodes(data: &[u8], reference: (u64, u64)) -> Result<Vec<FileNode<'_>>> {
+        let mut nodes = Vec::new();
+        let mut next = Some(reference);
+        let mut visited = HashSet::new();
+
+        while let Some(reference) = next.take().filter(|reference| visited.insert(*reference)) {
+            let fragment = Self::chunk(data, reference)?;
+            if fragment.len() < FRAGMENT_HEADER_LEN + FRAGMENT_TRAILER_LEN
+                || read_u64(fragment, 0) != Some(FRAGMENT_MAGIC)
+                || read_u64(fragment, fragment.len() - 8) != Some(FRAGMENT_FOOTER)
+            {
+                return Err(anyhow::anyhow!("Invalid file node list fragment at offset {}", reference.0));
+            }
+
+            let trailer = fragment.len() - FRAGMENT_TRAILER_LEN;
+            let mut position = FRAGMENT_HEADER_LEN;
+            while let Some((node, size)) = Self::file_node(&fragment[position..trailer]) {
+                // Zero bytes pad the rest of the fragment
+                if node.id == 0 || size < 4 {
+                    break;
+                }
+                position += size;
+                if node.id == CHUNK_TERMINATOR {
+                    break;
+                }
+                nodes.push(node);
+            }
+
+            // The last fragment points nowhere (`fcrNil`)
+            let next_offset = read_u64(fragment, trailer).unwrap_or(u64::MAX);
+            let next_size = read_u32(fragment, trailer + 8).unwrap_or_default() as u64;
+            if next_offset != u64::MAX && next_size > 0 {
+                next = Some((next_offset, next_size));
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// Skips the data of one property in a property set's data stream
+    fn skip_property(prid: u32, data: &[u8], position: &mut usize) -> Result<()> {
+        let property_type = (prid >> 26) & 0x1f;
+        let count = |position: usize| read_u32(data, position).context("Truncated property set");
+        match property_type {
+            0x3 => *position += 1,
+            0x4 => *position += 2,
+            0x5 => *position += 4,
+            0x6 => *position += 8,
+            // Arrays of object, object space and context IDs: the IDs are in separate streams
+            0x9 | 0xb | 0xd => *position += 4,
+            0x7 => *position += 4 + count(*position)? as usize,
+            0x10 => {
+                let property_sets = count(*position)?;
+                *position += 4;
+                if property_sets > 0 {
+                    // The property ID of the elements, then the elements
+                    *position += 4;
+                    for _ in 0..property_sets {
+                        Self::property_set(data, position)?;
+                    }
+                }
+            }
+            0x11 => {
+                Self::property_set(data, position)?;
+            }
+            // No data, booleans (stored in the ID) and single IDs
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reads a property set, returning its length-prefixed values
+    fn property_set(data: &[u8], position: &mut usize) -> Result<Vec<(u32, Vec<u8>)>> {
+        let count = read_u16(data, *position).context("Truncated property set")? as usize;
+        let prids = (0..count)
+            .map(|index| read_u32(data, *position + 2 + index * 4))
+            .collect::<Option<Vec<_>>>()
+            .context("Truncated property set")?;
+        *position += 2 + count * 4;
+
+        let mut values = Vec::new();
+        for prid in prids {
+            if (prid >> 26) & 0x1f == PROPERTY_TYPE_BYTES {
+                let length = read_u32(data, *position).context("Truncated property set")? as usize;
+                let value = data
+                    .get(*position + 4..*position + 4 + length)
+                    .context("Truncated property value")?;
+                values.push((prid & 0x7fff_ffff, value.to_vec()));
+            }
+            Self::skip_property(prid, data, position)?;
+        }
+        Ok(values)
+    }
+
+    /// Reads the properties of an `ObjectSpaceObjectPropSet`, skipping its ID streams
+    fn object_properties(data: &[u8]) -> Result<Vec<(u32, Vec<u8>)>> {
+        let mut position = 0;
+        let mut more_streams = true;
+        // OIDs stream, then the optional OSIDs and ContextIDs streams
+        for stream in 0..3 {
+            if !more_streams {
+                break;
+            }
+            let header = read_u32(data, position).context("Truncated object stream header")?;
+            position += 4 + (header & 0x00ff_ffff) as usize * 4;
+            more_streams = if stream == 0 { header & 0x8000_0000 == 0 } else { header & 0x4000_0000 != 0 };
+        }
+        Self::property_set(data, &mut position)
+    }
+
+    /// Walks a file node list and the lists it references, declaring objects in `spaces`
+    fn walk(
+        data: &[u8],
+        reference: (u64, u64),
+        space: Option<usize>,
+        spaces: &mut Vec<ObjectSpace>,
+        depth: usize,
+    ) -> Result<()> {
+        if depth > MAX_LIST_DEPTH {
+            return Err(anyhow::anyhow!("File node lists are nested too deeply"));
+        }
+
+        // Compact object IDs index into the global ID table of their list
+        let mut global_ids: HashMap<u32, [u8; 16]> = HashMap::new();
+        for node in Self::file_nodes(data, reference)? {
+            match node.id {
+                GLOBAL_ID_TABLE_START | GLOBAL_ID_TABLE_START_2 => global_ids.clear(),
+                GLOBAL_ID_TABLE_ENTRY => {
+                    if let (Some(index), Some(guid)) = (read_u32(node.body, 0), node.body.get(4..20)) {
+                        global_ids.insert(index, guid.try_into().unwrap());
+                    }
+                }
+                OBJECT_DECLARATION_2_REF_COUNT
+                | OBJECT_DECLARATION_2_LARGE_REF_COUNT
+                | READ_ONLY_OBJECT_DECLARATION_2_REF_COUNT
+                | READ_ONLY_OBJECT_DECLARATION_2_LARGE_REF_COUNT => {
+                    let (Some(space), Some(reference)) = (space, node.reference) else {
+                        continue;
+                    };
+                    let (Some(compact_id), Some(jcid)) = (read_u32(node.body, 0), read_u32(node.body, 4)) else {
+                        continue;
+                    };
+                    let guid = global_ids.get(&(compact_id >> 8)).copied().unwrap_or_default();
+                    let properties = Self::object_properties(Self::chunk(data, reference)?)?;
+                    spaces[space].declare((guid, compact_id as u8), OneNoteObject { jcid, properties });
+                }
+                OBJECT_SPACE_MANIFEST_LIST_REFERENCE if node.base_type == BASE_TYPE_LIST => {
+                    spaces.push(ObjectSpace::default());
+                    let child = spaces.len() - 1;
+                    Self::walk(data, node.reference.unwrap_or_default(), Some(child), spaces, depth + 1)?;
+                }
+                _ if node.base_type == BASE_TYPE_LIST => {
+                    Self::walk(data, node.reference.unwrap_or_default(), space, spaces, depth + 1)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the title and paragraphs of every page of a section
+    pub fn read_pages(data: &[u8]) -> Result<Vec<(String, Vec<String>)>> {
+        match data.get(..16) {
+            Some(file_type) if file_type == ONE_FILE_TYPE => {}
+            Some(file_type) if file_type == ONETOC2_FILE_TYPE => {
+                return Err(anyhow::anyhow!("OneNote table of contents files (.onetoc2) hold no page content"));
+            }
+            _ => return Err(anyhow::anyhow!("Not a OneNote 2010 or later section file")),
+        }
+        let root = (
+            read_u64(data, ROOT_LIST_OFFSET).context("Truncated file header")?,
+            read_u32(data, ROOT_LIST_OFFSET + 8).context("Truncated file header")? as u64,
+        );
+
+        let mut spaces = Vec::new();
+        Self::walk(data, root, None, &mut spaces, 0)?;
+
+        let mut pages = Vec::new();
+        for space in spaces {
+            let title = space
+                .objects
+                .iter()
+                .filter(|object| object.jcid == JCID_PAGE_METADATA)
+                .find_map(|object| object.property(CACHED_TITLE_STRING))
+                .map(|title| utf16_string(title).trim_end_matches('\0').trim().to_string())
+                .unwrap_or_default();
+            let mut paragraphs: Vec<String> = space.objects.iter().filter_map(OneNoteObject::text).collect();
+            // The title is also the first paragraph of the page
+            if !title.is_empty() && paragraphs.first().is_some_and(|first| first.trim() == title) {
+                paragraphs.remove(0);
+            }
+
+            // The section itself and pages without text have nothing to show
+            if !title.is_empty() || !paragraphs.is_empty() {
+                pages.push((title, paragraphs));
+            }
+        }
+        Ok(pages)
+    }
+}
+
+impl DocumentExtractor for OneNoteExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "OneNoteExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let data = fs::read(file_path)
+            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+        let pages = Self::read_pages(&data)
+            .with_context(|| format!("Failed to read OneNote section: {}", file_path.display()))?;
+
+        let sections: Vec<String> = pages
+            .iter()
+            .enumerate()
+            .map(|(index, (title, paragraphs))| {
+                let mut section = if title.is_empty() {
+                    format!("Page {}", index + 1)
+                } else {
+                    format!("Page {}: {}", index + 1, title)
+                };
+                for paragraph in paragraphs {
+                    section.push('\n');
+                    section.push_str(paragraph);
+                }
+                section
+            })
+            .collect();
+
+        Ok(sections.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn one_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("travel.one");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_one() {
+        let result = OneNoteExtractor.extract_text_from_file(&one_path());
+        assert!(result.is_ok(), "Failed to extract OneNote section: {:?}", result.err());
+
+        assert_eq!(
+            result.unwrap(),
+            "Page 1: Packing list\n\
+             Passport and boarding pass\n\
+             Adapter for type A plugs\nand a spare cable\n\n\
+             Page 2: Panama City\n\
+             Casco Viejo walking tour on Saturday\n\
+             Canal visitor center opens at 8:00 – café on site"
+        );
+    }
+
+    #[test]
+    fn test_extract_text_from_non_onenote_file() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("sample_utf8.txt");
+
+        let error = format!("{:#}", OneNoteExtractor.extract_text_from_file(&path).unwrap_err());
+        assert!(error.contains("Not a OneNote 2010 or later section file"), "Got: {}", error);
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::iwork::{
+    storage_text, IworkPackage, STORAGE_KIND_BODY, STORAGE_KIND_FOOTNOTE, STORAGE_KIND_TEXTBOX,
+};
+
+/// Apple Pages (.pages) extractor reading the document's IWA archive
+///
+/// The body text comes first, followed by text boxes and footnotes. Headers and
+/// footers are skipped since they repeat on every page. Documents from older Pages
+/// versions without IWA data fall back to their embedded preview PDF.
+pub struct PagesExtractor;
+
+impl PagesExtractor {
+    /// Collects the text storages of `Index/Document.iwa`, body first
+    fn document_text(package: &mut IworkPackage) -> Result<Option<String>> {
+        let Some(messages) = package.read_iwa("Index/Document.iwa")? else {
+            return Ok(None);
+        };
+
+        let mut body = Vec::new();
+        let mut extras = Vec::new();
+        for message in &messages {
+            let Some((kind, text)) = storage_text(message)? else {
+                continue;
+            };
+            if text.is_empty() {
+                continue;
+            }
+            match kind {
+                STORAGE_KIND_BODY => body.push(text),
+                STORAGE_KIND_TEXTBOX | STORAGE_KIND_FOOTNOTE => extras.push(text),
+                _ => {}
+            }
+        }
+
+        body.extend(extras);
+        Ok(Some(body.join("\n\n")))
+    }
+}
+
+impl DocumentExtractor for PagesExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "PagesExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Pages documents can also be package directories, so only the existence check applies
+        let mut package = IworkPackage::open(file_path)?;
+        let text = Self::document_text(&mut package)
+            .with_context(|| format!("Failed to read Pages document: {}", file_path.display()))?;
+
+        match text {
+            Some(text) => Ok(text),
+            None => package
+                .preview_pdf_text()
+                .with_context(|| format!("Failed to read preview PDF of: {}", file_path.display()))?
+                .ok_or_else(|| anyhow::anyhow!("No document text or preview PDF found in: {}", file_path.display())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_pages() {
+        let mut pages_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pages_path.push("fixtures");
+        pages_path.push("memo.pages");
+
+        let result = PagesExtractor.extract_text_from_file(&pages_path);
+        assert!(result.is_ok(), "Failed to extract Pages document: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "Quarterly Travel Memo
+All trips must be booked through the portal.
+Receipts are due within 30 days.
+
+Approved by Finance";
+        assert_eq!(text, expected);
+    }
+}
@@ -0,0 +1,150 @@
+use std::fs::File;
+use std::path::Path;
+use anyhow::{Context, Result};
+use parquet::basic::ConvertedType;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::csv_extractor::{CsvExtractor, DEFAULT_MAX_ROWS};
+
+/// Parquet extractor that describes the schema and renders the first rows as a table
+pub struct ParquetExtractor {
+    /// Maximum number of rows to render
+    max_rows: usize,
+}
+
+impl Default for ParquetExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParquetExtractor {
+    /// Creates a Parquet extractor rendering at most `DEFAULT_MAX_ROWS` rows
+    pub fn new() -> Self {
+        Self { max_rows: DEFAULT_MAX_ROWS }
+    }
+
+    /// Sets the maximum number of rows to render
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Renders a field value without the quoting `Field`'s `Display` adds to strings
+    fn render_field(field: &Field) -> String {
+        match field {
+            Field::Null => String::new(),
+            Field::Str(s) => CsvExtractor::clean_cell(s),
+            other => CsvExtractor::clean_cell(&other.to_string()),
+        }
+    }
+}
+
+impl DocumentExtractor for ParquetExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "ParquetExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file = File::open(file_path)
+            .with_context(|| format!("Failed to open Parquet file: {}", file_path.display()))?;
+        let reader = SerializedFileReader::new(file)
+            .with_context(|| format!("Failed to read Parquet metadata: {}", file_path.display()))?;
+
+        let metadata = reader.metadata().file_metadata();
+        let schema = metadata.schema_descr();
+        let total_rows = metadata.num_rows();
+
+        let mut lines = vec![format!("Schema ({} columns, {} rows):", schema.num_columns(), total_rows)];
+        for column in schema.columns() {
+            let mut line = format!("- {}: {}", column.path().string(), column.physical_type());
+            if column.converted_type() != ConvertedType::NONE {
+                line.push_str(&format!(" ({})", column.converted_type()));
+            }
+            line.push_str(if column.self_type().is_optional() { ", optional" } else { ", required" });
+            lines.push(line);
+        }
+        let mut output = lines.join("\n");
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let row_iter = reader
+            .get_row_iter(None)
+            .with_context(|| format!("Failed to read Parquet rows: {}", file_path.display()))?;
+        for row in row_iter.take(self.max_rows) {
+            let row = row.with_context(|| format!("Failed to decode Parquet row: {}", file_path.display()))?;
+            if rows.is_empty() {
+                rows.push(row.get_column_iter().map(|(name, _)| name.clone()).collect());
+            }
+            rows.push(row.get_column_iter().map(|(_, field)| Self::render_field(field)).collect());
+        }
+
+        if !rows.is_empty() {
+            output.push_str("\n\n");
+            output.push_str(&CsvExtractor::render_table(&rows));
+        }
+
+        let omitted_rows = total_rows - (rows.len().saturating_sub(1)) as i64;
+        if omitted_rows > 0 {
+            output.push_str(&format!(
+                "\n... {} more rows not shown (limit: {} rows)",
+                omitted_rows, self.max_rows
+            ));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn parquet_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("flights.parquet");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_parquet() {
+        let result = ParquetExtractor::new().extract_text_from_file(&parquet_path());
+        assert!(result.is_ok(), "Failed to extract Parquet: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected_schema = "Schema (4 columns, 6 rows):
+- flight: BYTE_ARRAY (UTF8), required
+- destination: BYTE_ARRAY (UTF8), required
+- seats: INT32, optional
+- fare: DOUBLE, required";
+        assert!(text.starts_with(expected_schema), "Got: {}", text);
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[6], "flight | destination | seats | fare");
+        assert_eq!(lines[8], "CM716  | Panama City | 162   | 412.5");
+        assert_eq!(lines[10], "UA1521 | Denver      |       | 199.99", "Nulls should render as empty cells. Got: {}", text);
+        assert!(!text.contains("more rows"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_parquet_row_limit() {
+        let text = ParquetExtractor::new()
+            .with_max_rows(2)
+            .extract_text_from_file(&parquet_path())
+            .unwrap();
+        assert!(text.contains("CM204"), "Got: {}", text);
+        assert!(!text.contains("UA1521"), "Got: {}", text);
+        assert!(text.ends_with("... 4 more rows not shown (limit: 2 rows)"), "Got: {}", text);
+    }
+}
@@ -1,11 +1,80 @@
-use std::path::Path;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 use std::fs;
 use anyhow::{Context, Result};
-use extractous::Extractor;
-use crate::extractor::DocumentExtractor;
+use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use lopdf::{decode_text_string, Dictionary, Document, LoadOptions, Object, ObjectId, Stream};
+use crate::config::Config;
+use crate::extractor::{
+    clamp_page_range, create_extractor, is_supported_file, DocumentExtractor, DocumentMetadata, EmbeddedImage,
+    Heading, Link, PageTable, PageText, Table, MAX_IMAGE_SIZE,
+};
+use crate::extractors::pdf_layout::{detect_tables, page_text, page_text_runs};
+use crate::extractors::png::encode_png;
+use crate::ocr::ocr_available;
+
+/// Below this many non-whitespace characters the text layer is treated as missing
+const MIN_TEXT_LAYER_CHARS: usize = 20;
+
+/// Tesseract language used for the OCR fallback
+pub const OCR_LANGUAGE: &str = "eng";
+
+/// First line of the result when the text had to be recognized with OCR
+pub const OCR_NOTICE: &str = "[OCR] This PDF has no usable text layer; the text below was recognized with OCR and may contain errors.";
+
+/// First line of the result when the text layer is missing and OCR could not be run
+pub const OCR_UNAVAILABLE_NOTICE: &str = "[OCR unavailable] This PDF has no usable text layer and could not be recognized with OCR";
+
+/// Largest embedded file that is decompressed and extracted, in bytes
+const MAX_ATTACHMENT_SIZE: usize = 50 * 1024 * 1024;
+
+/// Name trees deeper than this are treated as malformed
+const MAX_NAME_TREE_DEPTH: usize = 32;
+
+/// Result of `get_document_outline` for PDFs without bookmarks
+pub const NO_OUTLINE_NOTICE: &str = "This PDF has no outline (bookmarks).";
+
+/// One bookmark of a PDF outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineEntry {
+    /// Nesting depth, starting at 1 for top-level bookmarks
+    pub level: usize,
+    pub title: String,
+    /// 1-based page number the bookmark points to
+    pub page: usize,
+}
+
+/// A file embedded in a PDF, e.g. the XML of a Factur-X/ZUGFeRD e-invoice
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfAttachment {
+    pub name: String,
+    pub data: Vec<u8>,
+}
 
 /// PDF document extractor using the extractous crate
-pub struct PdfExtractor;
+///
+/// Falls back to OCR (Tesseract, via extractous) when the PDF has no usable text layer.
+/// Encrypted PDFs are decrypted first, using the password registered for the file,
+/// the default password or the one saved in the configuration. Embedded files are
+/// listed after the text and extracted with the extractor matching their extension.
+pub struct PdfExtractor {
+    /// List and extract embedded files after the document text
+    attachments: bool,
+    /// Password tried for any encrypted PDF without a file-specific one
+    password: Option<String>,
+    /// Passwords for specific files, e.g. bank statements protected with an account number
+    file_passwords: HashMap<PathBuf, String>,
+    /// Configuration file whose saved passwords are tried when no password is registered
+    password_store: Option<PathBuf>,
+}
+
+impl Default for PdfExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DocumentExtractor for PdfExtractor {
     fn extractor_type(&self) -> &'static str {
@@ -27,20 +96,667 @@ impl DocumentExtractor for PdfExtractor {
         let file_bytes = fs::read(file_path)
             .with_context(|| format!("Failed to read PDF file: {}", file_path.display()))?;
 
+        let file_bytes = self.decrypt(&file_bytes, file_path)?;
+
+        let text = Self::extract_from_bytes(&file_bytes)
+            .with_context(|| format!("Failed to extract text from PDF: {}", file_path.display()))?;
+        if !self.attachments {
+            return Ok(text);
+        }
+
+        // A broken attachment must not hide the document text
+        let attachments = match Self::embedded_files(&file_bytes) {
+            Ok(attachments) if attachments.is_empty() => return Ok(text),
+            Ok(attachments) => Self::render_attachments(&attachments),
+            Err(e) => format!("[Failed to read attachments: {:#}]", e),
+        };
+        Ok(format!("{}\n\n{}", text.trim_end(), attachments))
+    }
+
+    /// Detects tables from the position of text on each page
+    ///
+    /// PDFs carry no table structure, so rows and columns are inferred from aligned
+    /// text (see `detect_tables`). Scanned pages without a text layer yield no tables.
+    fn extract_tables(&self, file_path: &Path) -> Result<Vec<Table>> {
+        let tables = self.extract_page_tables(file_path)?;
+        Ok(tables.into_iter().map(|table| table.rows).collect())
+    }
+
+    fn extract_page_tables(&self, file_path: &Path) -> Result<Vec<PageTable>> {
+        let document = self.load_document(file_path)?;
+
+        let mut tables = Vec::new();
+        for (page_number, page_id) in document.get_pages() {
+            let runs = page_text_runs(&document, page_id)
+                .with_context(|| format!("Failed to read page {} of: {}", page_number, file_path.display()))?;
+            let page = Some(page_number as usize);
+            tables.extend(detect_tables(&runs).into_iter().map(|rows| PageTable { page, rows }));
+        }
+        Ok(tables)
+    }
+
+    /// Reads the bookmarks, each pointing to its page
+    fn extract_outline(&self, file_path: &Path) -> Result<Vec<Heading>> {
+        Ok(self
+            .document_outline(file_path)?
+            .into_iter()
+            .map(|entry| Heading { level: entry.level, title: entry.title, page: Some(entry.page), offset: None })
+            .collect())
+    }
+
+    /// Reads the URI actions of link annotations, with the last bookmark at or before each page
+    /// as its section
+    fn extract_links(&self, file_path: &Path) -> Result<Vec<Link>> {
+        let document = self.load_document(file_path)?;
+        // A broken outline only costs the sections
+        let outline = Self::read_outline(&document, file_path).unwrap_or_default();
+
+        let mut links = Vec::new();
+        for (page_number, page_id) in document.get_pages() {
+            let page = page_number as usize;
+            for annotation in document.get_page_annotations(page_id).unwrap_or_default() {
+                let is_link =
+                    annotation.get(b"Subtype").and_then(Object::as_name).is_ok_and(|subtype| subtype == b"Link");
+                let Some(action) = document.get_dict_in_dict(annotation, b"A").ok().filter(|_| is_link) else {
+                    continue;
+                };
+                // Other actions (`GoTo`, `Launch`, ...) lead inside this document or to local files
+                let is_uri = action.get(b"S").and_then(Object::as_name).is_ok_and(|action| action == b"URI");
+                let uri = action
+                    .get(b"URI")
+                    .and_then(|uri| document.dereference(uri))
+                    .ok()
+                    .and_then(|(_, uri)| decode_text_string(uri).ok())
+                    .map(|uri| uri.trim().to_string())
+                    .filter(|uri| is_uri && !uri.is_empty());
+                let Some(url) = uri else {
+                    continue;
+                };
+                let section = outline.iter().rfind(|entry| entry.page <= page).map(|entry| entry.title.clone());
+                links.push(Link { url, text: None, page: Some(page), section });
+            }
+        }
+        Ok(links)
+    }
+
+    /// Reads the image XObjects of each page, once each, on the first page that uses them
+    ///
+    /// JPEG and JPEG 2000 images are returned as stored. Other 8-bit grayscale and RGB
+    /// images are converted to PNG; images in other color spaces (CMYK, indexed, ...),
+    /// stencil masks and images inside form XObjects are skipped.
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        let document = self.load_document(file_path)?;
+
+        let mut seen = HashSet::new();
+        let mut images = Vec::new();
+        for (page_number, page_id) in document.get_pages() {
+            for (name, image_id) in Self::page_images(&document, page_id) {
+                if !seen.insert(image_id) {
+                    continue;
+                }
+                let Ok(stream) = document.get_object(image_id).and_then(Object::as_stream) else {
+                    continue;
+                };
+                if let Some((mime_type, data)) = Self::encode_image(&document, stream) {
+                    images.push(EmbeddedImage { page: Some(page_number as usize), name: Some(name), mime_type, data });
+                }
+            }
+        }
+        Ok(images)
+    }
+
+    /// Reads the document information dictionary and counts the pages
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        let document = self.load_document(file_path)?;
+
+        let info = document
+            .trailer
+            .get(b"Info")
+            .and_then(|info| document.dereference(info))
+            .and_then(|(_, info)| info.as_dict())
+            .ok();
+        let field = |key: &[u8]| {
+            info.and_then(|info| info.get(key).ok())
+                .and_then(|value| decode_text_string(value).ok())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        Ok(DocumentMetadata {
+            title: field(b"Title"),
+            author: field(b"Author"),
+            created: field(b"CreationDate").map(|date| Self::format_date(&date)),
+            modified: field(b"ModDate").map(|date| Self::format_date(&date)),
+            page_count: Some(document.get_pages().len()),
+            producer: field(b"Producer"),
+        })
+    }
+
+    /// Reads the text layer of each page in the range
+    ///
+    /// Text is laid out from the position of each run (see `page_text`) rather than
+    /// extracted with Tika, so only the requested pages are decoded. Scanned pages
+    /// without a text layer come back empty; OCR is not applied per page.
+    fn extract_pages(&self, file_path: &Path, pages: RangeInclusive<usize>) -> Result<Vec<PageText>> {
+        let document = self.load_document(file_path)?;
+        let page_ids = document.get_pages();
+        let pages = clamp_page_range(&pages, page_ids.len())
+            .with_context(|| format!("Invalid page range for: {}", file_path.display()))?;
+
+        pages
+            .map(|page| {
+                let page_id = page_ids[&(page as u32)];
+                let runs = page_text_runs(&document, page_id)
+                    .with_context(|| format!("Failed to read page {} of: {}", page, file_path.display()))?;
+                Ok(PageText { page, text: page_text(&runs) })
+            })
+            .collect()
+    }
+}
+
+impl PdfExtractor {
+    /// Creates a PDF extractor without any passwords
+    pub fn new() -> Self {
+        Self {
+            attachments: true,
+            password: None,
+            file_passwords: HashMap::new(),
+            password_store: None,
+        }
+    }
+
+    /// Enables or disables listing and extracting embedded files
+    pub fn with_attachments(mut self, attachments: bool) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    /// Sets the password used for encrypted PDFs that have no file-specific password
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    /// Registers the password for one file; it takes precedence over `with_password`
+    pub fn with_file_password(mut self, file_path: impl Into<PathBuf>, password: &str) -> Self {
+        self.file_passwords.insert(file_path.into(), password.to_string());
+        self
+    }
+
+    /// Looks up the passwords saved in a configuration file (see `Config::set_document_password`)
+    ///
+    /// The file is only read for encrypted PDFs without a registered or default password.
+    pub fn with_password_store(mut self, config_path: impl Into<PathBuf>) -> Self {
+        self.password_store = Some(config_path.into());
+        self
+    }
+
+    /// Returns the file-specific password, or the default one
+    fn password_for(&self, file_path: &Path) -> Option<&str> {
+        self.file_passwords
+            .get(file_path)
+            .or(self.password.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Decrypts the bytes of a PDF file with its password, looking it up in the password store if needed
+    fn decrypt<'a>(&self, file_bytes: &'a [u8], file_path: &Path) -> Result<Cow<'a, [u8]>> {
+        let stored;
+        let password = match (self.password_for(file_path), &self.password_store) {
+            (None, Some(config_path)) if Self::mentions_encryption(file_bytes) => {
+                stored = Config::load_from(config_path)
+                    .and_then(|config| config.document_password(file_path))
+                    .context("Failed to look up the saved password")?;
+                stored.as_deref()
+            }
+            (password, _) => password,
+        };
+        Self::decrypt_bytes(file_bytes, password)
+            .with_context(|| format!("Failed to decrypt PDF: {}", file_path.display()))
+    }
+
+    /// Reads, decrypts and parses a PDF for structure-level features (outline, tables)
+    fn load_document(&self, file_path: &Path) -> Result<Document> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read PDF file: {}", file_path.display()))?;
+        let file_bytes = self.decrypt(&file_bytes, file_path)?;
+        Document::load_mem(&file_bytes)
+            .with_context(|| format!("Failed to parse PDF: {}", file_path.display()))
+    }
+
+    /// Reads the bookmark tree of a PDF, in document order
+    ///
+    /// Bookmarks that do not point to a page in this document (links to other
+    /// files, URLs) are skipped.
+    pub fn document_outline(&self, file_path: &Path) -> Result<Vec<OutlineEntry>> {
+        let document = self.load_document(file_path)?;
+        Self::read_outline(&document, file_path)
+    }
+
+    /// Reads the bookmark tree of a parsed PDF; `file_path` is only used in errors
+    fn read_outline(document: &Document, file_path: &Path) -> Result<Vec<OutlineEntry>> {
+        let has_outline = document
+            .catalog()
+            .is_ok_and(|catalog| catalog.get(b"Outlines").is_ok());
+        if !has_outline {
+            return Ok(Vec::new());
+        }
+
+        let toc = match document.get_toc() {
+            Ok(toc) => toc,
+            Err(lopdf::Error::NoOutline) => return Ok(Vec::new()),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("Failed to read PDF outline: {}", file_path.display()));
+            }
+        };
+        Ok(toc
+            .toc
+            .into_iter()
+            .map(|entry| OutlineEntry {
+                level: entry.level,
+                title: entry.title.trim().to_string(),
+                page: entry.page,
+            })
+            .collect())
+    }
+
+    /// Returns the bookmark tree as indented `Title (page N)` lines, so a long
+    /// document can be navigated by section instead of extracted whole
+    pub fn get_document_outline(&self, file_path: &Path) -> Result<String> {
+        let outline = self.document_outline(file_path)?;
+        if outline.is_empty() {
+            return Ok(NO_OUTLINE_NOTICE.to_string());
+        }
+
+        let lines: Vec<String> = outline
+            .iter()
+            .map(|entry| {
+                let indent = "  ".repeat(entry.level.saturating_sub(1));
+                format!("{}{} (page {})", indent, entry.title, entry.page)
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Converts a PDF date (`D:YYYYMMDDHHmmSSOHH'mm`) to ISO 8601
+    ///
+    /// Every part after the year is optional. Dates that do not follow the format
+    /// are returned unchanged.
+    fn format_date(date: &str) -> String {
+        let raw = date.strip_prefix("D:").unwrap_or(date);
+        let digits = raw.bytes().take_while(u8::is_ascii_digit).count();
+        if digits < 4 || digits % 2 != 0 || digits > 14 {
+            return date.to_string();
+        }
+
+        let (stamp, zone) = raw.split_at(digits);
+        let part = |range: std::ops::Range<usize>, default: &'static str| stamp.get(range).unwrap_or(default);
+        let day = format!("{}-{}-{}", &stamp[..4], part(4..6, "01"), part(6..8, "01"));
+        if digits <= 8 {
+            return day;
+        }
+
+        let time = format!("{}:{}:{}", part(8..10, "00"), part(10..12, "00"), part(12..14, "00"));
+        let zone: String = zone.chars().filter(|c| *c != '\'').collect();
+        let zone = match zone.as_bytes() {
+            [b'Z', ..] => "Z".to_string(),
+            [sign @ (b'+' | b'-'), hours @ .., ] if hours.len() >= 2 && hours.iter().all(u8::is_ascii_digit) => {
+                let minutes = if hours.len() >= 4 { &zone[3..5] } else { "00" };
+                format!("{}{}:{}", *sign as char, &zone[1..3], minutes)
+            }
+            _ => String::new(),
+        };
+        format!("{}T{}{}", day, time, zone)
+    }
+
+    /// Returns the files embedded in (decrypted) PDF bytes
+    ///
+    /// Both the document-level `EmbeddedFiles` name tree and `FileAttachment`
+    /// annotations on pages are read; a file referenced from both is returned once.
+    pub fn embedded_files(file_bytes: &[u8]) -> Result<Vec<PdfAttachment>> {
+        // Cheap check first so PDFs without attachments are never parsed twice
+        if !file_bytes.windows(b"/EmbeddedFile".len()).any(|window| window == b"/EmbeddedFile") {
+            return Ok(Vec::new());
+        }
+
+        let document = Document::load_mem(file_bytes).context("Failed to parse PDF")?;
+        let mut filespecs: Vec<(String, &Dictionary)> = Vec::new();
+
+        let embedded_files = document
+            .catalog()
+            .and_then(|catalog| document.get_dict_in_dict(catalog, b"Names"))
+            .and_then(|names| document.get_dict_in_dict(names, b"EmbeddedFiles"));
+        if let Ok(tree) = embedded_files {
+            Self::collect_name_tree(&document, tree, &mut filespecs, 0)?;
+        }
+
+        for page_id in document.get_pages().into_values() {
+            for annotation in document.get_page_annotations(page_id).unwrap_or_default() {
+                let is_attachment = annotation
+                    .get(b"Subtype")
+                    .and_then(Object::as_name)
+                    .is_ok_and(|subtype| subtype == b"FileAttachment");
+                if let (true, Ok(filespec)) = (is_attachment, document.get_dict_in_dict(annotation, b"FS")) {
+                    filespecs.push((String::new(), filespec));
+                }
+            }
+        }
+
+        let mut seen: HashSet<ObjectId> = HashSet::new();
+        let mut attachments = Vec::new();
+        for (key, filespec) in filespecs {
+            let Some(stream_id) = Self::embedded_stream_id(filespec) else {
+                continue;
+            };
+            if !seen.insert(stream_id) {
+                continue;
+            }
+
+            // Prefer the Unicode file name, then the legacy one, then the name tree key
+            let name = [b"UF".as_slice(), b"F".as_slice()]
+                .into_iter()
+                .filter_map(|key| filespec.get(key).ok())
+                .find_map(|name| decode_text_string(name).ok())
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or(key);
+            let name = if name.trim().is_empty() { "(unnamed)".to_string() } else { name.trim().to_string() };
+
+            let stream = document
+                .get_object(stream_id)
+                .and_then(Object::as_stream)
+                .with_context(|| format!("Failed to read embedded file: {}", name))?;
+            let data = stream
+                .decompressed_content_with_limit(MAX_ATTACHMENT_SIZE)
+                .with_context(|| format!("Failed to decompress embedded file: {}", name))?;
+            attachments.push(PdfAttachment { name, data });
+        }
+        Ok(attachments)
+    }
+
+    /// Collects `(key, file specification)` pairs from a name tree node and its kids
+    fn collect_name_tree<'a>(
+        document: &'a Document,
+        node: &'a Dictionary,
+        filespecs: &mut Vec<(String, &'a Dictionary)>,
+        depth: usize,
+    ) -> Result<()> {
+        if depth > MAX_NAME_TREE_DEPTH {
+            return Err(anyhow::anyhow!("EmbeddedFiles name tree is nested too deeply"));
+        }
+
+        if let Ok(names) = node.get(b"Names").and_then(|names| document.dereference(names)) {
+            let names = names.1.as_array()?;
+            for pair in names.chunks(2) {
+                let [key, value] = pair else {
+                    continue;
+                };
+                let key = decode_text_string(key).unwrap_or_default();
+                if let Ok((_, Object::Dictionary(filespec))) = document.dereference(value) {
+                    filespecs.push((key, filespec));
+                }
+            }
+        }
+
+        if let Ok(kids) = node.get(b"Kids").and_then(|kids| document.dereference(kids)) {
+            for kid in kids.1.as_array()? {
+                if let Ok((_, Object::Dictionary(kid))) = document.dereference(kid) {
+                    Self::collect_name_tree(document, kid, filespecs, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists the image XObjects in a page's resources, including inherited ones, by name
+    fn page_images(document: &Document, page_id: ObjectId) -> Vec<(String, ObjectId)> {
+        let Ok((page_resources, inherited)) = document.get_page_resources(page_id) else {
+            return Vec::new();
+        };
+
+        let mut images = Vec::new();
+        let resources = page_resources
+            .into_iter()
+            .chain(inherited.into_iter().filter_map(|id| document.get_dictionary(id).ok()));
+        for resources in resources {
+            let Ok(xobjects) = resources.get_deref(b"XObject", document).and_then(Object::as_dict) else {
+                continue;
+            };
+            for (name, object) in xobjects.iter() {
+                let Ok(id) = object.as_reference() else {
+                    continue;
+                };
+                let is_image = document
+                    .get_object(id)
+                    .and_then(Object::as_stream)
+                    .and_then(|stream| stream.dict.get(b"Subtype"))
+                    .and_then(Object::as_name)
+                    .is_ok_and(|subtype| subtype == b"Image");
+                if is_image {
+                    images.push((String::from_utf8_lossy(name).into_owned(), id));
+                }
+            }
+        }
+        images
+    }
+
+    /// Converts an image XObject to a file format, returning its MIME type and bytes
+    fn encode_image(document: &Document, stream: &Stream) -> Option<(&'static str, Vec<u8>)> {
+        let dict = &stream.dict;
+        let filters = stream.filters().unwrap_or_default();
+        match filters.as_slice() {
+            [b"DCTDecode"] => return Some(("image/jpeg", stream.content.clone())),
+            [b"JPXDecode"] => return Some(("image/jp2", stream.content.clone())),
+            _ => {}
+        }
+
+        let is_mask = dict.get(b"ImageMask").and_then(Object::as_bool).unwrap_or(false);
+        let bits = dict.get(b"BitsPerComponent").and_then(Object::as_i64).ok();
+        if is_mask || bits != Some(8) {
+            return None;
+        }
+        let width = u32::try_from(dict.get(b"Width").and_then(Object::as_i64).ok()?).ok()?;
+        let height = u32::try_from(dict.get(b"Height").and_then(Object::as_i64).ok()?).ok()?;
+
+        // A color space is a name, or an array such as `[/ICCBased 12 0 R]`
+        let color_space = dict.get_deref(b"ColorSpace", document).ok()?;
+        let channels = match color_space {
+            Object::Name(name) => match name.as_slice() {
+                b"DeviceGray" | b"CalGray" => 1,
+                b"DeviceRGB" | b"CalRGB" => 3,
+                _ => return None,
+            },
+            Object::Array(array) => match array.first().and_then(|family| family.as_name().ok())? {
+                b"CalGray" => 1,
+                b"CalRGB" => 3,
+                b"ICCBased" => {
+                    let profile = document.dereference(array.get(1)?).ok()?.1.as_stream().ok()?;
+                    match profile.dict.get(b"N").and_then(Object::as_i64).ok()? {
+                        1 => 1,
+                        3 => 3,
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let pixels = if filters.is_empty() {
+            stream.content.clone()
+        } else {
+            stream.decompressed_content_with_limit(MAX_IMAGE_SIZE).ok()?
+        };
+        let png = encode_png(width, height, channels, &pixels).ok()?;
+        Some(("image/png", png))
+    }
+
+    /// Returns the embedded file stream of a file specification (`/EF /F`, or `/EF /UF`)
+    fn embedded_stream_id(filespec: &Dictionary) -> Option<ObjectId> {
+        let embedded = filespec.get(b"EF").and_then(Object::as_dict).ok()?;
+        embedded
+            .get(b"F")
+            .or_else(|_| embedded.get(b"UF"))
+            .and_then(Object::as_reference)
+            .ok()
+    }
+
+    /// Lists the attachments and extracts the ones with a supported file type
+    fn render_attachments(attachments: &[PdfAttachment]) -> String {
+        let listing: Vec<String> = attachments
+            .iter()
+            .map(|attachment| format!("- {} ({} bytes)", attachment.name, attachment.data.len()))
+            .collect();
+        let mut output = format!("Attachments ({}):\n{}", attachments.len(), listing.join("\n"));
+
+        let temp_dir = match tempfile::tempdir() {
+            Ok(temp_dir) => temp_dir,
+            Err(e) => return format!("{}\n\n[Failed to create temporary directory: {}]", output, e),
+        };
+        for (index, attachment) in attachments.iter().enumerate() {
+            let Some(extension) = Path::new(&attachment.name)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase)
+            else {
+                continue;
+            };
+            if !is_supported_file(Path::new(&attachment.name)) {
+                continue;
+            }
+
+            // Never trust the attachment name as a path; only its extension is kept
+            let attachment_path = temp_dir.path().join(format!("attachment-{}.{}", index, extension));
+            let text = fs::write(&attachment_path, &attachment.data)
+                .context("Failed to write temporary file")
+                .and_then(|_| create_extractor(&attachment_path))
+                .and_then(|extractor| extractor.extract_text_from_file(&attachment_path));
+            let text = match text {
+                Ok(text) => text.trim().to_string(),
+                Err(e) => format!("[Failed to extract: {:#}]", e),
+            };
+            output.push_str(&format!("\n\n=== Attachment: {} ===\n{}", attachment.name, text));
+        }
+        output
+    }
+
+    /// Tells whether the bytes may be an encrypted PDF, without parsing them
+    fn mentions_encryption(file_bytes: &[u8]) -> bool {
+        file_bytes.windows(b"/Encrypt".len()).any(|window| window == b"/Encrypt")
+    }
+
+    /// Returns the PDF bytes with encryption removed, or the input unchanged when it is not encrypted
+    ///
+    /// PDFs encrypted with an empty user password (owner-password only, common for
+    /// "no copy" restrictions) open without a password.
+    pub fn decrypt_bytes<'a>(file_bytes: &'a [u8], password: Option<&str>) -> Result<Cow<'a, [u8]>> {
+        // Cheap check first so unencrypted PDFs are never parsed twice
+        if !Self::mentions_encryption(file_bytes) {
+            return Ok(Cow::Borrowed(file_bytes));
+        }
+
+        let options = match password {
+            Some(password) => LoadOptions::with_password(password),
+            None => LoadOptions::default(),
+        };
+        let mut document = match Document::load_mem_with_options(file_bytes, options) {
+            Ok(document) => document,
+            Err(lopdf::Error::InvalidPassword) => {
+                return Err(anyhow::anyhow!("Incorrect password for password-protected PDF"));
+            }
+            Err(error) => return Err(error).context("Failed to parse encrypted PDF"),
+        };
+
+        // Still encrypted after loading: the empty user password did not open it and none was given
+        if document.is_encrypted() {
+            return Err(anyhow::anyhow!(
+                "PDF is password-protected; a password is required to extract its text"
+            ));
+        }
+        if !document.was_encrypted() {
+            // "/Encrypt" appeared in the content, not in the trailer
+            return Ok(Cow::Borrowed(file_bytes));
+        }
+
+        let mut decrypted = Vec::with_capacity(file_bytes.len());
+        document
+            .save_to(&mut decrypted)
+            .context("Failed to write decrypted PDF")?;
+        Ok(Cow::Owned(decrypted))
+    }
+
+    /// Extracts text from in-memory PDF bytes, falling back to OCR for image-only scans
+    pub fn extract_from_bytes(file_bytes: &[u8]) -> Result<String> {
+        // Try the embedded text layer first; OCR is much slower
+        let ocr_config = || TesseractOcrConfig::new().set_language(OCR_LANGUAGE);
+        let text = Self::extract_with_strategy(file_bytes, PdfOcrStrategy::NO_OCR, ocr_config())?;
+
+        if !Self::needs_ocr(&text) {
+            return Ok(text);
+        }
+
+        // Image-only scan: recognize the rendered pages instead, keeping what the text
+        // layer had when Tesseract is missing or fails
+        if !ocr_available() {
+            return Ok(Self::without_ocr(&text, "Tesseract is not installed"));
+        }
+        match Self::extract_with_strategy(file_bytes, PdfOcrStrategy::OCR_ONLY, ocr_config()) {
+            Ok(ocr_text) => Ok(format!("{}\n\n{}", OCR_NOTICE, ocr_text.trim())),
+            Err(error) => Ok(Self::without_ocr(&text, &format!("{:#}", error))),
+        }
+    }
+
+    /// Puts the notice that OCR could not be run, and why, before the text layer
+    fn without_ocr(text: &str, reason: &str) -> String {
+        format!("{} ({}).\n\n{}", OCR_UNAVAILABLE_NOTICE, reason, text.trim()).trim_end().to_string()
+    }
+
+    /// Returns true when the text layer is too thin to be a real document (e.g. an image-only scan)
+    pub fn needs_ocr(text: &str) -> bool {
+        text.chars().filter(|c| !c.is_whitespace()).count() < MIN_TEXT_LAYER_CHARS
+    }
+
+    /// Recognizes the text of every page with OCR, ignoring any text layer
+    ///
+    /// For PDFs whose text layer is present but garbage (bad font encodings, a poor
+    /// earlier OCR pass). The bytes must already be decrypted.
+    pub fn ocr_bytes(file_bytes: &[u8], ocr_config: TesseractOcrConfig) -> Result<String> {
+        Self::extract_with_strategy(file_bytes, PdfOcrStrategy::OCR_ONLY, ocr_config)
+    }
+
+    /// Reads, decrypts and recognizes a PDF file with OCR (see `ocr_bytes`)
+    pub fn ocr_file(&self, file_path: &Path, ocr_config: TesseractOcrConfig) -> Result<String> {
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read PDF file: {}", file_path.display()))?;
+        let file_bytes = self.decrypt(&file_bytes, file_path)?;
+        Self::ocr_bytes(&file_bytes, ocr_config).with_context(|| format!("OCR failed for: {}", file_path.display()))
+    }
+
+    /// Extracts text from PDF bytes with the given OCR strategy
+    fn extract_with_strategy(
+        file_bytes: &[u8],
+        strategy: PdfOcrStrategy,
+        ocr_config: TesseractOcrConfig,
+    ) -> Result<String> {
         // Create extractor instance
-        let extractor = Extractor::new();
+        let extractor = Extractor::new()
+            .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(strategy))
+            .set_ocr_config(ocr_config);
 
         // Extract text from PDF bytes (returns StreamReader and Metadata)
-        let (mut reader, _metadata) = extractor
-            .extract_bytes(&file_bytes)
-            .with_context(|| format!("Failed to extract text from PDF: {}", file_path.display()))?;
+        let (mut reader, _metadata) = extractor.extract_bytes(file_bytes)?;
 
         // Read all text from the StreamReader
         use std::io::Read;
         let mut text = String::new();
         reader
             .read_to_string(&mut text)
-            .with_context(|| format!("Failed to read extracted text from PDF: {}", file_path.display()))?;
+            .context("Failed to read extracted text from PDF")?;
 
         Ok(text)
     }
@@ -50,6 +766,7 @@ impl DocumentExtractor for PdfExtractor {
 mod tests {
     use super::*;
     use std::path::PathBuf;
+    use lopdf::dictionary;
 
     #[test]
     fn test_extract_text_from_pdf() {
@@ -59,7 +776,7 @@ mod tests {
         pdf_path.push("boardingPass.pdf");
 
         // Create extractor and extract text
-        let extractor = PdfExtractor;
+        let extractor = PdfExtractor::new();
         let result = extractor.extract_text_from_file(&pdf_path);
 
         // Verify extraction succeeded
@@ -89,7 +806,345 @@ mod tests {
         assert!(text.contains("Boarding gates"), "Should contain boarding information");
         assert!(text.contains("Important Information"), "Should contain important information section");
         
+        // The boarding pass has a real text layer, so OCR must not kick in
+        assert!(!text.starts_with(OCR_NOTICE), "OCR should not be used for text PDFs");
+
         // Log summary for debugging if needed
         println!("Successfully extracted {} characters from PDF", text.len());
     }
+
+    fn bank_statement_path() -> PathBuf {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("bankStatement.pdf");
+        pdf_path
+    }
+
+    #[test]
+    fn test_extract_text_from_encrypted_pdf() {
+        let pdf_path = bank_statement_path();
+        let extractor = PdfExtractor::new().with_file_password(&pdf_path, "statement2025");
+        let result = extractor.extract_text_from_file(&pdf_path);
+        assert!(result.is_ok(), "Failed to extract encrypted PDF: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.contains("Harbor Savings Bank"), "Should contain the bank name");
+        assert!(text.contains("Closing balance: 4,218.37 USD"), "Should contain the closing balance");
+    }
+
+    #[test]
+    fn test_decrypt_bytes() {
+        let file_bytes = fs::read(bank_statement_path()).unwrap();
+
+        let decrypted = PdfExtractor::decrypt_bytes(&file_bytes, Some("statement2025")).unwrap();
+        let document = Document::load_mem(&decrypted).unwrap();
+        assert!(!document.is_encrypted(), "Decrypted PDF should not be encrypted");
+
+        // The content stream is stored uncompressed, so the text is readable in the bytes
+        let raw = String::from_utf8_lossy(&decrypted);
+        assert!(raw.contains("(Account holder: Thomas Plantin) Tj"), "Content stream was not decrypted");
+    }
+
+    #[test]
+    fn test_decrypt_bytes_requires_password() {
+        let file_bytes = fs::read(bank_statement_path()).unwrap();
+
+        let missing = PdfExtractor::decrypt_bytes(&file_bytes, None).unwrap_err();
+        assert!(missing.to_string().contains("password-protected"), "Unexpected error: {}", missing);
+
+        let wrong = PdfExtractor::decrypt_bytes(&file_bytes, Some("guess")).unwrap_err();
+        assert!(wrong.to_string().contains("Incorrect password"), "Unexpected error: {}", wrong);
+
+        // The default password is used when no file-specific one is registered
+        let error = PdfExtractor::new()
+            .with_password("guess")
+            .extract_text_from_file(&bank_statement_path())
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("Incorrect password"), "Unexpected error: {:#}", error);
+    }
+
+    #[test]
+    fn test_extract_text_with_saved_password() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        let mut config = Config::default();
+        let glob = format!("{}/fixtures/bank*.pdf", env!("CARGO_MANIFEST_DIR"));
+        config.set_document_password(&glob, "statement2025", false).unwrap();
+        config.save_to(&config_path).unwrap();
+
+        let file_bytes = fs::read(bank_statement_path()).unwrap();
+        let extractor = PdfExtractor::new().with_password_store(&config_path);
+        let decrypted = extractor.decrypt(&file_bytes, &bank_statement_path()).unwrap();
+        assert!(matches!(decrypted, Cow::Owned(_)), "The saved password should decrypt the PDF");
+
+        // A registered password wins over the saved one
+        let error = extractor.with_password("guess").decrypt(&file_bytes, &bank_statement_path()).unwrap_err();
+        assert!(format!("{:#}", error).contains("Incorrect password"), "Unexpected error: {:#}", error);
+    }
+
+    #[test]
+    fn test_decrypt_bytes_passes_through_unencrypted_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("boardingPass.pdf");
+        let file_bytes = fs::read(&pdf_path).unwrap();
+
+        let result = PdfExtractor::decrypt_bytes(&file_bytes, Some("unused")).unwrap();
+        assert!(matches!(result, Cow::Borrowed(_)), "Unencrypted PDF should not be rewritten");
+    }
+
+    #[test]
+    fn test_extract_tables_from_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("fareTable.pdf");
+
+        let result = PdfExtractor::new().extract_tables(&pdf_path);
+        assert!(result.is_ok(), "Failed to extract PDF tables: {:?}", result.err());
+
+        let tables = result.unwrap();
+        assert_eq!(
+            tables,
+            vec![
+                vec![
+                    vec!["Flight", "Route", "Fare"],
+                    vec!["CM 716", "AUS - PTY", "412.50"],
+                    vec!["CM 155", "PTY - MDE", "289.00"],
+                ],
+                vec![vec!["Passenger", "Seat"], vec!["Thomas Plantin", "14A"], vec!["Hugo Flores", ""]],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_page_tables_from_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("fareTable.pdf");
+
+        let tables = PdfExtractor::new().extract_page_tables(&pdf_path).unwrap();
+        let pages: Vec<Option<usize>> = tables.iter().map(|table| table.page).collect();
+        assert_eq!(pages, [Some(1), Some(2)], "Each table is on its own page");
+        assert_eq!(tables[0].rows[0], ["Flight", "Route", "Fare"]);
+    }
+
+    #[test]
+    fn test_extract_images_from_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("boardingPass.pdf");
+
+        // The boarding pass has JPEG photos and Flate-compressed logos, re-encoded as PNG
+        let images = PdfExtractor::new().extract_images(&pdf_path).unwrap();
+        let jpeg = images.iter().find(|image| image.mime_type == "image/jpeg").expect("No JPEG image");
+        assert!(jpeg.data.starts_with(&[0xff, 0xd8, 0xff]));
+        let png = images.iter().find(|image| image.mime_type == "image/png").expect("No PNG image");
+        assert!(png.data.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(images.iter().all(|image| image.page.is_some_and(|page| page >= 1) && image.name.is_some()));
+        let names: HashSet<(Option<usize>, &Option<String>)> =
+            images.iter().map(|image| (image.page, &image.name)).collect();
+        assert_eq!(names.len(), images.len(), "Each image should be listed once");
+    }
+
+    #[test]
+    fn test_extract_images_from_pdf_without_images() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("fareTable.pdf");
+
+        assert_eq!(PdfExtractor::new().extract_images(&pdf_path).unwrap(), []);
+    }
+
+    #[test]
+    fn test_extract_metadata_from_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("boardingPass.pdf");
+
+        let result = PdfExtractor::new().extract_metadata(&pdf_path);
+        assert!(result.is_ok(), "Failed to read PDF metadata: {:?}", result.err());
+
+        let metadata = result.unwrap();
+        assert_eq!(metadata.title, None);
+        assert_eq!(metadata.created.as_deref(), Some("2025-08-28T00:16:25Z"));
+        assert_eq!(metadata.producer.as_deref(), Some("OpenPDF 1.3.32"));
+        assert_eq!(metadata.page_count, Some(2));
+    }
+
+    #[test]
+    fn test_extract_pages_from_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("fareTable.pdf");
+
+        let result = PdfExtractor::new().extract_pages(&pdf_path, 1..=usize::MAX);
+        assert!(result.is_ok(), "Failed to extract PDF pages: {:?}", result.err());
+
+        let pages = result.unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].page, 1);
+        assert!(pages[0].text.starts_with("Fare summary\nFlight Route Fare\nCM 716 AUS - PTY 412.50\n"), "Got: {}", pages[0].text);
+        assert_eq!(pages[1].text, "Passenger Seat\nThomas Plantin 14A\nHugo Flores");
+
+        let second = PdfExtractor::new().extract_pages(&pdf_path, 2..=5).unwrap();
+        assert_eq!(second, vec![pages[1].clone()]);
+
+        let error = format!("{:#}", PdfExtractor::new().extract_pages(&pdf_path, 3..=3).unwrap_err());
+        assert!(error.contains("Page 3 is out of range; the document has 2 pages"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(PdfExtractor::format_date("D:20250828001625Z"), "2025-08-28T00:16:25Z");
+        assert_eq!(PdfExtractor::format_date("D:20250828143000+02'00'"), "2025-08-28T14:30:00+02:00");
+        assert_eq!(PdfExtractor::format_date("D:202508"), "2025-08-01");
+        assert_eq!(PdfExtractor::format_date("28 August 2025"), "28 August 2025");
+    }
+
+    #[test]
+    fn test_get_document_outline() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("cabinManual.pdf");
+
+        let result = PdfExtractor::new().get_document_outline(&pdf_path);
+        assert!(result.is_ok(), "Failed to read PDF outline: {:?}", result.err());
+
+        let expected = "1. Introduction (page 1)
+2. Installation (page 2)
+  2.1 Requirements (page 2)
+  2.2 Set-up \u{2013} cabin (page 3)
+3. Troubleshooting (page 5)";
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_extract_links_from_pdf() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("cabinManual.pdf");
+
+        let mut document = Document::load(&pdf_path).unwrap();
+        let pages = document.get_pages();
+        let rect = || vec![0.into(), 0.into(), 10.into(), 10.into()];
+        let uri = |url: &str| {
+            dictionary! { "S" => "URI", "URI" => Object::string_literal(url) }
+        };
+        let support = document.add_object(dictionary! {
+            "Type" => "Annot", "Subtype" => "Link", "Rect" => rect(), "A" => uri("https://example.com/support"),
+        });
+        let goto = document.add_object(dictionary! {
+            "Type" => "Annot", "Subtype" => "Link", "Rect" => rect(),
+            "A" => dictionary! { "S" => "GoTo", "D" => vec![Object::Reference(pages[&1]), "Fit".into()] },
+        });
+        let parts = document.add_object(dictionary! {
+            "Type" => "Annot", "Subtype" => "Link", "Rect" => rect(), "A" => uri("https://example.com/parts"),
+        });
+        for (page, annotations) in [(2, vec![support]), (5, vec![goto, parts])] {
+            let annotations: Vec<Object> = annotations.into_iter().map(Object::Reference).collect();
+            document.get_object_mut(pages[&page]).unwrap().as_dict_mut().unwrap().set("Annots", annotations);
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let linked_path = dir.path().join("linked.pdf");
+        document.save(&linked_path).unwrap();
+
+        let links = PdfExtractor::new().extract_links(&linked_path).unwrap();
+        let found: Vec<_> = links
+            .iter()
+            .map(|link| (link.url.as_str(), link.page, link.section.as_deref(), link.text.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("https://example.com/support", Some(2), Some("2.1 Requirements"), None),
+                ("https://example.com/parts", Some(5), Some("3. Troubleshooting"), None),
+            ]
+        );
+        assert!(PdfExtractor::new().extract_links(&pdf_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_document_outline_without_bookmarks() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("boardingPass.pdf");
+
+        let outline = PdfExtractor::new().get_document_outline(&pdf_path).unwrap();
+        assert_eq!(outline, NO_OUTLINE_NOTICE);
+    }
+
+    #[test]
+    fn test_extract_text_from_pdf_with_attachments() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("invoice.pdf");
+
+        let result = PdfExtractor::new().extract_text_from_file(&pdf_path);
+        assert!(result.is_ok(), "Failed to extract PDF with attachments: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.contains("Total due: 1,284.00 EUR"), "Should contain the invoice text. Got: {}", text);
+        assert!(text.contains("Attachments (3):\n- factur-x.xml ("), "Attachments should be listed. Got: {}", text);
+        assert!(text.contains("=== Attachment: factur-x.xml ==="), "XML attachment should be extracted. Got: {}", text);
+        assert!(text.contains("1284.00"), "XML attachment content should be present. Got: {}", text);
+        assert!(!text.contains("=== Attachment: logo.png ==="), "Unsupported attachments should only be listed. Got: {}", text);
+
+        let text = PdfExtractor::new().with_attachments(false).extract_text_from_file(&pdf_path).unwrap();
+        assert!(!text.contains("Attachments ("), "Attachments should be skipped when disabled. Got: {}", text);
+    }
+
+    #[test]
+    fn test_embedded_files() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("invoice.pdf");
+        let file_bytes = fs::read(&pdf_path).unwrap();
+
+        let attachments = PdfExtractor::embedded_files(&file_bytes).unwrap();
+        let names: Vec<&str> = attachments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["factur-x.xml", "logo.png", "payment-note.txt"]);
+        assert!(attachments[0].data.starts_with(b"<?xml"), "Compressed attachment should be inflated");
+        assert_eq!(attachments[2].data, b"Paid by bank transfer on 19 Sep 2025.\n");
+    }
+
+    #[test]
+    fn test_render_attachments() {
+        let attachments = vec![
+            PdfAttachment { name: "payment-note.txt".to_string(), data: b"Paid on 19 Sep 2025.\n".to_vec() },
+            PdfAttachment { name: "logo.png".to_string(), data: vec![0x89, b'P', b'N', b'G'] },
+        ];
+
+        let expected = "Attachments (2):
+- payment-note.txt (21 bytes)
+- logo.png (4 bytes)
+
+=== Attachment: payment-note.txt ===
+Paid on 19 Sep 2025.";
+        assert_eq!(PdfExtractor::render_attachments(&attachments), expected);
+    }
+
+    #[test]
+    fn test_embedded_files_without_attachments() {
+        let mut pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pdf_path.push("fixtures");
+        pdf_path.push("boardingPass.pdf");
+        let file_bytes = fs::read(&pdf_path).unwrap();
+
+        assert!(PdfExtractor::embedded_files(&file_bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_without_ocr_keeps_text_layer() {
+        let text = PdfExtractor::without_ocr("\n Page 1 \n", "Tesseract is not installed");
+        assert_eq!(text, format!("{} (Tesseract is not installed).\n\nPage 1", OCR_UNAVAILABLE_NOTICE));
+        let empty = PdfExtractor::without_ocr("  ", "tesseract exited with status 1");
+        assert_eq!(empty, format!("{} (tesseract exited with status 1).", OCR_UNAVAILABLE_NOTICE));
+    }
+
+    #[test]
+    fn test_needs_ocr() {
+        assert!(PdfExtractor::needs_ocr(""), "Empty text layer should trigger OCR");
+        assert!(PdfExtractor::needs_ocr("\n\n  \x0c\n 1 \n"), "Whitespace and page numbers should trigger OCR");
+        assert!(!PdfExtractor::needs_ocr("BOARDING PASS THOMAS PLANTIN CM 716"), "Real text should not trigger OCR");
+    }
 }
@@ -0,0 +1,344 @@
+//! Positioned text from PDF content streams, for layout-aware features such as tables
+
+use std::collections::BTreeMap;
+use anyhow::{Context, Result};
+use lopdf::content::Content;
+use lopdf::{Document, Encoding, Object, ObjectId};
+use crate::extractor::Table;
+
+/// Largest decompressed content stream read for one page, in bytes
+const MAX_CONTENT_SIZE: usize = 50 * 1024 * 1024;
+
+/// Runs whose baselines are this close (in points) are on the same line
+const LINE_TOLERANCE: f32 = 2.0;
+
+/// Cells whose left edges are this close (in points) are in the same column
+const COLUMN_TOLERANCE: f32 = 4.0;
+
+/// In a `TJ` array, a gap wider than this (thousandths of an em) separates words
+const TJ_WORD_GAP: f32 = 200.0;
+
+/// A piece of text drawn at one position on a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun {
+    /// Left edge in page space, in points
+    pub x: f32,
+    /// Baseline in page space, in points (grows upwards)
+    pub y: f32,
+    pub text: String,
+}
+
+/// An affine transformation `[a b c d e f]` as used by PDF matrices
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// Returns `m1 × m2`
+fn multiply(m1: &Matrix, m2: &Matrix) -> Matrix {
+    [
+        m1[0] * m2[0] + m1[1] * m2[2],
+        m1[0] * m2[1] + m1[1] * m2[3],
+        m1[2] * m2[0] + m1[3] * m2[2],
+        m1[2] * m2[1] + m1[3] * m2[3],
+        m1[4] * m2[0] + m1[5] * m2[2] + m2[4],
+        m1[4] * m2[1] + m1[5] * m2[3] + m2[5],
+    ]
+}
+
+fn translation(tx: f32, ty: f32) -> Matrix {
+    [1.0, 0.0, 0.0, 1.0, tx, ty]
+}
+
+/// Reads the numeric operands of an operation
+fn numbers(operands: &[Object]) -> Vec<f32> {
+    operands.iter().filter_map(|operand| operand.as_float().ok()).collect()
+}
+
+/// Decodes the strings of a `Tj`/`TJ`/`'`/`"` operand list
+fn decode_operands(encoding: Option<&Encoding>, operands: &[Object], text: &mut String) {
+    for operand in operands {
+        match operand {
+            Object::String(bytes, _) => match encoding.map(|encoding| Document::decode_text(encoding, bytes)) {
+                Some(Ok(decoded)) => text.push_str(&decoded),
+                _ => text.push_str(&String::from_utf8_lossy(bytes)),
+            },
+            Object::Array(items) => decode_operands(encoding, items, text),
+            // Large negative adjustments in a TJ array move to the next word
+            other => {
+                if other.as_float().is_ok_and(|adjustment| adjustment < -TJ_WORD_GAP) && !text.ends_with(' ') {
+                    text.push(' ');
+                }
+            }
+        }
+    }
+}
+
+/// Returns the text runs drawn by a page's content stream, in drawing order
+///
+/// Consecutive show-text operators without a text positioning operator in between
+/// are joined into one run. Form XObjects are not followed.
+pub fn page_text_runs(document: &Document, page_id: ObjectId) -> Result<Vec<TextRun>> {
+    let fonts = document.get_page_fonts(page_id).unwrap_or_default();
+    let encodings: BTreeMap<Vec<u8>, Encoding> = fonts
+        .into_iter()
+        .filter_map(|(name, font)| Some((name, font.get_font_encoding(document).ok()?)))
+        .collect();
+
+    let content = document
+        .get_page_content_with_limit(page_id, MAX_CONTENT_SIZE)
+        .context("Failed to read page content")?;
+    let content = Content::decode(&content).context("Failed to parse page content")?;
+
+    let mut runs: Vec<TextRun> = Vec::new();
+    let mut ctm = IDENTITY;
+    let mut saved: Vec<Matrix> = Vec::new();
+    let mut text_matrix = IDENTITY;
+    let mut line_matrix = IDENTITY;
+    let mut leading = 0.0;
+    let mut encoding: Option<&Encoding> = None;
+    // Whether the next shown text starts a new run
+    let mut moved = true;
+
+    for operation in &content.operations {
+        let operands = &operation.operands;
+        match operation.operator.as_str() {
+            "q" => saved.push(ctm),
+            "Q" => ctm = saved.pop().unwrap_or(IDENTITY),
+            "cm" => {
+                if let [a, b, c, d, e, f] = numbers(operands)[..] {
+                    ctm = multiply(&[a, b, c, d, e, f], &ctm);
+                }
+            }
+            "BT" => {
+                text_matrix = IDENTITY;
+                line_matrix = IDENTITY;
+                moved = true;
+            }
+            "Tf" => {
+                encoding = operands
+                    .first()
+                    .and_then(|name| name.as_name().ok())
+                    .and_then(|name| encodings.get(name));
+            }
+            "TL" => {
+                if let [value] = numbers(operands)[..] {
+                    leading = value;
+                }
+            }
+            "Td" | "TD" => {
+                if let [tx, ty] = numbers(operands)[..] {
+                    if operation.operator == "TD" {
+                        leading = -ty;
+                    }
+                    line_matrix = multiply(&translation(tx, ty), &line_matrix);
+                    text_matrix = line_matrix;
+                    moved = true;
+                }
+            }
+            "Tm" => {
+                if let [a, b, c, d, e, f] = numbers(operands)[..] {
+                    line_matrix = [a, b, c, d, e, f];
+                    text_matrix = line_matrix;
+                    moved = true;
+                }
+            }
+            "T*" => {
+                line_matrix = multiply(&translation(0.0, -leading), &line_matrix);
+                text_matrix = line_matrix;
+                moved = true;
+            }
+            operator @ ("Tj" | "TJ" | "'" | "\"") => {
+                if matches!(operator, "'" | "\"") {
+                    line_matrix = multiply(&translation(0.0, -leading), &line_matrix);
+                    text_matrix = line_matrix;
+                    moved = true;
+                }
+                // `"` carries word and character spacing before the string
+                let shown = if operator == "\"" { operands.get(2..).unwrap_or_default() } else { operands };
+
+                let mut text = String::new();
+                decode_operands(encoding, shown, &mut text);
+                match runs.last_mut() {
+                    Some(run) if !moved => run.text.push_str(&text),
+                    _ => {
+                        let position = multiply(&text_matrix, &ctm);
+                        runs.push(TextRun { x: position[4], y: position[5], text });
+                    }
+                }
+                moved = false;
+            }
+            _ => {}
+        }
+    }
+
+    runs.retain(|run| !run.text.trim().is_empty());
+    Ok(runs)
+}
+
+/// Groups runs into lines, top to bottom, each sorted left to right
+fn group_lines(runs: &[TextRun]) -> Vec<Vec<&TextRun>> {
+    let mut sorted: Vec<&TextRun> = runs.iter().collect();
+    sorted.sort_by(|a, b| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)));
+
+    let mut lines: Vec<Vec<&TextRun>> = Vec::new();
+    for run in sorted {
+        match lines.last_mut() {
+            Some(line) if (line[0].y - run.y).abs() <= LINE_TOLERANCE => line.push(run),
+            _ => lines.push(vec![run]),
+        }
+    }
+    for line in &mut lines {
+        line.sort_by(|a, b| a.x.total_cmp(&b.x));
+    }
+    lines
+}
+
+/// Renders a page's runs as plain text: one line per baseline, top to bottom, with
+/// the runs of a line separated by a space
+pub fn page_text(runs: &[TextRun]) -> String {
+    group_lines(runs)
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|run| run.text.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A table being detected: column left edges, rows, and the baselines of the rows
+struct OpenTable {
+    columns: Vec<f32>,
+    rows: Table,
+    baselines: Vec<f32>,
+}
+
+impl OpenTable {
+    /// Places a line's cells by column edge; `None` when a run matches no column
+    fn align(&self, line: &[&TextRun], cells: &[String]) -> Option<Vec<String>> {
+        let mut row = vec![String::new(); self.columns.len()];
+        for (run, cell) in line.iter().zip(cells) {
+            let position = self.columns.iter().position(|x| (x - run.x).abs() <= COLUMN_TOLERANCE)?;
+            row[position] = cell.clone();
+        }
+        Some(row)
+    }
+
+    /// Returns true when `y` follows the last row at the spacing of the rows so far
+    fn continues_at(&self, y: f32) -> bool {
+        match self.baselines[..] {
+            [.., before, last] => last - y <= (before - last) * 1.5,
+            _ => false,
+        }
+    }
+}
+
+/// Detects tables in the text runs of one page
+///
+/// A table is a block of at least two consecutive lines with two or more runs each,
+/// where every line either has one run per column or has all of its runs starting
+/// at a known column edge (rows with empty cells). Once the row spacing is known, a
+/// line with a single aligned run also continues the table. Without glyph widths,
+/// each run is taken as one cell, so text laid out in two columns can look like a
+/// table too.
+pub fn detect_tables(runs: &[TextRun]) -> Vec<Table> {
+    let mut tables = Vec::new();
+    let mut current: Option<OpenTable> = None;
+
+    let mut finish = |current: &mut Option<OpenTable>| {
+        if let Some(table) = current.take().filter(|table| table.rows.len() >= 2) {
+            tables.push(table.rows);
+        }
+    };
+
+    for line in group_lines(runs) {
+        let cells: Vec<String> = line.iter().map(|run| run.text.split_whitespace().collect::<Vec<_>>().join(" ")).collect();
+        let y = line[0].y;
+
+        if let Some(table) = current.as_mut() {
+            let row = if line.len() == table.columns.len() {
+                Some(cells.clone())
+            } else if line.len() >= 2 || table.continues_at(y) {
+                table.align(&line, &cells)
+            } else {
+                None
+            };
+            if let Some(row) = row {
+                table.rows.push(row);
+                table.baselines.push(y);
+                continue;
+            }
+            finish(&mut current);
+        }
+
+        if line.len() >= 2 {
+            current = Some(OpenTable {
+                columns: line.iter().map(|run| run.x).collect(),
+                rows: vec![cells],
+                baselines: vec![y],
+            });
+        }
+    }
+    finish(&mut current);
+
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(x: f32, y: f32, text: &str) -> TextRun {
+        TextRun { x, y, text: text.to_string() }
+    }
+
+    #[test]
+    fn test_detect_tables() {
+        let runs = vec![
+            run(72.0, 720.0, "Fare summary"),
+            run(72.0, 690.0, "Flight"),
+            run(200.0, 690.5, "Route"),
+            run(360.0, 690.0, "Fare"),
+            run(72.0, 670.0, "CM 716"),
+            run(200.0, 670.0, "AUS - PTY"),
+            run(362.0, 670.0, "412.50"),
+            // Missing route cell: placed by column edge
+            run(72.0, 650.0, "CM 155"),
+            run(361.0, 650.0, "289.00"),
+            run(72.0, 630.0, "CM 204"),
+            // Too far below the last row to continue the table
+            run(72.0, 580.0, "Fares include taxes."),
+        ];
+
+        let tables = detect_tables(&runs);
+        assert_eq!(
+            tables,
+            vec![vec![
+                vec!["Flight", "Route", "Fare"],
+                vec!["CM 716", "AUS - PTY", "412.50"],
+                vec!["CM 155", "", "289.00"],
+                vec!["CM 204", "", ""],
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_detect_tables_ignores_single_rows() {
+        let runs = vec![run(72.0, 700.0, "Date:"), run(200.0, 700.0, "28 Aug 2025"), run(72.0, 680.0, "Thanks")];
+        assert!(detect_tables(&runs).is_empty());
+    }
+
+    #[test]
+    fn test_page_text() {
+        let runs = vec![run(200.0, 700.0, "Route"), run(72.0, 700.5, "Flight"), run(72.0, 680.0, "CM  716")];
+        assert_eq!(page_text(&runs), "Flight Route\nCM 716");
+    }
+
+    #[test]
+    fn test_multiply() {
+        let scaled = multiply(&translation(10.0, 20.0), &[2.0, 0.0, 0.0, 2.0, 5.0, 5.0]);
+        assert_eq!(scaled, [2.0, 0.0, 0.0, 2.0, 25.0, 45.0]);
+    }
+}
@@ -0,0 +1,95 @@
+//! Minimal PNG encoder, for raw pixel data such as uncompressed PDF images
+
+use std::io::Write;
+use anyhow::{Context, Result};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Encodes 8-bit pixels as a PNG: 1 channel for grayscale, 3 for RGB, 4 for RGBA
+///
+/// `pixels` holds the rows top to bottom without padding; extra trailing bytes are ignored.
+pub fn encode_png(width: u32, height: u32, channels: u8, pixels: &[u8]) -> Result<Vec<u8>> {
+    let color_type = match channels {
+        1 => 0,
+        3 => 2,
+        4 => 6,
+        _ => return Err(anyhow::anyhow!("Unsupported number of channels for PNG: {}", channels)),
+    };
+    let row_len = width as usize * channels as usize;
+    let data_len = row_len * height as usize;
+    if width == 0 || height == 0 || pixels.len() < data_len {
+        return Err(anyhow::anyhow!(
+            "Expected {} bytes of pixel data for a {}x{} image, got {}",
+            data_len,
+            width,
+            height,
+            pixels.len()
+        ));
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // Bit depth 8, no interlacing
+    header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+
+    // Each row starts with its filter type; 0 leaves the bytes as they are
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels[..data_len].chunks(row_len) {
+        encoder.write_all(&[0]).and_then(|_| encoder.write_all(row)).context("Failed to compress PNG data")?;
+    }
+    let compressed = encoder.finish().context("Failed to compress PNG data")?;
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &compressed);
+    write_chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+/// Appends a chunk: length, type, data and the CRC of type and data
+fn write_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(chunk_type);
+    crc.update(data);
+    png.extend_from_slice(&crc.finalize().to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_encode_png() {
+        // 2x2 RGB: red, green / blue, white
+        let pixels = [255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255];
+        let png = encode_png(2, 2, 3, &pixels).unwrap();
+
+        assert!(png.starts_with(SIGNATURE));
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        // CRC of the IHDR chunk, as computed by libpng
+        assert_eq!(&png[29..33], &[0xfd, 0xd4, 0x9a, 0x73]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(&png[37..41], b"IDAT");
+        let mut rows = Vec::new();
+        ZlibDecoder::new(&png[41..41 + idat_len]).read_to_end(&mut rows).unwrap();
+        assert_eq!(rows, [0, 255, 0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_encode_png_rejects_bad_input() {
+        assert!(encode_png(2, 2, 3, &[0; 11]).is_err(), "Too few pixels");
+        assert!(encode_png(2, 2, 2, &[0; 8]).is_err(), "Gray with alpha is not supported");
+        assert!(encode_png(0, 2, 1, &[]).is_err());
+    }
+}
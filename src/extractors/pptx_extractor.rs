@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use zip::ZipArchive;
+use crate::extractor::{DocumentExtractor, DocumentMetadata, EmbeddedImage};
+use crate::extractors::archive::{
+    open_zip, parse_relationships, read_core_properties, read_zip_entry, read_zip_images, resolve_part_path,
+};
+
+/// PowerPoint (.pptx) extractor that labels each slide and includes speaker notes
+pub struct PptxExtractor;
+
+/// Placeholder types that only carry slide chrome (numbers, dates, footers)
+const IGNORED_PLACEHOLDERS: &[&str] = &["sldNum", "dt", "ftr", "hdr", "sldImg"];
+
+/// A text-bearing shape on a slide
+#[derive(Debug, Default)]
+struct Shape {
+    /// Placeholder type (`title`, `body`, ...) when the shape is a layout placeholder
+    placeholder: Option<String>,
+    paragraphs: Vec<String>,
+}
+
+impl Shape {
+    fn is_title(&self) -> bool {
+        matches!(self.placeholder.as_deref(), Some("title" | "ctrTitle"))
+    }
+
+    fn is_ignored(&self) -> bool {
+        self.placeholder
+            .as_deref()
+            .is_some_and(|placeholder| IGNORED_PLACEHOLDERS.contains(&placeholder))
+    }
+}
+
+impl PptxExtractor {
+    /// Returns slide part paths (e.g. `ppt/slides/slide1.xml`) in presentation order
+    fn slide_paths(archive: &mut ZipArchive<File>) -> Result<Vec<String>> {
+        let presentation = read_zip_entry(archive, "ppt/presentation.xml")?
+            .ok_or_else(|| anyhow::anyhow!("Missing ppt/presentation.xml; not a PowerPoint file"))?;
+        let relationships = match read_zip_entry(archive, "ppt/_rels/presentation.xml.rels")? {
+            Some(xml) => parse_relationships(&xml)?,
+            None => Default::default(),
+        };
+
+        let mut paths = Vec::new();
+        let mut reader = Reader::from_str(&presentation);
+        loop {
+            match reader.read_event().context("Failed to parse ppt/presentation.xml")? {
+                Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"sldId" => {
+                    for attr in e.attributes().flatten() {
+                        if attr.key.local_name().as_ref() == b"id" && attr.key.prefix().is_some() {
+                            let rel_id = attr.unescape_value()?;
+                            if let Some((_, target)) = relationships.get(rel_id.as_ref()) {
+                                paths.push(resolve_part_path("ppt", target));
+                            }
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Parses the text-bearing shapes of a slide or notes part
+    fn parse_shapes(xml: &str) -> Result<Vec<Shape>> {
+        let mut reader = Reader::from_str(xml);
+        let mut shapes = Vec::new();
+        let mut current: Option<Shape> = None;
+        // Paragraphs outside of `p:sp` (e.g. table cells in a graphic frame)
+        let mut loose = Shape::default();
+        let mut paragraph: Option<String> = None;
+        let mut in_text = false;
+
+        loop {
+            match reader.read_event().context("Failed to parse slide XML")? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"sp" => current = Some(Shape::default()),
+                    b"ph" => Self::set_placeholder(&mut current, &e)?,
+                    b"p" => paragraph = Some(String::new()),
+                    b"t" => in_text = true,
+                    _ => {}
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"ph" => Self::set_placeholder(&mut current, &e)?,
+                    b"br" => {
+                        if let Some(paragraph) = paragraph.as_mut() {
+                            paragraph.push(' ');
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Text(e) if in_text => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.push_str(&e.unescape()?);
+                    }
+                }
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"t" => in_text = false,
+                    b"p" => {
+                        if let Some(text) = paragraph.take() {
+                            let text = text.trim();
+                            if !text.is_empty() {
+                                current.as_mut().unwrap_or(&mut loose).paragraphs.push(text.to_string());
+                            }
+                        }
+                    }
+                    b"sp" => {
+                        if let Some(shape) = current.take() {
+                            shapes.push(shape);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !loose.paragraphs.is_empty() {
+            shapes.push(loose);
+        }
+        Ok(shapes)
+    }
+
+    fn set_placeholder(current: &mut Option<Shape>, e: &quick_xml::events::BytesStart) -> Result<()> {
+        if let Some(shape) = current.as_mut() {
+            // A placeholder without an explicit type is a body placeholder
+            let mut placeholder = "body".to_string();
+            for attr in e.attributes().flatten() {
+                if attr.key.local_name().as_ref() == b"type" {
+                    placeholder = attr.unescape_value()?.into_owned();
+                }
+            }
+            shape.placeholder = Some(placeholder);
+        }
+        Ok(())
+    }
+
+    /// Returns the relationships of a slide as `(Type, part path)` pairs
+    fn slide_relationships(archive: &mut ZipArchive<File>, slide_path: &str) -> Result<Vec<(String, String)>> {
+        let (slide_dir, slide_file) = slide_path.rsplit_once('/').unwrap_or(("", slide_path));
+        let rels_path = format!("{}/_rels/{}.rels", slide_dir, slide_file);
+        let Some(rels_xml) = read_zip_entry(archive, &rels_path)? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(parse_relationships(&rels_xml)?
+            .into_values()
+            .map(|(rel_type, target)| (rel_type, resolve_part_path(slide_dir, &target)))
+            .collect())
+    }
+
+    /// Reads the speaker notes linked from a slide, if any
+    fn slide_notes(archive: &mut ZipArchive<File>, slide_path: &str) -> Result<Vec<String>> {
+        let notes_path = Self::slide_relationships(archive, slide_path)?
+            .into_iter()
+            .find(|(rel_type, _)| rel_type.ends_with("/notesSlide"))
+            .map(|(_, path)| path);
+        let Some(notes_path) = notes_path else {
+            return Ok(Vec::new());
+        };
+        let Some(notes_xml) = read_zip_entry(archive, &notes_path)? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(Self::parse_shapes(&notes_xml)?
+            .into_iter()
+            .filter(|shape| shape.placeholder.as_deref() == Some("body"))
+            .flat_map(|shape| shape.paragraphs)
+            .collect())
+    }
+}
+
+impl DocumentExtractor for PptxExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "PptxExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        let slide_paths = Self::slide_paths(&mut archive)
+            .with_context(|| format!("Failed to read slide list from: {}", file_path.display()))?;
+
+        let mut sections = Vec::new();
+        for (index, slide_path) in slide_paths.iter().enumerate() {
+            let slide_xml = read_zip_entry(&mut archive, slide_path)?
+                .ok_or_else(|| anyhow::anyhow!("Missing slide part: {}", slide_path))?;
+            let shapes = Self::parse_shapes(&slide_xml)
+                .with_context(|| format!("Failed to parse slide {} of: {}", index + 1, file_path.display()))?;
+
+            let title = shapes
+                .iter()
+                .filter(|shape| shape.is_title())
+                .flat_map(|shape| shape.paragraphs.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let mut section = if title.is_empty() {
+                format!("Slide {}", index + 1)
+            } else {
+                format!("Slide {}: {}", index + 1, title)
+            };
+
+            for paragraph in shapes
+                .iter()
+                .filter(|shape| !shape.is_title() && !shape.is_ignored())
+                .flat_map(|shape| &shape.paragraphs)
+            {
+                section.push('\n');
+                section.push_str(paragraph);
+            }
+
+            let notes = Self::slide_notes(&mut archive, slide_path)?;
+            if !notes.is_empty() {
+                section.push_str("\n\nSpeaker notes:\n");
+                section.push_str(&notes.join("\n"));
+            }
+
+            sections.push(section);
+        }
+
+        Ok(sections.join("\n\n"))
+    }
+
+    /// Reads the images in `ppt/media`, numbered by the first slide that shows them
+    ///
+    /// Images used only by layouts and masters come last, without a slide number.
+    fn extract_images(&self, file_path: &Path) -> Result<Vec<EmbeddedImage>> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        let slide_paths = Self::slide_paths(&mut archive)
+            .with_context(|| format!("Failed to read slide list from: {}", file_path.display()))?;
+        let mut first_slide = HashMap::new();
+        for (index, slide_path) in slide_paths.iter().enumerate() {
+            for (rel_type, path) in Self::slide_relationships(&mut archive, slide_path)? {
+                if rel_type.ends_with("/image") {
+                    first_slide.entry(path).or_insert(index + 1);
+                }
+            }
+        }
+
+        let mut images = read_zip_images(&mut archive, "ppt/media/")?;
+        for image in &mut images {
+            image.page = image.name.as_ref().and_then(|name| first_slide.get(name)).copied();
+        }
+        images.sort_by_key(|image| image.page.unwrap_or(usize::MAX));
+        Ok(images)
+    }
+
+    /// Reads the document properties; the slide count comes from the slide list
+    fn extract_metadata(&self, file_path: &Path) -> Result<DocumentMetadata> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        let mut metadata = read_core_properties(&mut archive)
+            .with_context(|| format!("Failed to read PPTX properties: {}", file_path.display()))?;
+        let slide_paths = Self::slide_paths(&mut archive)
+            .with_context(|| format!("Failed to read slide list from: {}", file_path.display()))?;
+        metadata.page_count = Some(slide_paths.len());
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_pptx() {
+        let mut pptx_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pptx_path.push("fixtures");
+        pptx_path.push("deck.pptx");
+
+        let result = PptxExtractor.extract_text_from_file(&pptx_path);
+        assert!(result.is_ok(), "Failed to extract PPTX: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Slide 1: Quarterly Review\n"), "First slide should be labeled. Got: {}", text);
+        assert!(text.contains("Revenue up 12%\nChurn down"), "Body text should follow the title. Got: {}", text);
+        assert!(text.contains("Speaker notes:\nMention the new Medellín office."), "Notes should be included. Got: {}", text);
+        assert!(text.contains("\n\nSlide 2: Next Steps\n"), "Second slide should be labeled. Got: {}", text);
+        assert!(!text.contains("‹#›"), "Slide number placeholders should be skipped. Got: {}", text);
+        assert!(text.find("Slide 1").unwrap() < text.find("Slide 2").unwrap());
+    }
+
+    #[test]
+    fn test_extract_text_from_non_zip_file() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("sample_utf8.txt");
+
+        let result = PptxExtractor.extract_text_from_file(&path);
+        assert!(result.is_err(), "Non-zip files should be rejected");
+    }
+
+    #[test]
+    fn test_extract_metadata_from_pptx() {
+        let mut pptx_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pptx_path.push("fixtures");
+        pptx_path.push("deck.pptx");
+
+        // The deck has no docProps parts; only the slide count is known
+        let metadata = PptxExtractor.extract_metadata(&pptx_path).unwrap();
+        assert_eq!(metadata, DocumentMetadata { page_count: Some(2), ..Default::default() });
+    }
+
+    #[test]
+    fn test_extract_images_from_pptx() {
+        use std::io::{Read, Write};
+        use zip::write::SimpleFileOptions;
+
+        let mut pptx_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        pptx_path.push("fixtures");
+        pptx_path.push("deck.pptx");
+
+        // The deck plus a chart on slide 2, a logo only the master uses and a vector image
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deck.pptx");
+        let mut source = open_zip(&pptx_path).unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        for index in 0..source.len() {
+            let mut entry = source.by_index(index).unwrap();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            writer.start_file(entry.name().unwrap(), SimpleFileOptions::default()).unwrap();
+            writer.write_all(&content).unwrap();
+        }
+        let entries: [(&str, &[u8]); 4] = [
+            ("ppt/media/image1.png", b"\x89PNG\r\n\x1a\nlogo"),
+            ("ppt/media/image2.jpeg", b"\xff\xd8\xff\xe0chart"),
+            ("ppt/media/image3.emf", b"\x01\0\0\0 EMF"),
+            (
+                "ppt/slides/_rels/slide2.xml.rels",
+                br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image2.jpeg"/>
+</Relationships>"#,
+            ),
+        ];
+        for (name, content) in entries {
+            writer.start_file(name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let images = PptxExtractor.extract_images(&path).unwrap();
+        let summary: Vec<(Option<usize>, Option<&str>, &str)> =
+            images.iter().map(|image| (image.page, image.name.as_deref(), image.mime_type)).collect();
+        assert_eq!(
+            summary,
+            [
+                (Some(2), Some("ppt/media/image2.jpeg"), "image/jpeg"),
+                (None, Some("ppt/media/image1.png"), "image/png"),
+            ]
+        );
+        assert_eq!(images[0].data, b"\xff\xd8\xff\xe0chart");
+    }
+}
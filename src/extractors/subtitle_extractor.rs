@@ -0,0 +1,192 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Subtitle extractor for SubRip (.srt) and WebVTT (.vtt) cue files
+///
+/// Each cue becomes one line of dialog with formatting tags removed. WebVTT voice
+/// spans (`<v Speaker>`) are rendered as `Speaker: text`, and cues repeating the
+/// previous cue's text are dropped.
+pub struct SubtitleExtractor {
+    /// Prefix each line with the cue's start time
+    timestamps: bool,
+}
+
+impl Default for SubtitleExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubtitleExtractor {
+    /// Creates a subtitle extractor that returns the dialog text only
+    pub fn new() -> Self {
+        Self { timestamps: false }
+    }
+
+    /// Enables or disables `[HH:MM:SS]` start times in front of each cue
+    pub fn with_timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Normalizes a cue start time (`00:01:02,500`, `01:02.500`) to `HH:MM:SS`
+    fn format_timestamp(timing: &str) -> Option<String> {
+        let start = timing.split("-->").next()?.trim();
+        let without_fraction = start.split(['.', ',']).next()?;
+        let parts: Vec<u32> = without_fraction
+            .split(':')
+            .map(|part| part.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let (hours, minutes, seconds) = match parts.as_slice() {
+            [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+            [minutes, seconds] => (0, *minutes, *seconds),
+            _ => return None,
+        };
+        Some(format!("{:02}:{:02}:{:02}", hours, minutes, seconds))
+    }
+
+    /// Removes markup from cue text: HTML-like tags, WebVTT voice spans and SSA overrides (`{\an8}`)
+    fn clean_cue_text(text: &str) -> String {
+        let mut speaker = None;
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find(['<', '{']) {
+            output.push_str(&rest[..start]);
+            let close = if rest[start..].starts_with('<') { '>' } else { '}' };
+            let Some(end) = rest[start..].find(close) else {
+                output.push_str(&rest[start..]);
+                rest = "";
+                break;
+            };
+            let tag = &rest[start + 1..start + end];
+            // `<v Speaker>` and `<v.class Speaker>` name who is talking
+            if let Some(voice) = tag.strip_prefix('v').filter(|v| v.starts_with([' ', '.'])) {
+                if let Some((_, name)) = voice.split_once(' ') {
+                    speaker.get_or_insert_with(|| name.trim().to_string());
+                }
+            }
+            rest = &rest[start + end + 1..];
+        }
+        output.push_str(rest);
+
+        let text = output
+            .replace("&amp;", "&")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&nbsp;", " ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        match speaker {
+            Some(speaker) if !text.is_empty() => format!("{}: {}", speaker, text),
+            _ => text,
+        }
+    }
+
+    /// Converts SRT or WebVTT source into dialog lines
+    pub fn extract_from_str(&self, source: &str) -> String {
+        let source = source.replace("\r\n", "\n").replace('\r', "\n");
+        let mut lines: Vec<String> = Vec::new();
+        let mut previous_text = String::new();
+
+        for block in source.split("\n\n") {
+            let block_lines: Vec<&str> = block.lines().collect();
+            // Headers, NOTE, STYLE and REGION blocks have no timing line
+            let Some(timing_index) = block_lines.iter().position(|line| line.contains("-->")) else {
+                continue;
+            };
+
+            let text = Self::clean_cue_text(&block_lines[timing_index + 1..].join("\n"));
+            if text.is_empty() || text == previous_text {
+                continue;
+            }
+
+            let timestamp = self
+                .timestamps
+                .then(|| Self::format_timestamp(block_lines[timing_index]))
+                .flatten();
+            match timestamp {
+                Some(timestamp) => lines.push(format!("[{}] {}", timestamp, text)),
+                None => lines.push(text.clone()),
+            }
+            previous_text = text;
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl DocumentExtractor for SubtitleExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "SubtitleExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read subtitle file: {}", file_path.display()))?;
+
+        let source = TxtExtractor::decode_bytes(&file_bytes);
+        Ok(self.extract_from_str(&source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push(name);
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_srt() {
+        let result = SubtitleExtractor::new().extract_text_from_file(&fixture_path("briefing.srt"));
+        assert!(result.is_ok(), "Failed to extract SRT: {:?}", result.err());
+
+        let expected = "Welcome to the safety briefing.
+Fasten your seatbelt when the sign is on.
+Exits are marked in red.";
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_extract_text_from_srt_with_timestamps() {
+        let text = SubtitleExtractor::new()
+            .with_timestamps(true)
+            .extract_text_from_file(&fixture_path("briefing.srt"))
+            .unwrap();
+        let expected = "[00:00:01] Welcome to the safety briefing.
+[00:00:04] Fasten your seatbelt when the sign is on.
+[01:02:03] Exits are marked in red.";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_extract_text_from_vtt() {
+        let text = SubtitleExtractor::new()
+            .with_timestamps(true)
+            .extract_text_from_file(&fixture_path("training.vtt"))
+            .unwrap();
+        let expected = "[00:00:00] Instructor: Good morning, everyone.
+[00:00:03] Trainee: Morning! Ready to start.";
+        assert_eq!(text, expected);
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Elements whose content is never text meant for the reader
+const SKIPPED_ELEMENTS: &[&[u8]] = &[b"style", b"script"];
+
+/// SVG extractor that collects the labels of a drawing
+///
+/// The document-level `<title>` and `<desc>` become a header; every `<text>`
+/// element (including its `<tspan>`/`<textPath>` children) and every nested
+/// `<title>`/`<desc>` becomes a line. A `<tspan>` positioned with `x`, `y` or `dy`
+/// starts a new line, as it does when rendered.
+pub struct SvgExtractor;
+
+impl SvgExtractor {
+    /// Returns true when a `<tspan>` is explicitly positioned on a new line
+    fn starts_new_line(e: &BytesStart) -> bool {
+        e.attributes()
+            .flatten()
+            .any(|attr| matches!(attr.key.local_name().as_ref(), b"x" | b"y" | b"dy"))
+    }
+
+    fn push_line(lines: &mut Vec<String>, text: &str) {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            lines.push(text);
+        }
+    }
+
+    /// Extracts the header and text lines from SVG source
+    pub fn extract_from_str(svg: &str) -> Result<String> {
+        let mut reader = Reader::from_str(svg);
+        let mut header = Vec::new();
+        let mut lines = Vec::new();
+        // Element names from the root down to the current element
+        let mut stack: Vec<Vec<u8>> = Vec::new();
+        // Text being collected for the current label element, and its name
+        let mut current: Option<(Vec<u8>, String)> = None;
+        let mut skip_depth = 0usize;
+
+        loop {
+            let position = reader.buffer_position();
+            let event = reader
+                .read_event()
+                .with_context(|| format!("Failed to parse SVG near byte {}", position))?;
+
+            match event {
+                Event::Start(e) => {
+                    let name = e.local_name().as_ref().to_vec();
+                    if skip_depth > 0 || SKIPPED_ELEMENTS.contains(&name.as_slice()) {
+                        skip_depth += 1;
+                    } else if let Some((_, text)) = current.as_mut() {
+                        if name == b"tspan" && Self::starts_new_line(&e) && !text.trim().is_empty() {
+                            text.push('\n');
+                        } else {
+                            text.push(' ');
+                        }
+                    } else if matches!(name.as_slice(), b"text" | b"title" | b"desc") {
+                        current = Some((name.clone(), String::new()));
+                    }
+                    stack.push(name);
+                }
+                Event::End(_) => {
+                    let name = stack.pop().unwrap_or_default();
+                    if skip_depth > 0 {
+                        skip_depth -= 1;
+                        continue;
+                    }
+                    if current.as_ref().is_some_and(|(label, _)| *label == name) {
+                        let (label, text) = current.take().unwrap_or_default();
+                        // A title or description directly under the root describes the whole drawing
+                        let is_root_label = stack.len() == 1 && label != b"text";
+                        if is_root_label && label == b"title" && header.is_empty() {
+                            header.push(format!("Title: {}", text.split_whitespace().collect::<Vec<_>>().join(" ")));
+                        } else if is_root_label && label == b"desc" {
+                            header.push(format!("Description: {}", text.split_whitespace().collect::<Vec<_>>().join(" ")));
+                        } else {
+                            for line in text.lines() {
+                                Self::push_line(&mut lines, line);
+                            }
+                        }
+                    }
+                }
+                Event::Text(e) if skip_depth == 0 => {
+                    if let Some((_, text)) = current.as_mut() {
+                        text.push_str(&e.unescape()?);
+                    }
+                }
+                Event::CData(e) if skip_depth == 0 => {
+                    if let Some((_, text)) = current.as_mut() {
+                        text.push_str(&String::from_utf8_lossy(&e));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        let header = header.join("\n");
+        let body = lines.join("\n");
+        Ok(match (header.is_empty(), body.is_empty()) {
+            (false, false) => format!("{}\n\n{}", header, body),
+            (false, true) => header,
+            _ => body,
+        })
+    }
+}
+
+impl DocumentExtractor for SvgExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "SvgExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read SVG file: {}", file_path.display()))?;
+
+        let svg = TxtExtractor::decode_bytes(&file_bytes);
+        Self::extract_from_str(&svg)
+            .with_context(|| format!("Failed to extract SVG: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_svg() {
+        let mut svg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        svg_path.push("fixtures");
+        svg_path.push("network.svg");
+
+        let result = SvgExtractor.extract_text_from_file(&svg_path);
+        assert!(result.is_ok(), "Failed to extract SVG: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "Title: Booking platform network
+Description: Traffic flow from the edge to the database tier.
+
+Edge tier
+Load balancer
+Booking API
+3 replicas & autoscaling
+Postgres primary";
+        assert_eq!(text, expected);
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+use crate::extractors::yaml_extractor::YamlExtractor;
+
+/// TOML extractor that validates documents and renders them as indented text
+pub struct TomlExtractor;
+
+impl TomlExtractor {
+    /// Converts a TOML value into the YAML value model used for rendering
+    fn to_yaml_value(value: &toml::Value) -> serde_yaml::Value {
+        match value {
+            toml::Value::String(s) => serde_yaml::Value::String(s.clone()),
+            toml::Value::Integer(i) => serde_yaml::Value::Number((*i).into()),
+            toml::Value::Float(f) => serde_yaml::Value::Number((*f).into()),
+            toml::Value::Boolean(b) => serde_yaml::Value::Bool(*b),
+            toml::Value::Datetime(dt) => serde_yaml::Value::String(dt.to_string()),
+            toml::Value::Array(items) => serde_yaml::Value::Sequence(items.iter().map(Self::to_yaml_value).collect()),
+            toml::Value::Table(table) => serde_yaml::Value::Mapping(
+                table
+                    .iter()
+                    .map(|(key, child)| (serde_yaml::Value::String(key.clone()), Self::to_yaml_value(child)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Parses (and thereby validates) TOML source and renders it as indented text
+    pub fn extract_from_str(source: &str) -> Result<String> {
+        let table: toml::Table = source.parse().context("Invalid TOML")?;
+        let value = Self::to_yaml_value(&toml::Value::Table(table));
+        Ok(YamlExtractor::render_value(&value))
+    }
+}
+
+impl DocumentExtractor for TomlExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "TomlExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read TOML file: {}", file_path.display()))?;
+
+        let source = TxtExtractor::decode_bytes(&file_bytes);
+        Self::extract_from_str(&source)
+            .with_context(|| format!("Failed to extract TOML: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_toml() {
+        let mut toml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        toml_path.push("fixtures");
+        toml_path.push("infra.toml");
+
+        let result = TomlExtractor.extract_text_from_file(&toml_path);
+        assert!(result.is_ok(), "Failed to extract TOML: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.contains("region: us-east-1\n"), "Got: {}", text);
+        assert!(text.contains("database:\n  host: db.internal\n  port: 5432\n"), "Tables should be nested. Got: {}", text);
+        assert!(text.contains("  replicas:\n    - db-1\n    - db-2"), "Arrays should be listed. Got: {}", text);
+        assert!(text.contains("deployed: 2025-08-28T06:10:00Z"), "Datetimes should be readable. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_from_invalid_toml() {
+        let result = TomlExtractor::extract_from_str("[database\nport = ");
+        assert!(result.is_err(), "Invalid TOML should be rejected");
+    }
+}
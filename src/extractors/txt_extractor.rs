@@ -0,0 +1,105 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use chardetng::{EncodingDetector, Iso2022JpDetection, Utf8Detection};
+use encoding_rs::Encoding;
+use crate::extractor::DocumentExtractor;
+
+/// Plain-text extractor that detects the character encoding and converts to UTF-8
+pub struct TxtExtractor;
+
+impl TxtExtractor {
+    /// Decodes raw bytes into a UTF-8 string, detecting the source encoding
+    ///
+    /// A byte order mark (UTF-8, UTF-16LE, UTF-16BE) takes precedence; otherwise
+    /// the encoding is guessed from the content (UTF-8, Latin-1, Shift-JIS, ...).
+    pub fn decode_bytes(bytes: &[u8]) -> String {
+        let encoding = match Encoding::for_bom(bytes) {
+            Some((encoding, _bom_length)) => encoding,
+            None => {
+                let mut detector = EncodingDetector::new(Iso2022JpDetection::Deny);
+                detector.feed(bytes, true);
+                detector.guess(None, Utf8Detection::Allow)
+            }
+        };
+
+        // `decode` strips the BOM and replaces malformed sequences with U+FFFD
+        let (text, _encoding_used, _had_errors) = encoding.decode(bytes);
+        text.into_owned()
+    }
+}
+
+impl DocumentExtractor for TxtExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "TxtExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read text file: {}", file_path.display()))?;
+
+        Ok(Self::decode_bytes(&file_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push(name);
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_utf8_file() {
+        let result = TxtExtractor.extract_text_from_file(&fixture("sample_utf8.txt"));
+        assert!(result.is_ok(), "Failed to extract text: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.contains("Café crème"), "Should decode UTF-8 accents. Got: {}", text);
+        assert!(text.contains("東京"), "Should decode UTF-8 CJK characters. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_text_from_utf16_file() {
+        let text = TxtExtractor
+            .extract_text_from_file(&fixture("sample_utf16le.txt"))
+            .expect("UTF-16 file should be extracted");
+
+        assert!(text.starts_with("Boarding pass"), "BOM should be stripped. Got: {:?}", text);
+        assert!(text.contains("Café crème"), "Should decode UTF-16 text. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_text_from_legacy_encodings() {
+        let latin1 = TxtExtractor
+            .extract_text_from_file(&fixture("sample_latin1.txt"))
+            .expect("Latin-1 file should be extracted");
+        assert!(latin1.contains("Café crème à la française"), "Should decode Latin-1. Got: {}", latin1);
+
+        let shift_jis = TxtExtractor
+            .extract_text_from_file(&fixture("sample_shift_jis.txt"))
+            .expect("Shift-JIS file should be extracted");
+        assert!(shift_jis.contains("東京駅"), "Should decode Shift-JIS. Got: {}", shift_jis);
+    }
+
+    #[test]
+    fn test_extract_text_from_missing_file() {
+        let result = TxtExtractor.extract_text_from_file(&fixture("does_not_exist.txt"));
+        assert!(result.is_err(), "Missing files should produce an error");
+    }
+}
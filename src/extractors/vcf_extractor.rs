@@ -0,0 +1,186 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::ics_extractor::IcsExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// A contact property: the uppercased name, its TYPE labels and the raw value
+struct VcardProperty {
+    name: String,
+    types: Vec<String>,
+    value: String,
+}
+
+/// vCard extractor that renders each contact as readable name/phone/email/org lines
+///
+/// vCard shares its line folding and escaping with iCalendar, so those helpers
+/// come from `IcsExtractor`.
+pub struct VcfExtractor;
+
+impl VcfExtractor {
+    /// Parses a content line, accepting both `TYPE=work,voice` (3.0+) and bare `WORK;VOICE` (2.1) labels
+    fn parse_property(line: &str) -> Option<VcardProperty> {
+        let (head, value) = line.split_once(':')?;
+        let mut parts = head.split(';');
+        let name = parts.next()?;
+        // Grouped properties look like `item1.EMAIL`
+        let name = name.rsplit('.').next().unwrap_or(name).to_uppercase();
+
+        let mut types = Vec::new();
+        for param in parts {
+            let labels = match param.split_once('=') {
+                Some((key, labels)) if key.eq_ignore_ascii_case("TYPE") => labels,
+                Some(_) => continue,
+                None => param,
+            };
+            types.extend(
+                labels
+                    .trim_matches('"')
+                    .split(',')
+                    .map(str::to_lowercase)
+                    .filter(|label| !label.is_empty() && label != "internet" && label != "pref"),
+            );
+        }
+
+        Some(VcardProperty { name, types, value: value.to_string() })
+    }
+
+    /// Joins the non-empty components of a structured value (N, ADR, ORG)
+    fn join_components(value: &str, order: &[usize], separator: &str) -> String {
+        let components: Vec<String> = value.split(';').map(IcsExtractor::unescape).collect();
+        order
+            .iter()
+            .filter_map(|&index| components.get(index))
+            .filter(|component| !component.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// Renders one card's properties
+    fn render_card(properties: &[VcardProperty]) -> String {
+        let formatted_name = properties.iter().find(|p| p.name == "FN").map(|p| IcsExtractor::unescape(&p.value));
+        // N is Family;Given;Additional;Prefix;Suffix and only used when FN is missing
+        let structured_name = properties
+            .iter()
+            .find(|p| p.name == "N")
+            .map(|p| Self::join_components(&p.value, &[3, 1, 2, 0, 4], " "));
+        let name = formatted_name
+            .filter(|name| !name.is_empty())
+            .or(structured_name)
+            .unwrap_or_else(|| "(no name)".to_string());
+
+        let mut lines = vec![format!("Name: {}", name)];
+        for property in properties {
+            let (label, value) = match property.name.as_str() {
+                "ORG" => ("Organization", Self::join_components(&property.value, &[0, 1, 2, 3], ", ")),
+                "TITLE" => ("Title", IcsExtractor::unescape(&property.value)),
+                "TEL" => ("Phone", IcsExtractor::unescape(&property.value)),
+                "EMAIL" => ("Email", IcsExtractor::unescape(&property.value)),
+                // ADR is PO box;Extended;Street;City;Region;Postal code;Country
+                "ADR" => ("Address", Self::join_components(&property.value, &[0, 1, 2, 3, 4, 5, 6], ", ")),
+                "URL" => ("URL", property.value.trim().to_string()),
+                "BDAY" => ("Birthday", property.value.trim().to_string()),
+                "NOTE" => ("Note", IcsExtractor::unescape(&property.value)),
+                _ => continue,
+            };
+            if value.is_empty() {
+                continue;
+            }
+            if property.types.is_empty() {
+                lines.push(format!("{}: {}", label, value));
+            } else {
+                lines.push(format!("{} ({}): {}", label, property.types.join(", "), value));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Renders every card in a vCard file, separated by blank lines
+    pub fn extract_from_str(source: &str) -> String {
+        let mut cards = Vec::new();
+        let mut current: Option<Vec<VcardProperty>> = None;
+
+        for line in IcsExtractor::unfold(source) {
+            let Some(property) = Self::parse_property(&line) else {
+                continue;
+            };
+            let value = property.value.trim();
+            match property.name.as_str() {
+                "BEGIN" if value.eq_ignore_ascii_case("VCARD") => current = Some(Vec::new()),
+                "END" if value.eq_ignore_ascii_case("VCARD") => {
+                    if let Some(properties) = current.take() {
+                        cards.push(Self::render_card(&properties));
+                    }
+                }
+                _ => {
+                    if let Some(properties) = current.as_mut() {
+                        properties.push(property);
+                    }
+                }
+            }
+        }
+
+        cards.join("\n\n")
+    }
+}
+
+impl DocumentExtractor for VcfExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "VcfExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read vCard file: {}", file_path.display()))?;
+
+        let source = TxtExtractor::decode_bytes(&file_bytes);
+        if !source.trim_start().to_uppercase().starts_with("BEGIN:VCARD") {
+            return Err(anyhow::anyhow!("Not a vCard file: {}", file_path.display()));
+        }
+        Ok(Self::extract_from_str(&source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_vcf() {
+        let mut vcf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        vcf_path.push("fixtures");
+        vcf_path.push("contacts.vcf");
+
+        let result = VcfExtractor.extract_text_from_file(&vcf_path);
+        assert!(result.is_ok(), "Failed to extract vCard: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "Name: Dr. Ana Maria Rivera
+Organization: Copa Airlines, Ground Operations
+Title: Station Manager
+Phone (work, voice): +507 217 2672
+Phone (cell): +507 6123 4567
+Email: ana.rivera@example.com
+Address (work): Av. Domingo Diaz, Panama City, 0816, Panama
+Note: Prefers WhatsApp, replies within a day. Ask about lounge access.
+
+Name: Wei Chen
+Phone (home, voice): +1 303 555 0148
+Email: wei.chen@example.org
+Birthday: 1988-04-12";
+        assert_eq!(text, expected);
+    }
+}
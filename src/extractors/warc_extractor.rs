@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use anyhow::{Context, Result};
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use crate::extractor::DocumentExtractor;
+use crate::extractors::html_extractor::HtmlExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// Largest record block read into memory; bigger captures (videos, downloads) are skipped
+const MAX_RECORD_SIZE: u64 = 50 * 1024 * 1024;
+
+/// A captured HTTP response from a WARC `response` record
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarcResponse {
+    /// Value of `WARC-Target-URI`
+    pub url: String,
+    /// Value of `WARC-Date`
+    pub date: Option<String>,
+    pub status: Option<u16>,
+    /// HTTP `Content-Type`, without parameters
+    pub content_type: Option<String>,
+    /// Payload with transfer and content encodings removed; empty when the record was too large
+    pub body: Vec<u8>,
+}
+
+impl WarcResponse {
+    /// Returns the text of HTML and plain-text payloads, `None` for other content
+    pub fn text(&self) -> Option<String> {
+        let content_type = self.content_type.as_deref().unwrap_or_default();
+        if content_type.contains("html") {
+            let html = TxtExtractor::decode_bytes(&self.body);
+            Some(HtmlExtractor::new().with_links(false).extract_from_str(&html))
+        } else if content_type == "text/plain" {
+            Some(TxtExtractor::decode_bytes(&self.body))
+        } else {
+            None
+        }
+    }
+}
+
+/// One WARC record: its headers (lowercase names) and, when kept, its content block
+struct WarcRecord {
+    headers: HashMap<String, String>,
+    block: Vec<u8>,
+}
+
+/// WARC web archive extractor (`.warc`, `.warc.gz`)
+///
+/// Without a URL the extractor lists the captured responses and extracts the text of
+/// every HTML and plain-text page; with one it extracts only that capture. The file
+/// is streamed record by record, so archives larger than memory can be read.
+#[derive(Default)]
+pub struct WarcExtractor {
+    url: Option<String>,
+}
+
+impl WarcExtractor {
+    /// Creates an extractor that extracts every captured page
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extracts only the response captured for `url` (its `WARC-Target-URI`)
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Opens a WARC file, decompressing it when it is gzipped (one member per record)
+    fn open(file_path: &Path) -> Result<Box<dyn BufRead>> {
+        let file = File::open(file_path)
+            .with_context(|| format!("Failed to open file: {}", file_path.display()))?;
+        let mut reader = BufReader::new(file);
+        let gzipped = reader.fill_buf().context("Failed to read WARC file")?.starts_with(&[0x1f, 0x8b]);
+        if gzipped {
+            Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+        } else {
+            Ok(Box::new(reader))
+        }
+    }
+
+    /// Reads the next record; the block is only kept for response records that fit in memory
+    fn next_record(reader: &mut dyn BufRead) -> Result<Option<WarcRecord>> {
+        let mut line = String::new();
+
+        // Records are separated by blank lines
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).context("Failed to read WARC record")? == 0 {
+                return Ok(None);
+            }
+            if !line.trim().is_empty() {
+                break;
+            }
+        }
+        if !line.starts_with("WARC/") {
+            return Err(anyhow::anyhow!("Expected a WARC record, found: {}", line.trim()));
+        }
+
+        let mut headers = HashMap::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).context("Failed to read WARC headers")? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+
+        let length: u64 = headers
+            .get("content-length")
+            .and_then(|length| length.parse().ok())
+            .context("WARC record without a valid Content-Length")?;
+        let is_response = headers.get("warc-type").map(String::as_str) == Some("response");
+
+        let mut block = Vec::new();
+        if is_response && length <= MAX_RECORD_SIZE {
+            reader.take(length).read_to_end(&mut block).context("Failed to read WARC record block")?;
+        } else {
+            io::copy(&mut reader.take(length), &mut io::sink()).context("Failed to skip WARC record block")?;
+        }
+        Ok(Some(WarcRecord { headers, block }))
+    }
+
+    /// Removes HTTP chunked transfer encoding
+    fn dechunk(mut data: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        while let Some(line_end) = data.windows(2).position(|window| window == b"\r\n") {
+            let size_line = String::from_utf8_lossy(&data[..line_end]);
+            let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+            let Ok(size) = usize::from_str_radix(size_hex, 16) else {
+                break;
+            };
+            let start = line_end + 2;
+            if size == 0 || start + size > data.len() {
+                body.extend_from_slice(&data[start.min(data.len())..(start + size).min(data.len())]);
+                break;
+            }
+            body.extend_from_slice(&data[start..start + size]);
+            data = data.get(start + size + 2..).unwrap_or_default();
+        }
+        body
+    }
+
+    /// Splits an HTTP response into status, content type and decoded payload
+    fn parse_http_response(url: String, date: Option<String>, block: &[u8]) -> WarcResponse {
+        let (head, payload) = match block.windows(4).position(|window| window == b"\r\n\r\n") {
+            Some(position) => (&block[..position], &block[position + 4..]),
+            None => (block, &[][..]),
+        };
+        let head = String::from_utf8_lossy(head);
+        let mut lines = head.lines();
+
+        let status = lines
+            .next()
+            .and_then(|status_line| status_line.split_whitespace().nth(1))
+            .and_then(|code| code.parse().ok());
+        let headers: HashMap<String, String> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_lowercase()))
+            .collect();
+        let header = |name: &str| headers.get(name).map(String::as_str).unwrap_or_default();
+
+        let mut body = if header("transfer-encoding").contains("chunked") {
+            Self::dechunk(payload)
+        } else {
+            payload.to_vec()
+        };
+        let mut decoded = Vec::new();
+        let decompressed = match header("content-encoding") {
+            "gzip" | "x-gzip" => MultiGzDecoder::new(&body[..]).take(MAX_RECORD_SIZE).read_to_end(&mut decoded),
+            "deflate" => ZlibDecoder::new(&body[..]).take(MAX_RECORD_SIZE).read_to_end(&mut decoded),
+            _ => Ok(0),
+        };
+        if decompressed.is_ok_and(|size| size > 0) {
+            body = decoded;
+        }
+
+        WarcResponse {
+            url,
+            date,
+            status,
+            content_type: headers
+                .get("content-type")
+                .and_then(|value| value.split(';').next())
+                .map(|value| value.trim().to_string()),
+            body,
+        }
+    }
+
+    /// Reads every captured response, in archive order
+    pub fn read_responses(&self, file_path: &Path) -> Result<Vec<WarcResponse>> {
+        let mut reader = Self::open(file_path)?;
+        let mut responses = Vec::new();
+
+        while let Some(WarcRecord { mut headers, block }) = Self::next_record(&mut reader)
+            .with_context(|| format!("Failed to read WARC archive: {}", file_path.display()))?
+        {
+            if headers.get("warc-type").map(String::as_str) != Some("response") {
+                continue;
+            }
+            let url = headers.remove("warc-target-uri").unwrap_or_default();
+            if self.url.as_ref().is_some_and(|selected| selected != &url) {
+                continue;
+            }
+            responses.push(Self::parse_http_response(url, headers.remove("warc-date"), &block));
+        }
+        Ok(responses)
+    }
+}
+
+impl DocumentExtractor for WarcExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "WarcExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let responses = self.read_responses(file_path)?;
+
+        if let Some(url) = &self.url {
+            // A page may be captured several times; the last capture wins
+            let response = responses
+                .last()
+                .ok_or_else(|| anyhow::anyhow!("No response for {} in: {}", url, file_path.display()))?;
+            return response.text().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The response for {} is not text ({})",
+                    url,
+                    response.content_type.as_deref().unwrap_or("no content type")
+                )
+            });
+        }
+
+        let mut output = format!("Web archive with {} responses:", responses.len());
+        for response in &responses {
+            output.push_str(&format!(
+                "\n- {} ({}, {}, {})",
+                response.url,
+                response.status.map_or("no status".to_string(), |status| status.to_string()),
+                response.content_type.as_deref().unwrap_or("no content type"),
+                response.date.as_deref().unwrap_or("no date"),
+            ));
+        }
+        for response in &responses {
+            if let Some(text) = response.text().filter(|text| !text.trim().is_empty()) {
+                output.push_str(&format!("\n\n=== {} ===\n{}", response.url, text.trim()));
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn warc_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("site.warc.gz");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_warc() {
+        let result = WarcExtractor::new().extract_text_from_file(&warc_path());
+        assert!(result.is_ok(), "Failed to extract WARC: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Web archive with 3 responses:\n"), "Got: {}", text);
+        assert!(
+            text.contains("- https://example.com/ (200, text/html, 2025-08-28T12:00:00Z)"),
+            "Responses should be listed. Got: {}",
+            text
+        );
+        assert!(text.contains("- https://example.com/logo.png (200, image/png,"), "Got: {}", text);
+        assert!(
+            text.contains("=== https://example.com/ ===\nFares\n\n# Fare alerts\n\nAUS to PTY from 412.50 USD."),
+            "HTML should be extracted. Got: {}",
+            text
+        );
+        assert!(
+            text.contains("=== https://example.com/robots.txt ===\nUser-agent: *"),
+            "Chunked, gzipped text should be decoded. Got: {}",
+            text
+        );
+        assert!(!text.contains("=== https://example.com/logo.png"), "Images should not be extracted. Got: {}", text);
+        assert!(!text.contains("GET / HTTP/1.1"), "Request records should be skipped. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_text_from_warc_by_url() {
+        let text = WarcExtractor::new()
+            .with_url("https://example.com/robots.txt")
+            .extract_text_from_file(&warc_path())
+            .unwrap();
+        assert_eq!(text, "User-agent: *\nDisallow: /private/\n");
+
+        let missing = WarcExtractor::new()
+            .with_url("https://example.com/missing")
+            .extract_text_from_file(&warc_path());
+        assert!(missing.is_err(), "Unknown URLs should be reported");
+
+        let image = WarcExtractor::new()
+            .with_url("https://example.com/logo.png")
+            .extract_text_from_file(&warc_path());
+        assert!(image.unwrap_err().to_string().contains("is not text (image/png)"));
+    }
+
+    #[test]
+    fn test_dechunk() {
+        assert_eq!(WarcExtractor::dechunk(b"5\r\nHello\r\n7;ext=1\r\n, world\r\n0\r\n\r\n"), b"Hello, world");
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// XML extractor that strips markup and prefixes each text node with its element path
+///
+/// `<invoice><customer><name>ACME</name></customer></invoice>` becomes
+/// `invoice/customer/name: ACME`.
+pub struct XmlExtractor;
+
+impl XmlExtractor {
+    /// Converts XML source into `element/path: text` lines
+    pub fn extract_from_str(xml: &str) -> Result<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut path: Vec<String> = Vec::new();
+        let mut lines = Vec::new();
+
+        loop {
+            let position = reader.buffer_position();
+            let event = reader
+                .read_event()
+                .with_context(|| format!("Failed to parse XML near byte {}", position))?;
+
+            let text = match event {
+                Event::Start(e) => {
+                    path.push(String::from_utf8_lossy(e.local_name().as_ref()).into_owned());
+                    continue;
+                }
+                Event::End(_) => {
+                    path.pop();
+                    continue;
+                }
+                Event::Text(e) => e.unescape()?.into_owned(),
+                Event::CData(e) => String::from_utf8_lossy(&e).into_owned(),
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !text.is_empty() {
+                lines.push(format!("{}: {}", path.join("/"), text));
+            }
+        }
+
+        Ok(lines.join("\n"))
+    }
+}
+
+impl DocumentExtractor for XmlExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "XmlExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read XML file: {}", file_path.display()))?;
+
+        let xml = TxtExtractor::decode_bytes(&file_bytes);
+        Self::extract_from_str(&xml)
+            .with_context(|| format!("Failed to extract XML: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_xml() {
+        let mut xml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        xml_path.push("fixtures");
+        xml_path.push("invoice.xml");
+
+        let result = XmlExtractor.extract_text_from_file(&xml_path);
+        assert!(result.is_ok(), "Failed to extract XML: {:?}", result.err());
+
+        let text = result.unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Invoice/ID: INV-2025-0042");
+        assert!(lines.contains(&"Invoice/AccountingCustomerParty/Party/PartyName/Name: ACME & Sons"), "Got: {}", text);
+        assert!(lines.contains(&"Invoice/InvoiceLine/Note: Fragile <handle with care>"), "CDATA should be kept. Got: {}", text);
+        assert!(lines.contains(&"Invoice/LegalMonetaryTotal/PayableAmount: 1140.00"), "Got: {}", text);
+        assert!(!text.contains("urn:oasis"), "Namespace declarations should not leak. Got: {}", text);
+    }
+
+    #[test]
+    fn test_extract_from_malformed_xml() {
+        assert!(XmlExtractor::extract_from_str("<a><b></a>").is_err());
+    }
+}
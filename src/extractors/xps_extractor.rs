@@ -0,0 +1,175 @@
+use std::fs::File;
+use std::path::Path;
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use zip::ZipArchive;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::archive::{open_zip, parse_relationships, read_zip_entry, resolve_part_path};
+use crate::extractors::odf::string_attribute;
+
+/// Glyph runs whose baselines are closer than this (in XPS units, 1/96 inch) share a line
+const LINE_TOLERANCE: f64 = 4.0;
+
+/// XPS/OXPS extractor that emits the text of each fixed page in document order
+pub struct XpsExtractor;
+
+/// A positioned run of text from a `Glyphs` element
+struct GlyphRun {
+    x: f64,
+    y: f64,
+    text: String,
+}
+
+impl XpsExtractor {
+    /// Returns the directory part of a package path
+    fn parent_dir(path: &str) -> &str {
+        path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+    }
+
+    /// Collects the `Source` attribute of every `element` in a part, resolved against the part's directory
+    fn sources(xml: &str, part_path: &str, element: &[u8]) -> Result<Vec<String>> {
+        let mut reader = Reader::from_str(xml);
+        let mut sources = Vec::new();
+        loop {
+            match reader.read_event().with_context(|| format!("Failed to parse XPS part: {}", part_path))? {
+                Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == element => {
+                    if let Some(source) = string_attribute(&e, b"Source") {
+                        sources.push(resolve_part_path(Self::parent_dir(part_path), &source));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Returns fixed page paths in document order, following root rels -> sequence -> documents
+    fn page_paths(archive: &mut ZipArchive<File>) -> Result<Vec<String>> {
+        let root_rels = read_zip_entry(archive, "_rels/.rels")?
+            .ok_or_else(|| anyhow::anyhow!("Missing _rels/.rels; not an XPS file"))?;
+        // XPS and OpenXPS use different namespaces for the same relationship
+        let sequence_path = parse_relationships(&root_rels)?
+            .into_values()
+            .find(|(rel_type, _)| rel_type.ends_with("/fixedrepresentation"))
+            .map(|(_, target)| resolve_part_path("", &target))
+            .ok_or_else(|| anyhow::anyhow!("No fixed document sequence found; not an XPS file"))?;
+
+        let sequence = read_zip_entry(archive, &sequence_path)?
+            .ok_or_else(|| anyhow::anyhow!("Missing document sequence: {}", sequence_path))?;
+
+        let mut pages = Vec::new();
+        for document_path in Self::sources(&sequence, &sequence_path, b"DocumentReference")? {
+            let document = read_zip_entry(archive, &document_path)?
+                .ok_or_else(|| anyhow::anyhow!("Missing fixed document: {}", document_path))?;
+            pages.extend(Self::sources(&document, &document_path, b"PageContent")?);
+        }
+        Ok(pages)
+    }
+
+    /// Extracts the text of a fixed page, ordering glyph runs top-to-bottom and left-to-right
+    fn page_text(xml: &str) -> Result<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut runs = Vec::new();
+        loop {
+            match reader.read_event().context("Failed to parse fixed page")? {
+                Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Glyphs" => {
+                    let Some(text) = string_attribute(&e, b"UnicodeString") else {
+                        continue;
+                    };
+                    // A leading `{}` escapes strings that would otherwise start with `{`
+                    let text = text.strip_prefix("{}").unwrap_or(&text).trim().to_string();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    let coordinate = |name: &[u8]| {
+                        string_attribute(&e, name).and_then(|v| v.trim().parse::<f64>().ok()).unwrap_or(0.0)
+                    };
+                    runs.push(GlyphRun { x: coordinate(b"OriginX"), y: coordinate(b"OriginY"), text });
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        runs.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+        let mut lines: Vec<(f64, Vec<GlyphRun>)> = Vec::new();
+        for run in runs {
+            match lines.last_mut() {
+                Some((baseline, line)) if run.y - *baseline <= LINE_TOLERANCE => line.push(run),
+                _ => lines.push((run.y, vec![run])),
+            }
+        }
+
+        Ok(lines
+            .into_iter()
+            .map(|(_, mut line)| {
+                line.sort_by(|a, b| a.x.total_cmp(&b.x));
+                line.into_iter().map(|run| run.text).collect::<Vec<_>>().join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+impl DocumentExtractor for XpsExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "XpsExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        let page_paths = Self::page_paths(&mut archive)
+            .with_context(|| format!("Failed to read page list from: {}", file_path.display()))?;
+
+        let mut sections = Vec::new();
+        for (index, page_path) in page_paths.iter().enumerate() {
+            let page_xml = read_zip_entry(&mut archive, page_path)?
+                .ok_or_else(|| anyhow::anyhow!("Missing page part: {}", page_path))?;
+            let text = Self::page_text(&page_xml)
+                .with_context(|| format!("Failed to parse page {} of: {}", index + 1, file_path.display()))?;
+            sections.push(format!("=== Page {} ===\n{}", index + 1, text));
+        }
+
+        Ok(sections.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_xps() {
+        let mut xps_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        xps_path.push("fixtures");
+        xps_path.push("passport_form.xps");
+
+        let result = XpsExtractor.extract_text_from_file(&xps_path);
+        assert!(result.is_ok(), "Failed to extract XPS: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "=== Page 1 ===
+Form DS-82: U.S. Passport Renewal
+Applicant name: Jane Q. Traveler
+Date of birth: 04/12/1988
+
+=== Page 2 ===
+{Section 2} Travel plans
+Sign here: Signature & date";
+        assert_eq!(text, expected);
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::Path;
+use std::fs;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_yaml::Value;
+use crate::extractor::DocumentExtractor;
+use crate::extractors::txt_extractor::TxtExtractor;
+
+/// YAML extractor that validates documents and renders them as indented text
+pub struct YamlExtractor;
+
+impl YamlExtractor {
+    /// Renders a scalar on one line
+    fn render_scalar(value: &Value) -> String {
+        match value {
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => s.clone(),
+            Value::Tagged(tagged) => format!("{} {}", tagged.tag, Self::render_scalar(&tagged.value)),
+            Value::Sequence(_) | Value::Mapping(_) => String::new(),
+        }
+    }
+
+    /// Renders a key/item label followed by `value`, nesting containers two spaces deeper
+    fn render_entry(label: &str, value: &Value, indent: usize, lines: &mut Vec<String>) {
+        let pad = " ".repeat(indent);
+        match value {
+            Value::Mapping(map) if map.is_empty() => lines.push(format!("{}{} {{}}", pad, label)),
+            Value::Sequence(items) if items.is_empty() => lines.push(format!("{}{} []", pad, label)),
+            Value::Mapping(_) | Value::Sequence(_) => {
+                lines.push(format!("{}{}", pad, label));
+                Self::render_into(value, indent + 2, lines);
+            }
+            Value::Tagged(tagged) if matches!(tagged.value, Value::Mapping(_) | Value::Sequence(_)) => {
+                lines.push(format!("{}{} {}", pad, label, tagged.tag));
+                Self::render_into(&tagged.value, indent + 2, lines);
+            }
+            Value::String(s) if s.contains('\n') => {
+                lines.push(format!("{}{} |", pad, label));
+                for line in s.trim_end().lines() {
+                    lines.push(format!("{}  {}", pad, line));
+                }
+            }
+            scalar => lines.push(format!("{}{} {}", pad, label, Self::render_scalar(scalar))),
+        }
+    }
+
+    fn render_into(value: &Value, indent: usize, lines: &mut Vec<String>) {
+        match value {
+            Value::Mapping(map) => {
+                for (key, child) in map {
+                    let key = match key {
+                        Value::String(s) => s.clone(),
+                        other => Self::render_scalar(other),
+                    };
+                    Self::render_entry(&format!("{}:", key), child, indent, lines);
+                }
+            }
+            Value::Sequence(items) => {
+                for item in items {
+                    Self::render_entry("-", item, indent, lines);
+                }
+            }
+            scalar => lines.push(format!("{}{}", " ".repeat(indent), Self::render_scalar(scalar))),
+        }
+    }
+
+    /// Renders a parsed value as indented `key: value` text
+    pub fn render_value(value: &Value) -> String {
+        let mut lines = Vec::new();
+        Self::render_into(value, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    /// Parses (and thereby validates) YAML source, rendering every document it contains
+    pub fn extract_from_str(yaml: &str) -> Result<String> {
+        let mut documents = Vec::new();
+        for (index, document) in serde_yaml::Deserializer::from_str(yaml).enumerate() {
+            let value = Value::deserialize(document)
+                .with_context(|| format!("Invalid YAML in document {}", index + 1))?;
+            documents.push(Self::render_value(&value));
+        }
+
+        if documents.len() <= 1 {
+            return Ok(documents.pop().unwrap_or_default());
+        }
+        Ok(documents
+            .iter()
+            .enumerate()
+            .map(|(index, document)| format!("--- Document {} ---\n{}", index + 1, document))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+}
+
+impl DocumentExtractor for YamlExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "YamlExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let file_bytes = fs::read(file_path)
+            .with_context(|| format!("Failed to read YAML file: {}", file_path.display()))?;
+
+        let yaml = TxtExtractor::decode_bytes(&file_bytes);
+        Self::extract_from_str(&yaml)
+            .with_context(|| format!("Failed to extract YAML: {}", file_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_text_from_yaml() {
+        let mut yaml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        yaml_path.push("fixtures");
+        yaml_path.push("deployment.yaml");
+
+        let result = YamlExtractor.extract_text_from_file(&yaml_path);
+        assert!(result.is_ok(), "Failed to extract YAML: {:?}", result.err());
+
+        let text = result.unwrap();
+        let expected = "--- Document 1 ---
+kind: Service
+metadata:
+  name: docu-mcp
+spec:
+  ports:
+    -
+      port: 80
+      targetPort: 8080
+
+--- Document 2 ---
+kind: ConfigMap
+data:
+  motd: |
+    Welcome aboard
+    Flight CM 716
+  empty: {}";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_extract_from_invalid_yaml() {
+        let result = YamlExtractor::extract_from_str("key: [unclosed");
+        assert!(result.is_err(), "Invalid YAML should be rejected");
+    }
+}
@@ -0,0 +1,265 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use anyhow::{Context, Result};
+use crate::constants::{GZ_EXTENSION, ZIP_CONTAINER_EXTENSIONS};
+use crate::extractor::{create_extractor, is_supported_file, DocumentExtractor};
+use crate::extractors::archive::open_zip;
+
+/// Limits applied while reading archives, to defuse decompression bombs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZipLimits {
+    /// Maximum number of entries in the archive
+    pub max_entries: usize,
+    /// Maximum uncompressed size of a single extracted member, in bytes
+    pub max_entry_size: u64,
+    /// Maximum uncompressed size of all extracted members together, in bytes
+    pub max_total_size: u64,
+    /// Maximum ratio between uncompressed and compressed size of a member
+    pub max_compression_ratio: u64,
+}
+
+impl Default for ZipLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 1_000,
+            max_entry_size: 50 * 1024 * 1024,
+            max_total_size: 200 * 1024 * 1024,
+            max_compression_ratio: 100,
+        }
+    }
+}
+
+/// ZIP archive extractor that lists members and extracts text from supported documents inside
+pub struct ZipExtractor {
+    limits: ZipLimits,
+}
+
+impl Default for ZipExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZipExtractor {
+    /// Creates a ZIP extractor with the default limits
+    pub fn new() -> Self {
+        Self { limits: ZipLimits::default() }
+    }
+
+    /// Overrides the decompression limits
+    pub fn with_limits(mut self, limits: ZipLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the lowercased extension of an archive member name
+    fn member_extension(name: &str) -> Option<String> {
+        Path::new(name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+    }
+
+    /// True for zip and gzip containers, told by their content since a member's name can lie
+    ///
+    /// Zip-based documents (Word, OpenDocument, iWork, EPUB, ...) and gzip files are
+    /// decompressed again by their own extractor, outside these limits.
+    fn is_container(member_path: &Path) -> Result<bool> {
+        let mut header = Vec::new();
+        File::open(member_path)
+            .and_then(|file| file.take(4).read_to_end(&mut header))
+            .context("Failed to read temporary file")?;
+        Ok(header.starts_with(b"PK\x03\x04") || header.starts_with(&[0x1f, 0x8b]))
+    }
+
+    /// Extracts one member into `dir` and runs the matching extractor on it; `None` for
+    /// nested containers, which are not expanded
+    fn extract_member(
+        &self,
+        entry: &mut zip::read::ZipFile<'_, File>,
+        extension: &str,
+        dir: &Path,
+        index: usize,
+        budget: &mut u64,
+    ) -> Result<Option<String>> {
+        let compressed = entry.compressed_size().max(1);
+        let declared = entry.size();
+        if declared > self.limits.max_entry_size {
+            return Err(anyhow::anyhow!("member is larger than {} bytes", self.limits.max_entry_size));
+        }
+        if declared / compressed > self.limits.max_compression_ratio {
+            return Err(anyhow::anyhow!(
+                "compression ratio exceeds {}:1 (possible decompression bomb)",
+                self.limits.max_compression_ratio
+            ));
+        }
+        if declared > *budget {
+            return Err(anyhow::anyhow!("archive exceeds {} bytes of extracted data", self.limits.max_total_size));
+        }
+
+        // Never trust the member name as a path; only its extension is kept
+        let member_path = dir.join(format!("member-{}.{}", index, extension));
+        let mut output = File::create(&member_path).context("Failed to create temporary file")?;
+
+        // Declared sizes can lie, so the copy itself is capped as well
+        let cap = self.limits.max_entry_size.min(*budget);
+        let written = io::copy(&mut entry.take(cap + 1), &mut output).context("Failed to decompress member")?;
+        if written > cap {
+            return Err(anyhow::anyhow!("member decompresses past the configured size limits"));
+        }
+        *budget -= written;
+
+        if Self::is_container(&member_path)? {
+            return Ok(None);
+        }
+        let extractor = create_extractor(&member_path)?;
+        extractor.extract_text_from_file(&member_path).map(Some)
+    }
+}
+
+impl DocumentExtractor for ZipExtractor {
+    fn extractor_type(&self) -> &'static str {
+        "ZipExtractor"
+    }
+
+    fn extract_text_from_file(&self, file_path: &Path) -> Result<String> {
+        // Validate that the file exists
+        if !file_path.exists() {
+            return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+        }
+
+        // Validate that it's a file (not a directory)
+        if !file_path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+        }
+
+        let mut archive = open_zip(file_path)?;
+        if archive.len() > self.limits.max_entries {
+            return Err(anyhow::anyhow!(
+                "Archive has {} entries, more than the limit of {}: {}",
+                archive.len(),
+                self.limits.max_entries,
+                file_path.display()
+            ));
+        }
+
+        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        let mut budget = self.limits.max_total_size;
+        let mut listing = Vec::new();
+        let mut sections = Vec::new();
+
+        for index in 0..archive.len() {
+            let mut entry = archive
+                .by_index(index)
+                .with_context(|| format!("Failed to read entry {} of: {}", index, file_path.display()))?;
+            if entry.is_dir() {
+                continue;
+            }
+
+            let name = entry
+                .name()
+                .with_context(|| format!("Failed to read name of entry {} in: {}", index, file_path.display()))?
+                .into_owned();
+            listing.push(format!("- {} ({} bytes)", name, entry.size()));
+
+            // Nested archives and container formats (zip-based documents, gzip) are listed but
+            // not expanded, so bombs cannot multiply or escape the limits
+            let is_container = |ext: &String| ZIP_CONTAINER_EXTENSIONS.contains(&ext.as_str()) || ext == GZ_EXTENSION;
+            let Some(extension) = Self::member_extension(&name).filter(|ext| !is_container(ext)) else {
+                continue;
+            };
+            if !is_supported_file(Path::new(&name)) {
+                continue;
+            }
+
+            let text = match self.extract_member(&mut entry, &extension, temp_dir.path(), index, &mut budget) {
+                Ok(Some(text)) => text.trim().to_string(),
+                Ok(None) => continue,
+                Err(e) => format!("[Failed to extract: {:#}]", e),
+            };
+            sections.push(format!("=== {} ===\n{}", name, text));
+        }
+
+        let mut output = format!("Archive with {} files:\n{}", listing.len(), listing.join("\n"));
+        for section in sections {
+            output.push_str("\n\n");
+            output.push_str(&section);
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn bundle_path() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("bundle.zip");
+        path
+    }
+
+    #[test]
+    fn test_extract_text_from_zip() {
+        let result = ZipExtractor::new().extract_text_from_file(&bundle_path());
+        assert!(result.is_ok(), "Failed to extract ZIP: {:?}", result.err());
+
+        let text = result.unwrap();
+        assert!(text.starts_with("Archive with 4 files:\n"), "Got: {}", text);
+        assert!(text.contains("- notes/trip.md ("), "Members should be listed. Got: {}", text);
+        assert!(text.contains("- photo.jpg ("), "Unsupported members should still be listed. Got: {}", text);
+        assert!(text.contains("=== notes/trip.md ===\nTitle: Packing list"), "Markdown member should be extracted. Got: {}", text);
+        assert!(text.contains("=== flights.csv ===\nflight | to"), "CSV member should be extracted. Got: {}", text);
+        assert!(!text.contains("=== photo.jpg ==="), "Unsupported members should not be extracted. Got: {}", text);
+        assert!(!text.contains("=== inner.zip ==="), "Nested archives should not be expanded. Got: {}", text);
+    }
+
+    #[test]
+    fn test_zip_limits_are_enforced() {
+        let strict = ZipLimits { max_entries: 2, ..ZipLimits::default() };
+        let result = ZipExtractor::new().with_limits(strict).extract_text_from_file(&bundle_path());
+        assert!(result.is_err(), "Archives with too many entries should be rejected");
+
+        let tiny = ZipLimits { max_entry_size: 4, ..ZipLimits::default() };
+        let text = ZipExtractor::new()
+            .with_limits(tiny)
+            .extract_text_from_file(&bundle_path())
+            .expect("Oversized members should be reported, not fail the archive");
+        assert!(text.contains("=== flights.csv ===\n[Failed to extract: member is larger than 4 bytes]"), "Got: {}", text);
+    }
+
+    #[test]
+    fn test_nested_containers_are_not_expanded() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let fixture = |name: &str| fs::read(fixtures.join(name)).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        let members = [
+            ("policy.docx", fixture("policy.docx")),
+            ("site.warc.gz", fixture("site.warc.gz")),
+            ("disguised.txt", fixture("policy.docx")),
+            ("readme.txt", b"Read me first".to_vec()),
+        ];
+        for (name, content) in members {
+            writer.start_file(name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(&content).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let text = ZipExtractor::new().extract_text_from_file(&path).unwrap();
+        assert!(text.starts_with("Archive with 4 files:\n"), "Got: {}", text);
+        assert!(text.contains("- policy.docx (") && text.contains("- disguised.txt ("), "Got: {}", text);
+        assert!(text.contains("=== readme.txt ===\nRead me first"), "Got: {}", text);
+        for name in ["policy.docx", "site.warc.gz", "disguised.txt"] {
+            assert!(!text.contains(&format!("=== {} ===", name)), "{} should not be expanded. Got: {}", name, text);
+        }
+    }
+}
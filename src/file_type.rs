@@ -0,0 +1,295 @@
+//! Content-based file type detection ("magic bytes"), for files whose extension is
+//! missing or does not match what they contain
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use crate::constants::{
+    AZW3_EXTENSION, CSV_EXTENSION, DOCX_EXTENSION, EML_EXTENSION, HTML_EXTENSION, ICS_EXTENSION,
+    JSON_EXTENSION, MBOX_EXTENSION, MD_EXTENSION, MOBI_EXTENSION, ODP_EXTENSION, ODP_MIME_TYPE,
+    ODS_EXTENSION, ODS_MIME_TYPE, ONE_EXTENSION, OXPS_EXTENSION, PARQUET_EXTENSION, PDF_EXTENSION,
+    PPTX_EXTENSION, SRT_EXTENSION, SVG_EXTENSION, TOML_EXTENSION, TSV_EXTENSION, TXT_EXTENSION,
+    VCF_EXTENSION, VTT_EXTENSION, WARC_EXTENSION, XML_EXTENSION, XPS_EXTENSION, YAML_EXTENSION,
+    ZIP_CONTAINER_EXTENSIONS, ZIP_EXTENSION,
+};
+use crate::extractors::archive::{open_zip, read_zip_entry};
+use crate::extractors::onenote_extractor::ONE_FILE_TYPE;
+
+/// Number of leading bytes inspected for a format signature
+const SNIFF_LEN: u64 = 68;
+
+/// Number of leading bytes inspected when deciding whether a file is text
+const TEXT_SNIFF_LEN: u64 = 8192;
+
+/// Reads up to `len` bytes from the start of a file; `None` when it cannot be read
+fn read_prefix(file_path: &Path, len: u64) -> Option<Vec<u8>> {
+    let mut prefix = Vec::new();
+    File::open(file_path).ok()?.take(len).read_to_end(&mut prefix).ok()?;
+    Some(prefix)
+}
+
+/// Detects the format of a file from its content, as the extension it should have
+///
+/// Only formats with a reliable signature are recognized: PDF, Parquet, Mobipocket,
+/// OneNote sections and zip containers (Word, PowerPoint, OpenDocument and XPS files are told apart
+/// by their entries; any other zip is reported as `zip`). Returns `None` for other
+/// content, directories and unreadable files.
+pub fn detect_extension(file_path: &Path) -> Option<&'static str> {
+    let header = read_prefix(file_path, SNIFF_LEN)?;
+
+    // Readers accept junk before `%PDF-`, but a text file mentioning it must not match
+    if header.starts_with(b"%PDF-") {
+        Some(PDF_EXTENSION)
+    } else if header.starts_with(b"PAR1") {
+        Some(PARQUET_EXTENSION)
+    } else if header.get(60..68) == Some(b"BOOKMOBI") {
+        Some(MOBI_EXTENSION)
+    } else if header.starts_with(&ONE_FILE_TYPE) {
+        Some(ONE_EXTENSION)
+    } else if header.starts_with(b"PK\x03\x04") {
+        Some(detect_zip_container(file_path))
+    } else {
+        None
+    }
+}
+
+/// Tells the zip-based document formats apart by their entries
+fn detect_zip_container(file_path: &Path) -> &'static str {
+    let Ok(mut archive) = open_zip(file_path) else {
+        return ZIP_EXTENSION;
+    };
+
+    match read_zip_entry(&mut archive, "mimetype").ok().flatten().as_deref().map(str::trim) {
+        Some(ODS_MIME_TYPE) => return ODS_EXTENSION,
+        Some(ODP_MIME_TYPE) => return ODP_EXTENSION,
+        _ => {}
+    }
+
+    // OOXML packages always carry a content types part
+    let has_entry = |prefix: &str| archive.file_names().flatten().any(|name| name.starts_with(prefix));
+    let is_ooxml = has_entry("[Content_Types].xml");
+    if is_ooxml && has_entry("word/") {
+        DOCX_EXTENSION
+    } else if is_ooxml && has_entry("ppt/") {
+        PPTX_EXTENSION
+    } else if has_entry("FixedDocSeq.fdseq") || has_entry("FixedDocumentSequence.fdseq") {
+        XPS_EXTENSION
+    } else {
+        ZIP_EXTENSION
+    }
+}
+
+/// Returns true when a file looks like text, as opposed to binary data
+///
+/// Like git, a file is taken as binary when its first 8 KiB contain a NUL byte,
+/// except for UTF-16 text, which starts with a byte order mark.
+pub fn looks_like_text(file_path: &Path) -> bool {
+    let Some(sample) = read_prefix(file_path, TEXT_SNIFF_LEN) else {
+        return false;
+    };
+    sample.starts_with(&[0xff, 0xfe]) || sample.starts_with(&[0xfe, 0xff]) || !sample.contains(&0)
+}
+
+/// Returns true when a file with `extension` may legitimately contain `detected` content
+///
+/// A generic zip matches every zip-based format, since not all of them (e.g. iWork
+/// packages) are recognized by their entries.
+pub fn extension_matches(extension: &str, detected: &str) -> bool {
+    extension == detected
+        || (detected == ZIP_EXTENSION && ZIP_CONTAINER_EXTENSIONS.contains(&extension))
+        || matches!((extension, detected), (AZW3_EXTENSION, MOBI_EXTENSION) | (OXPS_EXTENSION, XPS_EXTENSION))
+}
+
+/// MIME types and the extension of their extractor; the first type listed for an
+/// extension is the one reported for it
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("application/pdf", PDF_EXTENSION),
+    ("text/plain", TXT_EXTENSION),
+    ("text/markdown", MD_EXTENSION),
+    ("text/x-markdown", MD_EXTENSION),
+    ("text/html", HTML_EXTENSION),
+    ("application/xhtml+xml", HTML_EXTENSION),
+    ("text/csv", CSV_EXTENSION),
+    ("text/tab-separated-values", TSV_EXTENSION),
+    ("application/vnd.openxmlformats-officedocument.wordprocessingml.document", DOCX_EXTENSION),
+    ("application/vnd.openxmlformats-officedocument.presentationml.presentation", PPTX_EXTENSION),
+    (ODS_MIME_TYPE, ODS_EXTENSION),
+    (ODP_MIME_TYPE, ODP_EXTENSION),
+    ("message/rfc822", EML_EXTENSION),
+    ("application/mbox", MBOX_EXTENSION),
+    ("application/json", JSON_EXTENSION),
+    ("application/xml", XML_EXTENSION),
+    ("text/xml", XML_EXTENSION),
+    ("application/yaml", YAML_EXTENSION),
+    ("application/x-yaml", YAML_EXTENSION),
+    ("text/yaml", YAML_EXTENSION),
+    ("application/toml", TOML_EXTENSION),
+    ("application/zip", ZIP_EXTENSION),
+    ("application/x-zip-compressed", ZIP_EXTENSION),
+    ("application/vnd.apache.parquet", PARQUET_EXTENSION),
+    ("text/calendar", ICS_EXTENSION),
+    ("text/vcard", VCF_EXTENSION),
+    ("text/x-vcard", VCF_EXTENSION),
+    ("application/x-mobipocket-ebook", MOBI_EXTENSION),
+    ("application/vnd.ms-xpsdocument", XPS_EXTENSION),
+    ("application/oxps", OXPS_EXTENSION),
+    ("image/svg+xml", SVG_EXTENSION),
+    ("text/vtt", VTT_EXTENSION),
+    ("application/x-subrip", SRT_EXTENSION),
+    ("application/warc", WARC_EXTENSION),
+    ("application/onenote", ONE_EXTENSION),
+];
+
+/// Returns the extension of the extractor for a MIME type (`Content-Type` value)
+///
+/// Parameters such as `charset` are ignored. Returns `None` for unknown types and
+/// for `application/octet-stream`, which says nothing about the format.
+pub fn extension_for_mime_type(mime_type: &str) -> Option<&'static str> {
+    let mime_type = mime_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    MIME_TYPES.iter().find(|(known, _)| *known == mime_type).map(|(_, extension)| *extension)
+}
+
+/// Returns the MIME type reported for files with an extension
+fn mime_type_for_extension(extension: &str) -> Option<&'static str> {
+    MIME_TYPES.iter().find(|(_, known)| *known == extension).map(|(mime_type, _)| *mime_type)
+}
+
+/// Returns the MIME type of a file, for clients that receive its raw bytes
+///
+/// Images are recognized by their signature, documents by their content where it has
+/// a signature and otherwise by their extension. Other text files are `text/plain`,
+/// anything else `application/octet-stream`.
+pub fn mime_type_for_file(file_path: &Path) -> &'static str {
+    if let Some(mime_type) = read_prefix(file_path, SNIFF_LEN).as_deref().and_then(image_mime_type) {
+        return mime_type;
+    }
+    let mut extension = file_path.extension().map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+    let detected = detect_extension(file_path);
+    // The extension decides unless the content contradicts it
+    if let Some(detected) = detected {
+        if !extension.as_deref().is_some_and(|extension| extension_matches(extension, detected)) {
+            extension = Some(detected.to_string());
+        }
+    }
+    // Container formats without a type of their own, e.g. `key`, get the container's
+    let mime_type = extension.as_deref().and_then(mime_type_for_extension);
+    match mime_type.or_else(|| detected.and_then(mime_type_for_extension)) {
+        Some(mime_type) => mime_type,
+        None if looks_like_text(file_path) => "text/plain",
+        None => "application/octet-stream",
+    }
+}
+
+/// Returns the MIME type of raster image data from its signature
+///
+/// Recognizes PNG, JPEG, GIF, WebP, BMP and TIFF; vector formats such as EMF, WMF
+/// and SVG return `None`.
+pub fn image_mime_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("image/jpeg")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        Some("image/webp")
+    } else if data.starts_with(b"BM") && data.len() > 14 {
+        Some("image/bmp")
+    } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        Some("image/tiff")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push(name);
+        path
+    }
+
+    #[test]
+    fn test_detect_extension() {
+        let cases = [
+            ("boardingPass.pdf", Some("pdf")),
+            ("policy.docx", Some("docx")),
+            ("deck.pptx", Some("pptx")),
+            ("budget.ods", Some("ods")),
+            ("slides.odp", Some("odp")),
+            ("passport_form.xps", Some("xps")),
+            ("bundle.zip", Some("zip")),
+            ("flights.parquet", Some("parquet")),
+            ("isthmus.mobi", Some("mobi")),
+            ("travel.one", Some("one")),
+            ("sample_utf8.txt", None),
+            ("missing.bin", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(detect_extension(&fixture(name)), expected, "Wrong type for {}", name);
+        }
+    }
+
+    #[test]
+    fn test_looks_like_text() {
+        assert!(looks_like_text(&fixture("sample_utf8.txt")));
+        assert!(looks_like_text(&fixture("sample_utf16le.txt")), "UTF-16 text has NUL bytes but a BOM");
+        assert!(!looks_like_text(&fixture("flights.parquet")));
+        assert!(!looks_like_text(&fixture("missing.bin")));
+    }
+
+    #[test]
+    fn test_extension_for_mime_type() {
+        assert_eq!(extension_for_mime_type("application/pdf"), Some("pdf"));
+        assert_eq!(extension_for_mime_type("Text/HTML; charset=utf-8"), Some("html"));
+        assert_eq!(extension_for_mime_type("application/vnd.oasis.opendocument.spreadsheet"), Some("ods"));
+        assert_eq!(extension_for_mime_type("application/octet-stream"), None);
+    }
+
+    #[test]
+    fn test_mime_type_for_file() {
+        let cases = [
+            ("boardingPass.pdf", "application/pdf"),
+            ("policy.docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+            ("budget.ods", ODS_MIME_TYPE),
+            ("sample_utf8.txt", "text/plain"),
+            ("flights.parquet", "application/vnd.apache.parquet"),
+            ("missing.bin", "application/octet-stream"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(mime_type_for_file(&fixture(name)), expected, "Wrong MIME type for {}", name);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("scan.dat");
+        std::fs::write(&image, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        assert_eq!(mime_type_for_file(&image), "image/png");
+        let notes = dir.path().join("notes.md");
+        std::fs::write(&notes, "# Notes").unwrap();
+        assert_eq!(mime_type_for_file(&notes), "text/markdown");
+    }
+
+    #[test]
+    fn test_extension_matches() {
+        assert!(extension_matches("pdf", "pdf"));
+        assert!(extension_matches("key", "zip"));
+        assert!(extension_matches("azw3", "mobi"));
+        assert!(!extension_matches("pdf", "docx"));
+        assert!(!extension_matches("zip", "docx"));
+    }
+
+    #[test]
+    fn test_image_mime_type() {
+        assert_eq!(image_mime_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some("image/png"));
+        assert_eq!(image_mime_type(&[0xff, 0xd8, 0xff, 0xe0, 0, 0x10]), Some("image/jpeg"));
+        assert_eq!(image_mime_type(b"GIF89a\x01\0\x01\0"), Some("image/gif"));
+        assert_eq!(image_mime_type(b"RIFF\x24\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(image_mime_type(b"\x01\0\0\0 EMF"), None);
+        assert_eq!(image_mime_type(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"), None);
+    }
+}
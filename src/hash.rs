@@ -0,0 +1,100 @@
+//! Checksums of documents, to tell whether two files are the same document
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use blake3::Hasher as Blake3;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Size of the buffer files are read through
+const READ_BUFFER_LEN: usize = 64 * 1024;
+
+/// Result of `hash_file`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileHash {
+    pub path: PathBuf,
+    /// File size in bytes
+    pub size: u64,
+    /// SHA-256 of the contents, in hex
+    pub sha256: String,
+    /// BLAKE3 of the contents, in hex, when asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blake3: Option<String>,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Feeds a file's contents to `update` in chunks
+fn read_chunks(path: &Path, mut update: impl FnMut(&[u8])) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; READ_BUFFER_LEN];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        update(&buffer[..read]);
+    }
+}
+
+/// Returns the SHA-256 of a file's contents, in hex
+pub(crate) fn file_sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    read_chunks(path, |bytes| hasher.update(bytes))?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Returns the SHA-256 of a document, and its BLAKE3 when `blake3` is set, reading the file once
+pub fn hash_file(file_path: &Path, blake3: bool) -> Result<FileHash> {
+    let metadata = fs::metadata(file_path).with_context(|| format!("File not found: {}", file_path.display()))?;
+    if !metadata.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+    }
+
+    let mut sha256 = Sha256::new();
+    let mut blake3_hasher = blake3.then(Blake3::new);
+    let mut size = 0;
+    read_chunks(file_path, |bytes| {
+        size += bytes.len() as u64;
+        sha256.update(bytes);
+        if let Some(hasher) = blake3_hasher.as_mut() {
+            hasher.update(bytes);
+        }
+    })
+    .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+
+    Ok(FileHash {
+        path: file_path.to_path_buf(),
+        size,
+        sha256: to_hex(&sha256.finalize()),
+        blake3: blake3_hasher.map(|hasher| to_hex(hasher.finalize().as_bytes())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.txt");
+        fs::write(&path, "abc").unwrap();
+
+        let hash = hash_file(&path, false).unwrap();
+        assert_eq!(hash.size, 3);
+        assert_eq!(hash.sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(hash.blake3, None);
+        assert_eq!(file_sha256(&path).unwrap(), hash.sha256);
+
+        let hash = hash_file(&path, true).unwrap();
+        assert_eq!(hash.blake3.as_deref(), Some("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"));
+
+        assert!(hash_file(&dir.path().join("missing.txt"), false).is_err());
+        assert!(hash_file(dir.path(), false).is_err());
+    }
+}
@@ -0,0 +1,454 @@
+//! A persistent index of the extracted text and metadata of a directory's documents
+//!
+//! Indexing extracts each supported document once and saves the result next to the
+//! configuration; indexing again only re-extracts documents whose size or
+//! modification time changed. Searches and term statistics over the index read the
+//! saved text instead of extracting every document on each call.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::config::{config_file_path, load_json, save_json};
+use crate::extractor::{create_extractor, DocumentMetadata};
+use crate::listing::{list_files_in_directory, FileInfo, ListOptions};
+use crate::search::{Search, SearchResults};
+use crate::timestamp::{format_unix_time, unix_seconds};
+
+/// Directory holding the indexes, in the configuration directory
+const INDEX_DIR_NAME: &str = "indexes";
+
+/// Documents indexed by default before indexing is cut short
+const DEFAULT_MAX_FILES: usize = 10_000;
+
+/// A document of the index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedDocument {
+    /// Path relative to the indexed directory, with `/` separators
+    pub relative_path: String,
+    /// Size in bytes when indexed
+    pub size: u64,
+    /// Last modification time when indexed, as an ISO 8601 UTC timestamp
+    pub modified: Option<String>,
+    /// Extracted text; empty when extraction failed
+    #[serde(default)]
+    pub text: String,
+    /// Descriptive metadata; empty when the format has none
+    #[serde(default)]
+    pub metadata: DocumentMetadata,
+    /// Why the text could not be extracted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The indexed documents of a directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentIndex {
+    /// Canonical path of the indexed directory
+    pub directory: PathBuf,
+    /// When the index was last updated, as an ISO 8601 UTC timestamp
+    pub indexed_at: Option<String>,
+    /// True when the directory held more documents than were indexed
+    #[serde(default)]
+    pub truncated: bool,
+    /// The documents, by relative path
+    #[serde(default)]
+    pub documents: Vec<IndexedDocument>,
+}
+
+/// A document that could not be indexed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexFailure {
+    pub relative_path: String,
+    pub error: String,
+}
+
+/// Result of `index_directory`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexReport {
+    pub directory: PathBuf,
+    /// Where the index is saved
+    pub index_path: Option<PathBuf>,
+    /// Documents indexed for the first time, by relative path
+    pub added: Vec<String>,
+    /// Documents re-extracted because they changed, by relative path
+    pub updated: Vec<String>,
+    /// Documents no longer in the directory, by relative path
+    pub removed: Vec<String>,
+    /// Number of documents already indexed and unchanged
+    pub unchanged: usize,
+    /// Documents added or updated whose text could not be extracted
+    pub failed: Vec<IndexFailure>,
+    /// Number of documents in the index
+    pub documents: usize,
+    pub truncated: bool,
+}
+
+/// Freshness of a directory's index, as returned by `index_status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexStatus {
+    pub directory: PathBuf,
+    /// Where the index is saved
+    pub index_path: Option<PathBuf>,
+    /// When the index was last updated; `None` when the directory was never indexed
+    pub indexed_at: Option<String>,
+    /// Number of documents in the index
+    pub documents: usize,
+    /// Number of indexed documents whose text could not be extracted
+    pub failed: usize,
+    /// Indexed documents changed since, by relative path
+    pub stale: Vec<String>,
+    /// Documents of the directory not indexed yet, by relative path
+    pub unindexed: Vec<String>,
+    /// Indexed documents no longer in the directory, by relative path
+    pub removed: Vec<String>,
+    /// True when indexing again would change nothing
+    pub up_to_date: bool,
+    /// True when the directory holds more documents than are indexed
+    pub truncated: bool,
+}
+
+/// Matches of a search in one indexed document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexSearchResult {
+    pub relative_path: String,
+    #[serde(flatten)]
+    pub results: SearchResults,
+}
+
+/// Settings for `index_directory`
+pub struct IndexOptions {
+    list: ListOptions,
+    full: bool,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IndexOptions {
+    /// Indexes every supported document of the directory and its subdirectories
+    pub fn new() -> Self {
+        Self {
+            list: ListOptions::new()
+                .with_recursive(true)
+                .with_supported_only(true)
+                .with_max_results(DEFAULT_MAX_FILES),
+            full: false,
+        }
+    }
+
+    /// Chooses the documents indexed, e.g. only PDFs
+    pub fn with_list_options(mut self, list: ListOptions) -> Self {
+        self.list = list;
+        self
+    }
+
+    /// Extracts every document again, not only new and changed ones
+    pub fn with_full_reindex(mut self, full: bool) -> Self {
+        self.full = full;
+        self
+    }
+}
+
+impl IndexedDocument {
+    /// Returns true when the file has the size and modification time it was indexed with
+    fn is_current(&self, file: &FileInfo) -> bool {
+        self.size == file.size && self.modified == file.modified
+    }
+}
+
+impl DocumentIndex {
+    /// Returns the path of a directory's index (`<config dir>/docu-mcp/indexes/<hash>.json`)
+    pub fn index_path(directory: &Path) -> Result<PathBuf> {
+        let directory = directory
+            .canonicalize()
+            .with_context(|| format!("Directory not found: {}", directory.display()))?;
+        let hash = Sha256::digest(directory.to_string_lossy().as_bytes());
+        let name: String = hash.iter().take(8).map(|byte| format!("{:02x}", byte)).collect();
+        config_file_path(&format!("{}/{}.json", INDEX_DIR_NAME, name))
+    }
+
+    /// Loads the index of a directory, failing when the directory was never indexed
+    pub fn load(directory: &Path) -> Result<Self> {
+        let index = Self::load_from(&Self::index_path(directory)?)?;
+        if index.indexed_at.is_none() {
+            return Err(anyhow::anyhow!("Directory is not indexed: {}", directory.display()));
+        }
+        Ok(index)
+    }
+
+    /// Loads an index file; a missing file gives an empty index
+    pub fn load_from(path: &Path) -> Result<Self> {
+        load_json(path, "index")
+    }
+
+    /// Saves the index to the default location of its directory
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::index_path(&self.directory)?)
+    }
+
+    /// Saves the index, replacing the file atomically
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        save_json(path, self, "index")
+    }
+
+    /// Brings the index up to date with a directory, extracting only new and changed documents
+    ///
+    /// An index of another directory is replaced. Documents that failed to extract are
+    /// kept with their error, so they are only tried again once they change.
+    pub fn update(&mut self, directory: &Path, options: &IndexOptions) -> Result<IndexReport> {
+        let directory = directory
+            .canonicalize()
+            .with_context(|| format!("Directory not found: {}", directory.display()))?;
+        if self.directory != directory {
+            *self = Self { directory: directory.clone(), ..Self::default() };
+        }
+        let listing = list_files_in_directory(&directory, &options.list)?;
+
+        let mut previous: HashMap<String, IndexedDocument> = self
+            .documents
+            .drain(..)
+            .map(|document| (document.relative_path.clone(), document))
+            .collect();
+        let mut report = IndexReport {
+            directory: directory.clone(),
+            index_path: None,
+            added: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+            unchanged: 0,
+            failed: Vec::new(),
+            documents: 0,
+            truncated: listing.truncated,
+        };
+        for file in listing.files {
+            match previous.remove(&file.relative_path) {
+                Some(document) if !options.full && document.is_current(&file) => {
+                    report.unchanged += 1;
+                    self.documents.push(document);
+                    continue;
+                }
+                Some(_) => report.updated.push(file.relative_path.clone()),
+                None => report.added.push(file.relative_path.clone()),
+            }
+
+            let extracted = create_extractor(&file.path).and_then(|extractor| {
+                let text = extractor.extract_text_from_file(&file.path)?;
+                // Formats without metadata support are indexed all the same
+                let metadata = extractor.extract_metadata(&file.path).unwrap_or_default();
+                Ok((text, metadata))
+            });
+            let (text, metadata, error) = match extracted {
+                Ok((text, metadata)) => (text, metadata, None),
+                Err(e) => {
+                    let error = format!("{:#}", e);
+                    report.failed.push(IndexFailure { relative_path: file.relative_path.clone(), error: error.clone() });
+                    (String::new(), DocumentMetadata::default(), Some(error))
+                }
+            };
+            self.documents.push(IndexedDocument {
+                relative_path: file.relative_path,
+                size: file.size,
+                modified: file.modified,
+                text,
+                metadata,
+                error,
+            });
+        }
+
+        report.removed = previous.into_keys().collect();
+        report.removed.sort();
+        self.documents.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        self.indexed_at = unix_seconds(SystemTime::now()).map(format_unix_time);
+        self.truncated = listing.truncated;
+        report.documents = self.documents.len();
+        Ok(report)
+    }
+
+    /// Compares the index with a directory, without extracting anything
+    ///
+    /// An index of another directory counts as no index.
+    pub fn status(&self, directory: &Path, options: &IndexOptions) -> Result<IndexStatus> {
+        let directory = directory
+            .canonicalize()
+            .with_context(|| format!("Directory not found: {}", directory.display()))?;
+        let empty = Self::default();
+        let index = if self.directory == directory { self } else { &empty };
+        let listing = list_files_in_directory(&directory, &options.list)?;
+
+        let mut indexed: HashMap<&str, &IndexedDocument> =
+            index.documents.iter().map(|document| (document.relative_path.as_str(), document)).collect();
+        let mut stale = Vec::new();
+        let mut unindexed = Vec::new();
+        for file in &listing.files {
+            match indexed.remove(file.relative_path.as_str()) {
+                Some(document) if document.is_current(file) => {}
+                Some(_) => stale.push(file.relative_path.clone()),
+                None => unindexed.push(file.relative_path.clone()),
+            }
+        }
+        let mut removed: Vec<String> = indexed.into_keys().map(str::to_string).collect();
+        removed.sort();
+
+        Ok(IndexStatus {
+            directory,
+            index_path: None,
+            indexed_at: index.indexed_at.clone(),
+            documents: index.documents.len(),
+            failed: index.documents.iter().filter(|document| document.error.is_some()).count(),
+            up_to_date: index.indexed_at.is_some() && stale.is_empty() && unindexed.is_empty() && removed.is_empty(),
+            stale,
+            unindexed,
+            removed,
+            truncated: listing.truncated,
+        })
+    }
+
+    /// Searches the saved text of every indexed document, returning the documents with matches
+    ///
+    /// The search's match limit applies to each document.
+    pub fn search(&self, search: &Search) -> Result<Vec<IndexSearchResult>> {
+        let mut results = Vec::new();
+        for document in self.documents.iter().filter(|document| document.error.is_none()) {
+            let found = search
+                .search_text(&document.text)
+                .with_context(|| format!("Failed to search: {}", document.relative_path))?;
+            if !found.matches.is_empty() {
+                results.push(IndexSearchResult { relative_path: document.relative_path.clone(), results: found });
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Indexes a directory, updating and saving its index in the configuration directory
+///
+/// Only new and changed documents are extracted unless a full reindex is asked for.
+pub fn index_directory(directory: &Path, options: &IndexOptions) -> Result<IndexReport> {
+    let index_path = DocumentIndex::index_path(directory)?;
+    let mut index = DocumentIndex::load_from(&index_path)?;
+    let mut report = index.update(directory, options)?;
+    index.save_to(&index_path)?;
+    report.index_path = Some(index_path);
+    Ok(report)
+}
+
+/// Reports how current a directory's index is: last run, and documents changed, added
+/// or deleted since
+pub fn index_status(directory: &Path, options: &IndexOptions) -> Result<IndexStatus> {
+    let index_path = DocumentIndex::index_path(directory)?;
+    let index = DocumentIndex::load_from(&index_path)?;
+    let mut status = index.status(directory, options)?;
+    status.index_path = Some(index_path);
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("2024")).unwrap();
+        fs::write(dir.path().join("notes.txt"), "Call the bank about the mortgage.").unwrap();
+        fs::write(dir.path().join("2024/taxes.md"), "# Taxes\n\nMortgage interest is deductible.").unwrap();
+        fs::write(dir.path().join("broken.docx"), "not a zip").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_update_index() {
+        let dir = sample_directory();
+        let mut index = DocumentIndex::default();
+
+        let report = index.update(dir.path(), &IndexOptions::new()).unwrap();
+        assert_eq!(report.added, ["2024/taxes.md", "broken.docx", "notes.txt"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].relative_path, "broken.docx");
+        assert_eq!(index.documents[2].text, "Call the bank about the mortgage.");
+        assert!(index.indexed_at.is_some());
+
+        // Only changed documents are extracted again; broken files are not retried
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::write(dir.path().join("notes.txt"), "Call the bank about the loan.").unwrap();
+        fs::File::options().write(true).open(dir.path().join("notes.txt")).unwrap().set_modified(later).unwrap();
+        fs::remove_file(dir.path().join("2024/taxes.md")).unwrap();
+        fs::write(dir.path().join("todo.txt"), "Renew passport.").unwrap();
+
+        let report = index.update(dir.path(), &IndexOptions::new()).unwrap();
+        assert_eq!(report.added, ["todo.txt"]);
+        assert_eq!(report.updated, ["notes.txt"]);
+        assert_eq!(report.removed, ["2024/taxes.md"]);
+        assert_eq!(report.unchanged, 1);
+        assert!(report.failed.is_empty());
+        assert_eq!(report.documents, 3);
+        assert_eq!(index.documents[1].text, "Call the bank about the loan.");
+
+        assert!(index.update(&dir.path().join("missing"), &IndexOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_index_status_and_full_reindex() {
+        let dir = sample_directory();
+        let mut index = DocumentIndex::default();
+
+        let status = index.status(dir.path(), &IndexOptions::new()).unwrap();
+        assert_eq!((status.indexed_at, status.up_to_date), (None, false));
+        assert_eq!(status.unindexed.len(), 3);
+
+        index.update(dir.path(), &IndexOptions::new()).unwrap();
+        let status = index.status(dir.path(), &IndexOptions::new()).unwrap();
+        assert!(status.up_to_date, "Got: {:?}", status);
+        assert_eq!((status.documents, status.failed), (3, 1));
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::File::options().write(true).open(dir.path().join("notes.txt")).unwrap().set_modified(later).unwrap();
+        fs::remove_file(dir.path().join("2024/taxes.md")).unwrap();
+        fs::write(dir.path().join("todo.txt"), "Renew passport.").unwrap();
+        let status = index.status(dir.path(), &IndexOptions::new()).unwrap();
+        assert!(!status.up_to_date);
+        assert_eq!(status.stale, ["notes.txt"]);
+        assert_eq!(status.unindexed, ["todo.txt"]);
+        assert_eq!(status.removed, ["2024/taxes.md"]);
+
+        let report = index.update(dir.path(), &IndexOptions::new().with_full_reindex(true)).unwrap();
+        assert_eq!(report.updated, ["broken.docx", "notes.txt"], "Unchanged documents are extracted again");
+        assert_eq!(report.unchanged, 0);
+        assert!(index.status(dir.path(), &IndexOptions::new()).unwrap().up_to_date);
+    }
+
+    #[test]
+    fn test_save_and_search_index() {
+        let dir = sample_directory();
+        let mut index = DocumentIndex::default();
+        index.update(dir.path(), &IndexOptions::new()).unwrap();
+
+        let saved = tempfile::tempdir().unwrap();
+        let path = saved.path().join("index.json");
+        index.save_to(&path).unwrap();
+        let index = DocumentIndex::load_from(&path).unwrap();
+        assert_eq!(index.documents.len(), 3);
+
+        let search = Search::new("mortgage", false).unwrap().with_ignore_case(true);
+        let results = index.search(&search).unwrap();
+        let paths: Vec<&str> = results.iter().map(|result| result.relative_path.as_str()).collect();
+        assert_eq!(paths, ["2024/taxes.md", "notes.txt"]);
+        assert_eq!(results[1].results.matches[0].text, "mortgage");
+    }
+
+    #[test]
+    fn test_index_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = DocumentIndex::index_path(dir.path()).unwrap();
+        assert_eq!(path, DocumentIndex::index_path(&dir.path().join(".")).unwrap());
+        assert!(path.ends_with(Path::new(INDEX_DIR_NAME).join(path.file_name().unwrap())));
+        assert!(DocumentIndex::index_path(&dir.path().join("missing")).is_err());
+    }
+}
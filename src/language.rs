@@ -0,0 +1,277 @@
+//! Detecting the languages of extracted text
+//!
+//! Latin-script languages are told apart by their most frequent function words;
+//! other languages by their script. Text is scored paragraph by paragraph, so a
+//! document mixing languages reports each of them with its share of the text.
+
+use serde::Serialize;
+
+/// Characters examined at most; the rest of a long document is assumed to follow suit
+const MAX_SAMPLE_CHARS: usize = 100_000;
+
+/// Languages covering less than this share of the text are not reported
+const MIN_SHARE: f64 = 0.1;
+
+/// Words a paragraph needs before its function words are trusted
+const MIN_WORDS: usize = 4;
+
+/// Function words needed for a Latin-script paragraph to count
+const MIN_HITS: usize = 2;
+
+/// A language found in a text
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DetectedLanguage {
+    /// ISO 639-1 code, such as `en`
+    pub code: &'static str,
+    /// English name of the language
+    pub name: &'static str,
+    /// Share of the text in this language, from 0 to 1
+    pub share: f64,
+}
+
+/// A Latin-script language and its most frequent function words
+struct StopWords {
+    code: &'static str,
+    name: &'static str,
+    words: &'static [&'static str],
+}
+
+/// Words shared by several languages count for each of them; the distinctive ones decide
+const LATIN_LANGUAGES: &[StopWords] = &[
+    StopWords {
+        code: "en",
+        name: "English",
+        words: &[
+            "the", "and", "of", "to", "in", "is", "that", "for", "it", "with", "as", "was", "on", "are", "be",
+            "this", "by", "at", "from", "have", "not", "or", "which", "you", "will", "your", "we", "our", "has",
+            "been", "were", "an", "their", "all", "can", "would", "there", "must", "should",
+        ],
+    },
+    StopWords {
+        code: "es",
+        name: "Spanish",
+        words: &[
+            "el", "la", "los", "las", "de", "del", "y", "que", "en", "un", "una", "es", "por", "con", "para",
+            "se", "no", "su", "al", "lo", "como", "más", "pero", "sus", "le", "ya", "fue", "este", "esta", "son",
+            "entre", "cuando", "muy", "sin", "sobre", "también", "hasta", "hay", "donde", "desde", "todo",
+            "nos", "usted", "debe",
+        ],
+    },
+    StopWords {
+        code: "fr",
+        name: "French",
+        words: &[
+            "le", "la", "les", "de", "des", "du", "et", "est", "un", "une", "que", "qui", "dans", "pour", "pas",
+            "sur", "au", "aux", "avec", "ce", "cette", "il", "elle", "ne", "se", "sont", "par", "plus", "nous",
+            "vous", "mais", "ou", "été", "être", "leur", "ont", "sa", "son", "ses", "aussi", "doit", "l", "d",
+            "qu", "n",
+        ],
+    },
+    StopWords {
+        code: "de",
+        name: "German",
+        words: &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "zu", "den", "von", "mit", "sich", "des",
+            "auf", "für", "im", "dem", "auch", "es", "an", "als", "wird", "bei", "oder", "nach", "werden", "aus",
+            "sie", "wir", "ich", "sind", "wie", "noch", "einen", "einer", "über", "muss",
+        ],
+    },
+    StopWords {
+        code: "it",
+        name: "Italian",
+        words: &[
+            "il", "lo", "la", "gli", "le", "di", "del", "della", "che", "è", "e", "un", "una", "per", "non",
+            "con", "sono", "da", "nel", "nella", "si", "come", "anche", "più", "ma", "questo", "questa", "dei",
+            "delle", "alla", "al", "ha", "hanno", "essere", "suo", "sua", "deve",
+        ],
+    },
+    StopWords {
+        code: "pt",
+        name: "Portuguese",
+        words: &[
+            "o", "a", "os", "as", "de", "do", "da", "dos", "das", "e", "que", "em", "um", "uma", "para", "com",
+            "não", "no", "na", "por", "mais", "se", "como", "mas", "ao", "foi", "é", "são", "pelo", "pela",
+            "seu", "sua", "também", "está", "nos", "já", "muito", "você", "deve",
+        ],
+    },
+    StopWords {
+        code: "nl",
+        name: "Dutch",
+        words: &[
+            "de", "het", "een", "en", "van", "is", "dat", "op", "te", "in", "voor", "met", "zijn", "niet", "aan",
+            "er", "ook", "als", "bij", "door", "maar", "om", "wordt", "naar", "dan", "nog", "wel", "deze", "kan",
+            "heeft", "hij", "zij", "wij", "ze", "worden", "geen", "uit", "over", "moet",
+        ],
+    },
+];
+
+/// Writing systems, told apart by Unicode block
+///
+/// Scripts shared by several languages map to the most widely used one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Hangul,
+    Kana,
+    Han,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Self> {
+        match c {
+            'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Self::Latin),
+            '\u{0370}'..='\u{03FF}' => Some(Self::Greek),
+            '\u{0400}'..='\u{04FF}' => Some(Self::Cyrillic),
+            '\u{0590}'..='\u{05FF}' => Some(Self::Hebrew),
+            '\u{0600}'..='\u{06FF}' => Some(Self::Arabic),
+            '\u{0900}'..='\u{097F}' => Some(Self::Devanagari),
+            '\u{0E00}'..='\u{0E7F}' => Some(Self::Thai),
+            '\u{1100}'..='\u{11FF}' | '\u{AC00}'..='\u{D7AF}' => Some(Self::Hangul),
+            '\u{3040}'..='\u{30FF}' => Some(Self::Kana),
+            '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => Some(Self::Han),
+            _ => None,
+        }
+    }
+
+    fn language(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Self::Latin => None,
+            Self::Cyrillic => Some(("ru", "Russian")),
+            Self::Greek => Some(("el", "Greek")),
+            Self::Arabic => Some(("ar", "Arabic")),
+            Self::Hebrew => Some(("he", "Hebrew")),
+            Self::Devanagari => Some(("hi", "Hindi")),
+            Self::Thai => Some(("th", "Thai")),
+            Self::Hangul => Some(("ko", "Korean")),
+            Self::Kana => Some(("ja", "Japanese")),
+            Self::Han => Some(("zh", "Chinese")),
+        }
+    }
+}
+
+/// Identifies the language of one paragraph, returning it with the paragraph's letter count
+fn detect_paragraph(paragraph: &str) -> Option<((&'static str, &'static str), usize)> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    for script in paragraph.chars().filter_map(Script::of) {
+        match counts.iter_mut().find(|(counted, _)| *counted == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    let letters: usize = counts.iter().map(|(_, count)| count).sum();
+    let &(script, _) = counts.iter().max_by_key(|(_, count)| *count)?;
+
+    // Japanese mixes kana with Han characters, which alone would read as Chinese
+    if script == Script::Han && counts.iter().any(|&(counted, count)| counted == Script::Kana && count * 10 >= letters) {
+        return Some((("ja", "Japanese"), letters));
+    }
+    if let Some(language) = script.language() {
+        return Some((language, letters));
+    }
+
+    let words: Vec<String> = paragraph
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < MIN_WORDS {
+        return None;
+    }
+
+    // Ties go to the language listed first
+    let (best, hits) = LATIN_LANGUAGES
+        .iter()
+        .map(|language| {
+            let hits = words.iter().filter(|word| language.words.contains(&word.as_str())).count();
+            (language, hits)
+        })
+        .rev()
+        .max_by_key(|(_, hits)| *hits)?;
+    (hits >= MIN_HITS).then_some(((best.code, best.name), letters))
+}
+
+/// The function words of every Latin-script language told apart, e.g. `the` or `und`
+pub fn stop_words() -> impl Iterator<Item = &'static str> {
+    LATIN_LANGUAGES.iter().flat_map(|language| language.words.iter().copied())
+}
+
+/// Detects the languages of a text, most used first
+///
+/// Each language is reported with its share of the paragraphs that could be
+/// identified; languages under 10% are left out. Only the first 100,000 characters
+/// are examined. Returns an empty list when no language can be told, e.g. for tables
+/// of numbers.
+pub fn detect_languages(text: &str) -> Vec<DetectedLanguage> {
+    let sample = match text.char_indices().nth(MAX_SAMPLE_CHARS) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    };
+
+    let mut totals: Vec<((&'static str, &'static str), usize)> = Vec::new();
+    for paragraph in sample.split("\n\n") {
+        let Some((language, letters)) = detect_paragraph(paragraph) else { continue };
+        match totals.iter_mut().find(|(counted, _)| *counted == language) {
+            Some((_, total)) => *total += letters,
+            None => totals.push((language, letters)),
+        }
+    }
+
+    let detected: usize = totals.iter().map(|(_, letters)| letters).sum();
+    let mut languages: Vec<DetectedLanguage> = totals
+        .into_iter()
+        .map(|((code, name), letters)| DetectedLanguage {
+            code,
+            name,
+            share: (letters as f64 / detected as f64 * 100.0).round() / 100.0,
+        })
+        .filter(|language| language.share >= MIN_SHARE)
+        .collect();
+    languages.sort_by(|a, b| b.share.total_cmp(&a.share));
+    languages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(text: &str) -> Vec<&'static str> {
+        detect_languages(text).into_iter().map(|language| language.code).collect()
+    }
+
+    #[test]
+    fn test_detect_single_language() {
+        assert_eq!(codes("Flights must be booked through the travel portal and receipts are required for every claim."), ["en"]);
+        assert_eq!(codes("Los vuelos deben reservarse en el portal de viajes y se requieren los recibos."), ["es"]);
+        assert_eq!(codes("Les vols doivent être réservés sur le portail et les reçus sont obligatoires."), ["fr"]);
+        assert_eq!(codes("Die Flüge müssen über das Reiseportal gebucht werden und Belege sind nicht optional."), ["de"]);
+        assert_eq!(codes("I voli devono essere prenotati sul portale e le ricevute sono obbligatorie per il rimborso."), ["it"]);
+        assert_eq!(codes("Os voos devem ser reservados no portal e os recibos são obrigatórios para o reembolso."), ["pt"]);
+        assert_eq!(codes("De vluchten moeten via het reisportaal worden geboekt en bonnetjes zijn niet optioneel."), ["nl"]);
+        assert_eq!(codes("Рейсы необходимо бронировать через портал."), ["ru"]);
+        assert_eq!(codes("航空券はポータルで予約してください。"), ["ja"]);
+        assert_eq!(codes("机票必须通过旅行门户预订。"), ["zh"]);
+    }
+
+    #[test]
+    fn test_detect_mixed_languages() {
+        let text = "The expense policy applies to all employees and contractors who travel for work.\n\n\
+                    La política de gastos se aplica a todos los empleados que viajan por trabajo.";
+        let languages = detect_languages(text);
+        assert_eq!(languages.iter().map(|language| language.code).collect::<Vec<_>>(), ["en", "es"]);
+        assert_eq!(languages[0].name, "English");
+        assert!((languages[0].share + languages[1].share - 1.0).abs() < 0.011, "Got: {:?}", languages);
+    }
+
+    #[test]
+    fn test_detect_nothing() {
+        assert!(detect_languages("").is_empty());
+        assert!(detect_languages("CM 716 | AUS | PTY | 06:10").is_empty());
+        assert!(detect_languages("Invoice").is_empty(), "Too few words to tell");
+    }
+}
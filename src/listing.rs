@@ -0,0 +1,477 @@
+//! Listing the documents of a directory
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use crate::extractor::is_supported_file;
+use crate::timestamp::{format_unix_time, parse_timestamp, unix_seconds};
+
+/// Files returned by default before the listing is cut short
+const DEFAULT_MAX_RESULTS: usize = 1000;
+
+/// Files returned by default in each ranking of `list_largest_and_newest`
+pub const DEFAULT_TOP_FILES: usize = 10;
+
+/// Glob matching: `*` stays within one path component, case is ignored
+pub(crate) const GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A file found in a document directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileInfo {
+    pub path: PathBuf,
+    /// Path relative to the listed directory, with `/` separators
+    pub relative_path: String,
+    /// True when a dedicated extractor handles the file
+    pub supported: bool,
+    /// Size in bytes
+    pub size: u64,
+    /// Last modification time, as an ISO 8601 UTC timestamp
+    pub modified: Option<String>,
+}
+
+/// Order of the files in a listing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    /// By relative path, A to Z
+    #[default]
+    Name,
+    /// Most recently modified first
+    #[serde(rename = "mtime")]
+    Modified,
+    /// Largest first
+    Size,
+}
+
+/// Files listed from a directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileListing {
+    pub files: Vec<FileInfo>,
+    /// True when more files matched than the result limit
+    pub truncated: bool,
+}
+
+/// A file found by `glob_search`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GlobMatch {
+    /// The searched directory the file was found in
+    pub directory: PathBuf,
+    #[serde(flatten)]
+    pub file: FileInfo,
+}
+
+/// Files matching a glob in several directories
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GlobSearchResult {
+    pub matches: Vec<GlobMatch>,
+    /// Searched directories that do not exist (any more)
+    pub missing_directories: Vec<PathBuf>,
+    /// True when more files matched than the result limit
+    pub truncated: bool,
+}
+
+/// The largest and the most recently modified files of a directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LargestAndNewest {
+    /// Largest first
+    pub largest: Vec<FileInfo>,
+    /// Most recently modified first
+    pub newest: Vec<FileInfo>,
+    /// Number of files ranked
+    pub total_files: usize,
+}
+
+/// Filters for `list_files_in_directory`
+#[derive(Clone)]
+pub struct ListOptions {
+    pattern: Option<Pattern>,
+    extensions: Vec<String>,
+    supported_only: bool,
+    recursive: bool,
+    modified_after: Option<u64>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    sort_by: SortBy,
+    max_results: usize,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ListOptions {
+    /// Lists every file directly in the directory
+    pub fn new() -> Self {
+        Self {
+            pattern: None,
+            extensions: Vec::new(),
+            supported_only: false,
+            recursive: false,
+            modified_after: None,
+            min_size: None,
+            max_size: None,
+            sort_by: SortBy::Name,
+            max_results: DEFAULT_MAX_RESULTS,
+        }
+    }
+
+    /// Keeps files whose relative path matches a glob such as `*.pdf` or `**/2024/*.docx`
+    ///
+    /// A pattern spanning directories (containing `/` or `**`) makes the listing recursive.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim().trim_start_matches("./");
+        self.recursive |= pattern.contains('/') || pattern.contains("**");
+        self.pattern = Some(Pattern::new(pattern).with_context(|| format!("Invalid glob pattern: {}", pattern))?);
+        Ok(self)
+    }
+
+    /// Keeps files with one of these extensions (case-insensitive, with or without a dot)
+    pub fn with_extensions<S: AsRef<str>>(mut self, extensions: &[S]) -> Self {
+        self.extensions = extensions
+            .iter()
+            .map(|extension| extension.as_ref().trim().trim_start_matches('.').to_lowercase())
+            .filter(|extension| !extension.is_empty())
+            .collect();
+        self
+    }
+
+    /// Keeps only files with a dedicated extractor
+    pub fn with_supported_only(mut self, supported_only: bool) -> Self {
+        self.supported_only = supported_only;
+        self
+    }
+
+    /// Lists subdirectories too
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Keeps files modified at or after a timestamp such as `2025-08-28` or `2025-08-28T09:30:00+02:00`
+    pub fn with_modified_after(mut self, timestamp: &str) -> Result<Self> {
+        self.modified_after = Some(parse_timestamp(timestamp)?);
+        Ok(self)
+    }
+
+    /// Keeps files of at least this many bytes
+    pub fn with_min_size(mut self, min_size: u64) -> Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    /// Keeps files of at most this many bytes
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Sets the order of the files, applied before the listing is cut short
+    pub fn with_sort_by(mut self, sort_by: SortBy) -> Self {
+        self.sort_by = sort_by;
+        self
+    }
+
+    /// Sets the number of files after which the listing is cut short
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    fn matches(&self, relative_path: &str, supported: bool, size: u64, modified: Option<u64>) -> bool {
+        let name = relative_path.rsplit('/').next().unwrap_or(relative_path).to_lowercase();
+        (!self.supported_only || supported)
+            && self.min_size.is_none_or(|min_size| size >= min_size)
+            && self.max_size.is_none_or(|max_size| size <= max_size)
+            // Files without a readable modification time cannot be shown to be recent
+            && self.modified_after.is_none_or(|after| modified.is_some_and(|modified| modified >= after))
+            && (self.extensions.is_empty()
+                || self.extensions.iter().any(|extension| name.ends_with(&format!(".{}", extension))))
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.matches_with(relative_path, GLOB_OPTIONS))
+    }
+}
+
+/// Lists the files of a directory, sorted by relative path unless another order is chosen
+///
+/// Hidden files and directories (starting with `.`) are skipped, and symbolic links
+/// to directories are not followed.
+pub fn list_files_in_directory(directory: &Path, options: &ListOptions) -> Result<FileListing> {
+    if !directory.is_dir() {
+        return Err(anyhow::anyhow!("Directory not found: {}", directory.display()));
+    }
+
+    let walker = WalkDir::new(directory)
+        .min_depth(1)
+        .max_depth(if options.recursive { usize::MAX } else { 1 })
+        .into_iter()
+        .filter_entry(|entry| !entry.file_name().to_string_lossy().starts_with('.'));
+
+    let mut files = Vec::new();
+    for entry in walker {
+        // Unreadable subdirectories are skipped rather than failing the whole listing
+        let Ok(entry) = entry else { continue };
+        // Follows symbolic links to files, like `Path::is_file`
+        let Ok(metadata) = fs::metadata(entry.path()) else { continue };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let relative_path = entry
+            .path()
+            .strip_prefix(directory)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let supported = is_supported_file(entry.path());
+        let modified = metadata.modified().ok().and_then(unix_seconds);
+        if options.matches(&relative_path, supported, metadata.len(), modified) {
+            files.push((
+                modified,
+                FileInfo {
+                    path: entry.path().to_path_buf(),
+                    relative_path,
+                    supported,
+                    size: metadata.len(),
+                    modified: modified.map(format_unix_time),
+                },
+            ));
+        }
+    }
+
+    // Ties keep the name order
+    files.sort_by(|(_, a), (_, b)| a.relative_path.cmp(&b.relative_path));
+    match options.sort_by {
+        SortBy::Name => {}
+        SortBy::Modified => files.sort_by_key(|(modified, _)| Reverse(*modified)),
+        SortBy::Size => files.sort_by_key(|(_, file)| Reverse(file.size)),
+    }
+    let mut files: Vec<FileInfo> = files.into_iter().map(|(_, file)| file).collect();
+    let truncated = files.len() > options.max_results;
+    files.truncate(options.max_results);
+    Ok(FileListing { files, truncated })
+}
+
+/// Finds the files matching a glob such as `**/2024*tax*.pdf` in each directory, e.g.
+/// every directory of the configuration
+///
+/// The pattern is matched against paths relative to each directory, as in
+/// `ListOptions::with_pattern`. Directories are searched in order; a file reachable
+/// from two of them (nested directories) is reported once, under the first.
+pub fn glob_search(directories: &[PathBuf], pattern: &str, max_results: usize) -> Result<GlobSearchResult> {
+    let options = ListOptions::new().with_pattern(pattern)?;
+
+    let mut result = GlobSearchResult { matches: Vec::new(), missing_directories: Vec::new(), truncated: false };
+    let mut seen = HashSet::new();
+    for directory in directories {
+        if !directory.is_dir() {
+            result.missing_directories.push(directory.clone());
+            continue;
+        }
+        // One more than needed, so a full result can tell whether more files matched
+        let remaining = (max_results + 1).saturating_sub(result.matches.len());
+        let listing = list_files_in_directory(directory, &options.clone().with_max_results(remaining))?;
+        result.truncated |= listing.truncated;
+        for file in listing.files {
+            let canonical = file.path.canonicalize().unwrap_or_else(|_| file.path.clone());
+            if seen.insert(canonical) {
+                result.matches.push(GlobMatch { directory: directory.clone(), file });
+            }
+        }
+    }
+
+    result.truncated |= result.matches.len() > max_results;
+    result.matches.truncate(max_results);
+    Ok(result)
+}
+
+/// Returns the `top` largest and the `top` most recently modified files of a directory and its subdirectories
+///
+/// The filters of `options` apply; its order and result limit are ignored. The directory is
+/// walked once for both rankings.
+pub fn list_largest_and_newest(directory: &Path, options: &ListOptions, top: usize) -> Result<LargestAndNewest> {
+    let options = options.clone().with_recursive(true).with_max_results(usize::MAX);
+    let files = list_files_in_directory(directory, &options)?.files;
+
+    // Timestamps share one ISO 8601 format, so they sort as strings; ties keep the name order
+    let mut newest = files.clone();
+    newest.sort_by(|a, b| b.modified.cmp(&a.modified));
+    newest.truncate(top);
+    let total_files = files.len();
+    let mut largest = files;
+    largest.sort_by_key(|file| Reverse(file.size));
+    largest.truncate(top);
+    Ok(LargestAndNewest { largest, newest, total_files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["invoice.pdf", "notes.MD", "photo.heic", "2024/tax-return.PDF", "2024/receipts/taxi.pdf", ".hidden/secret.pdf", ".DS_Store"] {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        dir
+    }
+
+    fn names(listing: &FileListing) -> Vec<&str> {
+        listing.files.iter().map(|file| file.relative_path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_list_files_in_directory() {
+        let dir = sample_directory();
+
+        let listing = list_files_in_directory(dir.path(), &ListOptions::new()).unwrap();
+        assert_eq!(names(&listing), ["invoice.pdf", "notes.MD", "photo.heic"]);
+        assert!(!listing.truncated);
+        assert!(listing.files[0].supported);
+        assert!(!listing.files[2].supported);
+
+        let listing = list_files_in_directory(dir.path(), &ListOptions::new().with_recursive(true)).unwrap();
+        assert_eq!(
+            names(&listing),
+            ["2024/receipts/taxi.pdf", "2024/tax-return.PDF", "invoice.pdf", "notes.MD", "photo.heic"]
+        );
+    }
+
+    #[test]
+    fn test_list_files_with_filters() {
+        let dir = sample_directory();
+        let list = |options: ListOptions| names(&list_files_in_directory(dir.path(), &options).unwrap()).join(", ");
+
+        assert_eq!(
+            list(ListOptions::new().with_pattern("**/*.pdf").unwrap()),
+            "2024/receipts/taxi.pdf, 2024/tax-return.PDF, invoice.pdf"
+        );
+        assert_eq!(list(ListOptions::new().with_pattern("2024/*tax*").unwrap()), "2024/tax-return.PDF");
+        assert_eq!(list(ListOptions::new().with_pattern("*.pdf").unwrap()), "invoice.pdf", "`*` stays in the directory");
+        assert_eq!(list(ListOptions::new().with_extensions(&[".md", "HEIC"])), "notes.MD, photo.heic");
+        assert_eq!(list(ListOptions::new().with_supported_only(true)), "invoice.pdf, notes.MD");
+
+        let listing = list_files_in_directory(dir.path(), &ListOptions::new().with_recursive(true).with_max_results(2)).unwrap();
+        assert_eq!(listing.files.len(), 2);
+        assert!(listing.truncated);
+
+        assert!(ListOptions::new().with_pattern("[").is_err());
+        assert!(list_files_in_directory(&dir.path().join("missing"), &ListOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_list_files_by_date_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, size, modified) in [
+            ("contract.pdf", 300, "2025-08-20T10:00:00Z"),
+            ("budget.xlsx", 1200, "2025-06-01"),
+            ("agenda.docx", 40, "2025-08-27T08:15:00Z"),
+        ] {
+            let path = dir.path().join(name);
+            fs::write(&path, vec![b'x'; size]).unwrap();
+            let modified = UNIX_EPOCH + Duration::from_secs(parse_timestamp(modified).unwrap());
+            fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        }
+        let list = |options: ListOptions| names(&list_files_in_directory(dir.path(), &options).unwrap()).join(", ");
+
+        let listing = list_files_in_directory(dir.path(), &ListOptions::new()).unwrap();
+        assert_eq!(listing.files[0].size, 40);
+        assert_eq!(listing.files[0].modified.as_deref(), Some("2025-08-27T08:15:00Z"));
+
+        assert_eq!(list(ListOptions::new().with_sort_by(SortBy::Modified)), "agenda.docx, contract.pdf, budget.xlsx");
+        assert_eq!(list(ListOptions::new().with_sort_by(SortBy::Size)), "budget.xlsx, contract.pdf, agenda.docx");
+        assert_eq!(
+            list(ListOptions::new().with_modified_after("2025-08-20").unwrap()),
+            "agenda.docx, contract.pdf"
+        );
+        assert_eq!(list(ListOptions::new().with_min_size(40).with_max_size(300)), "agenda.docx, contract.pdf");
+
+        let listing = list_files_in_directory(
+            dir.path(),
+            &ListOptions::new().with_sort_by(SortBy::Modified).with_max_results(1),
+        )
+        .unwrap();
+        assert_eq!(names(&listing), ["agenda.docx"], "Sorting happens before truncation");
+
+        assert_eq!(serde_json::from_str::<SortBy>("\"mtime\"").unwrap(), SortBy::Modified);
+        assert!(ListOptions::new().with_modified_after("last week").is_err());
+    }
+
+    #[test]
+    fn test_list_largest_and_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, size, modified) in [
+            ("scans/contract.pdf", 9000, "2025-08-20T10:00:00Z"),
+            ("budget.xlsx", 1200, "2025-06-01"),
+            ("agenda.docx", 40, "2025-08-27T08:15:00Z"),
+            ("notes.txt", 10, "2024-01-05"),
+        ] {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, vec![b'x'; size]).unwrap();
+            let modified = UNIX_EPOCH + Duration::from_secs(parse_timestamp(modified).unwrap());
+            fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        }
+        let relative = |files: &[FileInfo]| files.iter().map(|file| file.relative_path.clone()).collect::<Vec<_>>();
+
+        let result = list_largest_and_newest(dir.path(), &ListOptions::new(), 2).unwrap();
+        assert_eq!(result.total_files, 4);
+        assert_eq!(relative(&result.largest), ["scans/contract.pdf", "budget.xlsx"], "Subdirectories are included");
+        assert_eq!(relative(&result.newest), ["agenda.docx", "scans/contract.pdf"]);
+
+        let options = ListOptions::new().with_extensions(&["docx", "txt"]).with_max_results(0);
+        let result = list_largest_and_newest(dir.path(), &options, DEFAULT_TOP_FILES).unwrap();
+        assert_eq!(relative(&result.largest), ["agenda.docx", "notes.txt"], "The result limit is ignored");
+        assert_eq!(relative(&result.newest), ["agenda.docx", "notes.txt"]);
+    }
+
+    #[test]
+    fn test_glob_search() {
+        let personal = sample_directory();
+        let work = tempfile::tempdir().unwrap();
+        fs::create_dir_all(work.path().join("2024")).unwrap();
+        fs::write(work.path().join("2024/2024-tax-summary.pdf"), "x").unwrap();
+        fs::write(work.path().join("2024-tax-notes.txt"), "x").unwrap();
+        let missing = work.path().join("archive");
+        let directories =
+            [personal.path().to_path_buf(), missing.clone(), work.path().to_path_buf(), personal.path().join("2024")];
+
+        let result = glob_search(&directories, "**/*tax*.pdf", 10).unwrap();
+        let found: Vec<(&Path, &str)> =
+            result.matches.iter().map(|m| (m.directory.as_path(), m.file.relative_path.as_str())).collect();
+        assert_eq!(
+            found,
+            [
+                (personal.path(), "2024/receipts/taxi.pdf"),
+                (personal.path(), "2024/tax-return.PDF"),
+                (work.path(), "2024/2024-tax-summary.pdf"),
+            ],
+            "Files under a nested directory should be reported once"
+        );
+        assert_eq!(result.missing_directories, [missing]);
+        assert!(!result.truncated);
+
+        let result = glob_search(&directories, "**/*", 3).unwrap();
+        assert_eq!(result.matches.len(), 3);
+        assert!(result.truncated);
+
+        assert!(glob_search(&directories, "[", 10).is_err());
+    }
+}
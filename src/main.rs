@@ -1,5 +1,40 @@
+pub mod ask;
+pub mod bookmarks;
+pub mod cache;
+pub mod chunk;
+pub mod citations;
+pub mod completion;
+pub mod config;
+pub mod constants;
+pub mod duplicates;
+pub mod elicitation;
+pub mod download;
+pub mod entities;
+pub mod export;
 pub mod extractor;
 pub mod extractors;
+pub mod file_type;
+pub mod hash;
+pub mod index;
+pub mod language;
+pub mod listing;
+pub mod ocr;
+pub mod pagination;
+pub mod pattern;
+pub mod prompts;
+pub mod protocol;
+pub mod quote;
+pub mod redact;
+pub mod resources;
+pub mod rpc_error;
+pub mod search;
+pub mod summarize;
+pub mod terms;
+pub mod timestamp;
+pub mod tool_result;
+pub mod upload;
+pub mod validate;
+pub mod watch;
 
 fn main() {
     println!("Hello, world!!!!");
@@ -0,0 +1,183 @@
+//! Forced OCR of PDFs and images, with a choice of languages and resolution
+//!
+//! Unlike the automatic fallback in `PdfExtractor`, which only recognizes PDFs without
+//! a usable text layer, `ocr_file` always recognizes the rendered pages. That helps
+//! with PDFs whose text layer exists but is garbage.
+
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use extractous::{Extractor, TesseractOcrConfig};
+use serde::Serialize;
+use crate::config::{program_on_path, Config};
+use crate::extractors::pdf_extractor::{PdfExtractor, OCR_LANGUAGE};
+use crate::file_type::image_mime_type;
+
+/// Resolution Tesseract works at unless the caller asks otherwise, in dots per inch
+pub const DEFAULT_OCR_DPI: u32 = 300;
+
+/// Resolutions Tesseract accepts, in dots per inch
+const MIN_OCR_DPI: u32 = 150;
+const MAX_OCR_DPI: u32 = 1200;
+
+/// Text recognized by `ocr_file`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OcrText {
+    pub text: String,
+    /// MIME type of the recognized file, e.g. `application/pdf` or `image/png`
+    pub mime_type: String,
+    /// Tesseract languages used, e.g. `["eng", "fra"]`
+    pub languages: Vec<String>,
+    pub dpi: u32,
+}
+
+/// Settings for `ocr_file`
+pub struct OcrOptions {
+    languages: Vec<String>,
+    dpi: u32,
+    password: Option<String>,
+}
+
+impl Default for OcrOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OcrOptions {
+    /// Recognizes English text at `DEFAULT_OCR_DPI`
+    pub fn new() -> Self {
+        Self { languages: vec![OCR_LANGUAGE.to_string()], dpi: DEFAULT_OCR_DPI, password: None }
+    }
+
+    /// Sets the Tesseract languages, e.g. `["eng", "deu"]`; `"eng+deu"` is split too
+    ///
+    /// Each language's trained data must be installed with Tesseract.
+    pub fn with_languages<S: AsRef<str>>(mut self, languages: &[S]) -> Result<Self> {
+        let languages: Vec<String> = languages
+            .iter()
+            .flat_map(|language| language.as_ref().split(['+', ',']))
+            .map(str::trim)
+            .filter(|language| !language.is_empty())
+            .map(str::to_string)
+            .collect();
+        if languages.is_empty() {
+            return Err(anyhow::anyhow!("At least one OCR language is required"));
+        }
+        if let Some(invalid) = languages
+            .iter()
+            .find(|language| !language.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid OCR language: '{}' (expected Tesseract codes such as eng, fra, chi_sim)",
+                invalid
+            ));
+        }
+        self.languages = languages;
+        Ok(self)
+    }
+
+    /// Sets the resolution Tesseract works at, between 150 and 1200 DPI
+    pub fn with_dpi(mut self, dpi: u32) -> Result<Self> {
+        if !(MIN_OCR_DPI..=MAX_OCR_DPI).contains(&dpi) {
+            return Err(anyhow::anyhow!(
+                "OCR resolution must be between {} and {} DPI, got {}",
+                MIN_OCR_DPI,
+                MAX_OCR_DPI,
+                dpi
+            ));
+        }
+        self.dpi = dpi;
+        Ok(self)
+    }
+
+    /// Sets the password for encrypted PDFs
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    fn tesseract_config(&self) -> TesseractOcrConfig {
+        TesseractOcrConfig::new()
+            .set_language(&self.languages.join("+"))
+            .set_density(self.dpi as i32)
+    }
+}
+
+/// Tells whether Tesseract, which OCR relies on, is installed
+pub fn ocr_available() -> bool {
+    program_on_path("tesseract")
+}
+
+/// Recognizes the text of a PDF or an image (PNG, JPEG, GIF, WebP, BMP, TIFF) with OCR
+///
+/// PDFs are recognized page by page even when they have a text layer. Other formats
+/// are rejected; the format is told from the content, not the extension.
+pub fn ocr_file(file_path: &Path, options: &OcrOptions) -> Result<OcrText> {
+    if !file_path.is_file() {
+        return Err(anyhow::anyhow!("File not found: {}", file_path.display()));
+    }
+    let data = fs::read(file_path).with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+
+    let (mime_type, text) = if data.starts_with(b"%PDF-") {
+        let mut extractor = PdfExtractor::new();
+        if let Ok(config_path) = Config::config_path() {
+            extractor = extractor.with_password_store(config_path);
+        }
+        if let Some(password) = &options.password {
+            extractor = extractor.with_password(password);
+        }
+        ("application/pdf", extractor.ocr_file(file_path, options.tesseract_config())?)
+    } else if let Some(mime_type) = image_mime_type(&data) {
+        let (text, _metadata) = Extractor::new()
+            .set_ocr_config(options.tesseract_config())
+            .extract_bytes_to_string(&data)
+            .map_err(|e| anyhow::anyhow!("OCR failed for: {} ({})", file_path.display(), e))?;
+        (mime_type, text)
+    } else {
+        return Err(anyhow::anyhow!("OCR is only supported for PDFs and images: {}", file_path.display()));
+    };
+
+    Ok(OcrText {
+        text: text.trim().to_string(),
+        mime_type: mime_type.to_string(),
+        languages: options.languages.clone(),
+        dpi: options.dpi,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_ocr_options() {
+        let options = OcrOptions::new();
+        assert_eq!(options.languages, ["eng"]);
+        assert_eq!(options.dpi, DEFAULT_OCR_DPI);
+
+        let options = OcrOptions::new()
+            .with_languages(&["eng+fra", " deu ", "chi_sim"])
+            .unwrap()
+            .with_dpi(600)
+            .unwrap();
+        assert_eq!(options.languages, ["eng", "fra", "deu", "chi_sim"]);
+        assert_eq!(options.dpi, 600);
+
+        assert!(OcrOptions::new().with_languages(&[" ", "+"]).is_err());
+        assert!(OcrOptions::new().with_languages(&["eng; rm -rf"]).is_err());
+        assert!(OcrOptions::new().with_dpi(72).is_err());
+        assert!(OcrOptions::new().with_dpi(2400).is_err());
+    }
+
+    #[test]
+    fn test_ocr_file_rejects_other_formats() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+
+        let error = ocr_file(&path.join("policy.docx"), &OcrOptions::new()).unwrap_err();
+        assert!(error.to_string().starts_with("OCR is only supported for PDFs and images"), "Got: {}", error);
+        assert!(ocr_file(&path.join("missing.pdf"), &OcrOptions::new()).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Cursor pagination for MCP list requests (`tools/list`, `prompts/list`, ...)
+//!
+//! Cursors are opaque to clients: the base64 encoding of the position of the next item.
+
+use anyhow::Result;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Serialize;
+
+/// Items returned per page by default
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// One page of a list
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to send for the next page; `None` on the last page
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Returns the page of `items` starting at `cursor` (the first page without one)
+///
+/// A cursor past the end of the list gives an empty last page, so a list that shrank
+/// between two requests does not fail; a cursor this module did not produce is an error.
+pub fn paginate<T>(items: Vec<T>, cursor: Option<&str>, page_size: usize) -> Result<Page<T>> {
+    if page_size == 0 {
+        return Err(anyhow::anyhow!("Page size must be at least 1"));
+    }
+    let start = match cursor {
+        Some(cursor) => decode_cursor(cursor).ok_or_else(|| anyhow::anyhow!("Invalid cursor: {}", cursor))?,
+        None => 0,
+    };
+    let end = start.saturating_add(page_size);
+    let next_cursor = (end < items.len()).then(|| encode_cursor(end));
+    let items = items.into_iter().skip(start).take(page_size).collect();
+    Ok(Page { items, next_cursor })
+}
+
+fn encode_cursor(position: usize) -> String {
+    URL_SAFE_NO_PAD.encode(position.to_string())
+}
+
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    let decoded = URL_SAFE_NO_PAD.decode(cursor.trim()).ok()?;
+    String::from_utf8(decoded).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let items: Vec<usize> = (1..=5).collect();
+        let first = paginate(items.clone(), None, 2).unwrap();
+        assert_eq!(first.items, [1, 2]);
+        let second = paginate(items.clone(), first.next_cursor.as_deref(), 2).unwrap();
+        assert_eq!(second.items, [3, 4]);
+        let last = paginate(items.clone(), second.next_cursor.as_deref(), 2).unwrap();
+        assert_eq!(last, Page { items: vec![5], next_cursor: None });
+
+        let whole = paginate(items.clone(), None, DEFAULT_PAGE_SIZE).unwrap();
+        assert_eq!(whole.items, items);
+        assert_eq!(serde_json::to_value(&whole).unwrap(), serde_json::json!({ "items": [1, 2, 3, 4, 5] }));
+        assert!(serde_json::to_string(&first).unwrap().contains("\"nextCursor\""));
+    }
+
+    #[test]
+    fn test_paginate_rejects_bad_cursors() {
+        let past_end = paginate(vec![1, 2], Some(&encode_cursor(10)), 2).unwrap();
+        assert_eq!(past_end, Page { items: vec![], next_cursor: None }, "A shrunk list ends early");
+
+        let error = paginate(vec![1, 2], Some("not a cursor"), 2).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid cursor: not a cursor");
+        assert!(paginate(vec![1, 2], Some(&URL_SAFE_NO_PAD.encode("-1")), 2).is_err());
+        assert!(paginate(vec![1, 2], None, 0).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Regular expressions for searching extracted text, backed by the `regex` crate
+//!
+//! The syntax is the `regex` crate's: literals and escapes, `.`, character classes
+//! (`[a-z]`, `[^...]`, `\d`, `\w`, `\s` and their negations), anchors (`^`, `$`, `\b`,
+//! `\B`), groups (`(...)`, `(?:...)`), alternation and the quantifiers `*`, `+`, `?`
+//! and `{m,n}` (greedy or lazy). A leading `(?i)` makes the pattern case-insensitive.
+//! `^` and `$` match at line boundaries and `.` does not match newlines.
+//! Backreferences and lookaround are not supported.
+//!
+//! Matching runs in time linear in the text, without recursion, so long lines and
+//! pathological patterns cannot exhaust the stack or hang a search.
+
+use std::sync::OnceLock;
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+
+/// A compiled search pattern
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    /// Source in `regex` syntax; literal patterns are escaped
+    source: String,
+    ignore_case: bool,
+    compiled: OnceLock<Regex>,
+}
+
+/// A match, as char offsets into the searched text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Pattern {
+    /// Compiles a regular expression
+    pub fn regex(pattern: &str) -> Result<Self> {
+        let pattern = Self { source: pattern.to_string(), ignore_case: false, compiled: OnceLock::new() };
+        let regex = pattern.build()?;
+        pattern.compiled.get_or_init(|| regex);
+        Ok(pattern)
+    }
+
+    /// Creates a pattern matching `text` literally
+    pub fn literal(text: &str) -> Self {
+        Self { source: regex::escape(text), ignore_case: false, compiled: OnceLock::new() }
+    }
+
+    /// Makes the pattern case-insensitive (like a leading `(?i)`)
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        if ignore_case != self.ignore_case {
+            self.ignore_case = ignore_case;
+            self.compiled = OnceLock::new();
+        }
+        self
+    }
+
+    fn build(&self) -> Result<Regex> {
+        RegexBuilder::new(&self.source)
+            .case_insensitive(self.ignore_case)
+            .multi_line(true)
+            .build()
+            .map_err(|error| anyhow::anyhow!("{}", error))
+    }
+
+    /// Finds the non-overlapping matches in `text`, leftmost first, stopping after `limit`
+    pub fn find_all(&self, text: &[char], limit: usize) -> Result<Vec<Match>> {
+        let regex = match self.compiled.get() {
+            Some(regex) => regex,
+            None => {
+                let regex = self.build()?;
+                self.compiled.get_or_init(|| regex)
+            }
+        };
+        let haystack: String = text.iter().collect();
+
+        // Byte offsets of the matches increase, so char offsets are counted as they go
+        let (mut byte, mut offset) = (0, 0);
+        let mut char_offset = |to: usize| {
+            offset += haystack[byte..to].chars().count();
+            byte = to;
+            offset
+        };
+        Ok(regex
+            .find_iter(&haystack)
+            .take(limit)
+            .map(|found| Match { start: char_offset(found.start()), end: char_offset(found.end()) })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, text: &str) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        Pattern::regex(pattern)
+            .unwrap()
+            .find_all(&chars, usize::MAX)
+            .unwrap()
+            .iter()
+            .map(|found| chars[found.start..found.end].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_all() {
+        assert_eq!(find(r"CM \d{3}", "CM 716 and CM 155, not CM 1"), ["CM 716", "CM 155"]);
+        assert_eq!(find(r"[A-Z]{3}-[A-Z]{3}", "AUS-PTY, PTY-MDE"), ["AUS-PTY", "PTY-MDE"]);
+        assert_eq!(find(r"\bfare\b", "fare fares airfare fare."), ["fare", "fare"]);
+        assert_eq!(find(r"(?i)^total: .*$", "Subtotal: 3\nTOTAL: 412.50\n"), ["TOTAL: 412.50"]);
+        assert_eq!(find(r"colou?r|gray", "color colour grey gray"), ["color", "colour", "gray"]);
+        assert_eq!(find(r"<.+?>", "<b>bold</b>"), ["<b>", "</b>"]);
+        assert_eq!(find(r"(?:ab)+c", "ababc abc ac"), ["ababc", "abc"]);
+        assert_eq!(find(r"[^\s,]+", "a1, b2"), ["a1", "b2"]);
+        assert_eq!(find(r"x{2,}", "x xx xxxx"), ["xx", "xxxx"]);
+        assert_eq!(find(r"(a*)*b", "aaab"), ["aaab"]);
+        assert_eq!(find(r"[\w.]+@\w+\.com", "mail thomas.p@example.com now"), ["thomas.p@example.com"]);
+        assert_eq!(find(r"(?i)é", "CAFÉ café"), ["É", "é"]);
+    }
+
+    #[test]
+    fn test_char_offsets() {
+        let chars: Vec<char> = "Café — 12 €, thé — 3 €".chars().collect();
+        let matches = Pattern::regex(r"\d+ €").unwrap().find_all(&chars, 10).unwrap();
+        assert_eq!(matches, [Match { start: 7, end: 11 }, Match { start: 19, end: 22 }]);
+        assert_eq!(Pattern::regex(r"\d+").unwrap().find_all(&chars, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_literal() {
+        let chars: Vec<char> = "Fare (USD): 412.50".chars().collect();
+        let matches = Pattern::literal("(usd)").with_ignore_case(true).find_all(&chars, 10).unwrap();
+        assert_eq!(matches, [Match { start: 5, end: 10 }]);
+        assert!(Pattern::literal("(usd)").find_all(&chars, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        for pattern in ["(fare", "fare)", "[a-", "*fare", r"\q", "a{3,1}", "(?=a)", "[z-a]"] {
+            assert!(Pattern::regex(pattern).is_err(), "{} should be rejected", pattern);
+        }
+    }
+
+    #[test]
+    fn test_long_texts() {
+        let word: Vec<char> = "a".repeat(200_000).chars().collect();
+        assert_eq!(Pattern::regex(r"\w+").unwrap().find_all(&word, 10).unwrap(), [Match { start: 0, end: 200_000 }]);
+        assert!(Pattern::regex("a.*z").unwrap().find_all(&word, 10).unwrap().is_empty());
+
+        let lines: Vec<char> = "fare 412.50 USD\n".repeat(10_000).chars().collect();
+        let fares = Pattern::regex(r"(?i)^FARE [\d.]+").unwrap().find_all(&lines, usize::MAX).unwrap();
+        assert_eq!(fares.len(), 10_000);
+        assert_eq!(fares[9_999], Match { start: 159_984, end: 159_995 });
+
+        // Exponential for a backtracking engine, linear here
+        let text: Vec<char> = "a".repeat(100_000).chars().collect();
+        assert!(Pattern::regex("(a|a)*(a|a)*(a|a)*b").unwrap().find_all(&text, 1).unwrap().is_empty());
+    }
+}
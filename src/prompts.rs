@@ -0,0 +1,279 @@
+//! Document-centric prompts, for MCP's `prompts/list` and `prompts/get`
+//!
+//! Each prompt embeds the extracted text of the documents it names in its message, so
+//! the client's model starts with the content instead of having to call tools for it.
+//! Text beyond the token budget is cut, with a note saying so.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::chunk::{estimate_tokens, ChunkUnit, Chunker};
+use crate::extractor::create_extractor;
+use crate::pagination::{paginate, Page};
+
+/// Tokens of document text embedded in one prompt by default, shared by its documents
+pub const DEFAULT_PROMPT_TOKENS: usize = 50_000;
+
+const SUMMARIZE_PROMPT: &str = "summarize";
+const COMPARE_PROMPT: &str = "compare";
+const FIND_CLAUSES_PROMPT: &str = "find_clauses";
+
+/// A prompt template, as listed by `prompts/list`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Prompt {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// An argument of a prompt template
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptArgument {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+}
+
+/// A prompt filled in with its arguments, as returned by `prompts/get`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptResult {
+    pub description: String,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// A message of a filled-in prompt
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptMessage {
+    /// `user`, as the prompts are requests from the user
+    pub role: &'static str,
+    pub content: PromptContent,
+}
+
+/// Content of a prompt message
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PromptContent {
+    Text { text: String },
+}
+
+fn argument(name: &'static str, description: &'static str, required: bool) -> PromptArgument {
+    PromptArgument { name, description, required }
+}
+
+/// Returns the prompt templates offered by `prompts/list`
+pub fn list_prompts() -> Vec<Prompt> {
+    vec![
+        Prompt {
+            name: SUMMARIZE_PROMPT,
+            description: "Summarize a document",
+            arguments: vec![
+                argument("file", "Path of the document", true),
+                argument("focus", "Topic to concentrate on, e.g. payment terms", false),
+            ],
+        },
+        Prompt {
+            name: COMPARE_PROMPT,
+            description: "Compare two documents, e.g. two versions of a contract",
+            arguments: vec![
+                argument("file_a", "Path of the first document", true),
+                argument("file_b", "Path of the second document", true),
+            ],
+        },
+        Prompt {
+            name: FIND_CLAUSES_PROMPT,
+            description: "Find the clauses of a document about a topic",
+            arguments: vec![
+                argument("topic", "What the clauses are about, e.g. indemnification", true),
+                argument("file", "Path of the document", true),
+            ],
+        },
+    ]
+}
+
+/// Returns one page of the prompt templates, for `prompts/list` requests with a cursor
+pub fn list_prompts_page(cursor: Option<&str>, page_size: usize) -> Result<Page<Prompt>> {
+    paginate(list_prompts(), cursor, page_size)
+}
+
+/// Fills in a prompt template, embedding the text of its documents within `max_tokens` tokens
+pub fn get_prompt(name: &str, arguments: &BTreeMap<String, String>, max_tokens: usize) -> Result<PromptResult> {
+    let template = list_prompts()
+        .into_iter()
+        .find(|prompt| prompt.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Prompt not found: {}", name))?;
+    let value = |argument: &str| arguments.get(argument).map(|value| value.trim()).filter(|value| !value.is_empty());
+    for argument in template.arguments.iter().filter(|argument| argument.required) {
+        if value(argument.name).is_none() {
+            return Err(anyhow::anyhow!("Missing argument for prompt {}: {}", name, argument.name));
+        }
+    }
+    let required = |argument: &str| value(argument).unwrap_or_default();
+
+    let (description, text) = match name {
+        SUMMARIZE_PROMPT => {
+            let file = required("file");
+            let focus = match value("focus") {
+                Some(focus) => format!(" Concentrate on: {}.", focus),
+                None => String::new(),
+            };
+            (
+                format!("Summarize {}", file),
+                format!(
+                    "Summarize the document below. Keep names, dates, amounts and decisions, and do not add \
+                     information that is not in it.{}\n\n{}",
+                    focus,
+                    embed_document(Path::new(file), max_tokens)?
+                ),
+            )
+        }
+        COMPARE_PROMPT => {
+            let (file_a, file_b) = (required("file_a"), required("file_b"));
+            (
+                format!("Compare {} and {}", file_a, file_b),
+                format!(
+                    "Compare the two documents below. Say briefly what they have in common, then list every \
+                     difference in substance (parties, dates, amounts, obligations, terms), quoting both \
+                     versions.\n\n{}\n\n{}",
+                    embed_document(Path::new(file_a), max_tokens / 2)?,
+                    embed_document(Path::new(file_b), max_tokens / 2)?
+                ),
+            )
+        }
+        _ => {
+            let (topic, file) = (required("topic"), required("file"));
+            (
+                format!("Find clauses about {} in {}", topic, file),
+                format!(
+                    "Find every clause of the document below about {}. Quote each clause exactly with its \
+                     number or heading, then explain in a sentence what it means. If there is none, say so.\n\n{}",
+                    topic,
+                    embed_document(Path::new(file), max_tokens)?
+                ),
+            )
+        }
+    };
+    Ok(PromptResult {
+        description,
+        messages: vec![PromptMessage { role: "user", content: PromptContent::Text { text } }],
+    })
+}
+
+/// Extracts a document and wraps its text in a `<document>` element, cut at `max_tokens` tokens
+fn embed_document(file_path: &Path, max_tokens: usize) -> Result<String> {
+    let text = create_extractor(file_path)?.extract_text_from_file(file_path)?;
+    let text = text.trim();
+    let total_tokens = estimate_tokens(text);
+    let chunks = Chunker::new(max_tokens.max(1), 0)
+        .context("Invalid prompt size")?
+        .with_unit(ChunkUnit::Tokens)
+        .chunk_text(text, "");
+
+    let mut embedded = format!("<document path=\"{}\">\n", file_path.display());
+    embedded.push_str(chunks.first().map_or("", |chunk| chunk.text.as_str()));
+    if chunks.len() > 1 {
+        embedded.push_str(&format!(
+            "\n[Truncated: only the first {} of about {} tokens are shown]",
+            estimate_tokens(&chunks[0].text),
+            total_tokens
+        ));
+    }
+    embedded.push_str("\n</document>");
+    Ok(embedded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn arguments(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn text(result: &PromptResult) -> &str {
+        match &result.messages[0].content {
+            PromptContent::Text { text } => text,
+        }
+    }
+
+    #[test]
+    fn test_list_prompts() {
+        let prompts = list_prompts();
+        let names: Vec<&str> = prompts.iter().map(|prompt| prompt.name).collect();
+        assert_eq!(names, [SUMMARIZE_PROMPT, COMPARE_PROMPT, FIND_CLAUSES_PROMPT]);
+
+        let json = serde_json::to_value(&prompts[0]).unwrap();
+        assert_eq!(json["arguments"][1], serde_json::json!({
+            "name": "focus",
+            "description": "Topic to concentrate on, e.g. payment terms",
+            "required": false,
+        }));
+    }
+
+    #[test]
+    fn test_list_prompts_page() {
+        let first = list_prompts_page(None, 2).unwrap();
+        assert_eq!(first.items.len(), 2);
+        let rest = list_prompts_page(first.next_cursor.as_deref(), 2).unwrap();
+        assert_eq!(rest.items[0].name, FIND_CLAUSES_PROMPT);
+        assert_eq!(rest.next_cursor, None);
+    }
+
+    #[test]
+    fn test_get_prompt_embeds_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("lease-2024.txt");
+        let new = dir.path().join("lease-2025.txt");
+        fs::write(&old, "Rent: 1200 EUR per month.\n").unwrap();
+        fs::write(&new, "Rent: 1250 EUR per month.\n").unwrap();
+        let (old, new) = (old.to_string_lossy(), new.to_string_lossy());
+
+        let result = get_prompt(
+            COMPARE_PROMPT,
+            &arguments(&[("file_a", &old), ("file_b", &new)]),
+            DEFAULT_PROMPT_TOKENS,
+        )
+        .unwrap();
+        assert_eq!(result.description, format!("Compare {} and {}", old, new));
+        assert_eq!(result.messages[0].role, "user");
+        let embedded = format!("<document path=\"{}\">\nRent: 1200 EUR per month.\n</document>", old);
+        assert!(text(&result).contains(&embedded), "Got: {}", text(&result));
+        assert!(text(&result).contains("Rent: 1250 EUR"));
+
+        let result = get_prompt(SUMMARIZE_PROMPT, &arguments(&[("file", &old), ("focus", " rent ")]), 50).unwrap();
+        assert!(text(&result).contains("Concentrate on: rent."));
+
+        let json = serde_json::to_value(&result.messages[0]).unwrap();
+        assert_eq!(json["content"]["type"], "text");
+    }
+
+    #[test]
+    fn test_get_prompt_truncates_long_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contract.txt");
+        fs::write(&path, "The supplier indemnifies the client. ".repeat(100)).unwrap();
+
+        let result = get_prompt(
+            FIND_CLAUSES_PROMPT,
+            &arguments(&[("topic", "indemnification"), ("file", &path.to_string_lossy())]),
+            20,
+        )
+        .unwrap();
+        assert!(text(&result).starts_with("Find every clause of the document below about indemnification."));
+        // Cut at the end of a sentence, within the budget
+        let expected = "the client.\n[Truncated: only the first 18 of about 900 tokens are shown]";
+        assert!(text(&result).contains(expected), "Got: {}", text(&result));
+    }
+
+    #[test]
+    fn test_get_prompt_rejects_bad_requests() {
+        let error = get_prompt("translate", &BTreeMap::new(), DEFAULT_PROMPT_TOKENS).unwrap_err();
+        assert_eq!(error.to_string(), "Prompt not found: translate");
+
+        let error = get_prompt(COMPARE_PROMPT, &arguments(&[("file_a", "a.pdf"), ("file_b", " ")]), 100).unwrap_err();
+        assert_eq!(error.to_string(), "Missing argument for prompt compare: file_b");
+
+        assert!(get_prompt(SUMMARIZE_PROMPT, &arguments(&[("file", "/nonexistent/file.txt")]), 100).is_err());
+    }
+}
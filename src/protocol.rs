@@ -0,0 +1,257 @@
+//! The MCP `initialize` handshake
+//!
+//! The client's `initialize` request carries the protocol version it wants and the
+//! optional features it supports. The capabilities are kept in the session so the
+//! server only sends requests the client can answer (sampling, elicitation, roots).
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::constants::SUPPORTED_PROTOCOL_VERSIONS;
+
+/// First paragraph of the server instructions
+const INSTRUCTIONS_INTRO: &str = "docu-mcp reads local documents (PDF, Office, OpenDocument, e-books, mail, web \
+                                  pages, data files, ...) and returns their text and data; nothing is uploaded.";
+
+/// How to use each tool well, in the order a model would usually call them; only the
+/// hints of registered tools go into the instructions
+const TOOL_HINTS: &[(&str, &str)] = &[
+    ("set_active_directory", "Set the active directory first: directory-wide tools work on it by default."),
+    ("list_files_in_directory", "List a directory's files to find documents before opening them."),
+    ("get_supported_formats", "Call get_supported_formats when unsure whether a file type can be read."),
+    ("get_document_info", "Check a document's size, page count and metadata with get_document_info first."),
+    ("search_in_document", "Prefer search_in_document to locate passages over extracting a whole document."),
+    ("get_snippets", "Use get_snippets for short excerpts around every match of a search."),
+    ("get_document_outline", "Read the outline with get_document_outline to find the relevant sections."),
+    ("get_document_pages", "Extract only the pages you need with get_document_pages for long documents."),
+    ("index_directory", "Index a large directory once with index_directory to make later searches fast."),
+    ("ask_documents", "Ask questions across the active directory with ask_documents; answers cite file and page."),
+];
+
+/// How long results are paged, appended when any tool is registered
+const PAGINATION_HINT: &str = "Long lists are paginated: when a result has a nextCursor, pass it back as cursor \
+                               to get the next page.";
+
+/// Parameters of the client's `initialize` request
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct InitializeParams {
+    #[serde(rename = "protocolVersion")]
+    pub protocol_version: String,
+    #[serde(default)]
+    pub capabilities: ClientCapabilities,
+    #[serde(rename = "clientInfo", default)]
+    pub client_info: Option<ClientInfo>,
+}
+
+impl InitializeParams {
+    /// The protocol version to answer with (see `negotiate_protocol_version`)
+    pub fn negotiated_version(&self) -> &'static str {
+        negotiate_protocol_version(&self.protocol_version)
+    }
+}
+
+/// Name and version of the client application
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ClientInfo {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+}
+
+/// Optional features the client declared; an absent feature is not supported
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ClientCapabilities {
+    #[serde(default)]
+    pub roots: Option<RootsCapability>,
+    /// `sampling/createMessage`, which summaries and answers are generated through
+    #[serde(default)]
+    pub sampling: Option<Value>,
+    /// `elicitation/create`, which passwords and confirmations are asked through
+    #[serde(default)]
+    pub elicitation: Option<Value>,
+}
+
+/// The client's `roots` capability
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RootsCapability {
+    /// True when the client sends `notifications/roots/list_changed`
+    #[serde(rename = "listChanged", default)]
+    pub list_changed: bool,
+}
+
+impl ClientCapabilities {
+    /// True when the server may send `sampling/createMessage` requests
+    pub fn supports_sampling(&self) -> bool {
+        self.sampling.is_some()
+    }
+
+    /// True when the server may send `elicitation/create` requests; without it, a
+    /// missing password or confirmation fails the tool call
+    pub fn supports_elicitation(&self) -> bool {
+        self.elicitation.is_some()
+    }
+
+    /// True when the server may send `roots/list` requests
+    pub fn supports_roots(&self) -> bool {
+        self.roots.is_some()
+    }
+
+    /// True when the client notifies root changes, so the server need not poll `roots/list`
+    pub fn roots_list_changed(&self) -> bool {
+        self.roots.as_ref().is_some_and(|roots| roots.list_changed)
+    }
+
+    /// Fails a tool that needs the client's model when the client has no sampling
+    pub fn require_sampling(&self, tool: &str) -> Result<()> {
+        if self.supports_sampling() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("{} needs a client that supports sampling (sampling/createMessage)", tool))
+        }
+    }
+}
+
+/// Result of the `initialize` request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InitializeResult {
+    #[serde(rename = "protocolVersion")]
+    pub protocol_version: &'static str,
+    pub capabilities: Value,
+    #[serde(rename = "serverInfo")]
+    pub server_info: ServerInfo,
+    /// How to use the server, for the model's context
+    pub instructions: String,
+}
+
+/// Name and version of the server
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+impl InitializeResult {
+    /// Answers a client's `initialize` request for a server offering `tools`
+    pub fn new(params: &InitializeParams, tools: &[&str]) -> Self {
+        Self {
+            protocol_version: params.negotiated_version(),
+            capabilities: json!({
+                "tools": {},
+                "resources": { "subscribe": true, "listChanged": true },
+                "prompts": {},
+                "completions": {},
+            }),
+            server_info: ServerInfo { name: env!("CARGO_PKG_NAME"), version: env!("CARGO_PKG_VERSION") },
+            instructions: server_instructions(tools),
+        }
+    }
+}
+
+/// Describes how to use the server well, from the names of its registered tools
+pub fn server_instructions(tools: &[&str]) -> String {
+    let hints: Vec<String> = TOOL_HINTS
+        .iter()
+        .filter(|(tool, _)| tools.contains(tool))
+        .map(|(_, hint)| format!("- {}", hint))
+        .collect();
+    let mut instructions = INSTRUCTIONS_INTRO.to_string();
+    if !hints.is_empty() {
+        instructions.push_str("\n\n");
+        instructions.push_str(&hints.join("\n"));
+    }
+    if !tools.is_empty() {
+        instructions.push_str("\n\n");
+        instructions.push_str(PAGINATION_HINT);
+    }
+    instructions
+}
+
+/// Returns the protocol version to answer a client's `initialize` request with
+///
+/// A version the server supports is accepted as is. For any other, newer or older,
+/// the server answers with the newest version it supports, as the MCP spec asks; the
+/// client then decides whether it can speak it.
+pub fn negotiate_protocol_version(requested: &str) -> &'static str {
+    let requested = requested.trim();
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .copied()
+        .find(|&version| version == requested)
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        assert_eq!(negotiate_protocol_version("2024-11-05"), "2024-11-05");
+        assert_eq!(negotiate_protocol_version(" 2025-06-18 "), "2025-06-18");
+
+        let newest = SUPPORTED_PROTOCOL_VERSIONS[0];
+        assert_eq!(negotiate_protocol_version("2099-01-01"), newest, "Unknown newer versions get the newest");
+        assert_eq!(negotiate_protocol_version("2024-01-01"), newest);
+        assert_eq!(negotiate_protocol_version(""), newest);
+
+        let mut sorted = SUPPORTED_PROTOCOL_VERSIONS.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(sorted, SUPPORTED_PROTOCOL_VERSIONS, "Versions are listed newest first");
+    }
+
+    #[test]
+    fn test_initialize_params() {
+        let params: InitializeParams = serde_json::from_value(serde_json::json!({
+            "protocolVersion": "2099-01-01",
+            "capabilities": { "roots": { "listChanged": true }, "sampling": {} },
+            "clientInfo": { "name": "Example Client", "version": "1.2" }
+        }))
+        .unwrap();
+        assert_eq!(params.negotiated_version(), SUPPORTED_PROTOCOL_VERSIONS[0]);
+        assert_eq!(params.client_info.unwrap().name, "Example Client");
+
+        let capabilities = params.capabilities;
+        assert!(capabilities.supports_sampling() && capabilities.supports_roots() && capabilities.roots_list_changed());
+        assert!(!capabilities.supports_elicitation());
+        assert!(capabilities.require_sampling("summarize_document").is_ok());
+    }
+
+    #[test]
+    fn test_missing_capabilities() {
+        let params: InitializeParams =
+            serde_json::from_value(serde_json::json!({ "protocolVersion": "2024-11-05" })).unwrap();
+        assert_eq!(params.negotiated_version(), "2024-11-05");
+        assert_eq!(params.capabilities, ClientCapabilities::default());
+
+        let capabilities: ClientCapabilities =
+            serde_json::from_value(serde_json::json!({ "roots": {}, "elicitation": {}, "sampling": null })).unwrap();
+        assert!(capabilities.supports_roots() && !capabilities.roots_list_changed());
+        assert!(capabilities.supports_elicitation());
+        let error = capabilities.require_sampling("ask_documents").unwrap_err();
+        assert_eq!(error.to_string(), "ask_documents needs a client that supports sampling (sampling/createMessage)");
+    }
+
+    #[test]
+    fn test_server_instructions() {
+        let instructions = server_instructions(&["search_in_document", "set_active_directory", "hash_file"]);
+        let lines: Vec<&str> = instructions.lines().collect();
+        assert_eq!(lines[0], INSTRUCTIONS_INTRO);
+        assert!(lines[2].starts_with("- Set the active directory first"), "Hints follow the usual call order");
+        assert!(lines[3].starts_with("- Prefer search_in_document"));
+        assert_eq!(lines[5], PAGINATION_HINT);
+        assert!(!instructions.contains("ask_documents"), "Unregistered tools are not mentioned");
+
+        assert_eq!(server_instructions(&[]), INSTRUCTIONS_INTRO);
+    }
+
+    #[test]
+    fn test_initialize_result() {
+        let params: InitializeParams =
+            serde_json::from_value(serde_json::json!({ "protocolVersion": "2025-03-26" })).unwrap();
+        let value = serde_json::to_value(InitializeResult::new(&params, &["get_snippets"])).unwrap();
+        assert_eq!(value["protocolVersion"], "2025-03-26");
+        assert_eq!(value["serverInfo"]["name"], env!("CARGO_PKG_NAME"));
+        assert_eq!(value["capabilities"]["resources"]["subscribe"], true);
+        assert!(value["instructions"].as_str().unwrap().contains("get_snippets"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Checking that a quoted passage really appears in a document
+//!
+//! Quotes and document text are compared ignoring case, spacing and punctuation, so
+//! curly quotes, dashes and line breaks do not matter. When the quote is not found
+//! as is, the closest passage is located by edit distance.
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::extractor::PageText;
+use crate::search::{document_pages, snippet_around, DEFAULT_SNIPPET_CONTEXT};
+
+/// Share of the quote that must match by default for it to count as found
+pub const DEFAULT_QUOTE_SIMILARITY: f64 = 0.9;
+
+/// Result of `verify_quote`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuoteVerification {
+    /// True when a passage matches the quote with at least the required similarity
+    pub found: bool,
+    /// Similarity of the closest passage, from 0 to 1; 1 when it matches but for case, spacing and punctuation
+    pub similarity: f64,
+    /// Closest passage, when the document has any text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passage: Option<QuotePassage>,
+}
+
+/// Where the passage closest to a quote is in a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuotePassage {
+    /// 1-based page, for formats with pages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// Offset of the passage in the extracted text (of the page, when there is one), in characters
+    pub offset: usize,
+    /// The passage exactly as extracted
+    pub text: String,
+    /// The passage with its surrounding text on one line, `…` marking where the text was cut
+    pub context: String,
+}
+
+/// A text reduced to lowercase letters and digits, with single spaces between words
+struct NormalizedText {
+    chars: Vec<char>,
+    /// Position in the original text of each normalized character
+    origins: Vec<usize>,
+}
+
+impl NormalizedText {
+    fn new(text: &str) -> Self {
+        let mut normalized = Self { chars: Vec::new(), origins: Vec::new() };
+        let mut pending_space = None;
+        for (position, c) in text.chars().enumerate() {
+            if !c.is_alphanumeric() {
+                pending_space = pending_space.or(Some(position));
+                continue;
+            }
+            if let Some(space) = pending_space.take().filter(|_| !normalized.chars.is_empty()) {
+                normalized.chars.push(' ');
+                normalized.origins.push(space);
+            }
+            for lower in c.to_lowercase() {
+                normalized.chars.push(lower);
+                normalized.origins.push(position);
+            }
+        }
+        normalized
+    }
+}
+
+/// Returns the passage of `text` closest to `quote` as (edit distance, start, end), in positions of `text`
+///
+/// Approximate substring matching: the quote may start and end anywhere in the text at
+/// no cost, while each inserted, deleted or replaced character counts one.
+fn closest_passage(quote: &[char], text: &[char]) -> Option<(usize, usize, usize)> {
+    if text.is_empty() {
+        return None;
+    }
+    if let Some(start) = text.windows(quote.len()).position(|window| window == quote) {
+        return Some((0, start, start + quote.len()));
+    }
+
+    // One column of the edit-distance table per text position, with where each alignment started
+    let mut costs: Vec<usize> = (0..=quote.len()).collect();
+    let mut starts = vec![0; quote.len() + 1];
+    let mut best: Option<(usize, usize, usize)> = None;
+    for (end, &c) in text.iter().enumerate() {
+        let (mut diagonal_cost, mut diagonal_start) = (costs[0], starts[0]);
+        costs[0] = 0;
+        starts[0] = end + 1;
+        for (row, &q) in quote.iter().enumerate() {
+            let substitution = (diagonal_cost + usize::from(q != c), diagonal_start);
+            let skipped_text = (costs[row + 1] + 1, starts[row + 1]);
+            let missing_text = (costs[row] + 1, starts[row]);
+            let (cost, start) = [substitution, skipped_text, missing_text]
+                .into_iter()
+                .min_by_key(|(cost, _)| *cost)
+                .unwrap_or(substitution);
+            (diagonal_cost, diagonal_start) = (costs[row + 1], starts[row + 1]);
+            costs[row + 1] = cost;
+            starts[row + 1] = start;
+        }
+        if best.is_none_or(|(cost, _, _)| costs[quote.len()] < cost) {
+            best = Some((costs[quote.len()], starts[quote.len()], end + 1));
+        }
+    }
+    best
+}
+
+/// Finds the passage of the pages closest to a quote (see `verify_quote`)
+pub fn verify_quote_in_pages(pages: &[PageText], quote: &str, min_similarity: f64) -> Result<QuoteVerification> {
+    if !(0.0..=1.0).contains(&min_similarity) || min_similarity == 0.0 {
+        return Err(anyhow::anyhow!("Similarity must be above 0 and at most 1: {}", min_similarity));
+    }
+    let quote = NormalizedText::new(quote).chars;
+    if quote.is_empty() {
+        return Err(anyhow::anyhow!("The quote is empty"));
+    }
+
+    let mut closest: Option<(usize, &PageText, NormalizedText, usize, usize)> = None;
+    for page in pages {
+        let text = NormalizedText::new(&page.text);
+        let Some((distance, start, end)) = closest_passage(&quote, &text.chars) else {
+            continue;
+        };
+        if closest.as_ref().is_none_or(|(best, ..)| distance < *best) {
+            closest = Some((distance, page, text, start, end));
+        }
+        if distance == 0 {
+            break;
+        }
+    }
+
+    let Some((distance, page, text, start, end)) = closest else {
+        return Ok(QuoteVerification { found: false, similarity: 0.0, passage: None });
+    };
+    let similarity = 1.0 - distance.min(quote.len()) as f64 / quote.len() as f64;
+    let chars: Vec<char> = page.text.chars().collect();
+    let (start, end) = (text.origins[start], text.origins[end - 1] + 1);
+    Ok(QuoteVerification {
+        found: similarity >= min_similarity,
+        similarity,
+        passage: Some(QuotePassage {
+            page: Some(page.page).filter(|&page| page > 0),
+            offset: start,
+            text: chars[start..end].iter().collect(),
+            context: snippet_around(&chars, start, end, DEFAULT_SNIPPET_CONTEXT),
+        }),
+    })
+}
+
+/// Checks whether a quote appears in a document, returning the closest passage with its page
+///
+/// Case, spacing and punctuation are ignored. The quote counts as found when at least
+/// `min_similarity` of it matches (see `DEFAULT_QUOTE_SIMILARITY`), which tolerates
+/// small differences such as OCR errors or a word changed by hyphenation.
+pub fn verify_quote(file_path: &Path, quote: &str, min_similarity: f64) -> Result<QuoteVerification> {
+    let pages = document_pages(file_path)?;
+    verify_quote_in_pages(&pages, quote, min_similarity)
+        .with_context(|| format!("Failed to verify the quote in: {}", file_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn contract() -> Vec<PageText> {
+        vec![
+            PageText { page: 1, text: "Master Services Agreement\n\n1. Scope of the services.".into() },
+            PageText {
+                page: 2,
+                text: "7. Indemnification. The Supplier shall indemnify and hold harmless the Client \
+                       against any third-party claims arising from the Supplier's negligence."
+                    .into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_verify_exact_quote() {
+        let quote = "the supplier shall INDEMNIFY and hold\nharmless the client";
+        let result = verify_quote_in_pages(&contract(), quote, DEFAULT_QUOTE_SIMILARITY).unwrap();
+        assert!(result.found);
+        assert_eq!(result.similarity, 1.0);
+        let passage = result.passage.unwrap();
+        assert_eq!(passage.page, Some(2));
+        assert_eq!(passage.offset, 20);
+        assert_eq!(passage.text, "The Supplier shall indemnify and hold harmless the Client");
+        assert!(passage.context.starts_with("7. Indemnification. The Supplier"), "Got: {}", passage.context);
+    }
+
+    #[test]
+    fn test_verify_approximate_quote() {
+        // Curly quotes and a changed word
+        let quote = "“claims arising from the Supplier’s gross negligence”";
+        let result = verify_quote_in_pages(&contract(), quote, 0.7).unwrap();
+        assert!(result.found, "Similarity: {}", result.similarity);
+        assert!(result.similarity < 1.0);
+        let passage = result.passage.unwrap();
+        assert_eq!(passage.text, "claims arising from the Supplier's negligence");
+
+        let strict = verify_quote_in_pages(&contract(), quote, 0.95).unwrap();
+        assert!(!strict.found, "A changed word is more than 5% of the quote");
+        assert_eq!(strict.passage, Some(passage), "The closest passage is reported even when not found");
+
+        let missing = verify_quote_in_pages(&contract(), "Payment is due within thirty days", 0.9).unwrap();
+        assert!(!missing.found);
+        assert!(missing.similarity < 0.6, "Similarity: {}", missing.similarity);
+    }
+
+    #[test]
+    fn test_verify_quote_rejects_bad_input() {
+        assert!(verify_quote_in_pages(&contract(), " … ", 0.9).is_err());
+        assert!(verify_quote_in_pages(&contract(), "scope", 0.0).is_err());
+        assert!(verify_quote_in_pages(&contract(), "scope", 1.5).is_err());
+
+        let empty = verify_quote_in_pages(&[], "scope", 0.9).unwrap();
+        assert_eq!(empty, QuoteVerification { found: false, similarity: 0.0, passage: None });
+    }
+
+    #[test]
+    fn test_verify_quote_in_document() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("flights.csv");
+
+        let result = verify_quote(&path, "PTY", DEFAULT_QUOTE_SIMILARITY).unwrap();
+        assert!(result.found);
+        assert_eq!(result.passage.unwrap().page, None);
+    }
+}
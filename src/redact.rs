@@ -0,0 +1,394 @@
+//! Masking personal data in extracted text before it is sent anywhere
+//!
+//! Emails, phone numbers, US Social Security numbers and payment card numbers are
+//! found by scanners tuned to their formats (card numbers must pass the Luhn
+//! check); anything else can be masked with custom regular expressions (see
+//! `pattern` for the syntax).
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::extractor::{create_extractor, OutputFormat};
+use crate::pattern::Pattern;
+
+/// A kind of personal data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    Ssn,
+    CreditCard,
+    /// Text matched by a custom pattern
+    Custom,
+}
+
+impl PiiKind {
+    /// Text replacing a masked value
+    fn mask(self) -> &'static str {
+        match self {
+            PiiKind::Email => "[EMAIL]",
+            PiiKind::Phone => "[PHONE]",
+            PiiKind::Ssn => "[SSN]",
+            PiiKind::CreditCard => "[CREDIT CARD]",
+            PiiKind::Custom => "[REDACTED]",
+        }
+    }
+}
+
+/// Built-in kinds, in the order overlapping matches are resolved
+const BUILT_IN_KINDS: &[PiiKind] = &[PiiKind::CreditCard, PiiKind::Ssn, PiiKind::Email, PiiKind::Phone];
+
+/// Number of values masked of one kind
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RedactionCount {
+    pub kind: PiiKind,
+    pub count: usize,
+}
+
+/// Text with its personal data masked
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RedactedText {
+    pub text: String,
+    /// Values masked, by kind; kinds with none are left out
+    pub redactions: Vec<RedactionCount>,
+}
+
+/// A value to mask, as char offsets
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Span {
+    start: usize,
+    end: usize,
+    kind: PiiKind,
+}
+
+/// Masks the chosen kinds of personal data in text
+pub struct Redactor {
+    kinds: Vec<PiiKind>,
+    custom: Vec<Pattern>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redactor {
+    /// Masks emails, phone numbers, Social Security numbers and card numbers
+    pub fn new() -> Self {
+        Self { kinds: BUILT_IN_KINDS.to_vec(), custom: Vec::new() }
+    }
+
+    /// Masks only these built-in kinds; `Custom` is ignored here
+    pub fn with_kinds(mut self, kinds: &[PiiKind]) -> Self {
+        self.kinds = BUILT_IN_KINDS.iter().copied().filter(|kind| kinds.contains(kind)).collect();
+        self
+    }
+
+    /// Also masks the matches of these regular expressions, e.g. `EMP-\d{6}` for employee IDs
+    pub fn with_custom_patterns<S: AsRef<str>>(mut self, patterns: &[S]) -> Result<Self> {
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            if pattern.is_empty() {
+                return Err(anyhow::anyhow!("A redaction pattern is empty"));
+            }
+            self.custom
+                .push(Pattern::regex(pattern).with_context(|| format!("Invalid redaction pattern: {}", pattern))?);
+        }
+        Ok(self)
+    }
+
+    /// Masks the personal data of a text
+    ///
+    /// Where matches overlap, the one starting first wins, then the longest.
+    pub fn redact(&self, text: &str) -> Result<RedactedText> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut spans = Vec::new();
+        for &kind in &self.kinds {
+            let found = match kind {
+                PiiKind::Email => find_emails(&chars),
+                PiiKind::Phone => find_phones(&chars),
+                PiiKind::Ssn => find_ssns(&chars),
+                PiiKind::CreditCard => find_cards(&chars),
+                PiiKind::Custom => Vec::new(),
+            };
+            spans.extend(found.into_iter().map(|(start, end)| Span { start, end, kind }));
+        }
+        for pattern in &self.custom {
+            let found = pattern.find_all(&chars, usize::MAX).context("Failed to apply a redaction pattern")?;
+            spans.extend(
+                found
+                    .into_iter()
+                    .filter(|found| found.end > found.start)
+                    .map(|found| Span { start: found.start, end: found.end, kind: PiiKind::Custom }),
+            );
+        }
+        spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+        let mut redacted = String::with_capacity(text.len());
+        let mut redactions: Vec<RedactionCount> = Vec::new();
+        let mut position = 0;
+        for span in spans {
+            if span.start < position {
+                continue;
+            }
+            redacted.extend(&chars[position..span.start]);
+            redacted.push_str(span.kind.mask());
+            position = span.end;
+            match redactions.iter_mut().find(|count| count.kind == span.kind) {
+                Some(count) => count.count += 1,
+                None => redactions.push(RedactionCount { kind: span.kind, count: 1 }),
+            }
+        }
+        redacted.extend(&chars[position..]);
+        Ok(RedactedText { text: redacted, redactions })
+    }
+}
+
+/// Returns true when no letter or digit comes right before `start`
+pub(crate) fn starts_word(chars: &[char], start: usize) -> bool {
+    start == 0 || !chars[start - 1].is_alphanumeric()
+}
+
+/// Returns true when no letter or digit touches the span, so `x123-45-6789` is not an SSN
+pub(crate) fn is_bounded(chars: &[char], start: usize, end: usize) -> bool {
+    let outside = |c: Option<&char>| c.is_none_or(|c| !c.is_alphanumeric());
+    outside(start.checked_sub(1).and_then(|before| chars.get(before))) && outside(chars.get(end))
+}
+
+/// Finds `local@domain.tld` addresses
+pub(crate) fn find_emails(chars: &[char]) -> Vec<(usize, usize)> {
+    let is_local = |c: char| c.is_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_alphanumeric() || c == '.' || c == '-';
+
+    let mut found = Vec::new();
+    let mut searched = 0;
+    for at in (0..chars.len()).filter(|&at| chars[at] == '@') {
+        let mut start = at;
+        while start > searched && is_local(chars[start - 1]) {
+            start -= 1;
+        }
+        while start < at && chars[start] == '.' {
+            start += 1;
+        }
+        let mut end = at + 1;
+        while end < chars.len() && is_domain(chars[end]) {
+            end += 1;
+        }
+        // A sentence ending right after the address leaves its period out
+        while end > at + 1 && matches!(chars[end - 1], '.' | '-') {
+            end -= 1;
+        }
+
+        let domain: String = chars[at + 1..end].iter().collect();
+        let valid_domain = domain.split('.').count() >= 2
+            && domain.split('.').all(|label| !label.is_empty())
+            && domain.rsplit('.').next().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(char::is_alphabetic));
+        if start < at && valid_domain {
+            found.push((start, end));
+            searched = end;
+        }
+    }
+    found
+}
+
+/// Finds US Social Security numbers written `123-45-6789`, skipping numbers never issued
+fn find_ssns(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    for start in 0..chars.len().saturating_sub(10) {
+        let candidate = &chars[start..start + 11];
+        let shape = candidate
+            .iter()
+            .enumerate()
+            .all(|(index, &c)| if index == 3 || index == 6 { c == '-' } else { c.is_ascii_digit() });
+        if !shape || !is_bounded(chars, start, start + 11) || chars.get(start + 11) == Some(&'-') {
+            continue;
+        }
+        let digits: String = candidate.iter().filter(|c| c.is_ascii_digit()).collect();
+        let (area, group, serial) = (&digits[..3], &digits[3..5], &digits[5..]);
+        if area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000" {
+            found.push((start, start + 11));
+        }
+    }
+    found
+}
+
+/// Finds runs of digits separated by single spaces or hyphens, returning each run's
+/// digit count and char span
+fn digit_runs(chars: &[char], separators: &[char]) -> Vec<(usize, usize, usize)> {
+    let mut runs = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        if !chars[position].is_ascii_digit() || !starts_word(chars, position) {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        let mut end = position;
+        let mut digits = 0;
+        while position < chars.len() {
+            if chars[position].is_ascii_digit() {
+                digits += 1;
+                position += 1;
+                end = position;
+            } else if separators.contains(&chars[position])
+                && chars.get(position + 1).is_some_and(char::is_ascii_digit)
+            {
+                position += 1;
+            } else {
+                break;
+            }
+        }
+        if is_bounded(chars, start, end) {
+            runs.push((digits, start, end));
+        }
+    }
+    runs
+}
+
+/// Returns true when a card number passes the Luhn checksum
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, &digit)| match index % 2 {
+            1 if digit > 4 => digit * 2 - 9,
+            1 => digit * 2,
+            _ => digit,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Finds payment card numbers: 13 to 19 digits, grouped or not, passing the Luhn check
+fn find_cards(chars: &[char]) -> Vec<(usize, usize)> {
+    digit_runs(chars, &[' ', '-'])
+        .into_iter()
+        .filter(|&(count, start, end)| {
+            let digits: Vec<u32> = chars[start..end].iter().filter_map(|c| c.to_digit(10)).collect();
+            (13..=19).contains(&count) && luhn_valid(&digits)
+        })
+        .map(|(_, start, end)| (start, end))
+        .collect()
+}
+
+/// Finds phone numbers: 10 to 15 digits written with a leading `+`, an area code in
+/// parentheses, or at least three groups, e.g. `555-123-4567` or `+44 20 7946 0958`
+pub(crate) fn find_phones(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        let c = chars[position];
+        let starts_number = c == '+' || c == '(' || c.is_ascii_digit();
+        if !starts_number || !starts_word(chars, position) {
+            position += 1;
+            continue;
+        }
+
+        let start = position;
+        let mut end = position;
+        let mut digits = 0;
+        let mut groups = 0;
+        let mut in_group = false;
+        let mut has_parentheses = false;
+        position += 1;
+        if c.is_ascii_digit() {
+            (digits, groups, in_group, end) = (1, 1, true, position);
+        }
+        while position < chars.len() {
+            let c = chars[position];
+            if c.is_ascii_digit() {
+                digits += 1;
+                if !in_group {
+                    groups += 1;
+                    in_group = true;
+                }
+                end = position + 1;
+            } else if matches!(c, ' ' | '-' | '.' | '(' | ')') {
+                // Separators come one at a time, except `) ` after an area code
+                let previous = chars[position - 1];
+                if !(in_group || (previous == ')' && c == ' ')) {
+                    break;
+                }
+                has_parentheses |= c == '(' || c == ')';
+                in_group = false;
+            } else {
+                break;
+            }
+            position += 1;
+        }
+
+        let written_as_phone = chars[start] == '+' || has_parentheses || groups >= 3;
+        if (10..=15).contains(&digits) && written_as_phone && is_bounded(chars, start, end) {
+            found.push((start, end));
+            position = end;
+        } else {
+            // Digits inside a rejected number do not start another one
+            position = end.max(start + 1);
+        }
+    }
+    found
+}
+
+/// Extracts a document and masks its personal data
+pub fn redact_document(file_path: &Path, redactor: &Redactor, format: OutputFormat) -> Result<RedactedText> {
+    let text = create_extractor(file_path)?.extract_formatted(file_path, format)?;
+    redactor
+        .redact(&text)
+        .with_context(|| format!("Failed to redact: {}", file_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn count(redacted: &RedactedText, kind: PiiKind) -> usize {
+        redacted.redactions.iter().find(|count| count.kind == kind).map_or(0, |count| count.count)
+    }
+
+    #[test]
+    fn test_redact_built_in_kinds() {
+        let text = "Contact jane.doe+hr@example.co.uk. SSN 123-45-6789, not 000-12-3456.\n\
+                    Card: 4111 1111 1111 1111 (expires 12/27); ref 4111 1111 1111 1112.\n\
+                    Call (555) 123-4567 or +44 20 7946 0958, invoice 2024-001-17.";
+        let redacted = Redactor::new().redact(text).unwrap();
+        assert_eq!(
+            redacted.text,
+            "Contact [EMAIL]. SSN [SSN], not 000-12-3456.\n\
+             Card: [CREDIT CARD] (expires 12/27); ref 4111 1111 1111 1112.\n\
+             Call [PHONE] or [PHONE], invoice 2024-001-17."
+        );
+        assert_eq!(count(&redacted, PiiKind::Phone), 2);
+        assert_eq!(count(&redacted, PiiKind::CreditCard), 1);
+
+        let emails_only = Redactor::new().with_kinds(&[PiiKind::Email]).redact(text).unwrap();
+        assert!(emails_only.text.contains("123-45-6789") && emails_only.text.contains("[EMAIL]"));
+        assert_eq!(emails_only.redactions, [RedactionCount { kind: PiiKind::Email, count: 1 }]);
+    }
+
+    #[test]
+    fn test_redact_custom_patterns() {
+        let redactor =
+            Redactor::new().with_kinds(&[]).with_custom_patterns(&[r"EMP-\d{6}", "(?i)project falcon"]).unwrap();
+        let redacted = redactor.redact("EMP-004211 leads Project Falcon; EMP-12 does not.").unwrap();
+        assert_eq!(redacted.text, "[REDACTED] leads [REDACTED]; EMP-12 does not.");
+        assert_eq!(redacted.redactions, [RedactionCount { kind: PiiKind::Custom, count: 2 }]);
+
+        assert!(Redactor::new().with_custom_patterns(&["("]).is_err());
+        assert!(Redactor::new().with_custom_patterns(&[""]).is_err());
+    }
+
+    #[test]
+    fn test_redact_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.txt");
+        fs::write(&path, "Émile: emile@example.fr, 555.123.4567").unwrap();
+
+        let redacted = redact_document(&path, &Redactor::new(), OutputFormat::Text).unwrap();
+        assert_eq!(redacted.text, "Émile: [EMAIL], [PHONE]");
+        assert!(redact_document(&dir.path().join("missing.txt"), &Redactor::new(), OutputFormat::Text).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Reading documents as MCP resources (`resources/read`)
+//!
+//! Documents are addressed by `file://` URIs. By default a resource holds the
+//! document's extracted text; with the `raw` query flag (`file:///a/report.pdf?raw`)
+//! it holds the file's bytes, base64-encoded, with the file's MIME type, for clients
+//! that can render the original document or image.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Serialize;
+use crate::cache::shared_cache;
+use crate::file_type::mime_type_for_file;
+
+/// Scheme of document URIs
+pub const FILE_SCHEME: &str = "file://";
+
+/// Query flag asking for the file's bytes instead of its text
+pub const RAW_FLAG: &str = "raw";
+
+/// Largest file returned as a blob, in bytes
+pub const MAX_BLOB_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Contents of a resource
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ResourceContents {
+    Text {
+        uri: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+        text: String,
+    },
+    /// Base64-encoded bytes
+    Blob {
+        uri: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+        blob: String,
+    },
+}
+
+/// Returns the URI of a document, its raw bytes when `raw` is set
+///
+/// Characters with a meaning in URIs (`%`, `?`, `#`, spaces, ...) are percent-encoded.
+pub fn resource_uri(file_path: &Path, raw: bool) -> String {
+    let path = file_path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from(FILE_SCHEME);
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    if raw {
+        uri.push('?');
+        uri.push_str(RAW_FLAG);
+    }
+    uri
+}
+
+/// Splits a document URI into its path and whether it asks for the raw bytes
+fn parse_uri(uri: &str) -> Result<(PathBuf, bool)> {
+    let rest = uri
+        .strip_prefix(FILE_SCHEME)
+        .ok_or_else(|| anyhow::anyhow!("Invalid resource URI (expected file://): {}", uri))?;
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    // `file://localhost/...` names the local machine, like `file:///...`
+    let path = path.strip_prefix("localhost").unwrap_or(path);
+    if !path.starts_with('/') {
+        return Err(anyhow::anyhow!("Invalid resource URI (expected an absolute path): {}", uri));
+    }
+
+    let raw = query.split('&').any(|parameter| {
+        let (name, value) = parameter.split_once('=').unwrap_or((parameter, "true"));
+        name == RAW_FLAG && matches!(value, "" | "1" | "true")
+    });
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut input = path.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            // `from_str_radix` alone would accept a sign, as in `%+5`
+            let hex: String = input.by_ref().take(2).map(char::from).collect();
+            let decoded = (hex.len() == 2 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| u8::from_str_radix(&hex, 16).ok())
+                .flatten()
+                .ok_or_else(|| anyhow::anyhow!("Invalid resource URI (bad percent-encoding): {}", uri))?;
+            bytes.push(decoded);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    let path = String::from_utf8(bytes).with_context(|| format!("Invalid resource URI: {}", uri))?;
+    // `file:///C:/docs/a.pdf` on Windows
+    let path = match path.get(1..3) {
+        Some(drive) if cfg!(windows) && drive.ends_with(':') => path[1..].to_string(),
+        _ => path,
+    };
+    Ok((PathBuf::from(path), raw))
+}
+
+/// Reads a document resource: its extracted text, or its base64-encoded bytes for a
+/// URI with the `raw` flag
+pub fn read_resource(uri: &str) -> Result<ResourceContents> {
+    let (path, raw) = parse_uri(uri)?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File not found: {}", path.display()));
+    }
+    if !path.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", path.display()));
+    }
+
+    if !raw {
+        let text = shared_cache().text(&path)?.to_string();
+        return Ok(ResourceContents::Text { uri: uri.to_string(), mime_type: "text/plain".to_string(), text });
+    }
+
+    let size = fs::metadata(&path)?.len();
+    if size > MAX_BLOB_BYTES {
+        return Err(anyhow::anyhow!(
+            "File is too large to return as a blob ({} bytes, at most {}): {}",
+            size,
+            MAX_BLOB_BYTES,
+            path.display()
+        ));
+    }
+    let bytes = fs::read(&path).with_context(|| format!("Failed to read: {}", path.display()))?;
+    Ok(ResourceContents::Blob {
+        uri: uri.to_string(),
+        mime_type: mime_type_for_file(&path).to_string(),
+        blob: STANDARD.encode(bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resource_uri_round_trip() {
+        let path = Path::new("/docs/Q3 report #2 (50%).pdf");
+        let uri = resource_uri(path, false);
+        assert_eq!(uri, "file:///docs/Q3%20report%20%232%20%2850%25%29.pdf");
+        assert_eq!(parse_uri(&uri).unwrap(), (path.to_path_buf(), false));
+
+        let raw = resource_uri(path, true);
+        assert!(raw.ends_with(".pdf?raw"));
+        assert_eq!(parse_uri(&raw).unwrap(), (path.to_path_buf(), true));
+        assert_eq!(parse_uri("file://localhost/a.pdf?x=1&raw=true").unwrap(), (PathBuf::from("/a.pdf"), true));
+        assert!(!parse_uri("file:///a.pdf?raw=0").unwrap().1);
+
+        assert!(parse_uri("https://example.com/a.pdf").is_err());
+        assert!(parse_uri("file://server/a.pdf").is_err());
+        assert!(parse_uri("file:///a%2.pdf").is_err());
+        assert!(parse_uri("file:///a%+5.pdf").is_err());
+    }
+
+    #[test]
+    fn test_read_resource_text_and_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "# Notes\n\nHello").unwrap();
+
+        let text = read_resource(&resource_uri(&path, false)).unwrap();
+        let value = serde_json::to_value(&text).unwrap();
+        assert_eq!(value["mimeType"], "text/plain");
+        assert!(value["text"].as_str().unwrap().contains("Hello"));
+
+        let uri = resource_uri(&path, true);
+        let blob = read_resource(&uri).unwrap();
+        assert_eq!(
+            serde_json::to_value(&blob).unwrap(),
+            json!({ "uri": uri, "mimeType": "text/markdown", "blob": STANDARD.encode("# Notes\n\nHello") })
+        );
+
+        let image = dir.path().join("scan.png");
+        fs::write(&image, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        let ResourceContents::Blob { mime_type, .. } = read_resource(&resource_uri(&image, true)).unwrap() else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(mime_type, "image/png");
+    }
+
+    #[test]
+    fn test_read_resource_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = read_resource(&resource_uri(&dir.path().join("missing.pdf"), true)).unwrap_err();
+        assert!(missing.to_string().starts_with("File not found"));
+        let directory = read_resource(&resource_uri(dir.path(), true)).unwrap_err();
+        assert!(directory.to_string().starts_with("Path is not a file"));
+    }
+}
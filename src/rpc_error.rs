@@ -0,0 +1,218 @@
+//! JSON-RPC error codes for failed MCP requests
+//!
+//! Errors are classified from their chain: I/O errors by kind, the others by the
+//! messages this crate uses for them (`File not found: ...`, `... is not supported
+//! by ...`). The kind and whether retrying can help go in the error's `data`.
+//!
+//! Only failures of the request itself (an unknown tool, unreadable arguments) are
+//! JSON-RPC errors; a tool that fails while running returns an `isError` result (see
+//! `ToolResult::error`), so the model can read the failure and correct the call.
+
+use std::io;
+use serde::Serialize;
+
+/// JSON-RPC: the method does not exist, e.g. an unknown tool
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC: invalid method parameters
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC: internal error
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Server errors, in the range JSON-RPC reserves for implementations
+pub const FILE_NOT_FOUND: i64 = -32001;
+pub const PERMISSION_DENIED: i64 = -32002;
+pub const UNSUPPORTED_FORMAT: i64 = -32003;
+pub const PASSWORD_REQUIRED: i64 = -32004;
+
+/// Starts of messages reporting a missing file or directory
+const NOT_FOUND_MESSAGES: &[&str] = &["File not found", "Directory not found", "Bibliography file not found"];
+
+/// Starts of messages reporting a bad argument
+const INVALID_PARAMS_MESSAGES: &[&str] = &[
+    "Path is not a file",
+    "Path is not a directory",
+    "Invalid regular expression",
+    "Invalid glob",
+    "Invalid cursor",
+    "Invalid timestamp",
+    "Invalid OCR language",
+    "Invalid redaction pattern",
+    "Invalid page range",
+    "Invalid resource URI",
+    "Page numbers start at",
+    "Page size must",
+    "Similarity must",
+    "Prompt not found",
+    "Alias not found",
+    "Bookmark not found",
+    "Missing argument",
+];
+
+/// What went wrong, as reported in the error's `data`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    UnknownTool,
+    InvalidParams,
+    FileNotFound,
+    PermissionDenied,
+    UnsupportedFormat,
+    PasswordRequired,
+    Internal,
+}
+
+impl ErrorKind {
+    /// The JSON-RPC error code of the kind
+    pub fn code(self) -> i64 {
+        match self {
+            ErrorKind::UnknownTool => METHOD_NOT_FOUND,
+            ErrorKind::InvalidParams => INVALID_PARAMS,
+            ErrorKind::FileNotFound => FILE_NOT_FOUND,
+            ErrorKind::PermissionDenied => PERMISSION_DENIED,
+            ErrorKind::UnsupportedFormat => UNSUPPORTED_FORMAT,
+            ErrorKind::PasswordRequired => PASSWORD_REQUIRED,
+            ErrorKind::Internal => INTERNAL_ERROR,
+        }
+    }
+
+    /// True when the same request may succeed later without changing it
+    pub fn retryable(self) -> bool {
+        self == ErrorKind::Internal
+    }
+
+    /// What the caller can change to succeed, shown to the model with failed tool calls
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            ErrorKind::InvalidParams => Some("Check the arguments against the tool's input schema."),
+            ErrorKind::FileNotFound => Some("Check the path, e.g. by listing the files of its directory."),
+            ErrorKind::PermissionDenied => Some("The server may not read this path; choose another one."),
+            ErrorKind::UnsupportedFormat => {
+                Some("This file type cannot be read; get_supported_formats lists the ones that can.")
+            }
+            ErrorKind::PasswordRequired => {
+                Some("The document is encrypted; call the tool again with its password, or save the password first.")
+            }
+            ErrorKind::UnknownTool | ErrorKind::Internal => None,
+        }
+    }
+}
+
+/// A JSON-RPC error object
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: ErrorData,
+}
+
+/// The `data` of an error
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorData {
+    pub kind: ErrorKind,
+    pub retryable: bool,
+}
+
+impl RpcError {
+    /// An error of the given kind
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self { code: kind.code(), message: message.into(), data: ErrorData { kind, retryable: kind.retryable() } }
+    }
+
+    /// A call to a tool the server does not have
+    pub fn unknown_tool(name: &str) -> Self {
+        Self::new(ErrorKind::UnknownTool, format!("Unknown tool: {}", name))
+    }
+
+    /// Arguments that could not be read, e.g. a missing or mistyped field
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidParams, message)
+    }
+
+    /// Classifies an error from a tool or request handler; the message includes its causes
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        Self::new(classify(error), format!("{:#}", error))
+    }
+}
+
+/// Finds the kind of an error from its chain, the innermost causes first
+pub fn classify(error: &anyhow::Error) -> ErrorKind {
+    let causes: Vec<_> = error.chain().collect();
+    for cause in causes.iter().rev() {
+        if let Some(io_error) = cause.downcast_ref::<io::Error>() {
+            match io_error.kind() {
+                io::ErrorKind::NotFound => return ErrorKind::FileNotFound,
+                io::ErrorKind::PermissionDenied => return ErrorKind::PermissionDenied,
+                _ => {}
+            }
+        }
+
+        let message = cause.to_string();
+        if NOT_FOUND_MESSAGES.iter().any(|start| message.starts_with(start)) {
+            return ErrorKind::FileNotFound;
+        }
+        if message.contains("password-protected") || message.starts_with("Incorrect password") {
+            return ErrorKind::PasswordRequired;
+        }
+        if message.starts_with("Unsupported file format") || message.contains(" is not supported by ") {
+            return ErrorKind::UnsupportedFormat;
+        }
+        if INVALID_PARAMS_MESSAGES.iter().any(|start| message.starts_with(start))
+            || (message.starts_with("The ") && message.ends_with(" is empty"))
+        {
+            return ErrorKind::InvalidParams;
+        }
+    }
+    ErrorKind::Internal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use anyhow::Context;
+    use serde_json::json;
+    use crate::extractor::create_extractor;
+    use crate::search::Search;
+
+    #[test]
+    fn test_classify_errors() {
+        let missing = create_extractor(Path::new("/nonexistent/report.txt"))
+            .and_then(|extractor| extractor.extract_text_from_file(Path::new("/nonexistent/report.txt")))
+            .unwrap_err();
+        assert_eq!(classify(&missing), ErrorKind::FileNotFound);
+
+        let io_error = anyhow::Error::new(io::Error::from(io::ErrorKind::PermissionDenied)).context("Failed to read");
+        assert_eq!(classify(&io_error), ErrorKind::PermissionDenied);
+
+        let empty = Search::new("", false).err().unwrap();
+        assert_eq!(classify(&empty), ErrorKind::InvalidParams);
+        let regex = Search::new("(fare", true).err().unwrap();
+        assert_eq!(classify(&regex), ErrorKind::InvalidParams);
+
+        let unsupported = anyhow::anyhow!("Page extraction is not supported by TxtExtractor: notes.txt");
+        assert_eq!(classify(&unsupported), ErrorKind::UnsupportedFormat);
+        let encrypted = Err::<(), _>(anyhow::anyhow!("PDF is password-protected; a password is required"))
+            .context("Failed to decrypt PDF: statement.pdf")
+            .unwrap_err();
+        assert_eq!(classify(&encrypted), ErrorKind::PasswordRequired, "The cause decides, not the context");
+
+        assert_eq!(classify(&anyhow::anyhow!("Truncated PalmDOC header")), ErrorKind::Internal);
+    }
+
+    #[test]
+    fn test_rpc_errors() {
+        let error = RpcError::from_error(&anyhow::anyhow!("File not found: /tmp/a.pdf"));
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({
+                "code": FILE_NOT_FOUND,
+                "message": "File not found: /tmp/a.pdf",
+                "data": { "kind": "file_not_found", "retryable": false },
+            })
+        );
+
+        assert_eq!(RpcError::unknown_tool("delete_everything").code, METHOD_NOT_FOUND);
+        assert_eq!(RpcError::invalid_params("missing field `file_path`").code, INVALID_PARAMS);
+        let internal = RpcError::from_error(&anyhow::anyhow!("Failed to write index"));
+        assert_eq!((internal.code, internal.data.retryable), (INTERNAL_ERROR, true));
+    }
+}
@@ -0,0 +1,328 @@
+//! Searching the extracted text of a single document, and keyword-in-context snippets
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::extractor::{create_extractor, PageText};
+use crate::pattern::Pattern;
+
+/// Lines shown before and after each match by default
+const DEFAULT_CONTEXT_LINES: usize = 2;
+
+/// Matches returned by default before the search stops
+const DEFAULT_MAX_MATCHES: usize = 100;
+
+/// Characters shown before and after each occurrence in a snippet by default
+pub const DEFAULT_SNIPPET_CONTEXT: usize = 80;
+
+/// Marks the side of a snippet where the text was cut
+const ELLIPSIS: &str = "…";
+
+/// One match in a document's extracted text
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchMatch {
+    /// Offset of the match in the extracted text, in characters
+    pub offset: usize,
+    /// 1-based line of the start of the match
+    pub line: usize,
+    /// The matched text
+    pub text: String,
+    /// The matched lines with the surrounding context lines
+    pub context: String,
+}
+
+/// Matches found in a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchResults {
+    pub matches: Vec<SearchMatch>,
+    /// True when the search stopped at the match limit
+    pub truncated: bool,
+}
+
+/// An occurrence of a keyword with the text around it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snippet {
+    /// 1-based page of the occurrence, for formats with pages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// Offset of the occurrence in the extracted text (of the page, when there is one), in characters
+    pub offset: usize,
+    /// The matched text
+    pub text: String,
+    /// The occurrence with its surrounding text on one line, `…` marking where the text was cut
+    pub snippet: String,
+}
+
+/// Snippets found in a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snippets {
+    pub snippets: Vec<Snippet>,
+    /// True when the search stopped at the match limit
+    pub truncated: bool,
+}
+
+/// A search for a plain-text or regular expression pattern (see `pattern` for the syntax)
+pub struct Search {
+    pattern: Pattern,
+    context_lines: usize,
+    max_matches: usize,
+}
+
+impl Search {
+    /// Creates a search for `pattern`, read as a regular expression when `regex` is true
+    pub fn new(pattern: &str, regex: bool) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(anyhow::anyhow!("The search pattern is empty"));
+        }
+        let pattern = if regex {
+            Pattern::regex(pattern).with_context(|| format!("Invalid regular expression: {}", pattern))?
+        } else {
+            Pattern::literal(pattern)
+        };
+        Ok(Self { pattern, context_lines: DEFAULT_CONTEXT_LINES, max_matches: DEFAULT_MAX_MATCHES })
+    }
+
+    /// Ignores case when matching
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.pattern = self.pattern.with_ignore_case(ignore_case);
+        self
+    }
+
+    /// Sets the number of lines shown before and after each match
+    pub fn with_context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    /// Sets the number of matches after which the search stops
+    pub fn with_max_matches(mut self, max_matches: usize) -> Self {
+        self.max_matches = max_matches;
+        self
+    }
+
+    /// Searches a text, returning matches with line numbers and context
+    pub fn search_text(&self, text: &str) -> Result<SearchResults> {
+        let chars: Vec<char> = text.chars().collect();
+        // One extra match tells whether the limit cut the results short
+        let mut found = self.pattern.find_all(&chars, self.max_matches.saturating_add(1))?;
+        let truncated = found.len() > self.max_matches;
+        found.truncate(self.max_matches);
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(chars.iter().enumerate().filter(|(_, &c)| c == '\n').map(|(index, _)| index + 1))
+            .collect();
+        let lines: Vec<String> = text.split('\n').map(|line| line.trim_end_matches('\r').to_string()).collect();
+        let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+
+        let matches = found
+            .iter()
+            .map(|found| {
+                let first = line_of(found.start);
+                let last = line_of(found.end.saturating_sub(1).max(found.start));
+                let context_start = first.saturating_sub(self.context_lines);
+                let context_end = (last + self.context_lines).min(lines.len() - 1);
+                SearchMatch {
+                    offset: found.start,
+                    line: first + 1,
+                    text: chars[found.start..found.end].iter().collect(),
+                    context: lines[context_start..=context_end].join("\n"),
+                }
+            })
+            .collect();
+        Ok(SearchResults { matches, truncated })
+    }
+
+    /// Finds the occurrences in the text of each page, with `context_chars` characters around them
+    ///
+    /// A text without pages can be passed as a single page numbered 0, whose snippets have no page.
+    /// Snippets are not cut inside a word when a space is close enough to cut at instead.
+    pub fn snippets(&self, pages: &[PageText], context_chars: usize) -> Result<Snippets> {
+        let mut snippets = Vec::new();
+        for page in pages {
+            let chars: Vec<char> = page.text.chars().collect();
+            // One extra match tells whether the limit cut the results short
+            let limit = self.max_matches.saturating_add(1).saturating_sub(snippets.len());
+            for found in self.pattern.find_all(&chars, limit)? {
+                snippets.push(Snippet {
+                    page: Some(page.page).filter(|&page| page > 0),
+                    offset: found.start,
+                    text: chars[found.start..found.end].iter().collect(),
+                    snippet: snippet_around(&chars, found.start, found.end, context_chars),
+                });
+            }
+            if snippets.len() > self.max_matches {
+                break;
+            }
+        }
+        let truncated = snippets.len() > self.max_matches;
+        snippets.truncate(self.max_matches);
+        Ok(Snippets { snippets, truncated })
+    }
+}
+
+/// Returns `chars[start..end]` with up to `context_chars` characters on each side, on one line
+pub(crate) fn snippet_around(chars: &[char], start: usize, end: usize, context_chars: usize) -> String {
+    let mut from = start.saturating_sub(context_chars);
+    let mut to = end.saturating_add(context_chars).min(chars.len());
+    // Cut at a space inside the window rather than in the middle of a word
+    if from > 0 && !chars[from - 1].is_whitespace() {
+        if let Some(space) = chars[from..start].iter().position(|c| c.is_whitespace()) {
+            from += space;
+        }
+    }
+    if to < chars.len() && !chars[to].is_whitespace() {
+        if let Some(space) = chars[end..to].iter().rposition(|c| c.is_whitespace()) {
+            to = end + space;
+        }
+    }
+
+    let text: String = chars[from..to].iter().collect();
+    let mut snippet = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if from > 0 {
+        snippet.insert_str(0, ELLIPSIS);
+    }
+    if to < chars.len() {
+        snippet.push_str(ELLIPSIS);
+    }
+    snippet
+}
+
+/// Extracts a document with the extractor for its format and searches its text
+pub fn search_in_document(file_path: &Path, search: &Search) -> Result<SearchResults> {
+    let text = create_extractor(file_path)?.extract_text_from_file(file_path)?;
+    search
+        .search_text(&text)
+        .with_context(|| format!("Failed to search: {}", file_path.display()))
+}
+
+/// Extracts the pages of a document, or its whole text as a single page numbered 0 for formats without pages
+pub(crate) fn document_pages(file_path: &Path) -> Result<Vec<PageText>> {
+    let extractor = create_extractor(file_path)?;
+    match extractor.extract_pages(file_path, 1..=usize::MAX) {
+        Ok(pages) => Ok(pages),
+        Err(_) => Ok(vec![PageText { page: 0, text: extractor.extract_text_from_file(file_path)? }]),
+    }
+}
+
+/// Finds every occurrence of a search in a document, with `context_chars` characters around it
+///
+/// Occurrences carry their page number for formats with pages (PDF); for the others the
+/// whole extracted text is searched.
+pub fn get_snippets(file_path: &Path, search: &Search, context_chars: usize) -> Result<Snippets> {
+    let pages = document_pages(file_path)?;
+    search
+        .snippets(&pages, context_chars)
+        .with_context(|| format!("Failed to search: {}", file_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const POLICY: &str = "Travel policy\n\nFlights\nEconomy class for trips under 6 hours.\nBusiness class above.\n\nHotels\nUp to 180 USD per night.";
+
+    #[test]
+    fn test_search_text() {
+        let results = Search::new(r"\d+ (hours|USD)", true).unwrap().with_context_lines(1).search_text(POLICY).unwrap();
+        assert!(!results.truncated);
+        assert_eq!(
+            results.matches,
+            vec![
+                SearchMatch {
+                    offset: 53,
+                    line: 4,
+                    text: "6 hours".to_string(),
+                    context: "Flights\nEconomy class for trips under 6 hours.\nBusiness class above.".to_string(),
+                },
+                SearchMatch {
+                    offset: 98,
+                    line: 8,
+                    text: "180 USD".to_string(),
+                    context: "Hotels\nUp to 180 USD per night.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_text_plain_and_limited() {
+        let search = Search::new("CLASS", false).unwrap().with_ignore_case(true).with_context_lines(0).with_max_matches(1);
+        let results = search.search_text(POLICY).unwrap();
+        assert!(results.truncated);
+        assert_eq!(results.matches.len(), 1);
+        assert_eq!(results.matches[0].context, "Economy class for trips under 6 hours.");
+
+        // Plain patterns are not regular expressions
+        assert!(Search::new("6 hours.", false).unwrap().search_text("6 hoursX").unwrap().matches.is_empty());
+    }
+
+    #[test]
+    fn test_search_rejects_invalid_patterns() {
+        assert!(Search::new("", false).is_err());
+        let error = format!("{:#}", Search::new("(fare", true).err().unwrap());
+        assert!(error.starts_with("Invalid regular expression: (fare"), "Got: {}", error);
+    }
+
+    #[test]
+    fn test_search_in_document() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("flights.csv");
+
+        let results = search_in_document(&path, &Search::new("PTY", false).unwrap()).unwrap();
+        assert!(!results.matches.is_empty(), "flights.csv mentions PTY");
+        assert!(results.matches.iter().all(|found| found.text == "PTY"));
+    }
+
+    #[test]
+    fn test_snippets() {
+        let pages = [
+            PageText {
+                page: 1,
+                text: "Indemnification.\nThe supplier shall indemnify the client against claims.".into(),
+            },
+            PageText { page: 2, text: "No indemnification is owed for losses caused by the client itself.".into() },
+        ];
+        let search = Search::new("indemnif", false).unwrap().with_ignore_case(true);
+        let results = search.snippets(&pages, 12).unwrap();
+        assert!(!results.truncated);
+        let found: Vec<_> = results
+            .snippets
+            .iter()
+            .map(|snippet| (snippet.page, snippet.offset, snippet.snippet.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Some(1), 0, "Indemnification. The…"),
+                (Some(1), 36, "…shall indemnify the client…"),
+                (Some(2), 3, "No indemnification is…"),
+            ]
+        );
+
+        let limited = search.with_max_matches(2).snippets(&pages, 0).unwrap();
+        assert!(limited.truncated);
+        assert_eq!(limited.snippets[1].snippet, "…indemnif…");
+        let unlimited = Search::new("indemnif", false).unwrap().with_ignore_case(true).with_max_matches(usize::MAX);
+        let unlimited = unlimited.snippets(&pages, 12).unwrap();
+        assert_eq!(unlimited.snippets.len(), 3);
+
+        // Text without pages
+        let text = [PageText { page: 0, text: "Total: 180 USD".into() }];
+        let results = Search::new("180", false).unwrap().snippets(&text, 100).unwrap();
+        assert_eq!(results.snippets[0].page, None);
+        assert_eq!(results.snippets[0].snippet, "Total: 180 USD");
+    }
+
+    #[test]
+    fn test_get_snippets() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("flights.csv");
+
+        let results = get_snippets(&path, &Search::new("PTY", false).unwrap(), 10).unwrap();
+        assert!(!results.snippets.is_empty(), "flights.csv mentions PTY");
+        assert!(results.snippets.iter().all(|snippet| snippet.page.is_none() && snippet.snippet.contains("PTY")));
+    }
+}
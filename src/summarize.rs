@@ -0,0 +1,246 @@
+//! Summarizing documents with a language model the client provides
+//!
+//! The model is reached through a callback, which the server routes to the client's
+//! `sampling/createMessage`. Long documents are summarized chunk by chunk, and the
+//! partial summaries combined until one summary remains.
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::chunk::{estimate_tokens, ChunkUnit, Chunker};
+use crate::extractor::create_extractor;
+
+/// Tokens of document text sent in one request by default
+pub const DEFAULT_CHUNK_TOKENS: usize = 6000;
+
+/// Tokens the model may answer with in one request by default
+pub const DEFAULT_MAX_SUMMARY_TOKENS: usize = 600;
+
+/// Tokens shared by consecutive chunks, so no passage is cut in two unseen
+const CHUNK_OVERLAP_TOKENS: usize = 200;
+
+const SYSTEM_PROMPT: &str = "You summarize documents accurately and concisely. Keep names, dates, amounts and \
+                             decisions. Do not add information that is not in the text.";
+
+/// One request for the client's model
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SamplingRequest {
+    pub system_prompt: String,
+    /// The user message
+    pub prompt: String,
+    pub max_tokens: usize,
+}
+
+/// The summary of a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub summary: String,
+    /// Number of chunks the document was split into
+    pub chunks: usize,
+    /// Number of requests made to the model
+    pub requests: usize,
+}
+
+/// Settings for `summarize_text` and `summarize_document`
+pub struct SummaryOptions {
+    chunk_tokens: usize,
+    max_summary_tokens: usize,
+    focus: Option<String>,
+}
+
+impl Default for SummaryOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SummaryOptions {
+    /// Summarizes in chunks of `DEFAULT_CHUNK_TOKENS` tokens
+    pub fn new() -> Self {
+        Self { chunk_tokens: DEFAULT_CHUNK_TOKENS, max_summary_tokens: DEFAULT_MAX_SUMMARY_TOKENS, focus: None }
+    }
+
+    /// Sets the tokens of document text sent in one request; must exceed the summary length
+    pub fn with_chunk_tokens(mut self, chunk_tokens: usize) -> Self {
+        self.chunk_tokens = chunk_tokens;
+        self
+    }
+
+    /// Sets the tokens the model may answer with in one request
+    pub fn with_max_summary_tokens(mut self, max_summary_tokens: usize) -> Self {
+        self.max_summary_tokens = max_summary_tokens;
+        self
+    }
+
+    /// Asks the summary to concentrate on a topic, e.g. "payment terms"
+    pub fn with_focus(mut self, focus: &str) -> Self {
+        self.focus = Some(focus.trim().to_string()).filter(|focus| !focus.is_empty());
+        self
+    }
+
+    fn request(&self, instruction: &str, text: &str) -> SamplingRequest {
+        let focus = match &self.focus {
+            Some(focus) => format!(" Concentrate on: {}.", focus),
+            None => String::new(),
+        };
+        SamplingRequest {
+            system_prompt: SYSTEM_PROMPT.to_string(),
+            prompt: format!("{}{}\n\n{}", instruction, focus, text),
+            max_tokens: self.max_summary_tokens,
+        }
+    }
+}
+
+/// Summarizes a text, calling `sample` for every request to the model
+///
+/// A text that fits in one chunk takes a single request. Longer texts are summarized
+/// chunk by chunk, then the partial summaries are combined, in batches that fit in a
+/// chunk, until one summary remains.
+pub fn summarize_text<F>(text: &str, options: &SummaryOptions, mut sample: F) -> Result<Summary>
+where
+    F: FnMut(&SamplingRequest) -> Result<String>,
+{
+    if options.chunk_tokens <= options.max_summary_tokens.max(CHUNK_OVERLAP_TOKENS) {
+        return Err(anyhow::anyhow!(
+            "Chunks ({} tokens) must be larger than the summaries ({} tokens) and the overlap ({} tokens)",
+            options.chunk_tokens,
+            options.max_summary_tokens,
+            CHUNK_OVERLAP_TOKENS
+        ));
+    }
+    if text.trim().is_empty() {
+        return Err(anyhow::anyhow!("No text to summarize"));
+    }
+
+    let chunks = Chunker::new(options.chunk_tokens, CHUNK_OVERLAP_TOKENS)?
+        .with_unit(ChunkUnit::Tokens)
+        .chunk_text(text, "");
+    let mut requests = 0;
+    let mut call = |request: SamplingRequest| -> Result<String> {
+        requests += 1;
+        let answer = sample(&request).with_context(|| format!("Summary request {} failed", requests))?;
+        Ok(answer.trim().to_string())
+    };
+
+    if chunks.len() == 1 {
+        let summary = call(options.request("Summarize the following document.", &chunks[0].text))?;
+        return Ok(Summary { summary, chunks: 1, requests });
+    }
+
+    let mut summaries = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let instruction = format!("Summarize part {} of {} of a document.", index + 1, chunks.len());
+        summaries.push(call(options.request(&instruction, &chunk.text))?);
+    }
+
+    while summaries.len() > 1 {
+        // Batches of at least two summaries, so every round makes progress
+        let mut batches: Vec<Vec<String>> = Vec::new();
+        let mut batch_tokens = 0;
+        for summary in summaries {
+            let tokens = estimate_tokens(&summary);
+            match batches.last_mut() {
+                Some(batch) if batch.len() < 2 || batch_tokens + tokens <= options.chunk_tokens => {
+                    batch.push(summary);
+                    batch_tokens += tokens;
+                }
+                _ => {
+                    batches.push(vec![summary]);
+                    batch_tokens = tokens;
+                }
+            }
+        }
+
+        let last_round = batches.len() == 1;
+        summaries = Vec::new();
+        for batch in batches {
+            if batch.len() == 1 {
+                summaries.extend(batch);
+                continue;
+            }
+            let instruction = if last_round {
+                "Combine these summaries of consecutive parts of one document into a single summary."
+            } else {
+                "Combine these summaries of consecutive parts of a document into one shorter summary."
+            };
+            summaries.push(call(options.request(instruction, &batch.join("\n\n---\n\n")))?);
+        }
+    }
+
+    let summary = summaries.pop().unwrap_or_default();
+    Ok(Summary { summary, chunks: chunks.len(), requests })
+}
+
+/// Extracts a document and summarizes its text (see `summarize_text`)
+pub fn summarize_document<F>(file_path: &Path, options: &SummaryOptions, sample: F) -> Result<Summary>
+where
+    F: FnMut(&SamplingRequest) -> Result<String>,
+{
+    let text = create_extractor(file_path)?.extract_text_from_file(file_path)?;
+    summarize_text(&text, options, sample).with_context(|| format!("Failed to summarize: {}", file_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Answers every request with a short summary naming the request
+    fn fake_model(requests: &mut Vec<SamplingRequest>) -> impl FnMut(&SamplingRequest) -> Result<String> + '_ {
+        move |request| {
+            requests.push(request.clone());
+            Ok(format!(" summary {} ", requests.len()))
+        }
+    }
+
+    #[test]
+    fn test_summarize_short_text() {
+        let mut requests = Vec::new();
+        let options = SummaryOptions::new().with_focus("payment terms");
+        let summary = summarize_text("Payment is due within 30 days.", &options, fake_model(&mut requests)).unwrap();
+
+        assert_eq!(summary, Summary { summary: "summary 1".to_string(), chunks: 1, requests: 1 });
+        assert!(requests[0].prompt.starts_with("Summarize the following document. Concentrate on: payment terms."));
+        assert!(requests[0].prompt.ends_with("Payment is due within 30 days."));
+        assert_eq!(requests[0].max_tokens, DEFAULT_MAX_SUMMARY_TOKENS);
+    }
+
+    #[test]
+    fn test_summarize_long_text() {
+        let text = (1..=400).map(|n| format!("Clause {} sets out obligation number {}.", n, n)).collect::<Vec<_>>().join("\n\n");
+        let options = SummaryOptions::new().with_chunk_tokens(500).with_max_summary_tokens(100);
+
+        let mut requests = Vec::new();
+        let summary = summarize_text(&text, &options, fake_model(&mut requests)).unwrap();
+        assert!(summary.chunks > 2, "Got: {:?}", summary);
+        assert_eq!(summary.requests, requests.len());
+        assert!(summary.requests > summary.chunks, "Partial summaries should be combined");
+        assert!(requests[0].prompt.starts_with(&format!("Summarize part 1 of {} of a document.", summary.chunks)));
+        assert!(requests.last().unwrap().prompt.starts_with("Combine these summaries of consecutive parts of one document"));
+        assert_eq!(summary.summary, format!("summary {}", requests.len()));
+    }
+
+    #[test]
+    fn test_summarize_errors() {
+        let options = SummaryOptions::new();
+        assert!(summarize_text("  ", &options, |_| Ok(String::new())).is_err());
+
+        let error = summarize_text("Text", &options, |_| Err(anyhow::anyhow!("client refused"))).unwrap_err();
+        assert_eq!(format!("{:#}", error), "Summary request 1 failed: client refused");
+
+        let too_small = SummaryOptions::new().with_chunk_tokens(100).with_max_summary_tokens(100);
+        assert!(summarize_text("Text", &too_small, |_| Ok(String::new())).is_err());
+    }
+
+    #[test]
+    fn test_summarize_document() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("policy.docx");
+
+        let mut requests = Vec::new();
+        let summary = summarize_document(&path, &SummaryOptions::new(), fake_model(&mut requests)).unwrap();
+        assert_eq!(summary.requests, 1);
+        assert!(requests[0].prompt.contains("Travel Expense Policy"));
+    }
+}
@@ -0,0 +1,371 @@
+//! Term statistics of a document or a directory: frequent words and phrases, and
+//! the terms that set documents apart (TF-IDF)
+//!
+//! Words are lowercased; function words (`the`, `und`, ...) and numbers are not
+//! counted as terms, and phrases neither start nor end with one.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::extractor::create_extractor;
+use crate::index::DocumentIndex;
+use crate::language::stop_words;
+use crate::listing::{list_files_in_directory, ListOptions};
+
+/// Documents read by default before the statistics are cut short
+const DEFAULT_MAX_FILES: usize = 10_000;
+
+/// Terms returned unless the caller asks otherwise
+pub const DEFAULT_MAX_TERMS: usize = 25;
+
+/// Longest phrases counted, in words
+const MAX_NGRAM: usize = 5;
+
+/// How terms are ranked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TermMeasure {
+    /// By number of occurrences
+    #[default]
+    Frequency,
+    /// By TF-IDF summed over the documents: terms frequent in some documents and
+    /// rare in the others rank first, terms found everywhere score 0
+    TfIdf,
+}
+
+/// A term and how it ranks
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TermScore {
+    pub term: String,
+    /// Occurrences over all documents
+    pub count: usize,
+    /// Number of documents containing the term
+    pub documents: usize,
+    /// TF-IDF score, for `TermMeasure::TfIdf`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    /// Document where the term scores highest, by relative path, for `TermMeasure::TfIdf`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_document: Option<String>,
+}
+
+/// Result of `term_statistics`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TermStatistics {
+    pub measure: TermMeasure,
+    /// Words per term
+    pub ngram: usize,
+    /// The best ranked terms, best first
+    pub terms: Vec<TermScore>,
+    /// Occurrences of all terms counted
+    pub total_terms: usize,
+    /// Number of different terms counted
+    pub distinct_terms: usize,
+    /// Number of documents read
+    pub documents: usize,
+    /// Documents that could not be extracted, by relative path
+    pub unreadable: Vec<String>,
+    /// True when the directory held more documents than were read
+    pub truncated: bool,
+}
+
+/// Settings for `term_statistics`
+pub struct TermOptions {
+    list: ListOptions,
+    measure: TermMeasure,
+    ngram: usize,
+    max_terms: usize,
+}
+
+impl Default for TermOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TermOptions {
+    /// Ranks single words by frequency over every supported document of the directory
+    /// and its subdirectories
+    pub fn new() -> Self {
+        Self {
+            list: ListOptions::new()
+                .with_recursive(true)
+                .with_supported_only(true)
+                .with_max_results(DEFAULT_MAX_FILES),
+            measure: TermMeasure::Frequency,
+            ngram: 1,
+            max_terms: DEFAULT_MAX_TERMS,
+        }
+    }
+
+    /// Chooses the documents read from a directory, e.g. only PDFs
+    pub fn with_list_options(mut self, list: ListOptions) -> Self {
+        self.list = list;
+        self
+    }
+
+    pub fn with_measure(mut self, measure: TermMeasure) -> Self {
+        self.measure = measure;
+        self
+    }
+
+    /// Counts phrases of `ngram` words instead of single words, from 1 to 5
+    pub fn with_ngram(mut self, ngram: usize) -> Result<Self> {
+        if !(1..=MAX_NGRAM).contains(&ngram) {
+            return Err(anyhow::anyhow!("N-gram size must be between 1 and {}, got {}", MAX_NGRAM, ngram));
+        }
+        self.ngram = ngram;
+        Ok(self)
+    }
+
+    /// Sets the number of terms returned
+    pub fn with_max_terms(mut self, max_terms: usize) -> Self {
+        self.max_terms = max_terms;
+        self
+    }
+}
+
+/// Counts the terms of one text
+///
+/// Phrases do not run across sentence punctuation or paragraphs.
+fn count_terms(text: &str, ngram: usize, stop_words: &HashSet<&str>) -> HashMap<String, usize> {
+    let is_content_word =
+        |word: &str| word.chars().count() > 1 && word.chars().any(char::is_alphabetic) && !stop_words.contains(word);
+
+    let mut counts = HashMap::new();
+    for paragraph in text.split("\n\n") {
+        for sentence in paragraph.split(['.', '!', '?', ';', ':', '(', ')', '[', ']', '"']) {
+            let words: Vec<String> = sentence
+                .split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+                .map(|word| word.trim_matches(['\'', '’']).to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect();
+            for window in words.windows(ngram) {
+                if is_content_word(&window[0]) && is_content_word(&window[ngram - 1]) {
+                    *counts.entry(window.join(" ")).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Ranks the terms of a document, or of the documents of a directory
+///
+/// TF-IDF needs at least two documents: a term's score in a document is its share of
+/// the document's terms times the log of how rare the term is among the documents.
+pub fn term_statistics(source: &Path, options: &TermOptions) -> Result<TermStatistics> {
+    let (files, truncated) = if source.is_file() {
+        let name = source.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        (vec![(source.to_path_buf(), name)], false)
+    } else if source.is_dir() {
+        let listing = list_files_in_directory(source, &options.list)?;
+        let files = listing.files.into_iter().map(|file| (file.path, file.relative_path)).collect();
+        (files, listing.truncated)
+    } else {
+        return Err(anyhow::anyhow!("File not found: {}", source.display()));
+    };
+
+    let stop_words: HashSet<&str> = stop_words().collect();
+    let mut documents: Vec<(String, HashMap<String, usize>)> = Vec::new();
+    let mut unreadable = Vec::new();
+    for (path, relative_path) in files {
+        let text = create_extractor(&path).and_then(|extractor| extractor.extract_text_from_file(&path));
+        match text {
+            Ok(text) => documents.push((relative_path, count_terms(&text, options.ngram, &stop_words))),
+            // A single document that cannot be read is an error, not an empty result
+            Err(e) if source.is_file() => return Err(e),
+            Err(_) => unreadable.push(relative_path),
+        }
+    }
+    rank_terms(documents, unreadable, truncated, options, source)
+}
+
+/// Ranks the terms of a directory's index, reading the saved text instead of the documents
+///
+/// The index's own document selection applies; the list options are ignored.
+pub fn indexed_term_statistics(index: &DocumentIndex, options: &TermOptions) -> Result<TermStatistics> {
+    let stop_words: HashSet<&str> = stop_words().collect();
+    let mut documents = Vec::new();
+    let mut unreadable = Vec::new();
+    for document in &index.documents {
+        match document.error {
+            None => documents.push((
+                document.relative_path.clone(),
+                count_terms(&document.text, options.ngram, &stop_words),
+            )),
+            Some(_) => unreadable.push(document.relative_path.clone()),
+        }
+    }
+    rank_terms(documents, unreadable, index.truncated, options, &index.directory)
+}
+
+/// Ranks counted terms by the chosen measure, keeping the best ones
+fn rank_terms(
+    documents: Vec<(String, HashMap<String, usize>)>,
+    mut unreadable: Vec<String>,
+    truncated: bool,
+    options: &TermOptions,
+    source: &Path,
+) -> Result<TermStatistics> {
+    if options.measure == TermMeasure::TfIdf && documents.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "TF-IDF needs at least two readable documents, got {}: {}",
+            documents.len(),
+            source.display()
+        ));
+    }
+
+    // Totals per term: occurrences and documents containing it
+    let mut totals: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (_, counts) in &documents {
+        for (term, &count) in counts {
+            let total = totals.entry(term.as_str()).or_default();
+            total.0 += count;
+            total.1 += 1;
+        }
+    }
+
+    let mut terms: Vec<TermScore> = match options.measure {
+        TermMeasure::Frequency => totals
+            .iter()
+            .map(|(term, &(count, documents))| TermScore {
+                term: term.to_string(),
+                count,
+                documents,
+                score: None,
+                top_document: None,
+            })
+            .collect(),
+        TermMeasure::TfIdf => {
+            let mut scores: HashMap<&str, (f64, f64, &str)> = HashMap::new();
+            for (relative_path, counts) in &documents {
+                let length: usize = counts.values().sum();
+                for (term, &count) in counts {
+                    let idf = (documents.len() as f64 / totals[term.as_str()].1 as f64).ln();
+                    let score = count as f64 / length as f64 * idf;
+                    let entry = scores.entry(term.as_str()).or_insert((0.0, -1.0, relative_path.as_str()));
+                    entry.0 += score;
+                    if score > entry.1 {
+                        (entry.1, entry.2) = (score, relative_path.as_str());
+                    }
+                }
+            }
+            scores
+                .into_iter()
+                .filter(|(_, (score, _, _))| *score > 0.0)
+                .map(|(term, (score, _, top_document))| TermScore {
+                    term: term.to_string(),
+                    count: totals[term].0,
+                    documents: totals[term].1,
+                    score: Some((score * 10_000.0).round() / 10_000.0),
+                    top_document: Some(top_document.to_string()),
+                })
+                .collect()
+        }
+    };
+    // Ties are broken by term so the result does not depend on hashing
+    terms.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .total_cmp(&a.score.unwrap_or(0.0))
+            .then(b.count.cmp(&a.count))
+            .then_with(|| a.term.cmp(&b.term))
+    });
+    terms.truncate(options.max_terms);
+
+    unreadable.sort();
+    Ok(TermStatistics {
+        measure: options.measure,
+        ngram: options.ngram,
+        terms,
+        total_terms: totals.values().map(|(count, _)| count).sum(),
+        distinct_terms: totals.len(),
+        documents: documents.len(),
+        unreadable,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::index::IndexOptions;
+
+    fn sample_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in [
+            ("flights.txt", "The flight to Lisbon was late. The return flight was on time.\n\nFlight refunds take 30 days."),
+            ("hotel.txt", "The hotel in Lisbon charged a city tax. Hotel breakfast was included."),
+            ("taxes.txt", "Tax return filed. The tax refund arrives in May."),
+        ] {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        fs::write(dir.path().join("broken.docx"), "not a zip").unwrap();
+        dir
+    }
+
+    fn terms(statistics: &TermStatistics) -> Vec<(&str, usize)> {
+        statistics.terms.iter().map(|term| (term.term.as_str(), term.count)).collect()
+    }
+
+    #[test]
+    fn test_count_terms() {
+        let stop_words: HashSet<&str> = stop_words().collect();
+        let counts = count_terms("The IRS's form 1040. Form 1040 is due!\n\nA form", 1, &stop_words);
+        assert_eq!(counts.get("form"), Some(&3));
+        assert_eq!(counts.get("irs's"), Some(&1));
+        assert!(!counts.contains_key("the") && !counts.contains_key("1040") && !counts.contains_key("a"));
+
+        let counts = count_terms("The IRS's form 1040. Form 1040 is due!\n\nA form", 2, &stop_words);
+        assert_eq!(counts.get("form 1040"), None, "Phrases must not end with a number");
+        let counts = count_terms("Return of the tax form. Tax form due", 3, &stop_words);
+        assert_eq!(counts.get("return of the"), None, "Phrases must not end with a function word");
+        assert_eq!(counts.get("form tax form"), None, "Phrases must not cross sentences");
+    }
+
+    #[test]
+    fn test_term_frequency() {
+        let dir = sample_directory();
+        let statistics = term_statistics(dir.path(), &TermOptions::new().with_max_terms(3)).unwrap();
+
+        assert_eq!(statistics.documents, 3);
+        assert_eq!(statistics.unreadable, ["broken.docx"]);
+        assert_eq!(terms(&statistics), [("flight", 3), ("tax", 3), ("hotel", 2)]);
+        assert_eq!(statistics.terms[1].documents, 2);
+        assert_eq!(statistics.terms[0].score, None);
+
+        let options = TermOptions::new().with_ngram(2).unwrap().with_max_terms(1);
+        let statistics = term_statistics(&dir.path().join("flights.txt"), &options).unwrap();
+        assert_eq!(terms(&statistics), [("flight refunds", 1)]);
+        assert_eq!(statistics.documents, 1);
+
+        assert!(TermOptions::new().with_ngram(0).is_err());
+        assert!(TermOptions::new().with_ngram(6).is_err());
+        assert!(term_statistics(&dir.path().join("broken.docx"), &TermOptions::new()).is_err());
+        assert!(term_statistics(&dir.path().join("missing"), &TermOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_term_tf_idf() {
+        let dir = sample_directory();
+        let options = TermOptions::new().with_measure(TermMeasure::TfIdf).with_max_terms(50);
+        let statistics = term_statistics(dir.path(), &options).unwrap();
+
+        let top = &statistics.terms[0];
+        assert_eq!((top.term.as_str(), top.top_document.as_deref()), ("flight", Some("flights.txt")));
+        let tax = statistics.terms.iter().find(|term| term.term == "tax").unwrap();
+        assert_eq!((tax.documents, tax.top_document.as_deref()), (2, Some("taxes.txt")));
+        let scores: Vec<f64> = statistics.terms.iter().map(|term| term.score.unwrap()).collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]), "Got: {:?}", scores);
+
+        let mut index = DocumentIndex::default();
+        index.update(dir.path(), &IndexOptions::new()).unwrap();
+        assert_eq!(indexed_term_statistics(&index, &options).unwrap(), statistics);
+
+        let error = term_statistics(&dir.path().join("hotel.txt"), &options).unwrap_err();
+        assert!(error.to_string().starts_with("TF-IDF needs at least two readable documents"), "Got: {}", error);
+    }
+}
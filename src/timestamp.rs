@@ -0,0 +1,155 @@
+//! ISO 8601 timestamps, formatted and parsed without a date library
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::Result;
+
+/// Formats seconds since the Unix epoch as an ISO 8601 UTC timestamp
+pub fn format_unix_time(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Returns seconds since the Unix epoch, or `None` for times before it
+pub fn unix_seconds(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|elapsed| elapsed.as_secs())
+}
+
+/// Days since 1970-01-01 of a civil date (inverse of the algorithm above)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Parses a fixed-width decimal field, rejecting signs and other characters
+fn number(field: &str) -> Option<i64> {
+    if field.is_empty() || !field.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+/// Parses `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM[:SS]` with an optional `Z` or `±HH:MM`
+/// offset, returning seconds since the Unix epoch
+///
+/// Times without an offset are taken as UTC.
+pub fn parse_timestamp(timestamp: &str) -> Result<u64> {
+    parse(timestamp.trim()).ok_or_else(|| {
+        anyhow::anyhow!("Invalid timestamp (expected YYYY-MM-DD or YYYY-MM-DDTHH:MM:SSZ): {}", timestamp)
+    })
+}
+
+fn parse(timestamp: &str) -> Option<u64> {
+    let (date, time) = match timestamp.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (timestamp, None),
+    };
+
+    let mut fields = date.split('-');
+    let (year, month, day) = (fields.next()?, fields.next()?, fields.next()?);
+    if fields.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day) = (number(year)?, number(month)?, number(day)?);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    if let Some(time) = time {
+        let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+            Some(index) => time.split_at(index),
+            None => (time, ""),
+        };
+
+        let fields: Vec<&str> = clock.split(':').collect();
+        if !(2..=3).contains(&fields.len()) || fields.iter().any(|field| field.len() != 2) {
+            return None;
+        }
+        let (hours, minutes) = (number(fields[0])?, number(fields[1])?);
+        let secs = fields.get(2).map_or(Some(0), |field| number(field))?;
+        if hours > 23 || minutes > 59 || secs > 59 {
+            return None;
+        }
+        seconds += hours * 3600 + minutes * 60 + secs;
+
+        if let Some(offset) = offset.strip_prefix(['+', '-']) {
+            let (offset_hours, offset_minutes) = offset.split_once(':')?;
+            if offset_hours.len() != 2 || offset_minutes.len() != 2 {
+                return None;
+            }
+            let offset_seconds = number(offset_hours)? * 3600 + number(offset_minutes)? * 60;
+            // Local time = UTC + offset, so UTC = local time - offset
+            seconds -= if time.contains('+') { offset_seconds } else { -offset_seconds };
+        } else if !offset.is_empty() && !offset.eq_ignore_ascii_case("z") {
+            return None;
+        }
+    }
+
+    u64::try_from(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_unix_time() {
+        assert_eq!(format_unix_time(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_unix_time(1_756_340_185), "2025-08-28T00:16:25Z");
+        assert_eq!(format_unix_time(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_timestamp("2025-08-28T00:16:25Z").unwrap(), 1_756_340_185);
+        assert_eq!(parse_timestamp("2025-08-28T00:16:25").unwrap(), 1_756_340_185);
+        assert_eq!(parse_timestamp("2025-08-28T02:16:25+02:00").unwrap(), 1_756_340_185);
+        assert_eq!(parse_timestamp("2025-08-27T22:16-02:00").unwrap(), 1_756_340_160);
+        assert_eq!(parse_timestamp(" 2000-02-29 ").unwrap(), 951_782_400);
+
+        for invalid in ["", "2025", "2025-8-28", "2025-02-29", "2025-13-01", "2025-08-28T24:00", "2025-08-28T10:00X", "1969-12-31"] {
+            assert!(parse_timestamp(invalid).is_err(), "Should reject {:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_format_and_parse_round_trip() {
+        for seconds in [0, 951_782_400, 1_756_340_185, 4_102_444_800] {
+            assert_eq!(parse_timestamp(&format_unix_time(seconds)).unwrap(), seconds);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Results of MCP tool calls (`tools/call`)
+//!
+//! Tools return their data as `structuredContent`, with a short text block for clients
+//! and models that read only text, rather than the data encoded as a JSON string in
+//! a text block.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::extractor::ExtractedImage;
+use crate::resources::resource_uri;
+use crate::rpc_error::classify;
+
+/// Key under which a result that is not a JSON object goes in `structuredContent`
+const RESULT_KEY: &str = "result";
+
+/// Resource links added to one result at most, so long listings stay readable
+pub const MAX_RESOURCE_LINKS: usize = 50;
+
+/// A content block of a tool result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    /// A base64-encoded image
+    Image {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    /// A link to a resource the client can open, e.g. a document a search found
+    #[serde(rename = "resource_link")]
+    ResourceLink {
+        uri: String,
+        name: String,
+        /// Size of the file in bytes
+        #[serde(skip_serializing_if = "Option::is_none")]
+        size: Option<u64>,
+    },
+}
+
+impl ContentBlock {
+    /// A link to a document's resource, named after the file
+    pub fn resource_link(file_path: &Path) -> Self {
+        let name = file_path.file_name().unwrap_or(file_path.as_os_str()).to_string_lossy().into_owned();
+        let size = fs::metadata(file_path).ok().filter(|metadata| metadata.is_file()).map(|metadata| metadata.len());
+        ContentBlock::ResourceLink { uri: resource_uri(file_path, false), name, size }
+    }
+}
+
+/// The result of a tool call
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolResult {
+    pub content: Vec<ContentBlock>,
+    /// The result data; the MCP spec requires a JSON object
+    #[serde(rename = "structuredContent", skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Map<String, Value>>,
+    /// True when the tool failed, so the model can see the error and try again
+    #[serde(rename = "isError", skip_serializing_if = "std::ops::Not::not")]
+    pub is_error: bool,
+}
+
+impl ToolResult {
+    /// A result with only text, e.g. an extracted document
+    pub fn text(text: impl Into<String>) -> Self {
+        Self { content: vec![ContentBlock::Text { text: text.into() }], structured_content: None, is_error: false }
+    }
+
+    /// A result with structured data, summarized in a text block
+    ///
+    /// Values that do not serialize to a JSON object (lists, strings, ...) are put
+    /// under a `result` key.
+    pub fn structured<T: Serialize>(value: &T, summary: impl Into<String>) -> Result<Self> {
+        let value = serde_json::to_value(value).context("Failed to serialize the tool result")?;
+        let structured_content = match value {
+            Value::Object(object) => object,
+            value => Map::from_iter([(RESULT_KEY.to_string(), value)]),
+        };
+        Ok(Self {
+            content: vec![ContentBlock::Text { text: summary.into() }],
+            structured_content: Some(structured_content),
+            is_error: false,
+        })
+    }
+
+    /// A failed tool call, with the error and its causes as text, followed by a hint
+    /// on how to correct the call when the kind of error has one
+    pub fn error(error: &anyhow::Error) -> Self {
+        let mut result = Self { is_error: true, ..Self::text(format!("{:#}", error)) };
+        if let Some(hint) = classify(error).hint() {
+            result.content.push(ContentBlock::Text { text: hint.to_string() });
+        }
+        result
+    }
+
+    /// The `tools/call` result of running a tool: its result, or its failure as an
+    /// `isError` result rather than a JSON-RPC error
+    pub fn from_outcome(outcome: Result<Self>) -> Self {
+        outcome.unwrap_or_else(|error| Self::error(&error))
+    }
+
+    /// Adds links to the resources of documents the tool surfaced, once per document and
+    /// at most `MAX_RESOURCE_LINKS`
+    pub fn with_resource_links<P: AsRef<Path>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        let mut linked = HashSet::new();
+        for path in paths {
+            if linked.len() == MAX_RESOURCE_LINKS {
+                break;
+            }
+            if linked.insert(path.as_ref().to_path_buf()) {
+                self.content.push(ContentBlock::resource_link(path.as_ref()));
+            }
+        }
+        self
+    }
+
+    /// Images as image content blocks, each preceded by a text block describing it
+    pub fn images(images: &[ExtractedImage]) -> Self {
+        let mut content = Vec::new();
+        for image in images {
+            let mut description = format!("Image {}", image.index + 1);
+            if let Some(page) = image.page {
+                description.push_str(&format!(", page {}", page));
+            }
+            if let Some(name) = &image.name {
+                description.push_str(&format!(" ({})", name));
+            }
+            content.push(ContentBlock::Text { text: description });
+            content.push(ContentBlock::Image { data: image.data.clone(), mime_type: image.mime_type.clone() });
+        }
+        Self { content, structured_content: None, is_error: false }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::search::{Search, SearchMatch, SearchResults};
+
+    #[test]
+    fn test_structured_result() {
+        let results = Search::new("6 hours", false).unwrap().search_text("Economy under 6 hours.").unwrap();
+        let result = ToolResult::structured(&results, "1 match").unwrap();
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            json!({
+                "content": [{ "type": "text", "text": "1 match" }],
+                "structuredContent": {
+                    "matches": [{ "offset": 14, "line": 1, "text": "6 hours", "context": "Economy under 6 hours." }],
+                    "truncated": false,
+                },
+            })
+        );
+
+        let empty: Vec<SearchMatch> = Vec::new();
+        let result = ToolResult::structured(&empty, "No matches").unwrap();
+        assert_eq!(result.structured_content, Some(Map::from_iter([(RESULT_KEY.to_string(), json!([]))])));
+        assert!(ToolResult::structured(&SearchResults { matches: empty, truncated: true }, "").is_ok());
+    }
+
+    #[test]
+    fn test_text_error_and_image_results() {
+        assert_eq!(
+            serde_json::to_value(ToolResult::text("Hello")).unwrap(),
+            json!({ "content": [{ "type": "text", "text": "Hello" }] })
+        );
+
+        let error = anyhow::anyhow!("File not found: /tmp/a.pdf").context("Failed to extract");
+        let result = ToolResult::error(&error);
+        assert!(result.is_error);
+        let text = "Failed to extract: File not found: /tmp/a.pdf".to_string();
+        let hint = "Check the path, e.g. by listing the files of its directory.".to_string();
+        assert_eq!(result.content, [ContentBlock::Text { text }, ContentBlock::Text { text: hint }]);
+        let internal = ToolResult::error(&anyhow::anyhow!("Extraction crashed"));
+        assert_eq!(internal.content.len(), 1, "Errors without a hint have only their text");
+
+        let failed = ToolResult::from_outcome(Err(anyhow::anyhow!("Unsupported file format: .xyz")));
+        assert!(failed.is_error);
+        assert!(matches!(&failed.content[1], ContentBlock::Text { text } if text.contains("get_supported_formats")));
+        assert_eq!(ToolResult::from_outcome(Ok(ToolResult::text("Hello"))), ToolResult::text("Hello"));
+
+        let image = ExtractedImage {
+            index: 0,
+            page: Some(2),
+            name: Some("Im1".into()),
+            mime_type: "image/png".into(),
+            size: 3,
+            data: "AAEC".into(),
+        };
+        let json = serde_json::to_value(ToolResult::images(&[image])).unwrap();
+        assert_eq!(json["content"][0]["text"], "Image 1, page 2 (Im1)");
+        assert_eq!(json["content"][1], json!({ "type": "image", "data": "AAEC", "mimeType": "image/png" }));
+    }
+
+    #[test]
+    fn test_resource_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("Q3 report.txt");
+        fs::write(&report, "Revenue").unwrap();
+        let missing = dir.path().join("missing.pdf");
+
+        let result = ToolResult::text("2 files").with_resource_links([&report, &missing, &report]);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["content"].as_array().unwrap().len(), 3, "Each document should be linked once");
+        assert_eq!(
+            json["content"][1],
+            json!({ "type": "resource_link", "uri": resource_uri(&report, false), "name": "Q3 report.txt", "size": 7 })
+        );
+        let uri = resource_uri(&missing, false);
+        assert_eq!(json["content"][2], json!({ "type": "resource_link", "uri": uri, "name": "missing.pdf" }));
+
+        let many = (0..MAX_RESOURCE_LINKS + 5).map(|n| dir.path().join(format!("{}.txt", n)));
+        assert_eq!(ToolResult::text("").with_resource_links(many).content.len(), MAX_RESOURCE_LINKS + 1);
+    }
+}
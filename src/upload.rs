@@ -0,0 +1,128 @@
+//! Extracting documents sent inline as base64, without a file on disk
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use crate::extractor::{extract_text_from_bytes, is_supported_file, BytesExtraction};
+use crate::file_type::extension_for_mime_type;
+
+/// Largest decoded document accepted, in bytes
+const MAX_DECODED_SIZE: usize = 100 * 1024 * 1024;
+
+/// Name used when the client gives no file name
+const DEFAULT_FILE_NAME: &str = "document";
+
+/// Decoders for the standard and URL-safe alphabets, with or without padding
+const STANDARD: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+const URL_SAFE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Decodes base64 data, which may be a `data:` URL, returning the bytes and the
+/// MIME type the URL declares
+fn decode(data: &str) -> Result<(Vec<u8>, Option<String>)> {
+    let (data, mime_type) = match data.trim_start().strip_prefix("data:") {
+        Some(url) => {
+            let (header, payload) = url
+                .split_once(',')
+                .ok_or_else(|| anyhow::anyhow!("Invalid data URL: missing ','"))?;
+            let mime_type = header
+                .strip_suffix(";base64")
+                .ok_or_else(|| anyhow::anyhow!("Only base64 data URLs are supported"))?;
+            let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+            (payload, Some(mime_type.to_string()).filter(|mime_type| !mime_type.is_empty()))
+        }
+        None => (data, None),
+    };
+
+    // Line breaks and spaces are common in pasted base64
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    if data.is_empty() {
+        return Err(anyhow::anyhow!("No document data"));
+    }
+    if data.len() / 4 * 3 > MAX_DECODED_SIZE {
+        return Err(anyhow::anyhow!("Document is larger than {} bytes", MAX_DECODED_SIZE));
+    }
+
+    let engine = if data.contains(['-', '_']) { &URL_SAFE } else { &STANDARD };
+    let bytes = engine.decode(&data).context("Invalid base64 data")?;
+    Ok((bytes, mime_type))
+}
+
+/// Chooses the temporary file name, whose extension selects the extractor
+///
+/// A supported extension in the given name wins; otherwise the MIME type adds one.
+/// Directories in the given name are dropped.
+fn inline_file_name(file_name: Option<&str>, mime_type: Option<&str>) -> String {
+    let name = file_name
+        .and_then(|name| Path::new(name.trim()).file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or(DEFAULT_FILE_NAME);
+
+    if is_supported_file(Path::new(name)) {
+        return name.to_string();
+    }
+    match mime_type.and_then(extension_for_mime_type) {
+        Some(extension) => format!("{}.{}", name, extension),
+        None => name.to_string(),
+    }
+}
+
+/// Extracts a document sent as base64, e.g. an email attachment a client holds in memory
+///
+/// `data` may be plain base64 (standard or URL-safe alphabet, padding optional) or a
+/// `data:` URL. The extractor is chosen from `file_name`, then `mime_type` (or the
+/// data URL's type), then the content itself.
+pub fn extract_from_base64(data: &str, file_name: Option<&str>, mime_type: Option<&str>) -> Result<BytesExtraction> {
+    let (bytes, declared_type) = decode(data)?;
+    let name = inline_file_name(file_name, mime_type.or(declared_type.as_deref()));
+    extract_text_from_bytes(&bytes, &name).with_context(|| format!("Failed to extract text from: {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_from_base64() {
+        let data = base64::engine::general_purpose::STANDARD.encode("Gate B12\nBoarding 07:40");
+
+        let extraction = extract_from_base64(&data, Some("pass.txt"), None).unwrap();
+        assert_eq!(extraction.extractor, "TxtExtractor");
+        assert_eq!(extraction.text, "Gate B12\nBoarding 07:40");
+
+        let extraction = extract_from_base64(&data, None, Some("text/markdown")).unwrap();
+        assert_eq!(extraction.extractor, "MarkdownExtractor");
+
+        let data_url = format!("data:text/plain;charset=utf-8;base64,{}", data);
+        let extraction = extract_from_base64(&data_url, Some("attachment"), None).unwrap();
+        assert_eq!(extraction.extractor, "TxtExtractor");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("SGk/Pz8=").unwrap().0, b"Hi???");
+        assert_eq!(decode("SGk_Pz8").unwrap().0, b"Hi???", "URL-safe, unpadded");
+        assert_eq!(decode("SGk/\n Pz8=").unwrap().0, b"Hi???");
+        assert_eq!(decode("data:application/pdf;base64,JVBE").unwrap(), (b"%PD".to_vec(), Some("application/pdf".to_string())));
+        assert!(decode("data:text/plain,Hi").is_err());
+        assert!(decode("not base64!").is_err());
+        assert!(decode("  ").is_err());
+    }
+
+    #[test]
+    fn test_inline_file_name() {
+        assert_eq!(inline_file_name(Some("../../etc/report.pdf"), None), "report.pdf");
+        assert_eq!(inline_file_name(Some("report"), Some("application/pdf")), "report.pdf");
+        assert_eq!(inline_file_name(Some("scan.tmp"), Some("application/pdf")), "scan.tmp.pdf");
+        assert_eq!(inline_file_name(None, Some("text/csv")), "document.csv");
+        assert_eq!(inline_file_name(None, None), "document");
+    }
+}
@@ -0,0 +1,257 @@
+//! Checking that documents open cleanly, to triage directories of half-downloaded or
+//! mislabeled files without reading their content
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use lopdf::Document;
+use serde::Serialize;
+use crate::constants::{PDF_EXTENSION, ZIP_CONTAINER_EXTENSIONS, ZIP_EXTENSION};
+use crate::extractor::create_extractor;
+use crate::extractors::archive::open_zip;
+use crate::extractors::pdf_extractor::PdfExtractor;
+use crate::file_type::{detect_extension, extension_matches};
+
+/// Bytes at the end of a PDF searched for its `%%EOF` marker
+const PDF_TRAILER_LEN: usize = 1024;
+
+/// A problem found in a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// The file has no content
+    Empty,
+    /// The content is another format than the extension says
+    WrongExtension,
+    /// The file ends early, as an interrupted download does
+    Truncated,
+    /// A password is needed to read the document
+    Encrypted,
+    /// The document opens but has no pages
+    NoPages,
+    /// The file's structure cannot be read
+    Corrupt,
+    /// The extractor for the format failed
+    ExtractionFailed,
+    /// Extraction worked but gave no text, e.g. a scan without OCR or a blank document
+    NoText,
+}
+
+impl ProblemKind {
+    /// Returns true for problems that make extraction pointless
+    fn is_fatal(self) -> bool {
+        use ProblemKind::*;
+        matches!(self, Empty | Truncated | Encrypted | NoPages | Corrupt)
+    }
+}
+
+/// A problem found in a document, with details
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Problem {
+    pub kind: ProblemKind,
+    pub message: String,
+}
+
+/// Result of `validate_document`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationReport {
+    pub path: PathBuf,
+    /// File size in bytes
+    pub size: u64,
+    /// Format told from the content, as an extension, when it has a signature
+    pub detected_format: Option<&'static str>,
+    /// Extractor that handles the file
+    pub extractor: Option<&'static str>,
+    /// Number of pages, for PDFs
+    pub page_count: Option<usize>,
+    /// Length of the extracted text in characters, when extraction was tried and worked
+    pub chars: Option<usize>,
+    /// True when no problem was found
+    pub valid: bool,
+    pub problems: Vec<Problem>,
+}
+
+impl ValidationReport {
+    fn problem(&mut self, kind: ProblemKind, message: impl Into<String>) {
+        self.problems.push(Problem { kind, message: message.into() });
+    }
+
+    fn has_fatal_problem(&self) -> bool {
+        self.problems.iter().any(|problem| problem.kind.is_fatal())
+    }
+}
+
+/// Checks the structure of a PDF: complete, readable without a password, with pages
+fn check_pdf(file_path: &Path, report: &mut ValidationReport) -> Result<()> {
+    let file_bytes = fs::read(file_path).with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+    let trailer = &file_bytes[file_bytes.len().saturating_sub(PDF_TRAILER_LEN)..];
+    if !trailer.windows(b"%%EOF".len()).any(|window| window == b"%%EOF") {
+        report.problem(ProblemKind::Truncated, "The PDF has no end-of-file marker; it was probably cut short");
+        return Ok(());
+    }
+
+    let decrypted = match PdfExtractor::decrypt_bytes(&file_bytes, None) {
+        Ok(decrypted) => decrypted,
+        Err(e) if e.to_string().contains("password") => {
+            report.problem(ProblemKind::Encrypted, format!("{:#}", e));
+            return Ok(());
+        }
+        Err(e) => {
+            report.problem(ProblemKind::Corrupt, format!("{:#}", e));
+            return Ok(());
+        }
+    };
+    match Document::load_mem(&decrypted) {
+        Ok(document) => {
+            let pages = document.get_pages().len();
+            report.page_count = Some(pages);
+            if pages == 0 {
+                report.problem(ProblemKind::NoPages, "The PDF has no pages");
+            }
+        }
+        Err(e) => report.problem(ProblemKind::Corrupt, format!("Failed to parse PDF: {}", e)),
+    }
+    Ok(())
+}
+
+/// Checks that a zip-based document (Word, PowerPoint, OpenDocument, ...) has a readable directory
+fn check_zip(file_path: &Path, report: &mut ValidationReport) -> Result<()> {
+    let Err(e) = open_zip(file_path) else {
+        return Ok(());
+    };
+    let file_bytes = fs::read(file_path).with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+    // The central directory ends the archive, so a cut-short download loses it first
+    if !file_bytes.windows(4).any(|window| window == b"PK\x05\x06") {
+        report.problem(ProblemKind::Truncated, "The zip container has no central directory; it was probably cut short");
+    } else {
+        report.problem(ProblemKind::Corrupt, format!("{:#}", e));
+    }
+    Ok(())
+}
+
+/// Checks whether a document opens cleanly with its extractor, reporting problems
+/// instead of its content
+///
+/// Checks the extension against the content, the structure of PDFs and zip-based
+/// documents, then extracts the text unless the structure is already broken. Errors
+/// only when the path is not a readable file.
+pub fn validate_document(file_path: &Path) -> Result<ValidationReport> {
+    let metadata = fs::metadata(file_path).with_context(|| format!("File not found: {}", file_path.display()))?;
+    if !metadata.is_file() {
+        return Err(anyhow::anyhow!("Path is not a file: {}", file_path.display()));
+    }
+
+    let detected_format = detect_extension(file_path);
+    let mut report = ValidationReport {
+        path: file_path.to_path_buf(),
+        size: metadata.len(),
+        detected_format,
+        extractor: None,
+        page_count: None,
+        chars: None,
+        valid: false,
+        problems: Vec::new(),
+    };
+    if metadata.len() == 0 {
+        report.problem(ProblemKind::Empty, "The file is empty");
+        return Ok(report);
+    }
+
+    let extension = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if let Some(detected) = detected_format {
+        if !extension_matches(&extension, detected) {
+            let named = if extension.is_empty() { "no extension".to_string() } else { format!(".{}", extension) };
+            let message = format!("The content is {}, but the file has {}", detected, named);
+            report.problem(ProblemKind::WrongExtension, message);
+        }
+    }
+
+    let format = detected_format.unwrap_or(extension.as_str());
+    if format == PDF_EXTENSION {
+        check_pdf(file_path, &mut report)?;
+    } else if format == ZIP_EXTENSION || ZIP_CONTAINER_EXTENSIONS.contains(&format) {
+        check_zip(file_path, &mut report)?;
+    }
+
+    if !report.has_fatal_problem() {
+        match create_extractor(file_path) {
+            Ok(extractor) => {
+                report.extractor = Some(extractor.extractor_type());
+                match extractor.extract_text_from_file(file_path) {
+                    Ok(text) if text.trim().is_empty() => {
+                        report.chars = Some(0);
+                        report.problem(ProblemKind::NoText, "No text was extracted");
+                    }
+                    Ok(text) => report.chars = Some(text.chars().count()),
+                    Err(e) => report.problem(ProblemKind::ExtractionFailed, format!("{:#}", e)),
+                }
+            }
+            Err(e) => report.problem(ProblemKind::ExtractionFailed, format!("{:#}", e)),
+        }
+    }
+
+    report.valid = report.problems.is_empty();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name)
+    }
+
+    fn kinds(report: &ValidationReport) -> Vec<ProblemKind> {
+        report.problems.iter().map(|problem| problem.kind).collect()
+    }
+
+    #[test]
+    fn test_validate_valid_documents() {
+        let report = validate_document(&fixture("policy.docx")).unwrap();
+        assert!(report.valid, "Got: {:?}", report.problems);
+        assert_eq!(report.extractor, Some("DocxExtractor"));
+        assert!(report.chars.is_some_and(|chars| chars > 0));
+
+        let report = validate_document(&fixture("cabinManual.pdf")).unwrap();
+        assert_eq!(report.page_count, Some(5));
+        assert!(!report.has_fatal_problem(), "Got: {:?}", report.problems);
+    }
+
+    #[test]
+    fn test_validate_broken_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+        let docx = fs::read(fixture("policy.docx")).unwrap();
+        let pdf = fs::read(fixture("cabinManual.pdf")).unwrap();
+
+        let report = validate_document(&write("empty.pdf", b"")).unwrap();
+        assert_eq!(kinds(&report), [ProblemKind::Empty]);
+        assert!(!report.valid);
+
+        let report = validate_document(&write("partial.docx", &docx[..docx.len() / 2])).unwrap();
+        assert_eq!(kinds(&report), [ProblemKind::Truncated]);
+        let report = validate_document(&write("partial.pdf", &pdf[..pdf.len() / 2])).unwrap();
+        assert_eq!(kinds(&report), [ProblemKind::Truncated]);
+
+        let report = validate_document(&write("report.pdf", &docx)).unwrap();
+        assert_eq!(kinds(&report), [ProblemKind::WrongExtension]);
+        assert_eq!(report.detected_format, Some("docx"));
+
+        let report = validate_document(&fixture("bankStatement.pdf")).unwrap();
+        assert_eq!(kinds(&report), [ProblemKind::Encrypted]);
+
+        let report = validate_document(&write("blank.txt", b" \n\n ")).unwrap();
+        assert_eq!(kinds(&report), [ProblemKind::NoText]);
+
+        assert!(validate_document(&dir.path().join("missing.pdf")).is_err());
+        assert!(validate_document(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Watching documents for changes the server notifies clients about
+//!
+//! Subscribed files are polled: `Subscriptions::changed` compares each file's size and
+//! modification time with the previous check and reports the files that changed, for
+//! the server to send `notifications/resources/updated` about them. `DirectoryWatcher`
+//! does the same for the documents of the active directory, for
+//! `notifications/resources/list_changed`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use crate::listing::{list_files_in_directory, ListOptions};
+
+/// How often the server checks subscribed documents by default
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// What a change to a file is detected by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileState {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Returns the state of a file, or `None` when it does not exist (any more)
+fn file_state(path: &Path) -> Option<FileState> {
+    let metadata = fs::metadata(path).ok().filter(|metadata| metadata.is_file())?;
+    Some(FileState { size: metadata.len(), modified: metadata.modified().ok() })
+}
+
+/// Documents a client subscribed to, with the state they had when last checked
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions {
+    files: BTreeMap<PathBuf, Option<FileState>>,
+}
+
+impl Subscriptions {
+    /// Creates an empty set of subscriptions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to a document, returning its canonical path; subscribing twice is harmless
+    pub fn subscribe(&mut self, file_path: &Path) -> Result<PathBuf> {
+        let path = file_path
+            .canonicalize()
+            .with_context(|| format!("File not found: {}", file_path.display()))?;
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("Path is not a file: {}", path.display()));
+        }
+        let state = file_state(&path);
+        self.files.entry(path.clone()).or_insert(state);
+        Ok(path)
+    }
+
+    /// Unsubscribes from a document, even one deleted since; returns false when it was not subscribed
+    pub fn unsubscribe(&mut self, file_path: &Path) -> bool {
+        let canonical = file_path.canonicalize().unwrap_or_else(|_| file_path.to_path_buf());
+        self.files.remove(&canonical).is_some() || self.files.remove(file_path).is_some()
+    }
+
+    /// Returns the subscribed documents, A to Z
+    pub fn subscribed(&self) -> Vec<&Path> {
+        self.files.keys().map(PathBuf::as_path).collect()
+    }
+
+    /// Returns the subscribed documents modified, deleted or recreated since the previous check
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, state) in &mut self.files {
+            let current = file_state(path);
+            if current != *state {
+                *state = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// Documents added to or removed from a watched directory since the previous check
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ListChange {
+    pub added: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl ListChange {
+    /// True when no document was added or removed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Watches the documents of one directory at a time, e.g. the active directory
+pub struct DirectoryWatcher {
+    options: ListOptions,
+    directory: Option<PathBuf>,
+    documents: BTreeSet<PathBuf>,
+}
+
+impl Default for DirectoryWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DirectoryWatcher {
+    /// Watches the supported documents directly in the directory, without a directory yet
+    pub fn new() -> Self {
+        Self::with_options(ListOptions::new().with_supported_only(true))
+    }
+
+    /// Watches the files `options` lists; its result limit is ignored
+    pub fn with_options(options: ListOptions) -> Self {
+        Self { options: options.with_max_results(usize::MAX), directory: None, documents: BTreeSet::new() }
+    }
+
+    /// Returns the watched directory
+    pub fn directory(&self) -> Option<&Path> {
+        self.directory.as_deref()
+    }
+
+    /// Watches another directory, e.g. after the active directory was switched
+    ///
+    /// Returns true when the directory differs from the one watched before, in which
+    /// case the document list has changed whatever the directories contain.
+    pub fn set_directory(&mut self, directory: &Path) -> Result<bool> {
+        if self.directory.as_deref() == Some(directory) {
+            return Ok(false);
+        }
+        self.documents = self.list_documents(directory)?;
+        self.directory = Some(directory.to_path_buf());
+        Ok(true)
+    }
+
+    /// Returns the documents added to or removed from the watched directory since the previous check
+    ///
+    /// A directory deleted since counts as empty.
+    pub fn check(&mut self) -> Result<ListChange> {
+        let Some(directory) = &self.directory else {
+            return Ok(ListChange::default());
+        };
+        let documents = if directory.is_dir() {
+            self.list_documents(directory)?
+        } else {
+            BTreeSet::new()
+        };
+        let change = ListChange {
+            added: documents.difference(&self.documents).cloned().collect(),
+            removed: self.documents.difference(&documents).cloned().collect(),
+        };
+        self.documents = documents;
+        Ok(change)
+    }
+
+    fn list_documents(&self, directory: &Path) -> Result<BTreeSet<PathBuf>> {
+        let listing = list_files_in_directory(directory, &self.options)?;
+        Ok(listing.files.into_iter().map(|file| file.path).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let contract = dir.path().join("contract.txt");
+        let notes = dir.path().join("notes.txt");
+        fs::write(&contract, "Draft 1").unwrap();
+        fs::write(&notes, "Call the landlord").unwrap();
+
+        let mut subscriptions = Subscriptions::new();
+        let contract = subscriptions.subscribe(&contract).unwrap();
+        let notes = subscriptions.subscribe(&notes).unwrap();
+        subscriptions.subscribe(&dir.path().join(".").join("notes.txt")).unwrap();
+        assert_eq!(subscriptions.subscribed(), [contract.as_path(), notes.as_path()]);
+        assert!(subscriptions.changed().is_empty());
+
+        fs::write(&contract, "Draft 2, signed").unwrap();
+        assert_eq!(subscriptions.changed(), [contract.as_path()]);
+        assert!(subscriptions.changed().is_empty(), "A change is reported once");
+
+        // Same size, new modification time
+        let file = fs::File::options().write(true).open(&notes).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(3600)).unwrap();
+        assert_eq!(subscriptions.changed(), [notes.as_path()]);
+
+        fs::remove_file(&contract).unwrap();
+        assert_eq!(subscriptions.changed(), [contract.as_path()], "Deletions are changes");
+        fs::write(&contract, "Draft 3").unwrap();
+        assert_eq!(subscriptions.changed(), [contract.as_path()], "So are files recreated");
+
+        fs::remove_file(&notes).unwrap();
+        assert!(subscriptions.unsubscribe(&notes), "Deleted documents can be unsubscribed");
+        assert!(!subscriptions.unsubscribe(&notes));
+        assert_eq!(subscriptions.subscribed(), [contract.as_path()]);
+    }
+
+    #[test]
+    fn test_subscribe_requires_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut subscriptions = Subscriptions::new();
+        assert!(subscriptions.subscribe(&dir.path().join("missing.pdf")).is_err());
+        assert!(subscriptions.subscribe(dir.path()).is_err());
+        assert!(subscriptions.subscribed().is_empty());
+    }
+
+    #[test]
+    fn test_directory_watcher() {
+        let taxes = tempfile::tempdir().unwrap();
+        let work = tempfile::tempdir().unwrap();
+        fs::write(taxes.path().join("return.pdf"), "x").unwrap();
+        fs::write(taxes.path().join("scan.heic"), "x").unwrap();
+
+        let mut watcher = DirectoryWatcher::new();
+        assert!(watcher.check().unwrap().is_empty(), "Nothing is watched yet");
+        assert!(watcher.set_directory(taxes.path()).unwrap());
+        assert!(!watcher.set_directory(taxes.path()).unwrap(), "The same directory is not a change");
+        assert!(watcher.check().unwrap().is_empty());
+
+        fs::write(taxes.path().join("receipt.txt"), "x").unwrap();
+        fs::write(taxes.path().join("photo.heic"), "x").unwrap();
+        fs::remove_file(taxes.path().join("return.pdf")).unwrap();
+        let change = watcher.check().unwrap();
+        assert_eq!(change.added, [taxes.path().join("receipt.txt")], "Only supported documents are listed");
+        assert_eq!(change.removed, [taxes.path().join("return.pdf")]);
+        assert!(watcher.check().unwrap().is_empty(), "A change is reported once");
+
+        assert!(watcher.set_directory(work.path()).unwrap(), "Switching directories changes the list");
+        assert_eq!(watcher.directory(), Some(work.path()));
+        assert!(watcher.check().unwrap().is_empty());
+
+        fs::write(work.path().join("plan.md"), "x").unwrap();
+        let work_path = work.path().to_path_buf();
+        assert_eq!(watcher.check().unwrap().added, [work_path.join("plan.md")]);
+        drop(work);
+        assert_eq!(watcher.check().unwrap().removed, [work_path.join("plan.md")], "A deleted directory is empty");
+    }
+}